@@ -1,82 +1,400 @@
+//! Asynchronous task processor
+//!
+//! The processes are executed in the background and tracked by [task records](crate::rbum::domain::rbum_task::Model),
+//! which keep the status, progress, result and error of the executions.
+//!
+//! Status transitions:
+//!
+//! ```text
+//! Pending -> Running -> Succeeded
+//!               |  \--> Failed
+//!               \-----> Pending (retry)
+//! Pending | Running --> Cancelled
+//! ```
+//!
+//! Cancellation is cooperative, the process should check [`TaskHandle::is_cancelled`] and stop early,
+//! the outcome of a process that finishes after being cancelled is discarded.
 use std::future::Future;
+use std::time::Duration;
 
-use tardis::{
-    basic::{dto::TardisContext, result::TardisResult},
-    cache::cache_client::TardisCacheClient,
-    chrono::Local,
-    log, TardisFunsInst,
-};
+use tardis::basic::dto::TardisContext;
+use tardis::basic::error::TardisError;
+use tardis::basic::result::TardisResult;
+use tardis::chrono::Utc;
+use tardis::db::sea_orm::sea_query::*;
+use tardis::db::sea_orm::*;
+use tardis::serde_json::Value as JsonValue;
+use tardis::tokio::task::JoinHandle;
+use tardis::web::web_resp::TardisPage;
+use tardis::{log, TardisFuns, TardisFunsInst};
+
+use crate::rbum::domain::rbum_task;
+use crate::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumTaskFilterReq};
+use crate::rbum::dto::rbum_task_dto::{RbumTaskDetailResp, RbumTaskRetryPolicy};
+use crate::rbum::rbum_config::RbumConfigManager;
+use crate::rbum::rbum_enumeration::RbumTaskStatusKind;
+use crate::rbum::serv::rbum_crud_serv::RbumCrudQueryPackage;
 
 const TASK_IN_CTX_FLAG: &str = "task_id";
 
-pub struct TaskProcessor;
+/// Handle of the executing task, passed to its process
+#[derive(Clone)]
+pub struct TaskHandle {
+    task_id: String,
+    code: String,
+}
 
-impl TaskProcessor {
-    pub async fn init_task(cache_key: &str, cache_client: &TardisCacheClient) -> TardisResult<i64> {
-        let task_id = Local::now().timestamp_nanos();
-        let max: i64 = u32::MAX.into();
-        let task_id_split1: usize = (task_id / max).try_into()?;
-        let task_id_split2: usize = (task_id % max).try_into()?;
-        cache_client.setbit(&format!("{}:1", cache_key), task_id_split1, false).await?;
-        cache_client.setbit(&format!("{}:2", cache_key), task_id_split2, false).await?;
-        Ok(task_id)
+impl TaskHandle {
+    pub fn task_id(&self) -> &str {
+        &self.task_id
     }
 
-    pub async fn set_status(cache_key: &str, task_id: i64, status: bool, cache_client: &TardisCacheClient) -> TardisResult<()> {
-        let max: i64 = u32::MAX.into();
-        let task_id_split1: usize = (task_id / max).try_into()?;
-        let task_id_split2: usize = (task_id % max).try_into()?;
-        cache_client.setbit(&format!("{}:1", cache_key), task_id_split1, status).await?;
-        cache_client.setbit(&format!("{}:2", cache_key), task_id_split2, status).await?;
+    /// Report the progress percentage of the running task, values above 100 are treated as 100.
+    pub async fn set_progress(&self, progress: u8) -> TardisResult<()> {
+        let funs = TardisFuns::inst_with_db_conn(self.code.clone(), None);
+        funs.db()
+            .execute(
+                Query::update()
+                    .table(rbum_task::Entity)
+                    .values(vec![
+                        (rbum_task::Column::Progress, progress.min(100).into()),
+                        (rbum_task::Column::UpdateTime, Utc::now().into()),
+                    ])
+                    .and_where(Expr::col(rbum_task::Column::Id).eq(self.task_id.as_str()))
+                    .and_where(Expr::col(rbum_task::Column::Status).eq(RbumTaskStatusKind::Running.to_int())),
+            )
+            .await?;
         Ok(())
     }
 
-    pub async fn check_status(cache_key: &str, task_id: i64, cache_client: &TardisCacheClient) -> TardisResult<bool> {
-        let max: i64 = u32::MAX.into();
-        let task_id_split1: usize = (task_id / max).try_into()?;
-        let task_id_split2: usize = (task_id % max).try_into()?;
-        let result1 = cache_client.getbit(&format!("{}:1", cache_key), task_id_split1).await?;
-        let result2 = cache_client.getbit(&format!("{}:2", cache_key), task_id_split2).await?;
-        Ok(result1 && result2)
+    pub async fn is_cancelled(&self) -> TardisResult<bool> {
+        let funs = TardisFuns::inst_with_db_conn(self.code.clone(), None);
+        let cancelled = funs
+            .db()
+            .count(
+                Query::select()
+                    .column(rbum_task::Column::Id)
+                    .from(rbum_task::Entity)
+                    .and_where(Expr::col(rbum_task::Column::Id).eq(self.task_id.as_str()))
+                    .and_where(Expr::col(rbum_task::Column::Status).eq(RbumTaskStatusKind::Cancelled.to_int())),
+            )
+            .await?;
+        Ok(cancelled > 0)
     }
+}
+
+pub struct TaskProcessor;
 
-    pub async fn execute_task<P, T>(cache_key: &str, process: P, funs: &TardisFunsInst) -> TardisResult<i64>
+impl TaskProcessor {
+    /// Execute the process in the background, returns the task id.
+    ///
+    /// The task record is saved without the transaction of `funs`, so it is visible to the process right away and kept even if the caller rolls back.
+    /// The process is called again for each retry, a panic of the process fails the execution as an error does.
+    pub async fn execute_task<P, T>(kind: &str, retry_policy: Option<RbumTaskRetryPolicy>, process: P, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<String>
     where
-        P: FnOnce() -> T + Send + Sync + 'static,
-        T: Future<Output = TardisResult<()>> + Send + 'static,
+        P: Fn(TaskHandle) -> T + Send + Sync + 'static,
+        T: Future<Output = TardisResult<Option<JsonValue>>> + Send + 'static,
     {
-        let task_id = TaskProcessor::init_task(cache_key, funs.cache()).await?;
-        let cache_client = funs.cache();
-        let cache_key = cache_key.to_string();
-        tardis::tokio::spawn(async move {
-            let result = process().await;
-            match result {
-                Ok(_) => match TaskProcessor::set_status(&cache_key, task_id, true, cache_client).await {
-                    Ok(_) => {}
-                    Err(e) => log::error!("Asynchronous task [{}] process error:{:?}", task_id, e),
+        let retry_policy = retry_policy.unwrap_or_default();
+        let task_id = TardisFuns::field.nanoid();
+        let code = funs.module_code().to_string();
+        let task_funs = TardisFuns::inst_with_db_conn(code.clone(), None);
+        task_funs
+            .db()
+            .insert_one(
+                rbum_task::ActiveModel {
+                    id: Set(task_id.clone()),
+                    kind: Set(kind.to_string()),
+                    status: Set(RbumTaskStatusKind::Pending.to_int()),
+                    progress: Set(0),
+                    result: Set("".to_string()),
+                    error: Set("".to_string()),
+                    retry_times: Set(0),
+                    max_retry_times: Set(retry_policy.max_retry_times),
+                    retry_interval_sec: Set(retry_policy.retry_interval_sec),
+                    ..Default::default()
                 },
-                Err(e) => {
-                    log::error!("Asynchronous task [{}] process error:{:?}", task_id, e);
-                }
+                ctx,
+            )
+            .await?;
+        let handle = TaskHandle { task_id: task_id.clone(), code };
+        tardis::tokio::spawn(async move {
+            let task_id = handle.task_id.clone();
+            let heartbeat = Self::start_heartbeat(&handle);
+            let result = Self::do_execute_task(process, retry_policy, handle).await;
+            if let Some(heartbeat) = heartbeat {
+                heartbeat.abort();
+            }
+            if let Err(e) = result {
+                log::error!("[BIOS.Basic.Task] task [{}] process error:{:?}", task_id, e);
             }
         });
         Ok(task_id)
     }
 
-    pub async fn execute_task_with_ctx<P, T>(cache_key: &str, process: P, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()>
+    /// Same as [`execute_task`](Self::execute_task), the task id is appended to the context, see [`get_task_id_with_ctx`](Self::get_task_id_with_ctx).
+    pub async fn execute_task_with_ctx<P, T>(kind: &str, retry_policy: Option<RbumTaskRetryPolicy>, process: P, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()>
     where
-        P: FnOnce() -> T + Send + Sync + 'static,
-        T: Future<Output = TardisResult<()>> + Send + 'static,
+        P: Fn(TaskHandle) -> T + Send + Sync + 'static,
+        T: Future<Output = TardisResult<Option<JsonValue>>> + Send + 'static,
     {
-        let task_id = Self::execute_task(cache_key, process, funs).await?;
+        let task_id = Self::execute_task(kind, retry_policy, process, funs, ctx).await?;
         if let Some(exist_task_ids) = ctx.get_ext(TASK_IN_CTX_FLAG)? {
             ctx.add_ext(TASK_IN_CTX_FLAG, &format!("{},{}", exist_task_ids, task_id))
         } else {
-            ctx.add_ext(TASK_IN_CTX_FLAG, &task_id.to_string())
+            ctx.add_ext(TASK_IN_CTX_FLAG, &task_id)
         }
     }
 
+    /// Get the comma separated ids of the tasks executed with the context.
     pub fn get_task_id_with_ctx(ctx: &TardisContext) -> TardisResult<Option<String>> {
         ctx.get_ext(TASK_IN_CTX_FLAG)
     }
+
+    async fn do_execute_task<P, T>(process: P, retry_policy: RbumTaskRetryPolicy, handle: TaskHandle) -> TardisResult<()>
+    where
+        P: Fn(TaskHandle) -> T + Send + Sync + 'static,
+        T: Future<Output = TardisResult<Option<JsonValue>>> + Send + 'static,
+    {
+        let funs = TardisFuns::inst_with_db_conn(handle.code.clone(), None);
+        let mut retry_times = 0;
+        loop {
+            if !Self::transfer_status(&handle.task_id, &[RbumTaskStatusKind::Pending], RbumTaskStatusKind::Running, vec![], &funs).await? {
+                // Cancelled
+                return Ok(());
+            }
+            // The process is spawned separately so that its panic is caught by the join
+            let result = match tardis::tokio::spawn(process(handle.clone())).await {
+                Ok(result) => result,
+                Err(e) => Err(TardisError::internal_error(&format!("task panicked: {:?}", e), "500-rbum-task-panicked")),
+            };
+            match result {
+                Ok(result) => {
+                    Self::transfer_status(
+                        &handle.task_id,
+                        &[RbumTaskStatusKind::Running],
+                        RbumTaskStatusKind::Succeeded,
+                        vec![
+                            (rbum_task::Column::Progress, 100u8.into()),
+                            (rbum_task::Column::Result, result.map(|result| result.to_string()).unwrap_or_default().into()),
+                        ],
+                        &funs,
+                    )
+                    .await?;
+                    return Ok(());
+                }
+                Err(e) if retry_times < retry_policy.max_retry_times => {
+                    retry_times += 1;
+                    log::warn!(
+                        "[BIOS.Basic.Task] task [{}] process error, retry {} of {}:{:?}",
+                        handle.task_id,
+                        retry_times,
+                        retry_policy.max_retry_times,
+                        e
+                    );
+                    if !Self::transfer_status(
+                        &handle.task_id,
+                        &[RbumTaskStatusKind::Running],
+                        RbumTaskStatusKind::Pending,
+                        vec![(rbum_task::Column::Error, format!("{:?}", e).into()), (rbum_task::Column::RetryTimes, retry_times.into())],
+                        &funs,
+                    )
+                    .await?
+                    {
+                        return Ok(());
+                    }
+                    tardis::tokio::time::sleep(Duration::from_secs(retry_policy.retry_interval_sec as u64)).await;
+                }
+                Err(e) => {
+                    Self::transfer_status(
+                        &handle.task_id,
+                        &[RbumTaskStatusKind::Running],
+                        RbumTaskStatusKind::Failed,
+                        vec![(rbum_task::Column::Error, format!("{:?}", e).into())],
+                        &funs,
+                    )
+                    .await?;
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    /// Keep touching the update time of the task while the processor holds it, including the sleeps between the retries,
+    /// so that only the interrupted tasks are failed by the [timeout task](Self::start_timeout_task).
+    fn start_heartbeat(handle: &TaskHandle) -> Option<JoinHandle<()>> {
+        let timeout_sec = RbumConfigManager::get_config(&handle.code, |conf| conf.task_timeout_sec);
+        if timeout_sec == 0 {
+            return None;
+        }
+        let handle = handle.clone();
+        Some(tardis::tokio::spawn(async move {
+            let mut interval = tardis::tokio::time::interval(Duration::from_secs((timeout_sec / 4).max(1)));
+            loop {
+                interval.tick().await;
+                let funs = TardisFuns::inst_with_db_conn(handle.code.clone(), None);
+                let result = funs
+                    .db()
+                    .execute(
+                        Query::update()
+                            .table(rbum_task::Entity)
+                            .value(rbum_task::Column::UpdateTime, Utc::now().into())
+                            .and_where(Expr::col(rbum_task::Column::Id).eq(handle.task_id.as_str()))
+                            .and_where(Expr::col(rbum_task::Column::Status).is_in(vec![RbumTaskStatusKind::Pending.to_int(), RbumTaskStatusKind::Running.to_int()])),
+                    )
+                    .await;
+                if let Err(e) = result {
+                    log::warn!("[BIOS.Basic.Task] task [{}] heartbeat error:{:?}", handle.task_id, e);
+                }
+            }
+        }))
+    }
+
+    /// Transfer the task to the status if it is in one of the expected statuses, returns whether it is transferred.
+    async fn transfer_status(
+        task_id: &str,
+        from_status: &[RbumTaskStatusKind],
+        to_status: RbumTaskStatusKind,
+        mut values: Vec<(rbum_task::Column, SimpleExpr)>,
+        funs: &TardisFunsInst,
+    ) -> TardisResult<bool> {
+        values.push((rbum_task::Column::Status, to_status.to_int().into()));
+        values.push((rbum_task::Column::UpdateTime, Utc::now().into()));
+        let result = funs
+            .db()
+            .execute(
+                Query::update()
+                    .table(rbum_task::Entity)
+                    .values(values)
+                    .and_where(Expr::col(rbum_task::Column::Id).eq(task_id))
+                    .and_where(Expr::col(rbum_task::Column::Status).is_in(from_status.iter().map(|status| status.to_int()).collect::<Vec<u8>>())),
+            )
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Cancel the pending or running task.
+    pub async fn cancel_task(task_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        let task = Self::get_task(task_id, funs, ctx).await?;
+        if task.status.is_finished()
+            || !Self::transfer_status(
+                task_id,
+                &[RbumTaskStatusKind::Pending, RbumTaskStatusKind::Running],
+                RbumTaskStatusKind::Cancelled,
+                vec![],
+                funs,
+            )
+            .await?
+        {
+            return Err(funs.err().conflict("task", "cancel", &format!("task {} has been finished", task_id), "409-rbum-task-finished"));
+        }
+        Ok(())
+    }
+
+    /// Get the task executed in the own paths or its sub paths.
+    pub async fn get_task(task_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<RbumTaskDetailResp> {
+        let query = Self::package_task_query(
+            &RbumTaskFilterReq {
+                basic: RbumBasicFilterReq {
+                    ids: Some(vec![task_id.to_string()]),
+                    with_sub_own_paths: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ctx,
+        );
+        funs.db().get_dto(&query).await?.ok_or_else(|| funs.err().not_found("task", "get", &format!("not found task {}", task_id), "404-rbum-task-not-exist"))
+    }
+
+    pub async fn find_tasks(filter: &RbumTaskFilterReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Vec<RbumTaskDetailResp>> {
+        let mut query = Self::package_task_query(filter, ctx);
+        query.order_by(rbum_task::Column::CreateTime, Order::Desc);
+        funs.db().find_dtos(&query).await
+    }
+
+    pub async fn paginate_tasks(
+        filter: &RbumTaskFilterReq,
+        page_number: u64,
+        page_size: u64,
+        desc_sort_by_create: Option<bool>,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<TardisPage<RbumTaskDetailResp>> {
+        let mut query = Self::package_task_query(filter, ctx);
+        query.order_by(rbum_task::Column::CreateTime, if desc_sort_by_create.unwrap_or(true) { Order::Desc } else { Order::Asc });
+        let (records, total_size) = funs.db().paginate_dtos(&query, page_number, page_size).await?;
+        Ok(TardisPage {
+            page_size,
+            page_number,
+            total_size,
+            records,
+        })
+    }
+
+    fn package_task_query(filter: &RbumTaskFilterReq, ctx: &TardisContext) -> SelectStatement {
+        let mut query = Query::select();
+        query
+            .columns(vec![
+                (rbum_task::Entity, rbum_task::Column::Id),
+                (rbum_task::Entity, rbum_task::Column::Kind),
+                (rbum_task::Entity, rbum_task::Column::Status),
+                (rbum_task::Entity, rbum_task::Column::Progress),
+                (rbum_task::Entity, rbum_task::Column::Result),
+                (rbum_task::Entity, rbum_task::Column::Error),
+                (rbum_task::Entity, rbum_task::Column::RetryTimes),
+                (rbum_task::Entity, rbum_task::Column::MaxRetryTimes),
+                (rbum_task::Entity, rbum_task::Column::OwnPaths),
+                (rbum_task::Entity, rbum_task::Column::Owner),
+                (rbum_task::Entity, rbum_task::Column::CreateTime),
+                (rbum_task::Entity, rbum_task::Column::UpdateTime),
+            ])
+            .from(rbum_task::Entity);
+        if let Some(kind) = &filter.kind {
+            query.and_where(Expr::tbl(rbum_task::Entity, rbum_task::Column::Kind).eq(kind.as_str()));
+        }
+        if let Some(status) = &filter.status {
+            query.and_where(Expr::tbl(rbum_task::Entity, rbum_task::Column::Status).eq(status.to_int()));
+        }
+        query.with_filter(rbum_task::Entity.table_name(), &filter.basic, false, false, ctx);
+        query
+    }
+
+    /// Start a background task that fails the tasks of the module that have not been updated for a long time, e.g. interrupted by a restart,
+    /// the timeout is configured by [task_timeout_sec](crate::rbum::rbum_config::RbumConfig::task_timeout_sec).
+    ///
+    /// The tasks still held by a processor are kept updated by its heartbeat, however long they run.
+    pub fn start_timeout_task(code: &str) {
+        let timeout_sec = RbumConfigManager::get_config(code, |conf| conf.task_timeout_sec);
+        if timeout_sec == 0 {
+            return;
+        }
+        let code = code.to_string();
+        tardis::tokio::spawn(async move {
+            let mut interval = tardis::tokio::time::interval(Duration::from_secs(timeout_sec));
+            loop {
+                interval.tick().await;
+                let funs = TardisFuns::inst_with_db_conn(code.clone(), None);
+                let result = funs
+                    .db()
+                    .execute(
+                        Query::update()
+                            .table(rbum_task::Entity)
+                            .values(vec![
+                                (rbum_task::Column::Status, RbumTaskStatusKind::Failed.to_int().into()),
+                                (rbum_task::Column::Error, "task timeout, it may be interrupted".into()),
+                                (rbum_task::Column::UpdateTime, Utc::now().into()),
+                            ])
+                            .and_where(Expr::col(rbum_task::Column::Status).is_in(vec![RbumTaskStatusKind::Pending.to_int(), RbumTaskStatusKind::Running.to_int()]))
+                            .and_where(Expr::col(rbum_task::Column::UpdateTime).lt(Utc::now() - tardis::chrono::Duration::seconds(timeout_sec as i64))),
+                    )
+                    .await;
+                match result {
+                    Ok(result) if result.rows_affected() > 0 => log::warn!("[BIOS.Basic.Task] failed {} timeout tasks of {}", result.rows_affected(), code),
+                    Ok(_) => {}
+                    Err(e) => log::error!("[BIOS.Basic.Task] fail the timeout tasks of {} error: {:?}", code, e),
+                }
+            }
+        });
+    }
 }
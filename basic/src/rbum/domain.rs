@@ -1,6 +1,8 @@
 pub mod rbum_cert;
 pub mod rbum_cert_conf;
 pub mod rbum_domain;
+pub mod rbum_event_outbox;
+pub mod rbum_history;
 pub mod rbum_item;
 pub mod rbum_item_attr;
 pub mod rbum_kind;
@@ -11,3 +13,4 @@ pub mod rbum_rel_env;
 pub mod rbum_set;
 pub mod rbum_set_cate;
 pub mod rbum_set_item;
+pub mod rbum_task;
@@ -64,6 +64,7 @@ pub struct Model {
     pub owner: String,
     pub create_time: chrono::DateTime<Utc>,
     pub update_time: chrono::DateTime<Utc>,
+    pub version: u32,
 }
 
 impl TardisActiveModel for ActiveModel {
@@ -111,6 +112,7 @@ impl TardisActiveModel for ActiveModel {
             .col(ColumnDef::new(Column::Owner).not_null().string())
             .col(ColumnDef::new(Column::CreateTime).extra("DEFAULT CURRENT_TIMESTAMP".to_string()).timestamp())
             .col(ColumnDef::new(Column::UpdateTime).extra("DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP".to_string()).timestamp())
+            .col(ColumnDef::new(Column::Version).not_null().unsigned().default(0))
             .to_owned()
     }
 
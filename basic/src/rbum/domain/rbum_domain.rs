@@ -29,6 +29,7 @@ pub struct Model {
     pub owner: String,
     pub create_time: chrono::DateTime<Utc>,
     pub update_time: chrono::DateTime<Utc>,
+    pub version: u32,
 
     pub scope_level: i8,
 }
@@ -60,6 +61,7 @@ impl TardisActiveModel for ActiveModel {
             .col(ColumnDef::new(Column::Owner).not_null().string())
             .col(ColumnDef::new(Column::CreateTime).extra("DEFAULT CURRENT_TIMESTAMP".to_string()).timestamp())
             .col(ColumnDef::new(Column::UpdateTime).extra("DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP".to_string()).timestamp())
+            .col(ColumnDef::new(Column::Version).not_null().unsigned().default(0))
             // With Scope
             .col(ColumnDef::new(Column::ScopeLevel).not_null().tiny_integer())
             .to_owned()
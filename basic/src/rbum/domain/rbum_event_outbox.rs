@@ -0,0 +1,93 @@
+use tardis::basic::dto::TardisContext;
+use tardis::chrono::{self, Utc};
+use tardis::db::reldb_client::TardisActiveModel;
+use tardis::db::sea_orm;
+use tardis::db::sea_orm::prelude::*;
+use tardis::db::sea_orm::sea_query::{ColumnDef, Index, IndexCreateStatement, Table, TableCreateStatement};
+use tardis::db::sea_orm::*;
+
+/// Event outbox model
+///
+/// The events are written in the same transaction as the changes of the records,
+/// and published to the message queue by the [relay](crate::rbum::helper::rbum_event_helper::start_relay_task) after they are committed.
+/// A record is deleted once its event has been published, or kept as a dead letter after failing too many times
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "rbum_event_outbox")]
+pub struct Model {
+    /// Id of the event, also used by the consumers to dedupe the redelivered events
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// Code of the module that wrote the event, each module relays its own events
+    pub module_code: String,
+    /// Message queue topic to publish to, empty if the event is only handled by the relay
+    pub topic: String,
+    /// Table name of the changed record
+    pub table_name: String,
+    /// Id of the changed record, empty if the event covers the records of a batch operation
+    pub record_id: String,
+    /// Json message header
+    pub header: String,
+    pub message: String,
+    /// Auto-increment sequence assigned by the database, the events are published in this order
+    pub seq: i64,
+    /// Until when the event is claimed by a relay, it can be claimed by another relay after that
+    pub lease_expire_time: Option<chrono::DateTime<Utc>>,
+    /// Failed publishing attempts
+    pub retry_times: u32,
+    /// Error message of the last failed publishing attempt
+    pub error: String,
+    /// Whether the event is given up after failing [too many times](crate::rbum::rbum_config::RbumConfig::event_relay_max_retry_times)
+    pub dead_letter: bool,
+
+    pub create_time: chrono::DateTime<Utc>,
+}
+
+impl TardisActiveModel for ActiveModel {
+    fn fill_ctx(&mut self, _: &TardisContext, _: bool) {}
+
+    fn create_table_statement(_: DbBackend) -> TableCreateStatement {
+        Table::create()
+            .table(Entity.table_ref())
+            .if_not_exists()
+            .engine("InnoDB")
+            .character_set("utf8mb4")
+            .collate("utf8mb4_0900_as_cs")
+            .col(ColumnDef::new(Column::Id).not_null().string().primary_key())
+            // Specific
+            .col(ColumnDef::new(Column::ModuleCode).not_null().string())
+            .col(ColumnDef::new(Column::Topic).not_null().string())
+            .col(ColumnDef::new(Column::TableName).not_null().string())
+            .col(ColumnDef::new(Column::RecordId).not_null().string())
+            .col(ColumnDef::new(Column::Header).not_null().text())
+            .col(ColumnDef::new(Column::Message).not_null().text())
+            .col(ColumnDef::new(Column::Seq).not_null().big_integer().auto_increment().unique_key())
+            .col(ColumnDef::new(Column::LeaseExpireTime).timestamp())
+            .col(ColumnDef::new(Column::RetryTimes).not_null().unsigned())
+            .col(ColumnDef::new(Column::Error).not_null().text())
+            .col(ColumnDef::new(Column::DeadLetter).not_null().boolean())
+            // Basic
+            .col(ColumnDef::new(Column::CreateTime).extra("DEFAULT CURRENT_TIMESTAMP".to_string()).timestamp())
+            .to_owned()
+    }
+
+    fn create_index_statement() -> Vec<IndexCreateStatement> {
+        vec![Index::create()
+            .name(&format!(
+                "idx-{}-{}-{}-{}",
+                Entity.table_name(),
+                Column::ModuleCode.to_string(),
+                Column::DeadLetter.to_string(),
+                Column::Seq.to_string()
+            ))
+            .table(Entity)
+            .col(Column::ModuleCode)
+            .col(Column::DeadLetter)
+            .col(Column::Seq)
+            .to_owned()]
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
@@ -0,0 +1,85 @@
+use tardis::basic::dto::TardisContext;
+use tardis::chrono::{self, Utc};
+use tardis::db::reldb_client::TardisActiveModel;
+use tardis::db::sea_orm;
+use tardis::db::sea_orm::prelude::*;
+use tardis::db::sea_orm::sea_query::{ColumnDef, Index, IndexCreateStatement, Table, TableCreateStatement};
+use tardis::db::sea_orm::*;
+
+/// Change history model
+///
+/// Each add, modify or delete of a record in the tables configured by [history_domains](crate::rbum::rbum_config::RbumConfig::history_domains)
+/// is stored as a history with the snapshots of the record before and after the change
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "rbum_history")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// Table name of the changed record
+    pub table_name: String,
+    /// Id of the changed record
+    pub record_id: String,
+    /// Operate, `c` add, `u` modify, `d` delete
+    pub operate: String,
+    /// Comma separated names of the changed fields
+    pub changed_fields: String,
+    /// Json snapshot of the record before the change, empty when added
+    pub before: String,
+    /// Json snapshot of the record after the change, empty when deleted
+    pub after: String,
+    /// Auto-increment sequence assigned by the database, orders the changes made in the same second
+    pub seq: i64,
+
+    /// Own paths of the changed record
+    pub own_paths: String,
+    /// Operator of the change
+    pub owner: String,
+    pub create_time: chrono::DateTime<Utc>,
+}
+
+impl TardisActiveModel for ActiveModel {
+    fn fill_ctx(&mut self, ctx: &TardisContext, is_insert: bool) {
+        if is_insert {
+            self.owner = Set(ctx.owner.to_string());
+        }
+    }
+
+    fn create_table_statement(_: DbBackend) -> TableCreateStatement {
+        Table::create()
+            .table(Entity.table_ref())
+            .if_not_exists()
+            .engine("InnoDB")
+            .character_set("utf8mb4")
+            .collate("utf8mb4_0900_as_cs")
+            .col(ColumnDef::new(Column::Id).not_null().string().primary_key())
+            // Specific
+            .col(ColumnDef::new(Column::TableName).not_null().string())
+            .col(ColumnDef::new(Column::RecordId).not_null().string())
+            .col(ColumnDef::new(Column::Operate).not_null().string())
+            .col(ColumnDef::new(Column::ChangedFields).not_null().text())
+            .col(ColumnDef::new(Column::Before).not_null().text())
+            .col(ColumnDef::new(Column::After).not_null().text())
+            .col(ColumnDef::new(Column::Seq).not_null().big_integer().auto_increment().unique_key())
+            // Basic
+            .col(ColumnDef::new(Column::OwnPaths).not_null().string())
+            .col(ColumnDef::new(Column::Owner).not_null().string())
+            .col(ColumnDef::new(Column::CreateTime).not_null().timestamp())
+            .to_owned()
+    }
+
+    fn create_index_statement() -> Vec<IndexCreateStatement> {
+        vec![Index::create()
+            .name(&format!("idx-{}-{}-{}", Entity.table_name(), Column::TableName.to_string(), Column::RecordId.to_string()))
+            .table(Entity)
+            .col(Column::TableName)
+            .col(Column::RecordId)
+            .col(Column::CreateTime)
+            .col(Column::Seq)
+            .to_owned()]
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
@@ -30,10 +30,15 @@ pub struct Model {
     pub owner: String,
     pub create_time: chrono::DateTime<Utc>,
     pub update_time: chrono::DateTime<Utc>,
+    pub version: u32,
 
     pub scope_level: i8,
 
     pub disabled: bool,
+    /// Whether the resource is in the recycle bin, see [soft_delete_retention_sec](crate::rbum::domain::rbum_kind::Model::soft_delete_retention_sec)
+    pub deleted: bool,
+    /// Time of moving into the recycle bin
+    pub delete_time: Option<chrono::DateTime<Utc>>,
 }
 
 impl TardisActiveModel for ActiveModel {
@@ -62,10 +67,13 @@ impl TardisActiveModel for ActiveModel {
             .col(ColumnDef::new(Column::Owner).not_null().string())
             .col(ColumnDef::new(Column::CreateTime).extra("DEFAULT CURRENT_TIMESTAMP".to_string()).timestamp())
             .col(ColumnDef::new(Column::UpdateTime).extra("DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP".to_string()).timestamp())
+            .col(ColumnDef::new(Column::Version).not_null().unsigned().default(0))
             // With Scope
             .col(ColumnDef::new(Column::ScopeLevel).not_null().tiny_integer())
             // With Status
             .col(ColumnDef::new(Column::Disabled).not_null().boolean())
+            .col(ColumnDef::new(Column::Deleted).not_null().boolean())
+            .col(ColumnDef::new(Column::DeleteTime).timestamp())
             .to_owned()
     }
 
@@ -74,6 +82,7 @@ impl TardisActiveModel for ActiveModel {
             Index::create().name(&format!("idx-{}-{}", Entity.table_name(), Column::OwnPaths.to_string())).table(Entity).col(Column::OwnPaths).to_owned(),
             Index::create().name(&format!("idx-{}-{}", Entity.table_name(), Column::RelRbumKindId.to_string())).table(Entity).col(Column::RelRbumKindId).to_owned(),
             Index::create().name(&format!("idx-{}-{}", Entity.table_name(), Column::RelRbumDomainId.to_string())).table(Entity).col(Column::RelRbumDomainId).to_owned(),
+            Index::create().name(&format!("idx-{}-{}", Entity.table_name(), Column::Deleted.to_string())).table(Entity).col(Column::Deleted).to_owned(),
             Index::create()
                 .name(&format!("idx-{}-{}", Entity.table_name(), Column::Code.to_string()))
                 .table(Entity)
@@ -24,11 +24,14 @@ pub struct Model {
     pub sort: u32,
     /// Each resource kind can specify an extension table for storing customized data
     pub ext_table_name: String,
+    /// Seconds that the deleted resources of this kind are kept in the recycle bin, `0` means the resources are deleted directly
+    pub soft_delete_retention_sec: u32,
 
     pub own_paths: String,
     pub owner: String,
     pub create_time: chrono::DateTime<Utc>,
     pub update_time: chrono::DateTime<Utc>,
+    pub version: u32,
 
     pub scope_level: i8,
 }
@@ -56,11 +59,13 @@ impl TardisActiveModel for ActiveModel {
             .col(ColumnDef::new(Column::Icon).not_null().string())
             .col(ColumnDef::new(Column::Sort).not_null().unsigned())
             .col(ColumnDef::new(Column::ExtTableName).not_null().string())
+            .col(ColumnDef::new(Column::SoftDeleteRetentionSec).not_null().unsigned())
             // Basic
             .col(ColumnDef::new(Column::OwnPaths).not_null().string())
             .col(ColumnDef::new(Column::Owner).not_null().string())
             .col(ColumnDef::new(Column::CreateTime).extra("DEFAULT CURRENT_TIMESTAMP".to_string()).timestamp())
             .col(ColumnDef::new(Column::UpdateTime).extra("DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP".to_string()).timestamp())
+            .col(ColumnDef::new(Column::Version).not_null().unsigned().default(0))
             // With Scope
             .col(ColumnDef::new(Column::ScopeLevel).not_null().tiny_integer())
             .to_owned()
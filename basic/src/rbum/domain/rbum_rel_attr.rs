@@ -20,6 +20,8 @@ pub struct Model {
     pub is_from: bool,
     /// Attribute value
     pub value: String,
+    /// [Operator](crate::rbum::rbum_enumeration::RbumRelAttrOperatorKind) comparing the value with the checked value
+    pub operator: String,
     /// Attribute name, redundant field
     pub name: String,
     /// Is it for record only \
@@ -34,6 +36,7 @@ pub struct Model {
     pub owner: String,
     pub create_time: chrono::DateTime<Utc>,
     pub update_time: chrono::DateTime<Utc>,
+    pub version: u32,
 }
 
 impl TardisActiveModel for ActiveModel {
@@ -55,6 +58,7 @@ impl TardisActiveModel for ActiveModel {
             // Specific
             .col(ColumnDef::new(Column::IsFrom).not_null().boolean())
             .col(ColumnDef::new(Column::Value).not_null().string())
+            .col(ColumnDef::new(Column::Operator).not_null().string())
             .col(ColumnDef::new(Column::Name).not_null().string())
             .col(ColumnDef::new(Column::RecordOnly).not_null().boolean())
             .col(ColumnDef::new(Column::RelRbumKindAttrId).not_null().string())
@@ -64,6 +68,7 @@ impl TardisActiveModel for ActiveModel {
             .col(ColumnDef::new(Column::Owner).not_null().string())
             .col(ColumnDef::new(Column::CreateTime).extra("DEFAULT CURRENT_TIMESTAMP".to_string()).timestamp())
             .col(ColumnDef::new(Column::UpdateTime).extra("DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP".to_string()).timestamp())
+            .col(ColumnDef::new(Column::Version).not_null().unsigned().default(0))
             .to_owned()
     }
 
@@ -0,0 +1,85 @@
+use tardis::basic::dto::TardisContext;
+use tardis::chrono::{self, Utc};
+use tardis::db::reldb_client::TardisActiveModel;
+use tardis::db::sea_orm;
+use tardis::db::sea_orm::prelude::*;
+use tardis::db::sea_orm::sea_query::{ColumnDef, Index, IndexCreateStatement, Table, TableCreateStatement};
+use tardis::db::sea_orm::*;
+
+/// Asynchronous task model
+///
+/// Tasks are executed by the [task processor](crate::process::task_processor::TaskProcessor),
+/// the records keep the status, progress and outcome of the executions
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "rbum_task")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// Kind of the task, used to categorize the tasks
+    pub kind: String,
+    /// @see [status](crate::rbum::rbum_enumeration::RbumTaskStatusKind)
+    pub status: u8,
+    /// Progress percentage, 0 to 100
+    pub progress: u8,
+    /// Json result of the succeeded task
+    pub result: String,
+    /// Error message of the last failed execution
+    pub error: String,
+    /// Executed retries
+    pub retry_times: u32,
+    /// Maximum retries when the execution fails, 0 means no retry
+    pub max_retry_times: u32,
+    /// Seconds to wait before each retry
+    pub retry_interval_sec: u32,
+
+    pub own_paths: String,
+    pub owner: String,
+    pub create_time: chrono::DateTime<Utc>,
+    pub update_time: chrono::DateTime<Utc>,
+}
+
+impl TardisActiveModel for ActiveModel {
+    fn fill_ctx(&mut self, ctx: &TardisContext, is_insert: bool) {
+        if is_insert {
+            self.own_paths = Set(ctx.own_paths.to_string());
+            self.owner = Set(ctx.owner.to_string());
+        }
+    }
+
+    fn create_table_statement(_: DbBackend) -> TableCreateStatement {
+        Table::create()
+            .table(Entity.table_ref())
+            .if_not_exists()
+            .engine("InnoDB")
+            .character_set("utf8mb4")
+            .collate("utf8mb4_0900_as_cs")
+            .col(ColumnDef::new(Column::Id).not_null().string().primary_key())
+            // Specific
+            .col(ColumnDef::new(Column::Kind).not_null().string())
+            .col(ColumnDef::new(Column::Status).not_null().tiny_unsigned())
+            .col(ColumnDef::new(Column::Progress).not_null().tiny_unsigned())
+            .col(ColumnDef::new(Column::Result).not_null().text())
+            .col(ColumnDef::new(Column::Error).not_null().text())
+            .col(ColumnDef::new(Column::RetryTimes).not_null().unsigned())
+            .col(ColumnDef::new(Column::MaxRetryTimes).not_null().unsigned())
+            .col(ColumnDef::new(Column::RetryIntervalSec).not_null().unsigned())
+            // Basic
+            .col(ColumnDef::new(Column::OwnPaths).not_null().string())
+            .col(ColumnDef::new(Column::Owner).not_null().string())
+            .col(ColumnDef::new(Column::CreateTime).extra("DEFAULT CURRENT_TIMESTAMP".to_string()).timestamp())
+            .col(ColumnDef::new(Column::UpdateTime).extra("DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP".to_string()).timestamp())
+            .to_owned()
+    }
+
+    fn create_index_statement() -> Vec<IndexCreateStatement> {
+        vec![
+            Index::create().name(&format!("idx-{}-{}", Entity.table_name(), Column::OwnPaths.to_string())).table(Entity).col(Column::OwnPaths).to_owned(),
+            Index::create().name(&format!("idx-{}-{}", Entity.table_name(), Column::Status.to_string())).table(Entity).col(Column::Status).col(Column::UpdateTime).to_owned(),
+        ]
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
@@ -1,7 +1,11 @@
+pub mod rbum_batch_dto;
 pub mod rbum_cert_conf_dto;
 pub mod rbum_cert_dto;
+#[cfg(feature = "default")]
+pub mod rbum_cursor_dto;
 pub mod rbum_domain_dto;
 pub mod rbum_filer_dto;
+pub mod rbum_history_dto;
 pub mod rbum_item_attr_dto;
 pub mod rbum_item_dto;
 pub mod rbum_kind_attr_dto;
@@ -13,3 +17,4 @@ pub mod rbum_rel_env_dto;
 pub mod rbum_set_cate_dto;
 pub mod rbum_set_dto;
 pub mod rbum_set_item_dto;
+pub mod rbum_task_dto;
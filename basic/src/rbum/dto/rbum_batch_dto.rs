@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use tardis::basic::error::TardisError;
+#[cfg(feature = "default")]
+use tardis::web::poem_openapi;
+
+/// Result of a batch operation, the failed records do not prevent the others from succeeding
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[cfg_attr(feature = "default", derive(poem_openapi::Object))]
+pub struct RbumBatchResp {
+    /// Ids of the succeeded records, in the order of the requests
+    pub succeed_ids: Vec<String>,
+    pub errors: Vec<RbumBatchErrorResp>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "default", derive(poem_openapi::Object))]
+pub struct RbumBatchErrorResp {
+    /// Index of the failed request in the batch
+    pub index: usize,
+    /// Id of the failed record, empty if the record is not added
+    pub id: String,
+    pub code: String,
+    pub message: String,
+}
+
+impl RbumBatchResp {
+    pub fn add_error(&mut self, index: usize, id: &str, error: TardisError) {
+        self.errors.push(RbumBatchErrorResp {
+            index,
+            id: id.to_string(),
+            code: error.code,
+            message: error.message,
+        });
+    }
+}
@@ -150,6 +150,7 @@ pub struct RbumCertConfDetailResp {
     pub owner_name: Option<String>,
     pub create_time: DateTime<Utc>,
     pub update_time: DateTime<Utc>,
+    pub version: u32,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
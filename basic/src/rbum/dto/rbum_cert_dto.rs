@@ -115,4 +115,5 @@ pub struct RbumCertDetailResp {
     pub owner_name: Option<String>,
     pub create_time: DateTime<Utc>,
     pub update_time: DateTime<Utc>,
+    pub version: u32,
 }
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+use tardis::web::poem_openapi;
+use tardis::web::poem_openapi::types::{ParseFromJSON, ToJSON};
+
+/// Page of the cursor based pagination, which has no total size, counting is what makes deep pages slow on large tables
+#[derive(poem_openapi::Object, Serialize, Deserialize, Debug)]
+#[oai(inline)]
+pub struct RbumCursorPage<T: ParseFromJSON + ToJSON> {
+    pub page_size: u64,
+    /// Opaque cursor of the next page, `None` if this is the last page
+    pub next_cursor: Option<String>,
+    pub records: Vec<T>,
+}
@@ -36,6 +36,8 @@ pub struct RbumDomainModifyReq {
     pub sort: Option<u32>,
 
     pub scope_level: Option<RbumScopeLevelKind>,
+    /// Version of the domain that the modification is based on, a stale version is rejected with a conflict
+    pub version: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -70,6 +72,7 @@ pub struct RbumDomainDetailResp {
     pub owner_name: Option<String>,
     pub create_time: DateTime<Utc>,
     pub update_time: DateTime<Utc>,
+    pub version: u32,
 
     pub scope_level: RbumScopeLevelKind,
 }
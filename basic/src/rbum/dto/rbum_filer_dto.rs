@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "default")]
 use tardis::web::poem_openapi;
 
-use crate::rbum::rbum_enumeration::{RbumCertRelKind, RbumCertStatusKind, RbumRelFromKind, RbumScopeLevelKind, RbumSetCateLevelQueryKind};
+use crate::rbum::rbum_enumeration::{RbumCertRelKind, RbumCertStatusKind, RbumRelFromKind, RbumScopeLevelKind, RbumSetCateLevelQueryKind, RbumTaskStatusKind};
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[cfg_attr(feature = "default", derive(poem_openapi::Object))]
@@ -41,6 +41,7 @@ pub struct RbumCertConfFilterReq {
 pub struct RbumCertFilterReq {
     pub basic: RbumBasicFilterReq,
     pub ak: Option<String>,
+    pub aks: Option<Vec<String>>,
     pub status: Option<RbumCertStatusKind>,
     pub rel: Option<RbumItemRelFilterReq>,
     pub rel_rbum_kind: Option<RbumCertRelKind>,
@@ -138,6 +139,14 @@ pub struct RbumSetItemFilterReq {
     pub rel_rbum_item_domain_ids: Option<Vec<String>>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "default", derive(poem_openapi::Object))]
+pub struct RbumTaskFilterReq {
+    pub basic: RbumBasicFilterReq,
+    pub kind: Option<String>,
+    pub status: Option<RbumTaskStatusKind>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[cfg_attr(feature = "default", derive(poem_openapi::Object))]
 #[serde(default)]
@@ -152,10 +161,76 @@ pub struct RbumItemRelFilterReq {
     pub own_paths: Option<String>,
 }
 
+/// Predicate on an attribute value of the item, only attributes with `idx` can be queried.
+///
+/// All the given conditions must be satisfied.
+/// `eq`, `like` and `in_values` match the stored strings, the `ge` and `le` bounds are compared by the data type of the kind attribute, e.g. `9` is less than `10` for numbers.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "default", derive(poem_openapi::Object))]
+#[serde(default)]
+pub struct RbumItemAttrValueFilterReq {
+    /// Attribute name
+    pub name: String,
+    pub eq: Option<String>,
+    pub like: Option<String>,
+    pub in_values: Option<Vec<String>>,
+    /// Inclusive lower bound
+    pub ge: Option<String>,
+    /// Inclusive upper bound
+    pub le: Option<String>,
+}
+
+/// Ordering by an attribute value of the item, only attributes with `idx` can be sorted by.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "default", derive(poem_openapi::Object))]
+#[serde(default)]
+pub struct RbumItemAttrSortReq {
+    /// Attribute name
+    pub name: String,
+    pub desc: bool,
+}
+
+/// Ordering by a column of the record, used by the cursor based pagination.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "default", derive(poem_openapi::Object))]
+#[serde(default)]
+pub struct RbumSortReq {
+    /// Column name, e.g. `create_time`, `update_time`, `name`, `code` or `sort`
+    pub field: String,
+    pub desc: bool,
+}
+
+impl RbumSortReq {
+    /// Parse the comma separated columns of a query parameter, a column prefixed with `-` is sorted in descending order, e.g. `-sort,name`.
+    pub fn parse(sort_by: &str) -> Vec<RbumSortReq> {
+        sort_by
+            .split(',')
+            .map(|field| field.trim())
+            .filter(|field| !field.is_empty() && *field != "-")
+            .map(|field| match field.strip_prefix('-') {
+                Some(field) => RbumSortReq {
+                    field: field.to_string(),
+                    desc: true,
+                },
+                None => RbumSortReq {
+                    field: field.to_string(),
+                    desc: false,
+                },
+            })
+            .collect()
+    }
+}
+
 pub trait RbumItemFilterFetcher {
     fn basic(&self) -> &RbumBasicFilterReq;
     fn rel(&self) -> &Option<RbumItemRelFilterReq>;
     fn rel2(&self) -> &Option<RbumItemRelFilterReq>;
+    fn attrs(&self) -> &Option<Vec<RbumItemAttrValueFilterReq>> {
+        &None
+    }
+    fn attr_sorts(&self) -> &Option<Vec<RbumItemAttrSortReq>> {
+        &None
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -164,6 +239,8 @@ pub trait RbumItemFilterFetcher {
 pub struct RbumItemBasicFilterReq {
     pub basic: RbumBasicFilterReq,
     pub rel: Option<RbumItemRelFilterReq>,
+    pub attrs: Option<Vec<RbumItemAttrValueFilterReq>>,
+    pub attr_sorts: Option<Vec<RbumItemAttrSortReq>>,
 }
 
 impl RbumItemFilterFetcher for RbumItemBasicFilterReq {
@@ -176,4 +253,10 @@ impl RbumItemFilterFetcher for RbumItemBasicFilterReq {
     fn rel2(&self) -> &Option<RbumItemRelFilterReq> {
         &self.rel
     }
+    fn attrs(&self) -> &Option<Vec<RbumItemAttrValueFilterReq>> {
+        &self.attrs
+    }
+    fn attr_sorts(&self) -> &Option<Vec<RbumItemAttrSortReq>> {
+        &self.attr_sorts
+    }
 }
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use tardis::chrono::{DateTime, Utc};
+use tardis::serde_json::Value as JsonValue;
+#[cfg(feature = "default")]
+use tardis::web::poem_openapi;
+
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "default", derive(poem_openapi::Object))]
+pub struct RbumHistoryResp {
+    pub id: String,
+    pub table_name: String,
+    pub record_id: String,
+    /// `c` add, `u` modify, `d` delete
+    pub operate: String,
+    pub changed_fields: Vec<String>,
+    /// Snapshot of the record before the change, `None` when added
+    pub before: Option<JsonValue>,
+    /// Snapshot of the record after the change, `None` when deleted
+    pub after: Option<JsonValue>,
+
+    pub own_paths: String,
+    /// Operator of the change
+    pub owner: String,
+    pub create_time: DateTime<Utc>,
+}
@@ -67,4 +67,16 @@ pub struct RbumItemAttrDetailResp {
     pub owner_name: Option<String>,
     pub create_time: DateTime<Utc>,
     pub update_time: DateTime<Utc>,
+    pub version: u32,
+}
+
+/// Invalid attribute value, the errors of all the fields are serialized as a JSON array into the error message
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "default", derive(poem_openapi::Object))]
+pub struct RbumItemAttrFieldErrorResp {
+    pub name: String,
+    pub label: String,
+    /// Violated constraint, e.g. `required`, `min_length`, `max_length`, `data_type` or `options`
+    pub code: String,
+    pub message: String,
 }
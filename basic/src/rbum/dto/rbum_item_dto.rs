@@ -51,6 +51,8 @@ pub struct RbumItemModifyReq {
 
     pub scope_level: Option<RbumScopeLevelKind>,
     pub disabled: Option<bool>,
+    /// Version of the item that the modification is based on, a stale version is rejected with a conflict
+    pub version: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -87,7 +89,29 @@ pub struct RbumItemDetailResp {
     pub owner_name: Option<String>,
     pub create_time: DateTime<Utc>,
     pub update_time: DateTime<Utc>,
+    pub version: u32,
 
     pub scope_level: RbumScopeLevelKind,
     pub disabled: bool,
 }
+
+/// Item in the recycle bin
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "default", derive(poem_openapi::Object))]
+pub struct RbumItemRecycleSummaryResp {
+    pub id: String,
+    pub code: String,
+    pub name: String,
+    pub rel_rbum_kind_id: String,
+    pub rel_rbum_domain_id: String,
+
+    pub own_paths: String,
+    pub owner: String,
+    pub create_time: DateTime<Utc>,
+    /// Time of moving into the recycle bin
+    pub delete_time: DateTime<Utc>,
+    /// The item can be restored until this time, after which it will be purged
+    pub expire_time: DateTime<Utc>,
+
+    pub scope_level: RbumScopeLevelKind,
+}
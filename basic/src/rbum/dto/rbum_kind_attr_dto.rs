@@ -72,6 +72,8 @@ pub struct RbumKindAttrModifyReq {
     pub ext: Option<String>,
 
     pub scope_level: Option<RbumScopeLevelKind>,
+    /// Version of the kind attribute that the modification is based on, a stale version is rejected with a conflict
+    pub version: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -140,6 +142,7 @@ pub struct RbumKindAttrDetailResp {
     pub owner_name: Option<String>,
     pub create_time: DateTime<Utc>,
     pub update_time: DateTime<Utc>,
+    pub version: u32,
 
     pub scope_level: RbumScopeLevelKind,
 }
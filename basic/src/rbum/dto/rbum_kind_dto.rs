@@ -22,6 +22,8 @@ pub struct RbumKindAddReq {
     pub sort: Option<u32>,
     #[cfg_attr(feature = "default", oai(validator(min_length = "2", max_length = "255")))]
     pub ext_table_name: Option<String>,
+    /// Seconds that the deleted items are kept in the recycle bin, `0` means the items are deleted directly
+    pub soft_delete_retention_sec: Option<u32>,
 
     pub scope_level: Option<RbumScopeLevelKind>,
 }
@@ -38,8 +40,12 @@ pub struct RbumKindModifyReq {
     pub sort: Option<u32>,
     #[cfg_attr(feature = "default", oai(validator(min_length = "2", max_length = "255")))]
     pub ext_table_name: Option<String>,
+    /// Seconds that the deleted items are kept in the recycle bin, `0` means the items are deleted directly
+    pub soft_delete_retention_sec: Option<u32>,
 
     pub scope_level: Option<RbumScopeLevelKind>,
+    /// Version of the kind that the modification is based on, a stale version is rejected with a conflict
+    pub version: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -51,6 +57,7 @@ pub struct RbumKindSummaryResp {
     pub icon: String,
     pub sort: u32,
     pub ext_table_name: String,
+    pub soft_delete_retention_sec: u32,
 
     pub own_paths: String,
     pub owner: String,
@@ -70,12 +77,14 @@ pub struct RbumKindDetailResp {
     pub icon: String,
     pub sort: u32,
     pub ext_table_name: String,
+    pub soft_delete_retention_sec: u32,
 
     pub own_paths: String,
     pub owner: String,
     pub owner_name: Option<String>,
     pub create_time: DateTime<Utc>,
     pub update_time: DateTime<Utc>,
+    pub version: u32,
 
     pub scope_level: RbumScopeLevelKind,
 }
@@ -5,7 +5,7 @@ use tardis::web::poem_openapi;
 use crate::rbum::dto::rbum_rel_attr_dto::RbumRelAttrDetailResp;
 use crate::rbum::dto::rbum_rel_dto::{RbumRelAddReq, RbumRelDetailResp};
 use crate::rbum::dto::rbum_rel_env_dto::RbumRelEnvDetailResp;
-use crate::rbum::rbum_enumeration::RbumRelEnvKind;
+use crate::rbum::rbum_enumeration::{RbumRelAttrOperatorKind, RbumRelEnvKind};
 
 #[derive(Serialize, Deserialize, Debug)]
 #[cfg_attr(feature = "default", derive(poem_openapi::Object))]
@@ -22,6 +22,8 @@ pub struct RbumRelAttrAggAddReq {
     pub is_from: bool,
     #[cfg_attr(feature = "default", oai(validator(min_length = "2", max_length = "2000")))]
     pub value: String,
+    /// Defaults to [RbumRelAttrOperatorKind::Eq]
+    pub operator: Option<RbumRelAttrOperatorKind>,
     #[cfg_attr(feature = "default", oai(validator(min_length = "2", max_length = "255")))]
     pub name: String,
     pub record_only: bool,
@@ -5,12 +5,16 @@ use tardis::db::sea_orm;
 #[cfg(feature = "default")]
 use tardis::web::poem_openapi;
 
+use crate::rbum::rbum_enumeration::RbumRelAttrOperatorKind;
+
 #[derive(Serialize, Deserialize, Debug)]
 #[cfg_attr(feature = "default", derive(poem_openapi::Object))]
 pub struct RbumRelAttrAddReq {
     pub is_from: bool,
     #[cfg_attr(feature = "default", oai(validator(min_length = "2", max_length = "2000")))]
     pub value: String,
+    /// Defaults to [RbumRelAttrOperatorKind::Eq]
+    pub operator: Option<RbumRelAttrOperatorKind>,
     #[cfg_attr(feature = "default", oai(validator(min_length = "2", max_length = "255")))]
     pub name: String,
     pub record_only: bool,
@@ -25,6 +29,7 @@ pub struct RbumRelAttrAddReq {
 pub struct RbumRelAttrModifyReq {
     #[cfg_attr(feature = "default", oai(validator(min_length = "2", max_length = "2000")))]
     pub value: Option<String>,
+    pub operator: Option<RbumRelAttrOperatorKind>,
     #[cfg_attr(feature = "default", oai(validator(min_length = "2", max_length = "255")))]
     pub name: Option<String>,
 }
@@ -35,6 +40,7 @@ pub struct RbumRelAttrDetailResp {
     pub id: String,
     pub is_from: bool,
     pub value: String,
+    pub operator: RbumRelAttrOperatorKind,
     pub name: String,
     pub record_only: bool,
     pub rel_rbum_kind_attr_id: String,
@@ -46,4 +52,5 @@ pub struct RbumRelAttrDetailResp {
     pub owner_name: Option<String>,
     pub create_time: DateTime<Utc>,
     pub update_time: DateTime<Utc>,
+    pub version: u32,
 }
@@ -51,6 +51,8 @@ pub struct RbumRelCheckReq {
     pub to_rbum_item_id: String,
     pub from_attrs: HashMap<String, String>,
     pub to_attrs: HashMap<String, String>,
+    /// Client ip, required by the ips rel env
+    pub from_ip: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -134,4 +136,5 @@ pub struct RbumRelDetailResp {
     pub owner_name: Option<String>,
     pub create_time: DateTime<Utc>,
     pub update_time: DateTime<Utc>,
+    pub version: u32,
 }
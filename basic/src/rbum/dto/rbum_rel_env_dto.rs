@@ -43,4 +43,5 @@ pub struct RbumRelEnvDetailResp {
     pub owner_name: Option<String>,
     pub create_time: DateTime<Utc>,
     pub update_time: DateTime<Utc>,
+    pub version: u32,
 }
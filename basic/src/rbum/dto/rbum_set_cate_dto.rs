@@ -42,6 +42,21 @@ pub struct RbumSetCateModifyReq {
     pub ext: Option<String>,
 
     pub scope_level: Option<RbumScopeLevelKind>,
+    /// Version of the set category that the modification is based on, a stale version is rejected with a conflict
+    pub version: Option<u32>,
+}
+
+/// Move the set category together with its subtree, the set items of the subtree follow it
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "default", derive(poem_openapi::Object))]
+pub struct RbumSetCateMoveReq {
+    /// New parent category in the same set, `None` to move to the top level
+    #[cfg_attr(feature = "default", oai(validator(min_length = "2", max_length = "1000")))]
+    pub rbum_parent_cate_id: Option<String>,
+    /// New position among the sibling categories
+    pub sort: Option<u32>,
+    /// Version of the set category that the move is based on, a stale version is rejected with a conflict
+    pub version: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -83,6 +98,7 @@ pub struct RbumSetCateDetailResp {
     pub owner_name: Option<String>,
     pub create_time: DateTime<Utc>,
     pub update_time: DateTime<Utc>,
+    pub version: u32,
 
     pub scope_level: RbumScopeLevelKind,
 }
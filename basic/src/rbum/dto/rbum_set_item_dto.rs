@@ -69,6 +69,7 @@ pub struct RbumSetItemDetailResp {
     pub owner_name: Option<String>,
     pub create_time: DateTime<Utc>,
     pub update_time: DateTime<Utc>,
+    pub version: u32,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use tardis::chrono::{DateTime, Utc};
+#[cfg(feature = "default")]
+use tardis::db::sea_orm;
+#[cfg(feature = "default")]
+use tardis::web::poem_openapi;
+
+use crate::rbum::rbum_enumeration::RbumTaskStatusKind;
+
+/// Retry policy of the task, the task is not retried by default
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct RbumTaskRetryPolicy {
+    /// Maximum retries when the execution fails
+    pub max_retry_times: u32,
+    /// Seconds to wait before each retry
+    pub retry_interval_sec: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "default", derive(poem_openapi::Object, sea_orm::FromQueryResult))]
+pub struct RbumTaskDetailResp {
+    pub id: String,
+    pub kind: String,
+    pub status: RbumTaskStatusKind,
+    /// Percentage, 0 to 100
+    pub progress: u8,
+    /// Json result of the succeeded task, empty if none
+    pub result: String,
+    /// Error message of the last failed execution
+    pub error: String,
+    pub retry_times: u32,
+    pub max_retry_times: u32,
+
+    pub own_paths: String,
+    pub owner: String,
+    pub create_time: DateTime<Utc>,
+    pub update_time: DateTime<Utc>,
+}
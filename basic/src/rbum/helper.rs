@@ -1,2 +1,8 @@
+#[cfg(feature = "default")]
+pub mod rbum_cursor_helper;
+#[cfg(feature = "default")]
 pub mod rbum_event_helper;
+pub mod rbum_item_attr_helper;
+pub mod rbum_rel_attr_helper;
+pub mod rbum_rel_env_helper;
 pub mod rbum_scope_helper;
@@ -0,0 +1,147 @@
+//! Cursor based (keyset) pagination helper
+//!
+//! The records are ordered by the sort columns and then by `id`, and the cursor carries the sort values and the id of the last record of the previous page.
+//! The next page starts right after these values, so records inserted, modified or deleted concurrently do not shift the following pages,
+//! and the pagination goes on even if the last record itself has been deleted in the meantime.
+//!
+//! A `NULL` never compares greater or less than a value, so nullable columns can not be sorted by.
+use itertools::Itertools;
+use tardis::basic::result::TardisResult;
+use tardis::chrono::{DateTime, NaiveDateTime};
+use tardis::db::sea_orm::sea_query::*;
+use tardis::db::sea_orm::{self, DbErr, FromQueryResult, QueryResult};
+use tardis::serde_json::Value as JsonValue;
+use tardis::web::poem_openapi::types::{ParseFromJSON, ToJSON};
+use tardis::{TardisFuns, TardisFunsInst};
+
+use crate::rbum::dto::rbum_cursor_dto::RbumCursorPage;
+use crate::rbum::dto::rbum_filer_dto::RbumSortReq;
+
+const CURSOR_VALUE_ALIAS_PREFIX: &str = "rbum_cursor_value_";
+
+/// Sort column that has been validated and resolved to its table
+pub struct RbumCursorSortColumn {
+    pub table_name: String,
+    pub field: String,
+    pub desc: bool,
+    pub column_type: sea_orm::ColumnType,
+}
+
+/// Record fetched by the query of [package_cursor_query], with the values to build the cursor from
+pub struct RbumCursorRecord<T> {
+    pub record: T,
+    pub cursor_values: Vec<JsonValue>,
+}
+
+impl<T> FromQueryResult for RbumCursorRecord<T>
+where
+    T: FromQueryResult,
+{
+    fn from_query_result(res: &QueryResult, pre: &str) -> Result<Self, DbErr> {
+        let record = T::from_query_result(res, pre)?;
+        let row = JsonValue::from_query_result(res, pre)?;
+        let cursor_values = (0..).map_while(|idx| row.get(cursor_value_alias(idx)).cloned()).collect();
+        Ok(RbumCursorRecord { record, cursor_values })
+    }
+}
+
+/// Resolve the sort to a column of the table, nullable columns are rejected.
+pub fn package_sort_column(table_name: &str, column_def: sea_orm::ColumnDef, sort: &RbumSortReq, obj_name: &str, funs: &TardisFunsInst) -> TardisResult<RbumCursorSortColumn> {
+    if column_def.is_null() {
+        return Err(funs.err().bad_request(
+            obj_name,
+            "paginate",
+            &format!("sort field {} of {} is nullable, which can not be paginated by cursor", sort.field, table_name),
+            "400-rbum-*-sort-field-nullable",
+        ));
+    }
+    Ok(RbumCursorSortColumn {
+        table_name: table_name.to_string(),
+        field: sort.field.clone(),
+        desc: sort.desc,
+        column_type: column_def.get_column_type().clone(),
+    })
+}
+
+/// Decode the cursor into the values to start after, one for each sort column and the last one for `id`.
+pub fn decode_cursor(cursor: &str, sort_columns: &[RbumCursorSortColumn], funs: &TardisFunsInst) -> TardisResult<Vec<Value>> {
+    let invalid_err = || funs.err().bad_request("rbum_cursor", "decode", "cursor is invalid or does not match the sort fields", "400-rbum-cursor-invalid");
+    let values = TardisFuns::crypto.base64.decode(cursor).ok().and_then(|values| TardisFuns::json.str_to_obj::<Vec<JsonValue>>(&values).ok()).ok_or_else(invalid_err)?;
+    if values.len() != sort_columns.len() + 1 {
+        return Err(invalid_err());
+    }
+    let id_column_type = sea_orm::ColumnType::String(None);
+    values
+        .iter()
+        .zip(sort_columns.iter().map(|column| &column.column_type).chain([&id_column_type]))
+        .map(|(value, column_type)| to_value(value, column_type).ok_or_else(invalid_err))
+        .collect()
+}
+
+/// Order the query by the sort columns and `id`, start it after the values of the cursor and fetch one more record to tell whether there is a next page.
+///
+/// Any ordering already added to the query is replaced, the records should be fetched as [RbumCursorRecord].
+pub fn package_cursor_query(query: &mut SelectStatement, id_table_name: &str, sort_columns: &[RbumCursorSortColumn], cursor_values: Option<Vec<Value>>, page_size: u64) {
+    let columns = sort_columns.iter().map(|column| (column.table_name.as_str(), column.field.as_str(), column.desc)).chain([(id_table_name, "id", false)]).collect_vec();
+    query.clear_order_by();
+    for (idx, (table_name, field, desc)) in columns.iter().enumerate() {
+        query.expr_as(Expr::tbl(Alias::new(table_name), Alias::new(field)), Alias::new(&cursor_value_alias(idx)));
+        query.order_by((Alias::new(table_name), Alias::new(field)), if *desc { Order::Desc } else { Order::Asc });
+    }
+    if let Some(cursor_values) = cursor_values {
+        // (c1 > v1) OR (c1 = v1 AND c2 > v2) OR ... OR (c1 = v1 AND ... AND id > v_id)
+        let mut cond = Cond::any();
+        for (idx, (table_name, field, desc)) in columns.iter().enumerate() {
+            let mut column_cond = Cond::all();
+            for ((prev_table_name, prev_field, _), prev_value) in columns[..idx].iter().zip(cursor_values.iter()) {
+                column_cond = column_cond.add(Expr::tbl(Alias::new(prev_table_name), Alias::new(prev_field)).eq(prev_value.clone()));
+            }
+            let column = Expr::tbl(Alias::new(table_name), Alias::new(field));
+            let value = cursor_values[idx].clone();
+            column_cond = column_cond.add(if *desc { column.lt(value) } else { column.gt(value) });
+            cond = cond.add(column_cond);
+        }
+        query.cond_where(Cond::all().add(cond));
+    }
+    query.limit(page_size.saturating_add(1));
+}
+
+/// Build the page from the records fetched by [package_cursor_query].
+pub fn package_cursor_page<T>(mut records: Vec<RbumCursorRecord<T>>, page_size: u64) -> TardisResult<RbumCursorPage<T>>
+where
+    T: ParseFromJSON + ToJSON,
+{
+    let mut next_cursor = None;
+    if records.len() as u64 > page_size {
+        records.truncate(page_size as usize);
+        if let Some(last_record) = records.last() {
+            next_cursor = Some(TardisFuns::crypto.base64.encode(&TardisFuns::json.obj_to_string(&last_record.cursor_values)?));
+        }
+    }
+    Ok(RbumCursorPage {
+        page_size,
+        next_cursor,
+        records: records.into_iter().map(|record| record.record).collect(),
+    })
+}
+
+/// Convert the value read by the driver back to the type of the column, the time values are read as strings.
+fn to_value(value: &JsonValue, column_type: &sea_orm::ColumnType) -> Option<Value> {
+    match value {
+        JsonValue::Bool(value) => Some((*value).into()),
+        JsonValue::Number(value) => value.as_i64().map(|value| value.into()).or_else(|| value.as_f64().map(|value| value.into())),
+        JsonValue::String(value) => match column_type {
+            sea_orm::ColumnType::DateTime | sea_orm::ColumnType::Timestamp | sea_orm::ColumnType::TimestampWithTimeZone => DateTime::parse_from_rfc3339(value)
+                .map(|time| time.naive_utc())
+                .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f"))
+                .ok()
+                .map(|time| time.into()),
+            _ => Some(value.clone().into()),
+        },
+        _ => None,
+    }
+}
+
+fn cursor_value_alias(idx: usize) -> String {
+    format!("{}{}", CURSOR_VALUE_ALIAS_PREFIX, idx)
+}
@@ -1,37 +1,273 @@
+//! Rbum events
+//!
+//! The events are not sent to the message queue directly, they are written to the [outbox](crate::rbum::domain::rbum_event_outbox)
+//! in the transaction of the caller, so that the events of the rolled back changes are never published.
+//! The [relay](start_relay_task) of each module publishes the committed events of the module in the order they were written,
+//! if an event fails to be published, the later events of the same record are held back until it succeeds,
+//! or until it has failed [too many times](crate::rbum::rbum_config::RbumConfig::event_relay_max_retry_times) and is kept as a dead letter.
+//!
+//! The delivery is at least once, consumers should dedupe the events by [id](RbumEventMessage::id).
+//!
+//! The changes of the tables cached by [check_rel](crate::rbum::serv::rbum_rel_serv::RbumRelServ::check_rel) are always written,
+//! even if they are not published, so that the relay invalidates the cached rel checks after the changes are committed.
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::future::Future;
 
 use serde::{Deserialize, Serialize};
 use tardis::basic::dto::TardisContext;
 use tardis::basic::result::TardisResult;
-use tardis::chrono::Utc;
+use tardis::chrono::{DateTime, Duration, Utc};
+use tardis::db::sea_orm::sea_query::{Expr, Order, Query};
+use tardis::db::sea_orm::{self, Set};
+use tardis::log;
+use tardis::TardisFuns;
 use tardis::TardisFunsInst;
 
-use crate::rbum::rbum_config::RbumConfigApi;
+use crate::rbum::domain::rbum_event_outbox;
+use crate::rbum::rbum_config::{RbumConfigApi, RbumConfigManager};
+use crate::rbum::serv::rbum_rel_serv::RbumRelServ;
+
+const RELAY_BATCH_SIZE: u64 = 100;
 
 pub async fn try_notify<'a>(table_name: &str, operate: &str, record_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<bool> {
+    do_notify(table_name, operate, record_id, vec![], funs, ctx).await
+}
+
+/// Notify one event for the records changed by a batch operation, nothing is notified if there are no records.
+pub async fn try_notify_batch(table_name: &str, operate: &str, record_ids: &[String], funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<bool> {
+    if record_ids.is_empty() {
+        return Ok(false);
+    }
+    do_notify(table_name, operate, "", record_ids.to_vec(), funs, ctx).await
+}
+
+async fn do_notify(table_name: &str, operate: &str, record_id: &str, record_ids: Vec<String>, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<bool> {
+    // Invalidated in the transaction so that the caller reads its own changes,
+    // the relay invalidates again after committed in case the uncommitted state has been cached by others in between
+    RbumRelServ::try_evict_check_rel_cache(table_name, funs).await?;
+    let publish = cfg!(feature = "with-mq") && funs.rbum_conf_match_event(table_name, operate);
+    if publish || RbumRelServ::is_check_rel_cache_table(table_name) {
+        let id = TardisFuns::field.nanoid();
+        let message = TardisFuns::json.obj_to_string(&RbumEventMessage {
+            id: id.clone(),
+            table_name: table_name.to_string(),
+            operate: operate.to_string(),
+            operator: ctx.owner.clone(),
+            record_id: record_id.to_string(),
+            record_ids,
+            ts: Utc::now().timestamp_millis(),
+        })?;
+        let topic = if publish { funs.rbum_conf_mq_topic_event() } else { "".to_string() };
+        do_add_outbox_event(&id, &topic, table_name, record_id, HashMap::new(), message, funs, ctx).await?;
+    }
+    Ok(cfg!(feature = "with-mq"))
+}
+
+/// Write the deleted records to the outbox, they are published to the [entity deleted topic](crate::rbum::rbum_config::RbumConfig::mq_topic_entity_deleted) after committed.
+#[cfg(feature = "with-mq")]
+pub(crate) async fn add_entity_deleted_events<T: Serialize>(
+    table_name: &str,
+    record_id: &str,
+    delete_records: &[T],
+    funs: &TardisFunsInst,
+    ctx: &TardisContext,
+) -> TardisResult<()> {
+    let topic = funs.rbum_conf_mq_topic_entity_deleted();
+    for delete_record in delete_records {
+        let header = HashMap::from([(funs.rbum_conf_mq_header_name_operator(), ctx.owner.clone())]);
+        do_add_outbox_event(
+            &TardisFuns::field.nanoid(),
+            &topic,
+            table_name,
+            record_id,
+            header,
+            TardisFuns::json.obj_to_string(delete_record)?,
+            funs,
+            ctx,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+async fn do_add_outbox_event(
+    id: &str,
+    topic: &str,
+    table_name: &str,
+    record_id: &str,
+    mut header: HashMap<String, String>,
+    message: String,
+    funs: &TardisFunsInst,
+    ctx: &TardisContext,
+) -> TardisResult<()> {
+    header.insert(funs.rbum_conf_mq_header_name_event_id(), id.to_string());
+    funs.db()
+        .insert_one(
+            rbum_event_outbox::ActiveModel {
+                id: Set(id.to_string()),
+                module_code: Set(funs.module_code().to_string()),
+                topic: Set(topic.to_string()),
+                table_name: Set(table_name.to_string()),
+                record_id: Set(record_id.to_string()),
+                header: Set(TardisFuns::json.obj_to_string(&header)?),
+                message: Set(message),
+                retry_times: Set(0),
+                error: Set("".to_string()),
+                dead_letter: Set(false),
+                ..Default::default()
+            },
+            ctx,
+        )
+        .await?;
+    Ok(())
+}
+
+/// Start the relay that publishes the committed events of the module on the [interval](crate::rbum::rbum_config::RbumConfig::event_relay_interval_ms).
+///
+/// The relays of multiple instances claim each event with a [lease](crate::rbum::rbum_config::RbumConfig::event_relay_lease_sec) before publishing it,
+/// the later events of the same record wait until the claimed one is published, so the order of the record is kept across the instances.
+/// An event is published again only if its relay is interrupted before deleting it, or takes longer than the lease.
+pub fn start_relay_task(code: &str) {
+    let interval_ms = RbumConfigManager::get_config(code, |conf| conf.event_relay_interval_ms);
+    if interval_ms == 0 {
+        return;
+    }
+    let code = code.to_string();
+    tardis::tokio::spawn(async move {
+        let mut interval = tardis::tokio::time::interval(std::time::Duration::from_millis(interval_ms));
+        loop {
+            interval.tick().await;
+            let funs = TardisFuns::inst_with_db_conn(code.clone(), None);
+            match relay_events(&funs).await {
+                Ok(published) if published > 0 => log::trace!("[BIOS.Basic.RBUM] published {} events of {} from the outbox", published, code),
+                Ok(_) => {}
+                Err(e) => log::error!("[BIOS.Basic.RBUM] relay the outbox events of {} error: {:?}", code, e),
+            }
+        }
+    });
+}
+
+/// Publish the events of the module in the outbox page by page, returns the number of the published events.
+///
+/// The events held back by the failed ones or by the ones claimed by other relays are skipped, so that the later pages are still relayed.
+async fn relay_events(funs: &TardisFunsInst) -> TardisResult<u64> {
+    #[derive(sea_orm::FromQueryResult)]
+    struct OutboxEvent {
+        pub id: String,
+        pub topic: String,
+        pub table_name: String,
+        pub record_id: String,
+        pub header: String,
+        pub message: String,
+        pub seq: i64,
+        pub retry_times: u32,
+    }
+
+    let max_retry_times = funs.rbum_conf_event_relay_max_retry_times();
+    let lease_sec = funs.rbum_conf_event_relay_lease_sec();
+    // (table name, record id) of the failed or claimed events, an empty record id holds back all the events of the table
+    let mut blocked_records: HashSet<(String, String)> = HashSet::new();
+    let mut published = 0;
+    let mut last_seq = i64::MIN;
+    loop {
+        let mut query = Query::select();
+        query
+            .columns(vec![
+                rbum_event_outbox::Column::Id,
+                rbum_event_outbox::Column::Topic,
+                rbum_event_outbox::Column::TableName,
+                rbum_event_outbox::Column::RecordId,
+                rbum_event_outbox::Column::Header,
+                rbum_event_outbox::Column::Message,
+                rbum_event_outbox::Column::Seq,
+                rbum_event_outbox::Column::RetryTimes,
+            ])
+            .from(rbum_event_outbox::Entity)
+            .and_where(Expr::col(rbum_event_outbox::Column::ModuleCode).eq(funs.module_code()))
+            .and_where(Expr::col(rbum_event_outbox::Column::DeadLetter).eq(false))
+            .and_where(Expr::col(rbum_event_outbox::Column::Seq).gt(last_seq))
+            .order_by(rbum_event_outbox::Column::Seq, Order::Asc)
+            .limit(RELAY_BATCH_SIZE);
+        let events: Vec<OutboxEvent> = funs.db().find_dtos(&query).await?;
+        let is_last_page = (events.len() as u64) < RELAY_BATCH_SIZE;
+        for event in events {
+            last_seq = event.seq;
+            if blocked_records
+                .iter()
+                .any(|(table_name, record_id)| table_name == &event.table_name && (record_id.is_empty() || event.record_id.is_empty() || record_id == &event.record_id))
+            {
+                continue;
+            }
+            if !claim_event(&event.id, lease_sec, funs).await? {
+                blocked_records.insert((event.table_name, event.record_id));
+                continue;
+            }
+            match publish_event(&event.topic, &event.header, event.message, funs).await {
+                Ok(_) => {
+                    RbumRelServ::try_evict_check_rel_cache(&event.table_name, funs).await?;
+                    funs.db().execute(Query::delete().from_table(rbum_event_outbox::Entity).and_where(Expr::col(rbum_event_outbox::Column::Id).eq(event.id.as_str()))).await?;
+                    published += 1;
+                }
+                Err(e) => {
+                    let dead_letter = max_retry_times > 0 && event.retry_times + 1 >= max_retry_times;
+                    if dead_letter {
+                        log::error!("[BIOS.Basic.RBUM] publish the outbox event {} error: {:?}, kept as a dead letter", event.id, e);
+                    } else {
+                        log::warn!("[BIOS.Basic.RBUM] publish the outbox event {} error: {:?}", event.id, e);
+                        blocked_records.insert((event.table_name, event.record_id));
+                    }
+                    funs.db()
+                        .execute(
+                            Query::update()
+                                .table(rbum_event_outbox::Entity)
+                                .value_expr(rbum_event_outbox::Column::RetryTimes, Expr::col(rbum_event_outbox::Column::RetryTimes).add(1))
+                                .value(rbum_event_outbox::Column::Error, format!("{:?}", e).into())
+                                .value(rbum_event_outbox::Column::DeadLetter, dead_letter.into())
+                                .value(rbum_event_outbox::Column::LeaseExpireTime, Option::<DateTime<Utc>>::None.into())
+                                .and_where(Expr::col(rbum_event_outbox::Column::Id).eq(event.id.as_str())),
+                        )
+                        .await?;
+                }
+            }
+        }
+        if is_last_page {
+            return Ok(published);
+        }
+    }
+}
+
+/// Claim the event unless it is claimed by another relay whose lease has not expired.
+async fn claim_event(id: &str, lease_sec: u64, funs: &TardisFunsInst) -> TardisResult<bool> {
+    let now = Utc::now();
+    let result = funs
+        .db()
+        .execute(
+            Query::update()
+                .table(rbum_event_outbox::Entity)
+                .value(rbum_event_outbox::Column::LeaseExpireTime, (now + Duration::seconds(lease_sec as i64)).into())
+                .and_where(Expr::col(rbum_event_outbox::Column::Id).eq(id))
+                .and_where(Expr::col(rbum_event_outbox::Column::LeaseExpireTime).is_null().or(Expr::col(rbum_event_outbox::Column::LeaseExpireTime).lt(now))),
+        )
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Publish the event to the message queue, the events without a topic are only handled by the relay itself.
+async fn publish_event(topic: &str, header: &str, message: String, funs: &TardisFunsInst) -> TardisResult<()> {
+    if topic.is_empty() {
+        return Ok(());
+    }
     #[cfg(feature = "with-mq")]
     {
-        if funs.rbum_conf_match_event(table_name, operate) {
-            funs.mq()
-                .request(
-                    &funs.rbum_conf_mq_topic_event(),
-                    tardis::TardisFuns::json.obj_to_string(&RbumEventMessage {
-                        table_name: table_name.to_string(),
-                        operate: operate.to_string(),
-                        operator: ctx.owner.clone(),
-                        record_id: record_id.to_string(),
-                        ts: Utc::now().timestamp_millis(),
-                    })?,
-                    &HashMap::new(),
-                )
-                .await?;
-        }
-        Ok(true)
+        let header = TardisFuns::json.str_to_obj::<HashMap<String, String>>(header)?;
+        funs.mq().request(topic, message, &header).await?;
+        Ok(())
     }
     #[cfg(not(feature = "with-mq"))]
     {
-        Ok(false)
+        let _ = (header, message);
+        Err(funs.err().conflict("rbum_event", "publish", "the message queue is not enabled", "409-rbum-event-mq-disabled"))
     }
 }
 
@@ -52,14 +288,20 @@ where
 }
 
 pub fn parse_message(message: String) -> TardisResult<RbumEventMessage> {
-    tardis::TardisFuns::json.str_to_obj::<RbumEventMessage>(&message)
+    TardisFuns::json.str_to_obj::<RbumEventMessage>(&message)
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct RbumEventMessage {
+    /// Id of the event, the events may be delivered more than once, consumers should dedupe them by it
+    #[serde(default)]
+    pub id: String,
     pub table_name: String,
     pub operate: String,
     pub operator: String,
     pub record_id: String,
+    /// Ids of the records changed by a batch operation, the `record_id` is empty in this case
+    #[serde(default)]
+    pub record_ids: Vec<String>,
     pub ts: i64,
 }
@@ -0,0 +1,108 @@
+//! Item attribute helper
+//!
+//! Values are checked against the [kind attribute](crate::rbum::dto::rbum_kind_attr_dto::RbumKindAttrSummaryResp) definition:
+//!
+//! * `required`: the value can not be empty.
+//! * `min_length` / `max_length`: number of characters, `0` means no limit.
+//! * `data_type`: `Number`, `Boolean`, `Date` (`YYYY-MM-DD`) and `DateTime` (RFC 3339 or `YYYY-MM-DD HH:MM:SS`) are parsed,
+//!   `Json` must be a JSON document, `Strings`, `Numbers`, `Booleans`, `Dates`, `DateTimes` and `Array` must be JSON arrays of the element type.
+//! * `options`: for `Select`, `Radio` and `Checkbox` widgets the value, or each element of a JSON array value, must be an option,
+//!   options are a JSON array of `{"value":"label"}` objects.
+//!
+//! Empty values are only checked by `required`.
+use std::str::FromStr;
+
+use tardis::serde_json::Value as JsonValue;
+use tardis::TardisFuns;
+
+use crate::rbum::dto::rbum_item_attr_dto::RbumItemAttrFieldErrorResp;
+use crate::rbum::dto::rbum_kind_attr_dto::RbumKindAttrSummaryResp;
+use crate::rbum::helper::rbum_rel_attr_helper::{parse_date, parse_date_time};
+use crate::rbum::rbum_enumeration::{RbumDataTypeKind, RbumWidgetTypeKind};
+
+/// Check the value against the kind attribute, `None` if it is valid.
+pub fn validate(attr: &RbumKindAttrSummaryResp, value: &str) -> Option<RbumItemAttrFieldErrorResp> {
+    if value.is_empty() {
+        return if attr.required { Some(package_error(attr, "required", "is required")) } else { None };
+    }
+    let length = value.chars().count() as u32;
+    if attr.min_length > 0 && length < attr.min_length {
+        return Some(package_error(attr, "min_length", &format!("must be at least {} characters", attr.min_length)));
+    }
+    if attr.max_length > 0 && length > attr.max_length {
+        return Some(package_error(attr, "max_length", &format!("must be at most {} characters", attr.max_length)));
+    }
+    if !check_data_type(&attr.data_type, value) {
+        return Some(package_error(attr, "data_type", &format!("is not a valid {}", attr.data_type)));
+    }
+    if matches!(attr.widget_type, RbumWidgetTypeKind::Select | RbumWidgetTypeKind::Radio | RbumWidgetTypeKind::Checkbox) {
+        if let Some(options) = parse_options(&attr.options) {
+            let values = match TardisFuns::json.str_to_obj::<Vec<JsonValue>>(value) {
+                Ok(values) => values.iter().map(json_to_string).collect(),
+                Err(_) => vec![value.to_string()],
+            };
+            if let Some(value) = values.iter().find(|value| !options.contains(value)) {
+                return Some(package_error(attr, "options", &format!("{} is not one of the options", value)));
+            }
+        }
+    }
+    None
+}
+
+/// Whether the value can be parsed as the data type.
+pub(crate) fn check_data_type(data_type: &RbumDataTypeKind, value: &str) -> bool {
+    match data_type {
+        RbumDataTypeKind::String | RbumDataTypeKind::Label => true,
+        RbumDataTypeKind::Number => f64::from_str(value.trim()).is_ok(),
+        RbumDataTypeKind::Boolean => bool::from_str(value.trim()).is_ok(),
+        RbumDataTypeKind::Date => parse_date(value).is_some(),
+        RbumDataTypeKind::DateTime => parse_date_time(value).is_some(),
+        RbumDataTypeKind::Json => TardisFuns::json.str_to_obj::<JsonValue>(value).is_ok(),
+        RbumDataTypeKind::Strings => check_array(value, |element| element.is_string()),
+        RbumDataTypeKind::Numbers => check_array(value, |element| element.is_number()),
+        RbumDataTypeKind::Booleans => check_array(value, |element| element.is_boolean()),
+        RbumDataTypeKind::Dates => check_array(value, |element| element.as_str().and_then(parse_date).is_some()),
+        RbumDataTypeKind::DateTimes => check_array(value, |element| element.as_str().and_then(parse_date_time).is_some()),
+        RbumDataTypeKind::Array => check_array(value, |_| true),
+    }
+}
+
+fn check_array(value: &str, check_element: fn(&JsonValue) -> bool) -> bool {
+    match TardisFuns::json.str_to_obj::<Vec<JsonValue>>(value) {
+        Ok(elements) => elements.iter().all(check_element),
+        Err(_) => false,
+    }
+}
+
+/// Option values are the keys of the option objects, plain strings are accepted as well, `None` if there are no options.
+fn parse_options(options: &str) -> Option<Vec<String>> {
+    let options = TardisFuns::json.str_to_obj::<Vec<JsonValue>>(options).ok()?;
+    let values = options
+        .iter()
+        .flat_map(|option| match option {
+            JsonValue::Object(option) => option.keys().cloned().collect::<Vec<String>>(),
+            _ => vec![json_to_string(option)],
+        })
+        .collect::<Vec<String>>();
+    if values.is_empty() {
+        None
+    } else {
+        Some(values)
+    }
+}
+
+fn json_to_string(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(value) => value.clone(),
+        _ => value.to_string(),
+    }
+}
+
+fn package_error(attr: &RbumKindAttrSummaryResp, code: &str, message: &str) -> RbumItemAttrFieldErrorResp {
+    RbumItemAttrFieldErrorResp {
+        name: attr.name.clone(),
+        label: attr.label.clone(),
+        code: code.to_string(),
+        message: format!("{} {}", attr.label, message),
+    }
+}
@@ -0,0 +1,145 @@
+//! Relationship attribute helper
+//!
+//! Values are compared by the data type of the kind attribute:
+//!
+//! | data type                          | format                                      | operators                |
+//! | ---------------------------------- | ------------------------------------------- | ------------------------ |
+//! | Number, Numbers                    | decimal number                              | all except Prefix, Regex |
+//! | Date, Dates                        | `YYYY-MM-DD`                                | all except Prefix, Regex |
+//! | DateTime, DateTimes                | RFC 3339 or `YYYY-MM-DD HH:MM:SS`           | all except Prefix, Regex |
+//! | Boolean, Booleans                  | `true` or `false`                           | Eq, Ne, In, NotIn        |
+//! | String, Strings, Label             | any                                         | all                      |
+//! | Json, Array                        | any, compared as strings                    | Eq, Ne, In, NotIn        |
+//!
+//! The values of `In` and `NotIn` are comma separated lists of the above formats,
+//! the value of `Regex` must match the whole checked value.
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+
+use tardis::basic::error::TardisError;
+use tardis::basic::result::TardisResult;
+use tardis::chrono::{DateTime, NaiveDate, NaiveDateTime};
+use tardis::regex::Regex;
+
+use crate::rbum::rbum_enumeration::{RbumDataTypeKind, RbumRelAttrOperatorKind};
+
+// The compiled regexes are cleared when the cache reaches this size
+const REGEX_CACHE_CAPACITY: usize = 1024;
+
+lazy_static! {
+    static ref REGEX_CACHE: Mutex<HashMap<String, Regex>> = Mutex::new(HashMap::new());
+}
+
+/// Check that the value of the rel attr can be compared by the operator.
+pub fn validate(data_type: &RbumDataTypeKind, operator: &RbumRelAttrOperatorKind, value: &str) -> TardisResult<()> {
+    let supported = match operator {
+        RbumRelAttrOperatorKind::Eq | RbumRelAttrOperatorKind::Ne | RbumRelAttrOperatorKind::In | RbumRelAttrOperatorKind::NotIn => true,
+        RbumRelAttrOperatorKind::Gt | RbumRelAttrOperatorKind::Ge | RbumRelAttrOperatorKind::Lt | RbumRelAttrOperatorKind::Le => is_ordered(data_type),
+        RbumRelAttrOperatorKind::Prefix | RbumRelAttrOperatorKind::Regex => is_string(data_type),
+    };
+    if !supported {
+        return Err(TardisError::bad_request(
+            &format!("operator {} is not supported by data type {}", operator, data_type),
+            "400-rbum-rel-attr-operator-invalid",
+        ));
+    }
+    let valid = match operator {
+        RbumRelAttrOperatorKind::In | RbumRelAttrOperatorKind::NotIn => split_list(value).iter().all(|item| compare(data_type, item, item).is_some()),
+        RbumRelAttrOperatorKind::Regex => compile_regex(value).is_some(),
+        _ => compare(data_type, value, value).is_some(),
+    };
+    if !valid {
+        return Err(TardisError::bad_request(
+            &format!("value {} is invalid for operator {} and data type {}", value, operator, data_type),
+            "400-rbum-rel-attr-value-invalid",
+        ));
+    }
+    Ok(())
+}
+
+/// Whether the checked value satisfies the rel attr, a checked value that can not be parsed never matches.
+pub fn check(data_type: &RbumDataTypeKind, operator: &RbumRelAttrOperatorKind, value: &str, checked_value: &str) -> bool {
+    match operator {
+        RbumRelAttrOperatorKind::Eq => compare(data_type, checked_value, value) == Some(Ordering::Equal),
+        RbumRelAttrOperatorKind::Ne => matches!(compare(data_type, checked_value, value), Some(Ordering::Less) | Some(Ordering::Greater)),
+        RbumRelAttrOperatorKind::Gt => compare(data_type, checked_value, value) == Some(Ordering::Greater),
+        RbumRelAttrOperatorKind::Ge => matches!(compare(data_type, checked_value, value), Some(Ordering::Greater) | Some(Ordering::Equal)),
+        RbumRelAttrOperatorKind::Lt => compare(data_type, checked_value, value) == Some(Ordering::Less),
+        RbumRelAttrOperatorKind::Le => matches!(compare(data_type, checked_value, value), Some(Ordering::Less) | Some(Ordering::Equal)),
+        RbumRelAttrOperatorKind::In => split_list(value).iter().any(|item| compare(data_type, checked_value, item) == Some(Ordering::Equal)),
+        RbumRelAttrOperatorKind::NotIn => {
+            compare(data_type, checked_value, checked_value).is_some() && split_list(value).iter().all(|item| compare(data_type, checked_value, item) != Some(Ordering::Equal))
+        }
+        RbumRelAttrOperatorKind::Prefix => checked_value.starts_with(value),
+        RbumRelAttrOperatorKind::Regex => compile_regex(value).map(|regex| regex.is_match(checked_value)).unwrap_or(false),
+    }
+}
+
+/// Compile the regex anchored to the whole value, `None` if it is invalid.
+fn compile_regex(value: &str) -> Option<Regex> {
+    let mut cache = REGEX_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(regex) = cache.get(value) {
+        return Some(regex.clone());
+    }
+    // The value is parsed alone first so that it can not escape the anchoring group, e.g. `a)|(b`
+    Regex::new(value).ok()?;
+    let regex = Regex::new(&format!("^(?:{})$", value)).ok()?;
+    if cache.len() >= REGEX_CACHE_CAPACITY {
+        cache.clear();
+    }
+    cache.insert(value.to_string(), regex.clone());
+    Some(regex)
+}
+
+fn is_ordered(data_type: &RbumDataTypeKind) -> bool {
+    matches!(
+        data_type,
+        RbumDataTypeKind::Number
+            | RbumDataTypeKind::Numbers
+            | RbumDataTypeKind::Date
+            | RbumDataTypeKind::Dates
+            | RbumDataTypeKind::DateTime
+            | RbumDataTypeKind::DateTimes
+            | RbumDataTypeKind::String
+            | RbumDataTypeKind::Strings
+            | RbumDataTypeKind::Label
+    )
+}
+
+fn is_string(data_type: &RbumDataTypeKind) -> bool {
+    matches!(data_type, RbumDataTypeKind::String | RbumDataTypeKind::Strings | RbumDataTypeKind::Label)
+}
+
+fn split_list(value: &str) -> Vec<&str> {
+    value.split(',').map(|item| item.trim()).filter(|item| !item.is_empty()).collect()
+}
+
+/// Compare the values by the data type, `None` if either of them can not be parsed.
+fn compare(data_type: &RbumDataTypeKind, left: &str, right: &str) -> Option<Ordering> {
+    match data_type {
+        RbumDataTypeKind::Number | RbumDataTypeKind::Numbers => f64::from_str(left.trim()).ok()?.partial_cmp(&f64::from_str(right.trim()).ok()?),
+        RbumDataTypeKind::Date | RbumDataTypeKind::Dates => Some(parse_date(left)?.cmp(&parse_date(right)?)),
+        RbumDataTypeKind::DateTime | RbumDataTypeKind::DateTimes => Some(parse_date_time(left)?.cmp(&parse_date_time(right)?)),
+        RbumDataTypeKind::Boolean | RbumDataTypeKind::Booleans => {
+            if bool::from_str(left.trim()).ok()? == bool::from_str(right.trim()).ok()? {
+                Some(Ordering::Equal)
+            } else {
+                Some(Ordering::Less)
+            }
+        }
+        _ => Some(left.cmp(right)),
+    }
+}
+
+pub(crate) fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").ok()
+}
+
+pub(crate) fn parse_date_time(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    DateTime::parse_from_rfc3339(value).map(|date_time| date_time.naive_utc()).or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")).ok()
+}
@@ -0,0 +1,148 @@
+//! Relationship environment helper
+//!
+//! Value formats of the environments:
+//!
+//! | kind          | value1                                     | value2                     |
+//! | ------------- | ------------------------------------------ | -------------------------- |
+//! | DatetimeRange | start timestamp in seconds                 | end timestamp in seconds   |
+//! | TimeRange     | daily start time, `HH:MM` or `HH:MM:SS`    | daily end time, same format |
+//! | Ips           | comma separated IPs or CIDRs, IPv4 or IPv6 |                            |
+//!
+//! A time range whose start is later than its end crosses midnight, e.g. `22:00` to `06:00`.
+//!
+//! The client ip checked by the `Ips` environment is kept in the context ext by [set_client_ip], the gateway passes it by the [CLIENT_IP_HEADER_NAME] header.
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use tardis::basic::dto::TardisContext;
+use tardis::basic::error::TardisError;
+use tardis::basic::result::TardisResult;
+use tardis::chrono::{DateTime, FixedOffset, NaiveTime, Utc};
+
+use crate::rbum::rbum_enumeration::RbumRelEnvKind;
+
+/// Header of the client ip, set by the gateway and overwrites the one sent by the client
+pub const CLIENT_IP_HEADER_NAME: &str = "Tardis-Client-Ip";
+/// Key of the client ip in the context ext
+pub const CTX_EXT_CLIENT_IP: &str = "client_ip";
+
+/// Check that the values can be evaluated by the kind of environment.
+pub fn validate(kind: &RbumRelEnvKind, value1: &str, value2: &str) -> TardisResult<()> {
+    match kind {
+        RbumRelEnvKind::DatetimeRange => {
+            let start = parse_timestamp(value1)?;
+            let end = parse_timestamp(value2)?;
+            if start > end {
+                return Err(TardisError::bad_request(
+                    &format!("datetime range start {} is later than end {}", value1, value2),
+                    "400-rbum-rel-env-value-invalid",
+                ));
+            }
+        }
+        RbumRelEnvKind::TimeRange => {
+            parse_time(value1)?;
+            parse_time(value2)?;
+        }
+        RbumRelEnvKind::Ips => {
+            parse_ips(value1)?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether the current time, in the time zone of the offset, is in the daily time range.
+pub fn check_time_range(value1: &str, value2: &str, now: DateTime<Utc>, time_zone_offset_sec: i32) -> TardisResult<bool> {
+    let start = parse_time(value1)?;
+    let end = parse_time(value2)?;
+    let offset = FixedOffset::east_opt(time_zone_offset_sec)
+        .ok_or_else(|| TardisError::bad_request(&format!("time zone offset {} is invalid", time_zone_offset_sec), "400-rbum-rel-env-time-zone-invalid"))?;
+    let now = now.with_timezone(&offset).time();
+    if start <= end {
+        Ok(start <= now && now <= end)
+    } else {
+        Ok(now >= start || now <= end)
+    }
+}
+
+/// Whether the ip is one of the IPs or in one of the CIDRs, a missing or malformed ip never matches.
+pub fn check_ips(value1: &str, ip: Option<&str>) -> TardisResult<bool> {
+    let ip = match ip.and_then(|ip| IpAddr::from_str(ip.trim()).ok()) {
+        Some(ip) => ip,
+        None => return Ok(false),
+    };
+    Ok(parse_ips(value1)?.iter().any(|(net, prefix_len)| match_cidr(net, *prefix_len, &ip)))
+}
+
+/// Keep the client ip of the request in the context, the ip passed by the gateway is preferred to the peer address.
+#[cfg(feature = "default")]
+pub fn set_client_ip(request: &tardis::web::poem::Request, ctx: &TardisContext) -> TardisResult<()> {
+    let ip = match request.header(CLIENT_IP_HEADER_NAME) {
+        Some(ip) => Some(ip.trim().to_string()),
+        None => request.remote_addr().as_socket_addr().map(|addr| addr.ip().to_string()),
+    };
+    if let Some(ip) = ip {
+        ctx.add_ext(CTX_EXT_CLIENT_IP, &ip)?;
+    }
+    Ok(())
+}
+
+/// Get the client ip kept in the context, used as the `from_ip` of the rel checks for the context.
+pub fn get_client_ip(ctx: &TardisContext) -> TardisResult<Option<String>> {
+    ctx.get_ext(CTX_EXT_CLIENT_IP)
+}
+
+fn parse_timestamp(value: &str) -> TardisResult<i64> {
+    i64::from_str(value.trim()).map_err(|_| TardisError::bad_request(&format!("timestamp {} is invalid", value), "400-rbum-rel-env-value-invalid"))
+}
+
+fn parse_time(value: &str) -> TardisResult<NaiveTime> {
+    let value = value.trim();
+    NaiveTime::parse_from_str(value, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M"))
+        .map_err(|_| TardisError::bad_request(&format!("time {} is invalid, expected HH:MM or HH:MM:SS", value), "400-rbum-rel-env-value-invalid"))
+}
+
+fn parse_ips(value: &str) -> TardisResult<Vec<(IpAddr, u8)>> {
+    let ips = value
+        .split(',')
+        .map(|ip| ip.trim())
+        .filter(|ip| !ip.is_empty())
+        .map(|ip| parse_cidr(ip).ok_or_else(|| TardisError::bad_request(&format!("ip or cidr {} is invalid", ip), "400-rbum-rel-env-value-invalid")))
+        .collect::<TardisResult<Vec<(IpAddr, u8)>>>()?;
+    if ips.is_empty() {
+        return Err(TardisError::bad_request("ips can not be empty", "400-rbum-rel-env-value-invalid"));
+    }
+    Ok(ips)
+}
+
+fn parse_cidr(value: &str) -> Option<(IpAddr, u8)> {
+    let (ip, prefix_len) = match value.split_once('/') {
+        Some((ip, prefix_len)) => (IpAddr::from_str(ip).ok()?, Some(u8::from_str(prefix_len).ok()?)),
+        None => (IpAddr::from_str(value).ok()?, None),
+    };
+    let max_prefix_len = if ip.is_ipv4() { 32 } else { 128 };
+    let prefix_len = prefix_len.unwrap_or(max_prefix_len);
+    if prefix_len > max_prefix_len {
+        return None;
+    }
+    Some((ip, prefix_len))
+}
+
+fn match_cidr(net: &IpAddr, prefix_len: u8, ip: &IpAddr) -> bool {
+    match (net, ip) {
+        (IpAddr::V4(net), IpAddr::V4(ip)) => {
+            let mask = if prefix_len == 0 { 0 } else { u32::MAX << (32 - prefix_len) };
+            u32::from(*net) & mask == u32::from(*ip) & mask
+        }
+        (IpAddr::V6(net), IpAddr::V6(ip)) => {
+            let mask = if prefix_len == 0 { 0 } else { u128::MAX << (128 - prefix_len) };
+            u128::from(*net) & mask == u128::from(*ip) & mask
+        }
+        // IPv4 clients may be seen as IPv4-mapped IPv6 addresses
+        (IpAddr::V4(_), IpAddr::V6(ip)) => match ip.to_ipv4_mapped() {
+            Some(ip) => match_cidr(net, prefix_len, &IpAddr::V4(ip)),
+            None => false,
+        },
+        (IpAddr::V6(_), IpAddr::V4(_)) => false,
+    }
+}
@@ -25,6 +25,9 @@
 //! | 'AAAA/BBBB/CCCC' | 2           |                |                | AAAA/BBBB%     | AAAA/BBBB%      |
 //! | 'AAAA/BBBB/CCCC' | 3           |                |                |                | AAAA/BBBB/CCCC% |
 //!
+//! Deeper levels follow the same rule: a record with scope level N is visible to the own paths with at least N path items
+//! whose first N path items match those of the record (or all of them if the record has fewer).
+//!
 use std::cmp::Ordering;
 
 use crate::rbum::dto::rbum_filer_dto::RbumBasicFilterReq;
@@ -51,6 +54,17 @@ pub fn get_pre_paths(scope_level: i8, own_paths: &str) -> Option<String> {
     }
 }
 
+/// Get the paths of every level of the own paths, from the first level to the own paths itself.
+pub fn get_all_pre_paths(own_paths: &str) -> Vec<String> {
+    let own_paths = own_paths.trim();
+    let own_paths = own_paths.strip_suffix('/').unwrap_or(own_paths);
+    if own_paths.is_empty() {
+        return vec![];
+    }
+    let split_items = own_paths.split('/').collect::<Vec<_>>();
+    (1..=split_items.len()).map(|level| split_items.iter().take(level).join("/")).collect()
+}
+
 pub fn get_path_item(scope_level: i8, own_paths: &str) -> Option<String> {
     let own_paths = own_paths.trim();
     let own_paths = own_paths.strip_suffix('/').unwrap_or(own_paths).to_string();
@@ -96,24 +110,12 @@ pub fn check_scope(record_own_paths: &str, record_scope_level: Option<i8>, filte
         return false;
     }
     if let Some(record_scope_level) = record_scope_level {
-        if let Some(p1) = get_pre_paths(1, filter_own_paths) {
-            if record_scope_level == 1 {
-                return record_own_paths.is_empty() || record_own_paths.contains(&p1);
-            }
-            if let Some(p2) = get_pre_paths(2, filter_own_paths) {
-                let node_len = p2.len() - p1.len() - 1;
-                if record_scope_level == 2 {
-                    return record_own_paths.is_empty() || record_own_paths.contains(&p2) || (record_own_paths.len() == node_len && record_own_paths.contains(&p1));
-                }
-                if let Some(p3) = get_pre_paths(3, filter_own_paths) {
-                    if record_scope_level == 3 {
-                        return record_own_paths.is_empty()
-                            || record_own_paths.contains(&p3)
-                            || (record_own_paths.len() == node_len && record_own_paths.contains(&p1))
-                            || (record_own_paths.len() == node_len * 2 + 1 && record_own_paths.contains(&p2));
-                    }
-                }
-            }
+        if record_scope_level < 1 {
+            return false;
+        }
+        let pre_paths = get_all_pre_paths(filter_own_paths);
+        if let Some(level_pre_paths) = pre_paths.get(record_scope_level as usize - 1) {
+            return record_own_paths.is_empty() || record_own_paths.contains(level_pre_paths) || pre_paths[..record_scope_level as usize - 1].iter().any(|p| p == record_own_paths);
         }
     }
     false
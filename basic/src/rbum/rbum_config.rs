@@ -15,6 +15,7 @@ pub struct RbumConfig {
     pub mq_topic_entity_deleted: String,
     pub mq_topic_event: String,
     pub mq_header_name_operator: String,
+    pub mq_header_name_event_id: String,
     // own_paths:ak -> vcode
     pub cache_key_cert_vcode_info_: String,
     pub cache_key_cert_vcode_expire_sec: usize,
@@ -26,8 +27,25 @@ pub struct RbumConfig {
     pub cache_key_cert_locked_: String,
     // rbum_item_id -> error times by cycle
     pub cache_key_cert_err_times_: String,
+    // version:own_paths:from|rel:... -> related froms or rels, the version is bumped by rel and set events
+    pub cache_key_rel_check_: String,
+    pub cache_key_rel_check_expire_sec: usize,
     // table name (support prefix matching) -> <c><u><d>
     pub event_domains: HashMap<String, String>,
+    // table name (support prefix matching) -> <c><u><d>, the matched changes are recorded in the change history
+    pub history_domains: HashMap<String, String>,
+    // time zone offset in seconds east of UTC, used by the time range rel env
+    pub rel_env_time_zone_offset_sec: i32,
+    // interval of purging the expired items of the recycle bin, 0 means no purging
+    pub recycle_purge_interval_sec: u64,
+    // interval of publishing the committed events of the outbox, 0 means no relay
+    pub event_relay_interval_ms: u64,
+    // failed publishing attempts after which the event is kept as a dead letter and no longer holds back the later events of the record, 0 means never
+    pub event_relay_max_retry_times: u32,
+    // seconds an event claimed by a relay is kept from the other relays, it is claimed again after that in case the relay is interrupted
+    pub event_relay_lease_sec: u64,
+    // seconds after which a pending or running task without any update is failed as interrupted, 0 means never
+    pub task_timeout_sec: u64,
 }
 
 impl Default for RbumConfig {
@@ -37,6 +55,7 @@ impl Default for RbumConfig {
             mq_topic_entity_deleted: "rbum::entity_deleted".to_string(),
             mq_topic_event: "rbum::event".to_string(),
             mq_header_name_operator: "OP".to_string(),
+            mq_header_name_event_id: "EID".to_string(),
             cache_key_cert_vcode_info_: "rbum:cache:cert:vcode:".to_string(),
             cache_key_cert_vcode_expire_sec: 120,
             cache_key_cert_code_: "rbum:cache:cert:code:".to_string(),
@@ -45,7 +64,16 @@ impl Default for RbumConfig {
             cache_key_set_code_expire_sec: 60 * 60 * 24,
             cache_key_cert_locked_: "rbum:cert:locked:".to_string(),
             cache_key_cert_err_times_: "rbum:cert:err_times:".to_string(),
+            cache_key_rel_check_: "rbum:cache:rel:check:".to_string(),
+            cache_key_rel_check_expire_sec: 60 * 60,
             event_domains: HashMap::from([("rbum_".to_string(), "cud".to_string())]),
+            history_domains: HashMap::new(),
+            rel_env_time_zone_offset_sec: 0,
+            recycle_purge_interval_sec: 60 * 60,
+            event_relay_interval_ms: 500,
+            event_relay_max_retry_times: 100,
+            event_relay_lease_sec: 60,
+            task_timeout_sec: 60 * 60,
         }
     }
 }
@@ -67,6 +95,10 @@ impl RbumConfigManager {
         Self::get_config(code, |conf| conf.event_domains.iter().any(|(k, v)| table_name.contains(k) && v.contains(operate)))
     }
 
+    pub fn match_history(code: &str, table_name: &str, operate: &str) -> bool {
+        Self::get_config(code, |conf| conf.history_domains.iter().any(|(k, v)| table_name.contains(k) && v.contains(operate)))
+    }
+
     pub fn get_config<F, T>(code: &str, fun: F) -> T
     where
         F: Fn(&RbumConfig) -> T,
@@ -82,6 +114,7 @@ pub trait RbumConfigApi {
     fn rbum_conf_mq_topic_entity_deleted(&self) -> String;
     fn rbum_conf_mq_topic_event(&self) -> String;
     fn rbum_conf_mq_header_name_operator(&self) -> String;
+    fn rbum_conf_mq_header_name_event_id(&self) -> String;
     fn rbum_conf_cache_key_cert_vcode_info_(&self) -> String;
     fn rbum_conf_cache_key_cert_vcode_expire_sec(&self) -> usize;
     fn rbum_conf_cache_key_cert_code_(&self) -> String;
@@ -90,7 +123,15 @@ pub trait RbumConfigApi {
     fn rbum_conf_cache_key_set_code_expire_sec(&self) -> usize;
     fn rbum_conf_cache_key_cert_locked_(&self) -> String;
     fn rbum_conf_cache_key_cert_err_times_(&self) -> String;
+    fn rbum_conf_cache_key_rel_check_(&self) -> String;
+    fn rbum_conf_cache_key_rel_check_expire_sec(&self) -> usize;
     fn rbum_conf_match_event(&self, table_name: &str, operate: &str) -> bool;
+    fn rbum_conf_match_history(&self, table_name: &str, operate: &str) -> bool;
+    fn rbum_conf_rel_env_time_zone_offset_sec(&self) -> i32;
+    fn rbum_conf_recycle_purge_interval_sec(&self) -> u64;
+    fn rbum_conf_event_relay_max_retry_times(&self) -> u32;
+    fn rbum_conf_event_relay_lease_sec(&self) -> u64;
+    fn rbum_conf_task_timeout_sec(&self) -> u64;
 }
 
 impl RbumConfigApi for TardisFunsInst {
@@ -110,6 +151,10 @@ impl RbumConfigApi for TardisFunsInst {
         RbumConfigManager::get_config(self.module_code(), |conf| conf.mq_header_name_operator.to_string())
     }
 
+    fn rbum_conf_mq_header_name_event_id(&self) -> String {
+        RbumConfigManager::get_config(self.module_code(), |conf| conf.mq_header_name_event_id.to_string())
+    }
+
     fn rbum_conf_cache_key_cert_vcode_info_(&self) -> String {
         RbumConfigManager::get_config(self.module_code(), |conf| conf.cache_key_cert_vcode_info_.to_string())
     }
@@ -142,7 +187,39 @@ impl RbumConfigApi for TardisFunsInst {
         RbumConfigManager::get_config(self.module_code(), |conf| conf.cache_key_cert_err_times_.to_string())
     }
 
+    fn rbum_conf_cache_key_rel_check_(&self) -> String {
+        RbumConfigManager::get_config(self.module_code(), |conf| conf.cache_key_rel_check_.to_string())
+    }
+
+    fn rbum_conf_cache_key_rel_check_expire_sec(&self) -> usize {
+        RbumConfigManager::get_config(self.module_code(), |conf| conf.cache_key_rel_check_expire_sec)
+    }
+
     fn rbum_conf_match_event(&self, table_name: &str, operate: &str) -> bool {
         RbumConfigManager::match_event(self.module_code(), table_name, operate)
     }
+
+    fn rbum_conf_match_history(&self, table_name: &str, operate: &str) -> bool {
+        RbumConfigManager::match_history(self.module_code(), table_name, operate)
+    }
+
+    fn rbum_conf_rel_env_time_zone_offset_sec(&self) -> i32 {
+        RbumConfigManager::get_config(self.module_code(), |conf| conf.rel_env_time_zone_offset_sec)
+    }
+
+    fn rbum_conf_recycle_purge_interval_sec(&self) -> u64 {
+        RbumConfigManager::get_config(self.module_code(), |conf| conf.recycle_purge_interval_sec)
+    }
+
+    fn rbum_conf_event_relay_max_retry_times(&self) -> u32 {
+        RbumConfigManager::get_config(self.module_code(), |conf| conf.event_relay_max_retry_times)
+    }
+
+    fn rbum_conf_event_relay_lease_sec(&self) -> u64 {
+        RbumConfigManager::get_config(self.module_code(), |conf| conf.event_relay_lease_sec)
+    }
+
+    fn rbum_conf_task_timeout_sec(&self) -> u64 {
+        RbumConfigManager::get_config(self.module_code(), |conf| conf.task_timeout_sec)
+    }
 }
@@ -11,36 +11,43 @@ use tardis::derive_more::Display;
 #[cfg(feature = "default")]
 use tardis::web::poem_openapi;
 
+/// Scope level of the records, `-1` is private, `0` is root and `N` is shared with the own paths under the first N path items.
 #[derive(Display, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
-#[cfg_attr(feature = "default", derive(poem_openapi::Enum))]
-pub enum RbumScopeLevelKind {
-    Private,
-    Root,
-    L1,
-    L2,
-    L3,
-}
+#[cfg_attr(feature = "default", derive(poem_openapi::NewType))]
+#[serde(try_from = "i8", into = "i8")]
+pub struct RbumScopeLevelKind(i8);
 
+#[allow(non_upper_case_globals)]
 impl RbumScopeLevelKind {
+    pub const Private: RbumScopeLevelKind = RbumScopeLevelKind(-1);
+    pub const Root: RbumScopeLevelKind = RbumScopeLevelKind(0);
+    pub const L1: RbumScopeLevelKind = RbumScopeLevelKind(1);
+    pub const L2: RbumScopeLevelKind = RbumScopeLevelKind(2);
+    pub const L3: RbumScopeLevelKind = RbumScopeLevelKind(3);
+
     pub fn from_int(s: i8) -> TardisResult<RbumScopeLevelKind> {
-        match s {
-            -1 => Ok(RbumScopeLevelKind::Private),
-            0 => Ok(RbumScopeLevelKind::Root),
-            1 => Ok(RbumScopeLevelKind::L1),
-            2 => Ok(RbumScopeLevelKind::L2),
-            3 => Ok(RbumScopeLevelKind::L3),
-            _ => Err(TardisError::format_error(&format!("invalid RbumScopeLevelKind: {}", s), "406-rbum-*-enum-init-error")),
+        if s < -1 {
+            return Err(TardisError::format_error(&format!("invalid RbumScopeLevelKind: {}", s), "406-rbum-*-enum-init-error"));
         }
+        Ok(RbumScopeLevelKind(s))
     }
 
     pub fn to_int(&self) -> i8 {
-        match self {
-            RbumScopeLevelKind::Private => -1,
-            RbumScopeLevelKind::Root => 0,
-            RbumScopeLevelKind::L1 => 1,
-            RbumScopeLevelKind::L2 => 2,
-            RbumScopeLevelKind::L3 => 3,
-        }
+        self.0
+    }
+}
+
+impl TryFrom<i8> for RbumScopeLevelKind {
+    type Error = TardisError;
+
+    fn try_from(s: i8) -> Result<Self, Self::Error> {
+        RbumScopeLevelKind::from_int(s)
+    }
+}
+
+impl From<RbumScopeLevelKind> for i8 {
+    fn from(scope_level: RbumScopeLevelKind) -> Self {
+        scope_level.0
     }
 }
 
@@ -160,6 +167,51 @@ impl TryGetable for RbumCertStatusKind {
     }
 }
 
+#[derive(Display, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "default", derive(poem_openapi::Enum))]
+pub enum RbumTaskStatusKind {
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl RbumTaskStatusKind {
+    pub fn from_int(s: u8) -> TardisResult<RbumTaskStatusKind> {
+        match s {
+            0 => Ok(RbumTaskStatusKind::Pending),
+            1 => Ok(RbumTaskStatusKind::Running),
+            2 => Ok(RbumTaskStatusKind::Succeeded),
+            3 => Ok(RbumTaskStatusKind::Failed),
+            4 => Ok(RbumTaskStatusKind::Cancelled),
+            _ => Err(TardisError::format_error(&format!("invalid RbumTaskStatusKind: {}", s), "406-rbum-*-enum-init-error")),
+        }
+    }
+
+    pub fn to_int(&self) -> u8 {
+        match self {
+            RbumTaskStatusKind::Pending => 0,
+            RbumTaskStatusKind::Running => 1,
+            RbumTaskStatusKind::Succeeded => 2,
+            RbumTaskStatusKind::Failed => 3,
+            RbumTaskStatusKind::Cancelled => 4,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self, RbumTaskStatusKind::Succeeded | RbumTaskStatusKind::Failed | RbumTaskStatusKind::Cancelled)
+    }
+}
+
+#[cfg(feature = "default")]
+impl TryGetable for RbumTaskStatusKind {
+    fn try_get(res: &QueryResult, pre: &str, col: &str) -> Result<Self, TryGetError> {
+        let s = u8::try_get(res, pre, col)?;
+        RbumTaskStatusKind::from_int(s).map_err(|_| TryGetError::DbErr(DbErr::RecordNotFound(format!("{}:{}", pre, col))))
+    }
+}
+
 #[derive(Display, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[cfg_attr(feature = "default", derive(poem_openapi::Enum))]
 pub enum RbumRelEnvKind {
@@ -221,6 +273,32 @@ impl TryGetable for RbumDataTypeKind {
     }
 }
 
+/// Operator comparing the value of a rel attr with the value to check, typed by the data type of the kind attr
+///
+/// The values of `In` and `NotIn` are comma separated, `Prefix` and `Regex` only apply to string data types.
+#[derive(Display, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "default", derive(poem_openapi::Enum, sea_orm::strum::EnumString))]
+pub enum RbumRelAttrOperatorKind {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    In,
+    NotIn,
+    Prefix,
+    Regex,
+}
+
+#[cfg(feature = "default")]
+impl TryGetable for RbumRelAttrOperatorKind {
+    fn try_get(res: &QueryResult, pre: &str, col: &str) -> Result<Self, TryGetError> {
+        let s = String::try_get(res, pre, col)?;
+        RbumRelAttrOperatorKind::from_str(&s).map_err(|_| TryGetError::DbErr(DbErr::RecordNotFound(format!("{}:{}", pre, col))))
+    }
+}
+
 #[derive(Display, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[cfg_attr(feature = "default", derive(poem_openapi::Enum, sea_orm::strum::EnumString))]
 pub enum RbumWidgetTypeKind {
@@ -4,13 +4,18 @@ use tardis::basic::result::TardisResult;
 use tardis::db::reldb_client::TardisActiveModel;
 use tardis::db::sea_orm;
 use tardis::db::sea_orm::sea_query::*;
+use tardis::db::sea_orm::{DbBackend, EntityName};
 use tardis::TardisFuns;
 use tardis::TardisFunsInst;
 
 use crate::rbum::domain::{
-    rbum_cert, rbum_cert_conf, rbum_domain, rbum_item, rbum_item_attr, rbum_kind, rbum_kind_attr, rbum_rel, rbum_rel_attr, rbum_rel_env, rbum_set, rbum_set_cate, rbum_set_item,
+    rbum_cert, rbum_cert_conf, rbum_domain, rbum_event_outbox, rbum_history, rbum_item, rbum_item_attr, rbum_kind, rbum_kind_attr, rbum_rel, rbum_rel_attr, rbum_rel_env, rbum_set,
+    rbum_set_cate, rbum_set_item, rbum_task,
 };
 use crate::rbum::rbum_config::{RbumConfig, RbumConfigManager};
+use crate::rbum::rbum_enumeration::RbumRelAttrOperatorKind;
+
+const RBUM_MIGRATION_V1_KEY: &str = "__RBUM_MIGRATION_V1__";
 
 pub async fn init(code: &str, config: RbumConfig) -> TardisResult<()> {
     RbumConfigManager::add(code, config)?;
@@ -18,10 +23,12 @@ pub async fn init(code: &str, config: RbumConfig) -> TardisResult<()> {
     let db_kind = TardisFuns::reldb().backend();
     let mut tx = TardisFuns::reldb().conn();
     if TardisFuns::dict.get("__RBUM_INIT__", &tx).await?.is_some() {
-        return Ok(());
+        return migrate(db_kind).await;
     }
     tx.begin().await?;
     TardisFuns::dict.add("__RBUM_INIT__", "", "", &tx).await?;
+    // The new tables are created with the latest columns
+    TardisFuns::dict.add(RBUM_MIGRATION_V1_KEY, "", "", &tx).await?;
     tx.create_table_and_index(&rbum_domain::ActiveModel::create_table_and_index_statement(db_kind)).await?;
     tx.create_table_and_index(&rbum_kind::ActiveModel::create_table_and_index_statement(db_kind)).await?;
     tx.create_table_and_index(&rbum_item::ActiveModel::create_table_and_index_statement(db_kind)).await?;
@@ -35,6 +42,61 @@ pub async fn init(code: &str, config: RbumConfig) -> TardisResult<()> {
     tx.create_table_and_index(&rbum_set::ActiveModel::create_table_and_index_statement(db_kind)).await?;
     tx.create_table_and_index(&rbum_set_cate::ActiveModel::create_table_and_index_statement(db_kind)).await?;
     tx.create_table_and_index(&rbum_set_item::ActiveModel::create_table_and_index_statement(db_kind)).await?;
+    tx.create_table_and_index(&rbum_history::ActiveModel::create_table_and_index_statement(db_kind)).await?;
+    tx.create_table_and_index(&rbum_task::ActiveModel::create_table_and_index_statement(db_kind)).await?;
+    tx.create_table_and_index(&rbum_event_outbox::ActiveModel::create_table_and_index_statement(db_kind)).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Upgrade the tables created by an earlier version, the migration runs once and is marked in the dict.
+///
+/// `CREATE TABLE IF NOT EXISTS` keeps the existing tables as they are, so the added columns have to be altered in,
+/// their defaults fill the existing records.
+async fn migrate(db_kind: DbBackend) -> TardisResult<()> {
+    let mut tx = TardisFuns::reldb().conn();
+    if TardisFuns::dict.get(RBUM_MIGRATION_V1_KEY, &tx).await?.is_some() {
+        return Ok(());
+    }
+    tx.begin().await?;
+    TardisFuns::dict.add(RBUM_MIGRATION_V1_KEY, "", "", &tx).await?;
+    tx.create_table_and_index(&rbum_history::ActiveModel::create_table_and_index_statement(db_kind)).await?;
+    tx.create_table_and_index(&rbum_task::ActiveModel::create_table_and_index_statement(db_kind)).await?;
+    tx.create_table_and_index(&rbum_event_outbox::ActiveModel::create_table_and_index_statement(db_kind)).await?;
+    for table_name in [
+        rbum_domain::Entity.table_name(),
+        rbum_kind::Entity.table_name(),
+        rbum_item::Entity.table_name(),
+        rbum_kind_attr::Entity.table_name(),
+        rbum_item_attr::Entity.table_name(),
+        rbum_rel::Entity.table_name(),
+        rbum_rel_attr::Entity.table_name(),
+        rbum_rel_env::Entity.table_name(),
+        rbum_cert_conf::Entity.table_name(),
+        rbum_cert::Entity.table_name(),
+        rbum_set::Entity.table_name(),
+        rbum_set_cate::Entity.table_name(),
+        rbum_set_item::Entity.table_name(),
+    ] {
+        tx.execute(Table::alter().table(Alias::new(table_name)).add_column(ColumnDef::new(Alias::new("version")).not_null().unsigned().default(0))).await?;
+    }
+    tx.execute(Table::alter().table(rbum_kind::Entity).add_column(ColumnDef::new(rbum_kind::Column::SoftDeleteRetentionSec).not_null().unsigned().default(0))).await?;
+    tx.execute(Table::alter().table(rbum_item::Entity).add_column(ColumnDef::new(rbum_item::Column::Deleted).not_null().boolean().default(false))).await?;
+    tx.execute(Table::alter().table(rbum_item::Entity).add_column(ColumnDef::new(rbum_item::Column::DeleteTime).timestamp())).await?;
+    tx.execute(
+        Index::create()
+            .name(&format!("idx-{}-{}", rbum_item::Entity.table_name(), rbum_item::Column::Deleted.to_string()))
+            .table(rbum_item::Entity)
+            .col(rbum_item::Column::Deleted),
+    )
+    .await?;
+    // The rel attrs before were only compared by equality
+    tx.execute(
+        Table::alter()
+            .table(rbum_rel_attr::Entity)
+            .add_column(ColumnDef::new(rbum_rel_attr::Column::Operator).not_null().string().default(RbumRelAttrOperatorKind::Eq.to_string())),
+    )
+    .await?;
     tx.commit().await?;
     Ok(())
 }
@@ -83,6 +145,8 @@ pub async fn truncate_data<'a>(funs: &TardisFunsInst) -> TardisResult<()> {
     funs.db().execute(Table::truncate().table(rbum_cert::Entity)).await?;
     funs.db().execute(Table::truncate().table(rbum_cert_conf::Entity)).await?;
     funs.db().execute(Table::truncate().table(rbum_domain::Entity)).await?;
+    funs.db().execute(Table::truncate().table(rbum_event_outbox::Entity)).await?;
+    funs.db().execute(Table::truncate().table(rbum_history::Entity)).await?;
     funs.db().execute(Table::truncate().table(rbum_item::Entity)).await?;
     funs.db().execute(Table::truncate().table(rbum_item_attr::Entity)).await?;
     funs.db().execute(Table::truncate().table(rbum_kind::Entity)).await?;
@@ -93,6 +157,7 @@ pub async fn truncate_data<'a>(funs: &TardisFunsInst) -> TardisResult<()> {
     funs.db().execute(Table::truncate().table(rbum_set::Entity)).await?;
     funs.db().execute(Table::truncate().table(rbum_set_cate::Entity)).await?;
     funs.db().execute(Table::truncate().table(rbum_set_item::Entity)).await?;
+    funs.db().execute(Table::truncate().table(rbum_task::Entity)).await?;
     funs.cache().flushdb().await?;
     Ok(())
 }
@@ -1,6 +1,7 @@
 pub mod rbum_cert_serv;
 pub mod rbum_crud_serv;
 pub mod rbum_domain_serv;
+pub mod rbum_history_serv;
 pub mod rbum_item_serv;
 pub mod rbum_kind_serv;
 pub mod rbum_rel_serv;
@@ -218,7 +218,7 @@ impl RbumCrudOperation<rbum_cert_conf::ActiveModel, RbumCertConfAddReq, RbumCert
         Ok(())
     }
 
-    async fn before_delete_rbum(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<RbumCertConfDetailResp>> {
+    async fn check_delete_rbum(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<RbumCertConfDetailResp>> {
         if funs
             .db()
             .count(
@@ -233,7 +233,6 @@ impl RbumCrudOperation<rbum_cert_conf::ActiveModel, RbumCertConfAddReq, RbumCert
         {
             return Err(funs.err().conflict(&Self::get_obj_name(), "delete", "is_basic is true", "409-rbum-cert-conf-basic-delete"));
         }
-        Self::check_ownership(id, funs, ctx).await?;
         Self::check_exist_before_delete(id, RbumCertServ::get_table_name(), rbum_cert::Column::RelRbumCertConfId.as_str(), funs).await?;
         let result = Self::peek_rbum(
             id,
@@ -289,6 +288,7 @@ impl RbumCrudOperation<rbum_cert_conf::ActiveModel, RbumCertConfAddReq, RbumCert
                 (rbum_cert_conf::Entity, rbum_cert_conf::Column::Owner),
                 (rbum_cert_conf::Entity, rbum_cert_conf::Column::CreateTime),
                 (rbum_cert_conf::Entity, rbum_cert_conf::Column::UpdateTime),
+                (rbum_cert_conf::Entity, rbum_cert_conf::Column::Version),
             ])
             .from(rbum_cert_conf::Entity);
         if let Some(rel_rbum_domain_id) = &filter.rel_rbum_domain_id {
@@ -519,8 +519,7 @@ impl RbumCrudOperation<rbum_cert::ActiveModel, RbumCertAddReq, RbumCertModifyReq
         Ok(rbum_cert)
     }
 
-    async fn before_modify_rbum(id: &str, modify_req: &mut RbumCertModifyReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
-        Self::check_ownership(id, funs, ctx).await?;
+    async fn check_modify_rbum(id: &str, modify_req: &mut RbumCertModifyReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
         let rbum_cert = Self::peek_rbum(
             id,
             &RbumCertFilterReq {
@@ -581,6 +580,7 @@ impl RbumCrudOperation<rbum_cert::ActiveModel, RbumCertAddReq, RbumCertModifyReq
                 (rbum_cert::Entity, rbum_cert::Column::Owner),
                 (rbum_cert::Entity, rbum_cert::Column::CreateTime),
                 (rbum_cert::Entity, rbum_cert::Column::UpdateTime),
+                (rbum_cert::Entity, rbum_cert::Column::Version),
             ])
             .expr_as(
                 Expr::tbl(rbum_cert_conf::Entity, rbum_cert_conf::Column::Name).if_null(""),
@@ -598,6 +598,9 @@ impl RbumCrudOperation<rbum_cert::ActiveModel, RbumCertAddReq, RbumCertModifyReq
         if let Some(ak) = &filter.ak {
             query.and_where(Expr::tbl(rbum_cert::Entity, rbum_cert::Column::Ak).eq(ak.to_string()));
         }
+        if let Some(aks) = &filter.aks {
+            query.and_where(Expr::tbl(rbum_cert::Entity, rbum_cert::Column::Ak).is_in(aks.clone()));
+        }
         if let Some(status) = &filter.status {
             query.and_where(Expr::tbl(rbum_cert::Entity, rbum_cert::Column::Status).eq(status.to_int()));
         }
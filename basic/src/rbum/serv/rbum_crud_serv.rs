@@ -1,23 +1,32 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use async_trait::async_trait;
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use serde::Serialize;
 use tardis::basic::dto::TardisContext;
+use tardis::basic::error::TardisError;
 use tardis::basic::result::TardisResult;
+use tardis::chrono::{DateTime, Utc};
 use tardis::db::reldb_client::{IdResp, TardisActiveModel};
 use tardis::db::sea_orm::sea_query::{Alias, Cond, Expr, Func, IntoValueTuple, JoinType, Order, Query, SelectStatement, Value, ValueTuple};
-use tardis::db::sea_orm::{self, Condition, EntityTrait, FromQueryResult, QueryFilter, Select};
+use tardis::db::sea_orm::{
+    self, ActiveModelTrait, ColumnTrait, Condition, EntityTrait, FromQueryResult, IdenStatic, Iterable, PrimaryKeyToColumn, QueryFilter, QueryTrait, Select,
+};
 use tardis::regex::Regex;
+use tardis::serde_json::Value as JsonValue;
 use tardis::web::poem_openapi::types::{ParseFromJSON, ToJSON};
 use tardis::web::web_resp::TardisPage;
 use tardis::TardisFunsInst;
 
 use crate::rbum::domain::rbum_item;
-use crate::rbum::dto::rbum_filer_dto::RbumBasicFilterReq;
+use crate::rbum::dto::rbum_batch_dto::RbumBatchResp;
+use crate::rbum::dto::rbum_cursor_dto::RbumCursorPage;
+use crate::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumSortReq};
+use crate::rbum::dto::rbum_history_dto::RbumHistoryResp;
+use crate::rbum::helper::rbum_cursor_helper::{self, RbumCursorRecord, RbumCursorSortColumn};
 use crate::rbum::helper::{rbum_event_helper, rbum_scope_helper};
-use crate::rbum::rbum_config::RbumConfigApi;
+use crate::rbum::serv::rbum_history_serv::RbumHistoryServ;
 
 lazy_static! {
     pub static ref OWNER_TABLE: Alias = Alias::new("t_owner");
@@ -26,6 +35,7 @@ lazy_static! {
     pub static ref OWN_PATHS_FIELD: Alias = Alias::new("own_paths");
     pub static ref CREATE_TIME_FIELD: Alias = Alias::new("create_time");
     pub static ref UPDATE_TIME_FIELD: Alias = Alias::new("update_time");
+    pub static ref VERSION_FIELD: Alias = Alias::new("version");
     pub static ref CODE_FIELD: Alias = Alias::new("code");
     pub static ref NAME_FIELD: Alias = Alias::new("name");
     pub static ref SORT_FIELD: Alias = Alias::new("sort");
@@ -88,6 +98,42 @@ where
         query
     }
 
+    /// Ids of the records owned by the context in one query, the batch counterpart of [check_ownership](Self::check_ownership).
+    async fn find_owned_rbum_ids(ids: &[String], funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<HashSet<String>> {
+        Self::find_owned_rbum_ids_with_table_name(ids, Self::get_table_name(), funs, ctx).await
+    }
+
+    async fn find_owned_rbum_ids_with_table_name(ids: &[String], table_name: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<HashSet<String>> {
+        if ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+        let owned_ids = funs
+            .db()
+            .find_dtos::<IdResp>(
+                Query::select()
+                    .column(ID_FIELD.clone())
+                    .from(Alias::new(table_name))
+                    .and_where(Expr::col(ID_FIELD.clone()).is_in(ids.to_vec()))
+                    .and_where(Expr::col(OWN_PATHS_FIELD.clone()).like(format!("{}%", ctx.own_paths).as_str())),
+            )
+            .await?;
+        Ok(owned_ids.into_iter().map(|resp| resp.id).collect())
+    }
+
+    /// Ids of the records that the context can delete in one query, the batch counterpart of the ownership check of [before_delete_rbum](Self::before_delete_rbum).
+    async fn find_deletable_rbum_ids(ids: &[String], funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<HashSet<String>> {
+        Self::find_owned_rbum_ids(ids, funs, ctx).await
+    }
+
+    fn ownership_illegal_error(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisError {
+        funs.err().not_found(
+            &Self::get_obj_name(),
+            "check",
+            &format!("ownership {}.{} is illegal by {}", Self::get_obj_name(), id, ctx.owner),
+            "404-rbum-*-ownership-illegal",
+        )
+    }
+
     // ----------------------------- Scope -------------------------------
 
     async fn check_scope(id: &str, table_name: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
@@ -193,28 +239,23 @@ where
         Self::before_add_rbum(add_req, funs, ctx).await?;
         let domain = Self::package_add(add_req, funs, ctx).await?;
         let insert_result = funs.db().insert_one(domain, ctx).await?;
-        let id_value = insert_result.last_insert_id.into_value_tuple();
-        let id = match id_value {
-            ValueTuple::One(v) => {
-                if let Value::String(s) = v {
-                    s.map(|id| id.to_string())
-                } else {
-                    None
-                }
-            }
-            _ => None,
-        };
-        if let Some(id) = id {
-            Self::after_add_rbum(&id, add_req, funs, ctx).await?;
-            rbum_event_helper::try_notify(Self::get_table_name(), "c", &id, funs, ctx).await?;
+        let id = Self::parse_id(insert_result.last_insert_id.into_value_tuple(), funs)?;
+        Self::after_add_rbum(&id, add_req, funs, ctx).await?;
+        RbumHistoryServ::try_add_history(Self::get_table_name(), "c", &[Self::get_table_name()], &id, None, funs, ctx).await?;
+        rbum_event_helper::try_notify(Self::get_table_name(), "c", &id, funs, ctx).await?;
+        Ok(id)
+    }
+
+    fn parse_id(id_value: ValueTuple, funs: &TardisFunsInst) -> TardisResult<String> {
+        if let ValueTuple::One(Value::String(Some(id))) = id_value {
             Ok(id.to_string())
         } else {
-            return Err(funs.err().internal_error(
+            Err(funs.err().internal_error(
                 &Self::get_obj_name(),
                 "add",
                 "id data type is invalid, currently only the string is supported",
                 "500-rbum-crud-id-type",
-            ));
+            ))
         }
     }
 
@@ -222,19 +263,85 @@ where
 
     async fn package_modify(id: &str, modify_req: &ModifyReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<E>;
 
-    async fn before_modify_rbum(id: &str, _: &mut ModifyReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
-        Self::check_ownership(id, funs, ctx).await
+    async fn before_modify_rbum(id: &str, modify_req: &mut ModifyReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        Self::check_ownership(id, funs, ctx).await?;
+        Self::check_modify_rbum(id, modify_req, funs, ctx).await
+    }
+
+    /// Checks of the modification other than the ownership, which is checked once for the whole batch by [modify_rbums](Self::modify_rbums).
+    async fn check_modify_rbum(_: &str, _: &mut ModifyReq, _: &TardisFunsInst, _: &TardisContext) -> TardisResult<()> {
+        Ok(())
     }
 
     async fn after_modify_rbum(_: &str, _: &mut ModifyReq, _: &TardisFunsInst, _: &TardisContext) -> TardisResult<()> {
         Ok(())
     }
 
+    /// Version of the record that the modify request is based on, `None` means skip the version check.
+    fn get_modify_version(_: &ModifyReq) -> Option<u32> {
+        None
+    }
+
+    /// Increase the version of the record, return a conflict error if the current version is not the expected version,
+    /// or a not found error if the record does not exist.
+    async fn check_and_increase_version(id: &str, version: Option<u32>, funs: &TardisFunsInst) -> TardisResult<()> {
+        Self::check_and_increase_version_with_table_name(id, version, Self::get_table_name(), funs).await
+    }
+
+    async fn check_and_increase_version_with_table_name(id: &str, version: Option<u32>, table_name: &str, funs: &TardisFunsInst) -> TardisResult<()> {
+        let mut query = Query::update();
+        query.table(Alias::new(table_name)).value_expr(VERSION_FIELD.clone(), Expr::col(VERSION_FIELD.clone()).add(1)).and_where(Expr::col(ID_FIELD.clone()).eq(id));
+        if let Some(version) = version {
+            query.and_where(Expr::col(VERSION_FIELD.clone()).eq(version));
+        }
+        if funs.db().execute(&query).await?.rows_affected() == 0 {
+            if funs.db().count(Query::select().column(ID_FIELD.clone()).from(Alias::new(table_name)).and_where(Expr::col(ID_FIELD.clone()).eq(id))).await? == 0 {
+                return Err(funs.err().not_found(
+                    &Self::get_obj_name_from(table_name),
+                    "modify",
+                    &format!("not found {}.{}", Self::get_obj_name_from(table_name), id),
+                    "404-rbum-*-obj-not-exist",
+                ));
+            }
+            return Err(funs.err().conflict(
+                &Self::get_obj_name_from(table_name),
+                "modify",
+                &format!(
+                    "{}.{} has been modified by others, the version {} is stale",
+                    Self::get_obj_name_from(table_name),
+                    id,
+                    version.unwrap_or_default()
+                ),
+                "409-rbum-*-version-conflict",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Increase the versions of the records with one update, for the modifications without an expected version.
+    async fn increase_versions_with_table_name(ids: Vec<String>, table_name: &str, funs: &TardisFunsInst) -> TardisResult<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        funs.db()
+            .execute(
+                Query::update()
+                    .table(Alias::new(table_name))
+                    .value_expr(VERSION_FIELD.clone(), Expr::col(VERSION_FIELD.clone()).add(1))
+                    .and_where(Expr::col(ID_FIELD.clone()).is_in(ids)),
+            )
+            .await?;
+        Ok(())
+    }
+
     async fn modify_rbum(id: &str, modify_req: &mut ModifyReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
         Self::before_modify_rbum(id, modify_req, funs, ctx).await?;
         let domain = Self::package_modify(id, modify_req, funs, ctx).await?;
+        let before = RbumHistoryServ::try_snapshot(Self::get_table_name(), "u", &[Self::get_table_name()], id, funs).await?;
+        Self::check_and_increase_version(id, Self::get_modify_version(modify_req), funs).await?;
         funs.db().update_one(domain, ctx).await?;
         Self::after_modify_rbum(id, modify_req, funs, ctx).await?;
+        RbumHistoryServ::try_add_history(Self::get_table_name(), "u", &[Self::get_table_name()], id, before, funs, ctx).await?;
         rbum_event_helper::try_notify(Self::get_table_name(), "u", id, funs, ctx).await?;
         Ok(())
     }
@@ -247,6 +354,11 @@ where
 
     async fn before_delete_rbum(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<DetailResp>> {
         Self::check_ownership(id, funs, ctx).await?;
+        Self::check_delete_rbum(id, funs, ctx).await
+    }
+
+    /// Checks of the deletion other than the ownership, which is checked once for the whole batch by [delete_rbums](Self::delete_rbums).
+    async fn check_delete_rbum(_: &str, _: &TardisFunsInst, _: &TardisContext) -> TardisResult<Option<DetailResp>> {
         Ok(None)
     }
 
@@ -256,28 +368,225 @@ where
 
     async fn delete_rbum(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<u64> {
         let deleted_rbum = Self::before_delete_rbum(id, funs, ctx).await?;
+        let delete_records = Self::do_delete_rbum(id, &deleted_rbum, funs, ctx).await?;
+        rbum_event_helper::try_notify(Self::get_table_name(), "d", id, funs, ctx).await?;
+        Ok(delete_records)
+    }
+
+    /// Delete the record that has passed the checks of [before_delete_rbum](Self::before_delete_rbum), the event is not notified.
+    async fn do_delete_rbum(id: &str, deleted_rbum: &Option<DetailResp>, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<u64> {
+        let before = RbumHistoryServ::try_snapshot(Self::get_table_name(), "d", &[Self::get_table_name()], id, funs).await?;
         let select = Self::package_delete(id, funs, ctx).await?;
         #[cfg(feature = "with-mq")]
         {
             let delete_records = funs.db().soft_delete_custom(select, "id").await?;
-            let mq_topic_entity_deleted = &funs.rbum_conf_mq_topic_entity_deleted();
-            let mq_header = std::collections::HashMap::from([(funs.rbum_conf_mq_header_name_operator(), ctx.owner.clone())]);
-            for delete_record in &delete_records {
-                funs.mq().request(mq_topic_entity_deleted, tardis::TardisFuns::json.obj_to_string(delete_record)?, &mq_header).await?;
-            }
-            Self::after_delete_rbum(id, &deleted_rbum, funs, ctx).await?;
-            rbum_event_helper::try_notify(Self::get_table_name(), "d", id, funs, ctx).await?;
+            rbum_event_helper::add_entity_deleted_events(Self::get_table_name(), id, &delete_records, funs, ctx).await?;
+            Self::after_delete_rbum(id, deleted_rbum, funs, ctx).await?;
+            RbumHistoryServ::try_add_history(Self::get_table_name(), "d", &[Self::get_table_name()], id, before, funs, ctx).await?;
             Ok(delete_records.len() as u64)
         }
         #[cfg(not(feature = "with-mq"))]
         {
             let delete_records = funs.db().soft_delete(select, &ctx.owner).await?;
-            Self::after_delete_rbum(id, &deleted_rbum, funs, ctx).await?;
-            rbum_event_helper::try_notify(Self::get_table_name(), "d", &id, funs, ctx).await?;
+            Self::after_delete_rbum(id, deleted_rbum, funs, ctx).await?;
+            RbumHistoryServ::try_add_history(Self::get_table_name(), "d", &[Self::get_table_name()], id, before, funs, ctx).await?;
             Ok(delete_records)
         }
     }
 
+    // ----------------------------- Batch -------------------------------
+
+    /// Add the records with one bulk insert and notify one event for all of them.
+    ///
+    /// A request that fails the checks is reported by its index and does not prevent the others from being added.
+    /// The batch should be executed in one transaction, an error returned here means nothing should be committed.
+    async fn add_rbums(add_reqs: &mut [AddReq], funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<RbumBatchResp> {
+        let mut resp = RbumBatchResp::default();
+        let mut added_ids = HashSet::with_capacity(add_reqs.len());
+        let mut domains = Vec::with_capacity(add_reqs.len());
+        let mut added_indexes = Vec::with_capacity(add_reqs.len());
+        for (index, add_req) in add_reqs.iter_mut().enumerate() {
+            let checked: TardisResult<(String, E)> = async {
+                Self::before_add_rbum(add_req, funs, ctx).await?;
+                let domain = Self::package_add(add_req, funs, ctx).await?;
+                let id = Self::get_domain_id(&domain, funs)?;
+                Ok((id, domain))
+            }
+            .await;
+            match checked {
+                Ok((id, domain)) => {
+                    if !added_ids.insert(id.clone()) {
+                        resp.add_error(
+                            index,
+                            "",
+                            funs.err().conflict(&Self::get_obj_name(), "add", &format!("id {} is duplicated in the batch", id), "409-rbum-*-id-exist"),
+                        );
+                        continue;
+                    }
+                    resp.succeed_ids.push(id);
+                    domains.push(domain);
+                    added_indexes.push(index);
+                }
+                Err(e) => resp.add_error(index, "", e),
+            }
+        }
+        if domains.is_empty() {
+            return Ok(resp);
+        }
+        funs.db().insert_many(domains, ctx).await?;
+        for (id, index) in resp.succeed_ids.iter().zip(added_indexes) {
+            Self::after_add_rbum(id, &add_reqs[index], funs, ctx).await?;
+            RbumHistoryServ::try_add_history(Self::get_table_name(), "c", &[Self::get_table_name()], id, None, funs, ctx).await?;
+        }
+        rbum_event_helper::try_notify_batch(Self::get_table_name(), "c", &resp.succeed_ids, funs, ctx).await?;
+        Ok(resp)
+    }
+
+    fn get_domain_id(domain: &E, funs: &TardisFunsInst) -> TardisResult<String> {
+        let id = <E::Entity as EntityTrait>::PrimaryKey::iter().next().and_then(|key| domain.get(key.into_column()).into_value()).unwrap_or(Value::String(None));
+        Self::parse_id(ValueTuple::One(id), funs)
+    }
+
+    /// Modify the records, see [add_rbums](Self::add_rbums) for how the failed requests are handled.
+    ///
+    /// The ownership is checked once for the whole batch, the versions of the requests without an expected version are increased with one update
+    /// and the records modified the same way are updated together, see [update_many_by_changes].
+    async fn modify_rbums(modify_reqs: &mut [(String, ModifyReq)], funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<RbumBatchResp> {
+        let ids = modify_reqs.iter().map(|(id, _)| id.to_string()).collect::<Vec<String>>();
+        let owned_ids = Self::find_owned_rbum_ids(&ids, funs, ctx).await?;
+        let mut resp = RbumBatchResp::default();
+        let mut checked_ids = HashSet::with_capacity(modify_reqs.len());
+        let mut unversioned_ids = Vec::new();
+        let mut domains = Vec::with_capacity(modify_reqs.len());
+        let mut modified_indexes = Vec::with_capacity(modify_reqs.len());
+        for (index, (id, modify_req)) in modify_reqs.iter_mut().enumerate() {
+            let id = id.as_str();
+            if !owned_ids.contains(id) {
+                resp.add_error(index, id, Self::ownership_illegal_error(id, funs, ctx));
+                continue;
+            }
+            if !checked_ids.insert(id.to_string()) {
+                resp.add_error(
+                    index,
+                    id,
+                    funs.err().conflict(
+                        &Self::get_obj_name(),
+                        "modify",
+                        &format!("id {} is duplicated in the batch", id),
+                        "409-rbum-*-id-duplicated",
+                    ),
+                );
+                continue;
+            }
+            // Only checks before the version check, which is the first write, so a failed request changes nothing
+            let checked: TardisResult<(E, Option<JsonValue>, Option<u32>)> = async {
+                Self::check_modify_rbum(id, modify_req, funs, ctx).await?;
+                let domain = Self::package_modify(id, modify_req, funs, ctx).await?;
+                let before = RbumHistoryServ::try_snapshot(Self::get_table_name(), "u", &[Self::get_table_name()], id, funs).await?;
+                let version = Self::get_modify_version(modify_req);
+                if version.is_some() {
+                    Self::check_and_increase_version(id, version, funs).await?;
+                }
+                Ok((domain, before, version))
+            }
+            .await;
+            match checked {
+                Ok((domain, before, version)) => {
+                    if version.is_none() {
+                        unversioned_ids.push(id.to_string());
+                    }
+                    domains.push(domain);
+                    modified_indexes.push((index, before));
+                }
+                Err(e) => resp.add_error(index, id, e),
+            }
+        }
+        Self::increase_versions_with_table_name(unversioned_ids, Self::get_table_name(), funs).await?;
+        update_many_by_changes(domains, funs, ctx).await?;
+        for (index, before) in modified_indexes {
+            let (id, modify_req) = &mut modify_reqs[index];
+            Self::after_modify_rbum(id, modify_req, funs, ctx).await?;
+            RbumHistoryServ::try_add_history(Self::get_table_name(), "u", &[Self::get_table_name()], id, before, funs, ctx).await?;
+            resp.succeed_ids.push(id.to_string());
+        }
+        rbum_event_helper::try_notify_batch(Self::get_table_name(), "u", &resp.succeed_ids, funs, ctx).await?;
+        Ok(resp)
+    }
+
+    /// Delete the records, the ownership is checked once for the whole batch and the records are deleted with one statement,
+    /// see [add_rbums](Self::add_rbums) for how the failed requests are handled.
+    async fn delete_rbums(ids: &[String], funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<RbumBatchResp> {
+        let deletable_ids = Self::find_deletable_rbum_ids(ids, funs, ctx).await?;
+        let mut resp = RbumBatchResp::default();
+        let mut checked_ids = HashSet::with_capacity(ids.len());
+        let mut deleted_rbums = Vec::with_capacity(ids.len());
+        for (index, id) in ids.iter().enumerate() {
+            if !deletable_ids.contains(id) {
+                resp.add_error(index, id, Self::ownership_illegal_error(id, funs, ctx));
+                continue;
+            }
+            if !checked_ids.insert(id.to_string()) {
+                resp.add_error(
+                    index,
+                    id,
+                    funs.err().conflict(
+                        &Self::get_obj_name(),
+                        "delete",
+                        &format!("id {} is duplicated in the batch", id),
+                        "409-rbum-*-id-duplicated",
+                    ),
+                );
+                continue;
+            }
+            match Self::check_delete_rbum(id, funs, ctx).await {
+                Ok(deleted_rbum) => deleted_rbums.push((id.to_string(), deleted_rbum)),
+                Err(e) => resp.add_error(index, id, e),
+            }
+        }
+        Self::do_delete_rbums(&deleted_rbums, funs, ctx).await?;
+        resp.succeed_ids = deleted_rbums.into_iter().map(|(id, _)| id).collect();
+        rbum_event_helper::try_notify_batch(Self::get_table_name(), "d", &resp.succeed_ids, funs, ctx).await?;
+        Ok(resp)
+    }
+
+    /// Delete the records that have passed the checks with one statement, the events are not notified.
+    ///
+    /// The records are selected by their ids, [package_delete](Self::package_delete) is not used.
+    async fn do_delete_rbums(deleted_rbums: &[(String, Option<DetailResp>)], funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<u64> {
+        if deleted_rbums.is_empty() {
+            return Ok(0);
+        }
+        let ids = deleted_rbums.iter().map(|(id, _)| id.to_string()).collect::<Vec<String>>();
+        let mut befores = Vec::with_capacity(ids.len());
+        for id in &ids {
+            befores.push(RbumHistoryServ::try_snapshot(Self::get_table_name(), "d", &[Self::get_table_name()], id, funs).await?);
+        }
+        let select = E::Entity::find().filter(Expr::col(ID_FIELD.clone()).is_in(ids));
+        #[cfg(feature = "with-mq")]
+        let delete_records = {
+            let delete_records = funs.db().soft_delete_custom(select, "id").await?;
+            rbum_event_helper::add_entity_deleted_events(Self::get_table_name(), "", &delete_records, funs, ctx).await?;
+            delete_records.len() as u64
+        };
+        #[cfg(not(feature = "with-mq"))]
+        let delete_records = funs.db().soft_delete(select, &ctx.owner).await?;
+        for ((id, deleted_rbum), before) in deleted_rbums.iter().zip(befores) {
+            Self::after_delete_rbum(id, deleted_rbum, funs, ctx).await?;
+            RbumHistoryServ::try_add_history(Self::get_table_name(), "d", &[Self::get_table_name()], id, before, funs, ctx).await?;
+        }
+        Ok(delete_records)
+    }
+
+    // ----------------------------- History -------------------------------
+
+    async fn paginate_rbum_histories(id: &str, page_number: u64, page_size: u64, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<TardisPage<RbumHistoryResp>> {
+        RbumHistoryServ::paginate_histories(Self::get_table_name(), id, page_number, page_size, funs, ctx).await
+    }
+
+    async fn get_rbum_snapshot_as_of(id: &str, time: DateTime<Utc>, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<JsonValue>> {
+        RbumHistoryServ::get_snapshot_as_of(Self::get_table_name(), id, time, funs, ctx).await
+    }
+
     // ----------------------------- Query -------------------------------
 
     async fn package_query(is_detail: bool, filter: &FilterReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<SelectStatement>;
@@ -431,6 +740,58 @@ where
         })
     }
 
+    /// Paginate by the cursor of the previous page instead of the page number, see [rbum_cursor_helper] for how it works.
+    ///
+    /// The sort fields must be non-nullable columns of the table, the ordering of the filter is ignored.
+    async fn paginate_rbums_by_cursor(
+        filter: &FilterReq,
+        sorts: &[RbumSortReq],
+        cursor: Option<&str>,
+        page_size: u64,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<RbumCursorPage<SummaryResp>> {
+        Self::do_paginate_rbums_by_cursor(filter, sorts, cursor, page_size, funs, ctx).await
+    }
+
+    async fn do_paginate_rbums_by_cursor(
+        filter: &FilterReq,
+        sorts: &[RbumSortReq],
+        cursor: Option<&str>,
+        page_size: u64,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<RbumCursorPage<SummaryResp>> {
+        let sort_columns = Self::package_sort_columns(sorts, funs)?;
+        let cursor_values = match cursor {
+            Some(cursor) => Some(rbum_cursor_helper::decode_cursor(cursor, &sort_columns, funs)?),
+            None => None,
+        };
+        let mut query = Self::package_query(false, filter, funs, ctx).await?;
+        rbum_cursor_helper::package_cursor_query(&mut query, Self::get_table_name(), &sort_columns, cursor_values, page_size);
+        let records = funs.db().find_dtos::<RbumCursorRecord<SummaryResp>>(&query).await?;
+        rbum_cursor_helper::package_cursor_page(records, page_size)
+    }
+
+    /// Resolve the sort fields to the columns of the table, nullable columns are rejected.
+    fn package_sort_columns(sorts: &[RbumSortReq], funs: &TardisFunsInst) -> TardisResult<Vec<RbumCursorSortColumn>> {
+        sorts
+            .iter()
+            .map(|sort| {
+                if let Some(column) = <E::Entity as EntityTrait>::Column::iter().find(|column| column.as_str() == sort.field) {
+                    rbum_cursor_helper::package_sort_column(Self::get_table_name(), column.def(), sort, &Self::get_obj_name(), funs)
+                } else {
+                    Err(funs.err().bad_request(
+                        &Self::get_obj_name(),
+                        "paginate",
+                        &format!("sort field {} is not a column of {}", sort.field, Self::get_table_name()),
+                        "400-rbum-*-sort-field-illegal",
+                    ))
+                }
+            })
+            .collect()
+    }
+
     async fn find_one_rbum(filter: &FilterReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<SummaryResp>> {
         Self::do_find_one_rbum(filter, funs, ctx).await
     }
@@ -619,86 +980,67 @@ impl RbumCrudQueryPackage for SelectStatement {
         };
         cond = cond.add(own_cond);
 
-        if let Some(p1) = rbum_scope_helper::get_pre_paths(1, filter_own_paths) {
-            cond = cond.add(
-                Cond::all().add(Expr::tbl(Alias::new(table_name), SCOPE_LEVEL_FIELD.clone()).eq(1)).add(
-                    Cond::any()
-                        .add(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()).eq(""))
-                        .add(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()).like(format!("{}%", p1))),
-                ),
-            );
-            if let Some(p2) = rbum_scope_helper::get_pre_paths(2, filter_own_paths) {
-                let node_len = (p2.len() - p1.len() - 1) as u8;
-                cond = cond.add(
-                    Cond::all().add(Expr::tbl(Alias::new(table_name), SCOPE_LEVEL_FIELD.clone()).eq(2)).add(
-                        Cond::any()
-                            .add(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()).eq(""))
-                            .add(
-                                Cond::all()
-                                    .add(Expr::expr(Func::char_length(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()))).eq(node_len))
-                                    .add(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()).like(format!("{}%", p1))),
-                            )
-                            .add(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()).like(format!("{}%", p2))),
-                    ),
-                );
-                if let Some(p3) = rbum_scope_helper::get_pre_paths(3, filter_own_paths) {
-                    cond = cond.add(
-                        Cond::all().add(Expr::tbl(Alias::new(table_name), SCOPE_LEVEL_FIELD.clone()).eq(3)).add(
-                            Cond::any()
-                                .add(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()).eq(""))
-                                .add(
-                                    Cond::all()
-                                        .add(Expr::expr(Func::char_length(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()))).eq(node_len))
-                                        .add(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()).like(format!("{}%", p1))),
-                                )
-                                .add(
-                                    Cond::all()
-                                        .add(Expr::expr(Func::char_length(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()))).eq(node_len * 2 + 1))
-                                        .add(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()).like(format!("{}%", p2))),
-                                )
-                                .add(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()).like(format!("{}%", p3))),
-                        ),
-                    );
-                } else if with_sub_own_paths {
-                    // System admin (own_paths = "") created Tenant admin (scope_level = 1 & own_paths = "") and App admin (scope_level = 2 & own_paths = "").
-                    //
-                    // A tenant admin needs to query the roles under that tenant and app, the corresponding condition should be (with_sub_own_paths = true):
-                    //
-                    // ```sql
-                    // scope_level = 0
-                    // OR own_paths LIKE '<tenant_id>%'
-                    // OR (scope_level = 1 AND (own_paths = '' OR own_paths LIKE '<tenant_id>%'))
-                    // OR (scope_level = 2 AND (own_paths = '' OR own_paths LIKE '<tenant_id>%'))
-                    // ```
-                    cond = cond.add(
-                        Cond::all().add(Expr::tbl(Alias::new(table_name), SCOPE_LEVEL_FIELD.clone()).eq(3)).add(
-                            Cond::any()
-                                .add(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()).eq(""))
-                                .add(
-                                    Cond::all()
-                                        .add(Expr::expr(Func::char_length(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()))).eq(node_len))
-                                        .add(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()).like(format!("{}%", p1))),
-                                )
-                                .add(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()).like(format!("{}%", p2))),
-                        ),
-                    );
-                }
-            } else if with_sub_own_paths {
-                cond = cond.add(
-                    Cond::all().add(Expr::tbl(Alias::new(table_name), SCOPE_LEVEL_FIELD.clone()).eq(2)).add(
-                        Cond::any()
-                            .add(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()).eq(""))
-                            .add(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()).like(format!("{}%", p1))),
-                    ),
-                );
-            }
+        let pre_paths = rbum_scope_helper::get_all_pre_paths(filter_own_paths);
+        // System admin (own_paths = "") created Tenant admin (scope_level = 1 & own_paths = "") and App admin (scope_level = 2 & own_paths = "").
+        //
+        // A tenant admin needs to query the roles under that tenant and app, the corresponding condition should be (with_sub_own_paths = true):
+        //
+        // ```sql
+        // scope_level = 0
+        // OR own_paths LIKE '<tenant_id>%'
+        // OR (scope_level = 1 AND (own_paths = '' OR own_paths LIKE '<tenant_id>%'))
+        // OR (scope_level = 2 AND (own_paths = '' OR own_paths LIKE '<tenant_id>%'))
+        // ```
+        let max_scope_level = if with_sub_own_paths && !pre_paths.is_empty() {
+            pre_paths.len() + 1
+        } else {
+            pre_paths.len()
         };
+        for scope_level in 1..=max_scope_level {
+            // (scope_level = N AND (own_paths = '' OR own_paths IN ('<p1>', ..., '<pN-1>') OR own_paths LIKE '<pN>%'))
+            let (level_pre_paths, parent_pre_paths) = pre_paths[..scope_level.min(pre_paths.len())].split_last().unwrap();
+            let mut own_paths_cond = Cond::any()
+                .add(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()).eq(""))
+                .add(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()).like(format!("{}%", level_pre_paths)));
+            if !parent_pre_paths.is_empty() {
+                own_paths_cond = own_paths_cond.add(Expr::tbl(Alias::new(table_name), OWN_PATHS_FIELD.clone()).is_in(parent_pre_paths.to_vec()));
+            }
+            cond = cond.add(Cond::all().add(Expr::tbl(Alias::new(table_name), SCOPE_LEVEL_FIELD.clone()).eq(scope_level as i8)).add(own_paths_cond));
+        }
 
         self.cond_where(Cond::all().add(cond));
         self
     }
 }
 
+/// Update the records with one statement for each distinct set of changes, so the records modified the same way are updated together.
+///
+/// The records are matched by their primary keys, the domains without any change are skipped.
+pub async fn update_many_by_changes<T>(domains: Vec<T>, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()>
+where
+    T: TardisActiveModel + Sync + Send,
+{
+    let id_column = match <T::Entity as EntityTrait>::PrimaryKey::iter().next() {
+        Some(key) => key.into_column(),
+        None => return Err(funs.err().internal_error("rbum", "update", "the primary key is missing", "500-rbum-crud-id-type")),
+    };
+    let mut changes_groups: HashMap<String, (T, Vec<Value>)> = HashMap::new();
+    for mut domain in domains {
+        domain.fill_ctx(ctx, false);
+        let id = domain.get(id_column).into_value().unwrap_or(Value::String(None));
+        domain.not_set(id_column);
+        if !<T::Entity as EntityTrait>::Column::iter().any(|column| domain.get(column).is_set()) {
+            continue;
+        }
+        let changes = <T::Entity as EntityTrait>::Column::iter().map(|column| format!("{:?}", domain.get(column))).join(",");
+        changes_groups.entry(changes).or_insert_with(|| (domain, Vec::new())).1.push(id);
+    }
+    for (domain, ids) in changes_groups.into_values() {
+        funs.db().execute(&<T::Entity as EntityTrait>::update_many().set(domain).filter(id_column.is_in(ids)).into_query()).await?;
+    }
+    Ok(())
+}
+
 #[derive(Debug, sea_orm::FromQueryResult)]
 pub struct NameResp {
     pub name: String,
@@ -74,8 +74,11 @@ impl RbumCrudOperation<rbum_domain::ActiveModel, RbumDomainAddReq, RbumDomainMod
         Ok(rbum_domain)
     }
 
-    async fn before_delete_rbum(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<RbumDomainDetailResp>> {
-        Self::check_ownership(id, funs, ctx).await?;
+    fn get_modify_version(modify_req: &RbumDomainModifyReq) -> Option<u32> {
+        modify_req.version
+    }
+
+    async fn check_delete_rbum(id: &str, funs: &TardisFunsInst, _: &TardisContext) -> TardisResult<Option<RbumDomainDetailResp>> {
         Self::check_exist_before_delete(id, RbumItemServ::get_table_name(), rbum_item::Column::RelRbumDomainId.as_str(), funs).await?;
         Self::check_exist_before_delete(id, RbumCertConfServ::get_table_name(), rbum_cert_conf::Column::RelRbumDomainId.as_str(), funs).await?;
         Ok(None)
@@ -94,6 +97,7 @@ impl RbumCrudOperation<rbum_domain::ActiveModel, RbumDomainAddReq, RbumDomainMod
             (rbum_domain::Entity, rbum_domain::Column::Owner),
             (rbum_domain::Entity, rbum_domain::Column::CreateTime),
             (rbum_domain::Entity, rbum_domain::Column::UpdateTime),
+            (rbum_domain::Entity, rbum_domain::Column::Version),
             (rbum_domain::Entity, rbum_domain::Column::ScopeLevel),
         ]);
         query.from(rbum_domain::Entity).with_filter(Self::get_table_name(), filter, is_detail, true, ctx);
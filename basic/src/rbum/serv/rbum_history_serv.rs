@@ -0,0 +1,200 @@
+use tardis::basic::dto::TardisContext;
+use tardis::basic::result::TardisResult;
+use tardis::chrono::{DateTime, Utc};
+use tardis::db::sea_orm::sea_query::*;
+use tardis::db::sea_orm::*;
+use tardis::serde_json::{Map, Value as JsonValue};
+use tardis::web::web_resp::TardisPage;
+use tardis::{TardisFuns, TardisFunsInst};
+
+use crate::rbum::domain::{rbum_history, rbum_item};
+use crate::rbum::dto::rbum_history_dto::RbumHistoryResp;
+use crate::rbum::rbum_config::RbumConfigApi;
+use crate::rbum::serv::rbum_crud_serv::{ID_FIELD, UPDATE_TIME_FIELD, VERSION_FIELD};
+
+pub struct RbumHistoryServ;
+
+impl RbumHistoryServ {
+    /// Take the snapshot of the record before the change, `None` if the change is not recorded.
+    ///
+    /// The snapshot merges the records with the same id in the tables, e.g. an item and its extension.
+    pub async fn try_snapshot(table_name: &str, operate: &str, snapshot_table_names: &[&str], record_id: &str, funs: &TardisFunsInst) -> TardisResult<Option<JsonValue>> {
+        if !funs.rbum_conf_match_history(table_name, operate) {
+            return Ok(None);
+        }
+        Self::snapshot(snapshot_table_names, record_id, funs).await
+    }
+
+    /// Record the change with the snapshot taken by [`try_snapshot`](Self::try_snapshot), the snapshot after the change is taken here.
+    ///
+    /// A modification that changes nothing is not recorded.
+    pub async fn try_add_history(
+        table_name: &str,
+        operate: &str,
+        snapshot_table_names: &[&str],
+        record_id: &str,
+        before: Option<JsonValue>,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<()> {
+        if !funs.rbum_conf_match_history(table_name, operate) {
+            return Ok(());
+        }
+        let after = if operate == "d" {
+            None
+        } else {
+            Self::snapshot(snapshot_table_names, record_id, funs).await?
+        };
+        let changed_fields = Self::diff(&before, &after);
+        if changed_fields.is_empty() {
+            return Ok(());
+        }
+        let own_paths =
+            after.as_ref().or(before.as_ref()).and_then(|snapshot| snapshot.get("own_paths")).and_then(|own_paths| own_paths.as_str()).unwrap_or(&ctx.own_paths).to_string();
+        funs.db()
+            .insert_one(
+                rbum_history::ActiveModel {
+                    id: Set(TardisFuns::field.nanoid()),
+                    table_name: Set(table_name.to_string()),
+                    record_id: Set(record_id.to_string()),
+                    operate: Set(operate.to_string()),
+                    changed_fields: Set(changed_fields.join(",")),
+                    before: Set(before.map(|before| before.to_string()).unwrap_or_default()),
+                    after: Set(after.map(|after| after.to_string()).unwrap_or_default()),
+                    own_paths: Set(own_paths),
+                    create_time: Set(Utc::now()),
+                    ..Default::default()
+                },
+                ctx,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Same as [`try_snapshot`](Self::try_snapshot), the item is merged with its extension.
+    pub async fn try_snapshot_item(ext_table_name: &str, operate: &str, item_id: &str, funs: &TardisFunsInst) -> TardisResult<Option<JsonValue>> {
+        Self::try_snapshot(ext_table_name, operate, &[rbum_item::Entity.table_name(), ext_table_name], item_id, funs).await
+    }
+
+    /// Same as [`try_add_history`](Self::try_add_history), the item is merged with its extension.
+    pub async fn try_add_item_history(
+        ext_table_name: &str,
+        operate: &str,
+        item_id: &str,
+        before: Option<JsonValue>,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<()> {
+        Self::try_add_history(ext_table_name, operate, &[rbum_item::Entity.table_name(), ext_table_name], item_id, before, funs, ctx).await
+    }
+
+    pub async fn paginate_histories(
+        table_name: &str,
+        record_id: &str,
+        page_number: u64,
+        page_size: u64,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<TardisPage<RbumHistoryResp>> {
+        let mut query = Self::package_query(table_name, record_id, ctx);
+        query.order_by((rbum_history::Entity, rbum_history::Column::CreateTime), Order::Desc).order_by((rbum_history::Entity, rbum_history::Column::Seq), Order::Desc);
+        let (records, total_size) = funs.db().paginate_dtos::<rbum_history::Model>(&query, page_number, page_size).await?;
+        Ok(TardisPage {
+            page_size,
+            page_number,
+            total_size,
+            records: records.into_iter().map(Self::to_resp).collect(),
+        })
+    }
+
+    /// State of the record as of the time, `None` if the record did not exist or had been deleted at that time.
+    ///
+    /// Only the changes recorded in the history are considered, changes with the same time (in seconds) are ordered by their sequence.
+    pub async fn get_snapshot_as_of(table_name: &str, record_id: &str, time: DateTime<Utc>, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<JsonValue>> {
+        let mut query = Self::package_query(table_name, record_id, ctx);
+        query
+            .and_where(Expr::tbl(rbum_history::Entity, rbum_history::Column::CreateTime).lte(time))
+            .order_by((rbum_history::Entity, rbum_history::Column::CreateTime), Order::Desc)
+            .order_by((rbum_history::Entity, rbum_history::Column::Seq), Order::Desc);
+        let history = funs.db().get_dto::<rbum_history::Model>(&query).await?;
+        Ok(history.and_then(|history| Self::parse_snapshot(&history.after)))
+    }
+
+    async fn snapshot(table_names: &[&str], record_id: &str, funs: &TardisFunsInst) -> TardisResult<Option<JsonValue>> {
+        let mut snapshot = Map::new();
+        for table_name in table_names {
+            let record =
+                funs.db().get_dto::<JsonValue>(Query::select().expr(Expr::cust("*")).from(Alias::new(*table_name)).and_where(Expr::col(ID_FIELD.clone()).eq(record_id))).await?;
+            if let Some(JsonValue::Object(record)) = record {
+                snapshot.extend(record);
+            }
+        }
+        if snapshot.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(JsonValue::Object(snapshot)))
+        }
+    }
+
+    /// Names of the changed fields, the update time and version are ignored as they change with every modification.
+    fn diff(before: &Option<JsonValue>, after: &Option<JsonValue>) -> Vec<String> {
+        let empty = Map::new();
+        let before = before.as_ref().and_then(|before| before.as_object()).unwrap_or(&empty);
+        let after = after.as_ref().and_then(|after| after.as_object()).unwrap_or(&empty);
+        let ignored_fields = [UPDATE_TIME_FIELD.to_string(), VERSION_FIELD.to_string()];
+        let mut changed_fields = before
+            .keys()
+            .chain(after.keys().filter(|field| !before.contains_key(*field)))
+            .filter(|field| !ignored_fields.contains(*field) && before.get(*field) != after.get(*field))
+            .cloned()
+            .collect::<Vec<String>>();
+        changed_fields.sort();
+        changed_fields
+    }
+
+    fn package_query(table_name: &str, record_id: &str, ctx: &TardisContext) -> SelectStatement {
+        let mut query = Query::select();
+        query
+            .columns(vec![
+                (rbum_history::Entity, rbum_history::Column::Id),
+                (rbum_history::Entity, rbum_history::Column::TableName),
+                (rbum_history::Entity, rbum_history::Column::RecordId),
+                (rbum_history::Entity, rbum_history::Column::Operate),
+                (rbum_history::Entity, rbum_history::Column::ChangedFields),
+                (rbum_history::Entity, rbum_history::Column::Before),
+                (rbum_history::Entity, rbum_history::Column::After),
+                (rbum_history::Entity, rbum_history::Column::Seq),
+                (rbum_history::Entity, rbum_history::Column::OwnPaths),
+                (rbum_history::Entity, rbum_history::Column::Owner),
+                (rbum_history::Entity, rbum_history::Column::CreateTime),
+            ])
+            .from(rbum_history::Entity)
+            .and_where(Expr::tbl(rbum_history::Entity, rbum_history::Column::TableName).eq(table_name))
+            .and_where(Expr::tbl(rbum_history::Entity, rbum_history::Column::RecordId).eq(record_id))
+            .and_where(Expr::tbl(rbum_history::Entity, rbum_history::Column::OwnPaths).like(format!("{}%", ctx.own_paths).as_str()));
+        query
+    }
+
+    fn parse_snapshot(snapshot: &str) -> Option<JsonValue> {
+        if snapshot.is_empty() {
+            None
+        } else {
+            TardisFuns::json.str_to_obj::<JsonValue>(snapshot).ok()
+        }
+    }
+
+    fn to_resp(history: rbum_history::Model) -> RbumHistoryResp {
+        RbumHistoryResp {
+            changed_fields: history.changed_fields.split(',').filter(|field| !field.is_empty()).map(|field| field.to_string()).collect(),
+            before: Self::parse_snapshot(&history.before),
+            after: Self::parse_snapshot(&history.after),
+            id: history.id,
+            table_name: history.table_name,
+            record_id: history.record_id,
+            operate: history.operate,
+            own_paths: history.own_paths,
+            owner: history.owner,
+            create_time: history.create_time,
+        }
+    }
+}
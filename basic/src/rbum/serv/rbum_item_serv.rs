@@ -1,31 +1,42 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use async_trait::async_trait;
 use serde::Serialize;
 use tardis::basic::dto::TardisContext;
+use tardis::basic::error::TardisError;
 use tardis::basic::result::TardisResult;
+use tardis::chrono::{DateTime, Duration, Utc};
 use tardis::db::reldb_client::{IdResp, TardisActiveModel};
 use tardis::db::sea_orm;
 use tardis::db::sea_orm::sea_query::*;
 use tardis::db::sea_orm::*;
+use tardis::serde_json::Value as JsonValue;
 use tardis::web::poem_openapi::types::{ParseFromJSON, ToJSON};
 use tardis::web::web_resp::TardisPage;
-use tardis::{TardisFuns, TardisFunsInst};
+use tardis::{log, TardisFuns, TardisFunsInst};
 
 use crate::rbum::domain::{rbum_cert, rbum_cert_conf, rbum_domain, rbum_item, rbum_item_attr, rbum_kind, rbum_kind_attr, rbum_rel, rbum_set_item};
+use crate::rbum::dto::rbum_batch_dto::RbumBatchResp;
+use crate::rbum::dto::rbum_cursor_dto::RbumCursorPage;
 use crate::rbum::dto::rbum_filer_dto::{
-    RbumBasicFilterReq, RbumCertConfFilterReq, RbumCertFilterReq, RbumItemAttrFilterReq, RbumItemFilterFetcher, RbumItemRelFilterReq, RbumKindAttrFilterReq, RbumSetItemFilterReq,
+    RbumBasicFilterReq, RbumCertConfFilterReq, RbumCertFilterReq, RbumItemAttrFilterReq, RbumItemAttrSortReq, RbumItemAttrValueFilterReq, RbumItemFilterFetcher,
+    RbumItemRelFilterReq, RbumKindAttrFilterReq, RbumSetItemFilterReq, RbumSortReq,
 };
-use crate::rbum::dto::rbum_item_attr_dto::{RbumItemAttrAddReq, RbumItemAttrDetailResp, RbumItemAttrModifyReq, RbumItemAttrSummaryResp, RbumItemAttrsAddOrModifyReq};
-use crate::rbum::dto::rbum_item_dto::{RbumItemAddReq, RbumItemDetailResp, RbumItemKernelAddReq, RbumItemModifyReq, RbumItemSummaryResp};
+use crate::rbum::dto::rbum_history_dto::RbumHistoryResp;
+use crate::rbum::dto::rbum_item_attr_dto::{
+    RbumItemAttrAddReq, RbumItemAttrDetailResp, RbumItemAttrFieldErrorResp, RbumItemAttrModifyReq, RbumItemAttrSummaryResp, RbumItemAttrsAddOrModifyReq,
+};
+use crate::rbum::dto::rbum_item_dto::{RbumItemAddReq, RbumItemDetailResp, RbumItemKernelAddReq, RbumItemModifyReq, RbumItemRecycleSummaryResp, RbumItemSummaryResp};
 use crate::rbum::dto::rbum_kind_attr_dto::RbumKindAttrSummaryResp;
 use crate::rbum::dto::rbum_rel_dto::{RbumRelAddReq, RbumRelFindReq};
-use crate::rbum::helper::rbum_event_helper;
-use crate::rbum::rbum_config::RbumConfigApi;
-use crate::rbum::rbum_enumeration::{RbumCertRelKind, RbumRelFromKind, RbumScopeLevelKind};
+use crate::rbum::helper::rbum_cursor_helper::{self, RbumCursorRecord, RbumCursorSortColumn};
+use crate::rbum::helper::{rbum_event_helper, rbum_item_attr_helper};
+use crate::rbum::rbum_config::RbumConfigManager;
+use crate::rbum::rbum_enumeration::{RbumCertRelKind, RbumDataTypeKind, RbumRelFromKind, RbumScopeLevelKind};
 use crate::rbum::serv::rbum_cert_serv::{RbumCertConfServ, RbumCertServ};
-use crate::rbum::serv::rbum_crud_serv::{RbumCrudOperation, RbumCrudQueryPackage, CREATE_TIME_FIELD, ID_FIELD, UPDATE_TIME_FIELD};
+use crate::rbum::serv::rbum_crud_serv::{update_many_by_changes, RbumCrudOperation, RbumCrudQueryPackage, CREATE_TIME_FIELD, ID_FIELD, UPDATE_TIME_FIELD};
 use crate::rbum::serv::rbum_domain_serv::RbumDomainServ;
+use crate::rbum::serv::rbum_history_serv::RbumHistoryServ;
 use crate::rbum::serv::rbum_kind_serv::{RbumKindAttrServ, RbumKindServ};
 use crate::rbum::serv::rbum_rel_serv::RbumRelServ;
 use crate::rbum::serv::rbum_set_serv::RbumSetItemServ;
@@ -76,6 +87,7 @@ impl RbumCrudOperation<rbum_item::ActiveModel, RbumItemAddReq, RbumItemModifyReq
             rel_rbum_domain_id: Set(add_req.rel_rbum_domain_id.to_string()),
             scope_level: Set(add_req.scope_level.as_ref().unwrap_or(&RbumScopeLevelKind::Private).to_int()),
             disabled: Set(add_req.disabled.unwrap_or(false)),
+            deleted: Set(false),
             ..Default::default()
         })
     }
@@ -128,25 +140,55 @@ impl RbumCrudOperation<rbum_item::ActiveModel, RbumItemAddReq, RbumItemModifyReq
         Ok(rbum_item)
     }
 
+    fn get_modify_version(modify_req: &RbumItemModifyReq) -> Option<u32> {
+        modify_req.version
+    }
+
+    async fn check_ownership(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        // Items in the recycle bin can only be restored or purged
+        let mut query = Self::package_ownership_query(id, ctx);
+        query.and_where(Expr::col(rbum_item::Column::Deleted).eq(false));
+        if funs.db().count(&query).await? == 0 {
+            return Err(funs.err().not_found(
+                &Self::get_obj_name(),
+                "check",
+                &format!("ownership {}.{} is illegal by {}", Self::get_obj_name(), id, ctx.owner),
+                "404-rbum-*-ownership-illegal",
+            ));
+        }
+        Ok(())
+    }
+
+    async fn find_owned_rbum_ids(ids: &[String], funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<HashSet<String>> {
+        if ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+        let owned_ids = funs
+            .db()
+            .find_dtos::<IdResp>(
+                Query::select()
+                    .column(rbum_item::Column::Id)
+                    .from(rbum_item::Entity)
+                    .and_where(Expr::col(rbum_item::Column::Id).is_in(ids.to_vec()))
+                    .and_where(Expr::col(rbum_item::Column::OwnPaths).like(format!("{}%", ctx.own_paths).as_str()))
+                    .and_where(Expr::col(rbum_item::Column::Deleted).eq(false)),
+            )
+            .await?;
+        Ok(owned_ids.into_iter().map(|resp| resp.id).collect())
+    }
+
     async fn before_delete_rbum(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<RbumItemDetailResp>> {
-        Self::check_ownership(id, funs, ctx).await?;
-        Self::check_exist_before_delete(id, RbumItemAttrServ::get_table_name(), rbum_item_attr::Column::RelRbumItemId.as_str(), funs).await?;
-        Self::check_exist_with_cond_before_delete(
-            RbumRelServ::get_table_name(),
-            Cond::any()
-                .add(Cond::all().add(Expr::col(rbum_rel::Column::FromRbumKind).eq(RbumRelFromKind::Item.to_int())).add(Expr::col(rbum_rel::Column::FromRbumId).eq(id)))
-                .add(Expr::col(rbum_rel::Column::ToRbumItemId).eq(id)),
-            funs,
-        )
-        .await?;
-        Self::check_exist_before_delete(id, RbumSetItemServ::get_table_name(), rbum_set_item::Column::RelRbumItemId.as_str(), funs).await?;
-        Self::check_exist_before_delete(id, RbumCertConfServ::get_table_name(), rbum_cert_conf::Column::RelRbumItemId.as_str(), funs).await?;
-        Self::check_exist_with_cond_before_delete(
-            RbumCertServ::get_table_name(),
-            Cond::all().add(Expr::col(rbum_cert::Column::RelRbumKind).eq(RbumCertRelKind::Item.to_int())).add(Expr::col(rbum_cert::Column::RelRbumId).eq(id)),
-            funs,
-        )
-        .await?;
+        // Not `check_ownership`, which excludes the items in the recycle bin, so that they can be purged
+        Self::check_ownership_with_table_name(id, Self::get_table_name(), funs, ctx).await?;
+        Self::check_delete_rbum(id, funs, ctx).await
+    }
+
+    async fn find_deletable_rbum_ids(ids: &[String], funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<HashSet<String>> {
+        Self::find_owned_rbum_ids_with_table_name(ids, Self::get_table_name(), funs, ctx).await
+    }
+
+    async fn check_delete_rbum(id: &str, funs: &TardisFunsInst, _: &TardisContext) -> TardisResult<Option<RbumItemDetailResp>> {
+        Self::check_exist_rels_before_delete(id, funs).await?;
         Ok(None)
     }
 
@@ -170,6 +212,7 @@ impl RbumCrudOperation<rbum_item::ActiveModel, RbumItemAddReq, RbumItemModifyReq
 
         if is_detail {
             query
+                .column((rbum_item::Entity, rbum_item::Column::Version))
                 .expr_as(Expr::tbl(rbum_kind::Entity, rbum_kind::Column::Name), Alias::new("rel_rbum_kind_name"))
                 .expr_as(Expr::tbl(rbum_domain::Entity, rbum_domain::Column::Name), Alias::new("rel_rbum_domain_name"))
                 .inner_join(
@@ -182,10 +225,397 @@ impl RbumCrudOperation<rbum_item::ActiveModel, RbumItemAddReq, RbumItemModifyReq
                 );
         }
         query.with_filter(Self::get_table_name(), filter, is_detail, true, ctx);
+        query.and_where(Expr::tbl(rbum_item::Entity, rbum_item::Column::Deleted).eq(false));
         Ok(query)
     }
 }
 
+impl RbumItemServ {
+    async fn check_exist_rels_before_delete(id: &str, funs: &TardisFunsInst) -> TardisResult<()> {
+        Self::check_exist_before_delete(id, RbumItemAttrServ::get_table_name(), rbum_item_attr::Column::RelRbumItemId.as_str(), funs).await?;
+        Self::check_exist_with_cond_before_delete(
+            RbumRelServ::get_table_name(),
+            Cond::any()
+                .add(Cond::all().add(Expr::col(rbum_rel::Column::FromRbumKind).eq(RbumRelFromKind::Item.to_int())).add(Expr::col(rbum_rel::Column::FromRbumId).eq(id)))
+                .add(Expr::col(rbum_rel::Column::ToRbumItemId).eq(id)),
+            funs,
+        )
+        .await?;
+        Self::check_exist_before_delete(id, RbumSetItemServ::get_table_name(), rbum_set_item::Column::RelRbumItemId.as_str(), funs).await?;
+        Self::check_exist_before_delete(id, RbumCertConfServ::get_table_name(), rbum_cert_conf::Column::RelRbumItemId.as_str(), funs).await?;
+        Self::check_exist_with_cond_before_delete(
+            RbumCertServ::get_table_name(),
+            Cond::all().add(Expr::col(rbum_cert::Column::RelRbumKind).eq(RbumCertRelKind::Item.to_int())).add(Expr::col(rbum_cert::Column::RelRbumId).eq(id)),
+            funs,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Ids of the items of the kind owned by the context in one query, the items in the recycle bin are excluded like [check_ownership](Self::check_ownership).
+    async fn find_owned_ids(ids: &[String], kind_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<HashSet<String>> {
+        let owned_ids = funs
+            .db()
+            .find_dtos::<IdResp>(
+                Query::select()
+                    .column(rbum_item::Column::Id)
+                    .from(rbum_item::Entity)
+                    .and_where(Expr::col(rbum_item::Column::Id).is_in(ids.to_vec()))
+                    .and_where(Expr::col(rbum_item::Column::RelRbumKindId).eq(kind_id))
+                    .and_where(Expr::col(rbum_item::Column::OwnPaths).like(format!("{}%", ctx.own_paths).as_str()))
+                    .and_where(Expr::col(rbum_item::Column::Deleted).eq(false)),
+            )
+            .await?;
+        Ok(owned_ids.into_iter().map(|resp| resp.id).collect())
+    }
+
+    /// Delete the rels, set items, certs and cert confs associated with the item.
+    async fn delete_all_rels(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        // Delete rels
+        let rel_ids = RbumRelServ::find_rel_ids(
+            &RbumRelFindReq {
+                tag: None,
+                from_rbum_kind: Some(RbumRelFromKind::Item),
+                from_rbum_id: Some(id.to_string()),
+                to_rbum_item_id: None,
+                ..Default::default()
+            },
+            funs,
+            ctx,
+        )
+        .await?;
+        for rel_id in rel_ids {
+            RbumRelServ::delete_rel_with_ext(&rel_id, funs, ctx).await?;
+        }
+        let rel_ids = RbumRelServ::find_rel_ids(
+            &RbumRelFindReq {
+                tag: None,
+                from_rbum_kind: Some(RbumRelFromKind::Item),
+                from_rbum_id: None,
+                to_rbum_item_id: Some(id.to_string()),
+                ..Default::default()
+            },
+            funs,
+            ctx,
+        )
+        .await?;
+        for rel_id in rel_ids {
+            RbumRelServ::delete_rel_with_ext(&rel_id, funs, ctx).await?;
+        }
+
+        // Delete set items
+        let set_item_ids = RbumSetItemServ::find_id_rbums(
+            &RbumSetItemFilterReq {
+                basic: RbumBasicFilterReq {
+                    with_sub_own_paths: true,
+                    ..Default::default()
+                },
+                rel_rbum_item_ids: Some(vec![id.to_string()]),
+                ..Default::default()
+            },
+            None,
+            None,
+            funs,
+            ctx,
+        )
+        .await?;
+        for set_item_id in set_item_ids {
+            RbumSetItemServ::delete_rbum(&set_item_id, funs, ctx).await?;
+        }
+
+        // Delete Certs
+        let cert_ids = RbumCertServ::find_id_rbums(
+            &RbumCertFilterReq {
+                basic: RbumBasicFilterReq {
+                    with_sub_own_paths: true,
+                    ..Default::default()
+                },
+                rel_rbum_kind: Some(RbumCertRelKind::Item),
+                rel_rbum_id: Some(id.to_string()),
+                ..Default::default()
+            },
+            None,
+            None,
+            funs,
+            ctx,
+        )
+        .await?;
+        for cert_id in cert_ids {
+            RbumCertServ::delete_rbum(&cert_id, funs, ctx).await?;
+        }
+
+        // Delete Cert Conf
+        let cert_conf_ids = RbumCertConfServ::find_id_rbums(
+            &RbumCertConfFilterReq {
+                basic: RbumBasicFilterReq {
+                    with_sub_own_paths: true,
+                    ..Default::default()
+                },
+                rel_rbum_item_id: Some(id.to_string()),
+                ..Default::default()
+            },
+            None,
+            None,
+            funs,
+            ctx,
+        )
+        .await?;
+        for cert_conf_id in cert_conf_ids {
+            RbumCertConfServ::delete_rbum(&cert_conf_id, funs, ctx).await?;
+        }
+        Ok(())
+    }
+
+    async fn get_soft_delete_retention_sec_by_kind(kind_id: &str, funs: &TardisFunsInst) -> TardisResult<u32> {
+        #[derive(Debug, sea_orm::FromQueryResult)]
+        pub struct RetentionResp {
+            pub soft_delete_retention_sec: u32,
+        }
+        let result = funs
+            .db()
+            .get_dto::<RetentionResp>(
+                Query::select().column(rbum_kind::Column::SoftDeleteRetentionSec).from(rbum_kind::Entity).and_where(Expr::col(rbum_kind::Column::Id).eq(kind_id)),
+            )
+            .await?;
+        Ok(result.map(|resp| resp.soft_delete_retention_sec).unwrap_or(0))
+    }
+
+    async fn get_soft_delete_retention_sec(id: &str, funs: &TardisFunsInst) -> TardisResult<u32> {
+        #[derive(Debug, sea_orm::FromQueryResult)]
+        pub struct RetentionResp {
+            pub soft_delete_retention_sec: u32,
+        }
+        let result = funs
+            .db()
+            .get_dto::<RetentionResp>(
+                Query::select()
+                    .column((rbum_kind::Entity, rbum_kind::Column::SoftDeleteRetentionSec))
+                    .from(rbum_item::Entity)
+                    .inner_join(
+                        rbum_kind::Entity,
+                        Expr::tbl(rbum_kind::Entity, rbum_kind::Column::Id).equals(rbum_item::Entity, rbum_item::Column::RelRbumKindId),
+                    )
+                    .and_where(Expr::tbl(rbum_item::Entity, rbum_item::Column::Id).eq(id)),
+            )
+            .await?;
+        Ok(result.map(|resp| resp.soft_delete_retention_sec).unwrap_or(0))
+    }
+
+    /// Move the item into the recycle bin.
+    ///
+    /// The associated data is kept so that the item can be restored, it is removed when the item is [purged](Self::purge_expired_rbums).
+    pub async fn soft_delete_rbum(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        Self::check_ownership(id, funs, ctx).await?;
+        Self::do_soft_delete_rbums(vec![id.to_string()], funs).await?;
+        rbum_event_helper::try_notify(Self::get_table_name(), "d", id, funs, ctx).await?;
+        Ok(())
+    }
+
+    async fn do_soft_delete_rbums(ids: Vec<String>, funs: &TardisFunsInst) -> TardisResult<()> {
+        funs.db()
+            .execute(
+                Query::update()
+                    .table(rbum_item::Entity)
+                    .values(vec![(rbum_item::Column::Deleted, true.into()), (rbum_item::Column::DeleteTime, Utc::now().into())])
+                    .and_where(Expr::col(rbum_item::Column::Id).is_in(ids)),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Restore the item from the recycle bin, which is only allowed before the retention window expires.
+    pub async fn restore_rbum(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        let recycled_item = Self::find_recycled_rbums(Some(id), funs, ctx).await?.into_iter().next().ok_or_else(|| {
+            funs.err().not_found(
+                &Self::get_obj_name(),
+                "restore",
+                &format!("not found {}.{} in the recycle bin by {}", Self::get_obj_name(), id, ctx.owner),
+                "404-rbum-item-recycle-not-exist",
+            )
+        })?;
+        if recycled_item.expire_time() <= Utc::now() {
+            return Err(funs.err().conflict(
+                &Self::get_obj_name(),
+                "restore",
+                &format!("{}.{} has expired in the recycle bin", Self::get_obj_name(), id),
+                "409-rbum-item-recycle-expired",
+            ));
+        }
+        funs.db()
+            .execute(
+                Query::update()
+                    .table(rbum_item::Entity)
+                    .values(vec![
+                        (rbum_item::Column::Deleted, false.into()),
+                        (rbum_item::Column::DeleteTime, Option::<DateTime<Utc>>::None.into()),
+                        (rbum_item::Column::UpdateTime, Utc::now().into()),
+                    ])
+                    .and_where(Expr::col(rbum_item::Column::Id).eq(id)),
+            )
+            .await?;
+        rbum_event_helper::try_notify(Self::get_table_name(), "c", id, funs, ctx).await?;
+        Ok(())
+    }
+
+    /// Paginate the items in the recycle bin, the latest deleted first.
+    pub async fn paginate_recycled_rbums(
+        filter: &RbumBasicFilterReq,
+        page_number: u64,
+        page_size: u64,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<TardisPage<RbumItemRecycleSummaryResp>> {
+        let mut query = Self::package_recycle_query();
+        query.with_filter(Self::get_table_name(), filter, false, true, ctx);
+        query.order_by((rbum_item::Entity, rbum_item::Column::DeleteTime), Order::Desc);
+        let (records, total_size) = funs.db().paginate_dtos::<RbumItemRecycleResp>(&query, page_number, page_size).await?;
+        Ok(TardisPage {
+            page_size,
+            page_number,
+            total_size,
+            records: records.into_iter().map(|record| record.into_summary_resp()).collect(),
+        })
+    }
+
+    /// Purge the items whose retention window has expired, including their extension records and associated data.
+    pub async fn purge_expired_rbums(funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<u64> {
+        let now = Utc::now();
+        let mut purged = 0;
+        for recycled_item in Self::find_recycled_rbums(None, funs, ctx).await?.into_iter().filter(|item| item.expire_time() <= now) {
+            Self::delete_all_rels(&recycled_item.id, funs, ctx).await?;
+            let attr_ids = RbumItemAttrServ::find_id_rbums(
+                &RbumItemAttrFilterReq {
+                    basic: RbumBasicFilterReq {
+                        with_sub_own_paths: true,
+                        ..Default::default()
+                    },
+                    rel_rbum_item_id: Some(recycled_item.id.clone()),
+                    ..Default::default()
+                },
+                None,
+                None,
+                funs,
+                ctx,
+            )
+            .await?;
+            for attr_id in attr_ids {
+                RbumItemAttrServ::delete_rbum(&attr_id, funs, ctx).await?;
+            }
+            if !recycled_item.ext_table_name.is_empty() {
+                funs.db()
+                    .execute(Query::delete().from_table(Alias::new(&recycled_item.ext_table_name)).and_where(Expr::col(ID_FIELD.clone()).eq(recycled_item.id.as_str())))
+                    .await?;
+            }
+            purged += Self::delete_rbum(&recycled_item.id, funs, ctx).await?;
+        }
+        Ok(purged)
+    }
+
+    /// Start a background task that purges the expired items of the module periodically,
+    /// the interval is configured by [recycle_purge_interval_sec](crate::rbum::rbum_config::RbumConfig::recycle_purge_interval_sec).
+    pub fn start_purge_task(code: &str) {
+        let interval_sec = RbumConfigManager::get_config(code, |conf| conf.recycle_purge_interval_sec);
+        if interval_sec == 0 {
+            return;
+        }
+        let code = code.to_string();
+        tardis::tokio::spawn(async move {
+            let mut interval = tardis::tokio::time::interval(std::time::Duration::from_secs(interval_sec));
+            loop {
+                interval.tick().await;
+                let mut funs = TardisFuns::inst_with_db_conn(code.clone(), None);
+                let result = async {
+                    funs.begin().await?;
+                    let purged = Self::purge_expired_rbums(&funs, &TardisContext::default()).await?;
+                    funs.commit().await?;
+                    Ok::<u64, TardisError>(purged)
+                }
+                .await;
+                match result {
+                    Ok(purged) if purged > 0 => log::info!("[BIOS.Basic.RBUM] purged {} expired items of {} from the recycle bin", purged, code),
+                    Ok(_) => {}
+                    Err(e) => log::error!("[BIOS.Basic.RBUM] purge the recycle bin of {} error: {:?}", code, e),
+                }
+            }
+        });
+    }
+
+    fn package_recycle_query() -> SelectStatement {
+        let mut query = Query::select();
+        query
+            .columns(vec![
+                (rbum_item::Entity, rbum_item::Column::Id),
+                (rbum_item::Entity, rbum_item::Column::Code),
+                (rbum_item::Entity, rbum_item::Column::Name),
+                (rbum_item::Entity, rbum_item::Column::RelRbumKindId),
+                (rbum_item::Entity, rbum_item::Column::RelRbumDomainId),
+                (rbum_item::Entity, rbum_item::Column::OwnPaths),
+                (rbum_item::Entity, rbum_item::Column::Owner),
+                (rbum_item::Entity, rbum_item::Column::CreateTime),
+                (rbum_item::Entity, rbum_item::Column::DeleteTime),
+                (rbum_item::Entity, rbum_item::Column::ScopeLevel),
+            ])
+            .columns(vec![
+                (rbum_kind::Entity, rbum_kind::Column::ExtTableName),
+                (rbum_kind::Entity, rbum_kind::Column::SoftDeleteRetentionSec),
+            ])
+            .from(rbum_item::Entity)
+            .inner_join(
+                rbum_kind::Entity,
+                Expr::tbl(rbum_kind::Entity, rbum_kind::Column::Id).equals(rbum_item::Entity, rbum_item::Column::RelRbumKindId),
+            )
+            .and_where(Expr::tbl(rbum_item::Entity, rbum_item::Column::Deleted).eq(true));
+        query
+    }
+
+    async fn find_recycled_rbums(id: Option<&str>, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Vec<RbumItemRecycleResp>> {
+        let mut query = Self::package_recycle_query();
+        query.and_where(Expr::tbl(rbum_item::Entity, rbum_item::Column::OwnPaths).like(format!("{}%", ctx.own_paths).as_str()));
+        if let Some(id) = id {
+            query.and_where(Expr::tbl(rbum_item::Entity, rbum_item::Column::Id).eq(id));
+        }
+        funs.db().find_dtos(&query).await
+    }
+}
+
+#[derive(Debug, sea_orm::FromQueryResult)]
+struct RbumItemRecycleResp {
+    pub id: String,
+    pub code: String,
+    pub name: String,
+    pub rel_rbum_kind_id: String,
+    pub rel_rbum_domain_id: String,
+    pub own_paths: String,
+    pub owner: String,
+    pub create_time: DateTime<Utc>,
+    pub delete_time: DateTime<Utc>,
+    pub scope_level: RbumScopeLevelKind,
+    pub ext_table_name: String,
+    pub soft_delete_retention_sec: u32,
+}
+
+impl RbumItemRecycleResp {
+    fn expire_time(&self) -> DateTime<Utc> {
+        self.delete_time + Duration::seconds(self.soft_delete_retention_sec as i64)
+    }
+
+    fn into_summary_resp(self) -> RbumItemRecycleSummaryResp {
+        RbumItemRecycleSummaryResp {
+            expire_time: self.expire_time(),
+            id: self.id,
+            code: self.code,
+            name: self.name,
+            rel_rbum_kind_id: self.rel_rbum_kind_id,
+            rel_rbum_domain_id: self.rel_rbum_domain_id,
+            own_paths: self.own_paths,
+            owner: self.owner,
+            create_time: self.create_time,
+            delete_time: self.delete_time,
+            scope_level: self.scope_level,
+        }
+    }
+}
+
 #[async_trait]
 pub trait RbumItemCrudOperation<EXT, AddReq, ModifyReq, SummaryResp, DetailResp, ItemFilterReq>
 where
@@ -236,6 +666,7 @@ where
         let ext_domain = Self::package_ext_add(&id, add_req, funs, ctx).await?;
         funs.db().insert_one(ext_domain, ctx).await?;
         Self::after_add_item(&id, funs, ctx).await?;
+        RbumHistoryServ::try_add_item_history(Self::get_ext_table_name(), "c", &id, None, funs, ctx).await?;
         rbum_event_helper::try_notify(Self::get_ext_table_name(), "c", &id, funs, ctx).await?;
         Ok(id)
     }
@@ -294,19 +725,31 @@ where
         Ok(())
     }
 
+    /// Version of the item that the modify request is based on, `None` means skip the version check.
+    ///
+    /// The version is kept in the rbum item, so modifying only the extension table also increases it.
+    fn get_modify_version(_: &ModifyReq) -> Option<u32> {
+        None
+    }
+
     async fn modify_item(id: &str, modify_req: &mut ModifyReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
         Self::before_modify_item(id, modify_req, funs, ctx).await?;
+        let before = RbumHistoryServ::try_snapshot_item(Self::get_ext_table_name(), "u", id, funs).await?;
+        let version = Self::get_modify_version(modify_req);
         let item_modify_req = Self::package_item_modify(id, modify_req, funs, ctx).await?;
         if let Some(mut item_modify_req) = item_modify_req {
+            item_modify_req.version = item_modify_req.version.or(version);
             RbumItemServ::modify_rbum(id, &mut item_modify_req, funs, ctx).await?;
         } else {
             RbumItemServ::check_ownership(id, funs, ctx).await?;
+            RbumItemServ::check_and_increase_version(id, version, funs).await?;
         }
         let ext_domain = Self::package_ext_modify(id, modify_req, funs, ctx).await?;
         if let Some(ext_domain) = ext_domain {
             funs.db().update_one(ext_domain, ctx).await?;
         }
         Self::after_modify_item(id, modify_req, funs, ctx).await?;
+        RbumHistoryServ::try_add_item_history(Self::get_ext_table_name(), "u", id, before, funs, ctx).await?;
         rbum_event_helper::try_notify(Self::get_ext_table_name(), "u", id, funs, ctx).await?;
         Ok(())
     }
@@ -325,127 +768,323 @@ where
         Ok(())
     }
 
+    /// Delete the item, which is moved into the recycle bin if its kind has a soft delete retention window.
     async fn delete_item(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<u64> {
         let deleted_item = Self::before_delete_item(id, funs, ctx).await?;
+        if RbumItemServ::get_soft_delete_retention_sec(id, funs).await? > 0 {
+            let before = RbumHistoryServ::try_snapshot_item(Self::get_ext_table_name(), "d", id, funs).await?;
+            RbumItemServ::soft_delete_rbum(id, funs, ctx).await?;
+            Self::after_delete_item(id, &deleted_item, funs, ctx).await?;
+            RbumHistoryServ::try_add_item_history(Self::get_ext_table_name(), "d", id, before, funs, ctx).await?;
+            rbum_event_helper::try_notify(Self::get_ext_table_name(), "d", id, funs, ctx).await?;
+            return Ok(1);
+        }
+        RbumItemServ::before_delete_rbum(id, funs, ctx).await?;
+        let delete_records = Self::do_delete_item(id, &deleted_item, funs, ctx).await?;
+        rbum_event_helper::try_notify(RbumItemServ::get_table_name(), "d", id, funs, ctx).await?;
+        rbum_event_helper::try_notify(Self::get_ext_table_name(), "d", id, funs, ctx).await?;
+        Ok(delete_records)
+    }
+
+    /// Delete the item and its extension record that have passed the checks, the events are not notified.
+    async fn do_delete_item(id: &str, deleted_item: &Option<DetailResp>, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<u64> {
+        let before = RbumHistoryServ::try_snapshot_item(Self::get_ext_table_name(), "d", id, funs).await?;
         let select = Self::package_delete(id, funs, ctx).await?;
         #[cfg(feature = "with-mq")]
         {
             let delete_records = funs.db().soft_delete_custom(select, "id").await?;
-            RbumItemServ::delete_rbum(id, funs, ctx).await?;
-            let mq_topic_entity_deleted = &funs.rbum_conf_mq_topic_entity_deleted();
-            let mq_header = std::collections::HashMap::from([(funs.rbum_conf_mq_header_name_operator(), ctx.owner.clone())]);
-            for delete_record in &delete_records {
-                funs.mq().request(mq_topic_entity_deleted, TardisFuns::json.obj_to_string(delete_record)?, &mq_header).await?;
-            }
-            Self::after_delete_item(id, &deleted_item, funs, ctx).await?;
-            rbum_event_helper::try_notify(Self::get_ext_table_name(), "d", id, funs, ctx).await?;
+            RbumItemServ::do_delete_rbum(id, &None, funs, ctx).await?;
+            rbum_event_helper::add_entity_deleted_events(Self::get_ext_table_name(), id, &delete_records, funs, ctx).await?;
+            Self::after_delete_item(id, deleted_item, funs, ctx).await?;
+            RbumHistoryServ::try_add_item_history(Self::get_ext_table_name(), "d", id, before, funs, ctx).await?;
             Ok(delete_records.len() as u64)
         }
         #[cfg(not(feature = "with-mq"))]
         {
             let delete_records = funs.db().soft_delete(select, &ctx.owner).await?;
-            RbumItemServ::delete_rbum(id, funs, ctx).await?;
-            Self::after_delete_item(id, &deleted_item, funs, ctx).await?;
-            rbum_event_helper::try_notify(Self::get_ext_table_name(), "d", &id, funs, ctx).await?;
+            RbumItemServ::do_delete_rbum(id, &None, funs, ctx).await?;
+            Self::after_delete_item(id, deleted_item, funs, ctx).await?;
+            RbumHistoryServ::try_add_item_history(Self::get_ext_table_name(), "d", id, before, funs, ctx).await?;
             Ok(delete_records)
         }
     }
 
+    async fn after_restore_item(_: &str, _: &TardisFunsInst, _: &TardisContext) -> TardisResult<()> {
+        Ok(())
+    }
+
+    /// Restore the item from the recycle bin, the associated data removed by [delete_item_with_all_rels](Self::delete_item_with_all_rels) is not restored.
+    async fn restore_item(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        RbumItemServ::restore_rbum(id, funs, ctx).await?;
+        Self::after_restore_item(id, funs, ctx).await?;
+        RbumHistoryServ::try_add_item_history(Self::get_ext_table_name(), "c", id, None, funs, ctx).await?;
+        rbum_event_helper::try_notify(Self::get_ext_table_name(), "c", id, funs, ctx).await?;
+        Ok(())
+    }
+
     async fn delete_item_with_all_rels(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<u64> {
-        // Delete rels
-        let rel_ids = RbumRelServ::find_rel_ids(
-            &RbumRelFindReq {
-                tag: None,
-                from_rbum_kind: Some(RbumRelFromKind::Item),
-                from_rbum_id: Some(id.to_string()),
-                to_rbum_item_id: None,
-                ..Default::default()
-            },
-            funs,
-            ctx,
-        )
-        .await?;
-        for rel_id in rel_ids {
-            RbumRelServ::delete_rel_with_ext(&rel_id, funs, ctx).await?;
+        RbumItemServ::delete_all_rels(id, funs, ctx).await?;
+        Self::delete_item(id, funs, ctx).await
+    }
+
+    // ----------------------------- Batch -------------------------------
+
+    /// Add the items with bulk inserts and notify one event for all of them.
+    ///
+    /// The scopes of the kind and domain are checked once for the whole batch, a request that fails its own checks is reported by its index
+    /// and does not prevent the others from being added. The batch should be executed in one transaction.
+    async fn add_items(add_reqs: &mut [AddReq], funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<RbumBatchResp> {
+        RbumItemServ::check_scope(&Self::get_rbum_kind_id(), RbumKindServ::get_table_name(), funs, ctx).await?;
+        RbumItemServ::check_scope(&Self::get_rbum_domain_id(), RbumDomainServ::get_table_name(), funs, ctx).await?;
+        let mut resp = RbumBatchResp::default();
+        let mut added_ids = HashSet::with_capacity(add_reqs.len());
+        let mut added_codes = HashSet::with_capacity(add_reqs.len());
+        let mut item_domains = Vec::with_capacity(add_reqs.len());
+        let mut ext_domains = Vec::with_capacity(add_reqs.len());
+        for (index, add_req) in add_reqs.iter_mut().enumerate() {
+            let checked: TardisResult<(String, rbum_item::ActiveModel, EXT)> = async {
+                Self::before_add_item(add_req, funs, ctx).await?;
+                let item_add_req = Self::package_item_add(add_req, funs, ctx).await?;
+                let item_domain = RbumItemServ::package_add(
+                    &RbumItemAddReq {
+                        id: item_add_req.id.clone(),
+                        code: item_add_req.code.clone(),
+                        name: item_add_req.name.clone(),
+                        rel_rbum_kind_id: Self::get_rbum_kind_id(),
+                        rel_rbum_domain_id: Self::get_rbum_domain_id(),
+                        scope_level: item_add_req.scope_level.clone(),
+                        disabled: item_add_req.disabled,
+                    },
+                    funs,
+                    ctx,
+                )
+                .await?;
+                let id = RbumItemServ::get_domain_id(&item_domain, funs)?;
+                let ext_domain = Self::package_ext_add(&id, add_req, funs, ctx).await?;
+                // The existing codes are checked by the packaging, but not the ones added in the same batch
+                let code = item_domain.code.clone().unwrap();
+                if added_ids.contains(&id) || added_codes.contains(&code) {
+                    return Err(funs.err().conflict(
+                        &Self::get_obj_name(),
+                        "add",
+                        &format!("id {} or code {} is duplicated in the batch", id, code),
+                        "409-rbum-*-code-exist",
+                    ));
+                }
+                added_ids.insert(id.clone());
+                added_codes.insert(code);
+                Ok((id, item_domain, ext_domain))
+            }
+            .await;
+            match checked {
+                Ok((id, item_domain, ext_domain)) => {
+                    resp.succeed_ids.push(id);
+                    item_domains.push(item_domain);
+                    ext_domains.push(ext_domain);
+                }
+                Err(e) => resp.add_error(index, "", e),
+            }
         }
-        let rel_ids = RbumRelServ::find_rel_ids(
-            &RbumRelFindReq {
-                tag: None,
-                from_rbum_kind: Some(RbumRelFromKind::Item),
-                from_rbum_id: None,
-                to_rbum_item_id: Some(id.to_string()),
-                ..Default::default()
-            },
-            funs,
-            ctx,
-        )
-        .await?;
-        for rel_id in rel_ids {
-            RbumRelServ::delete_rel_with_ext(&rel_id, funs, ctx).await?;
+        if item_domains.is_empty() {
+            return Ok(resp);
         }
-
-        // Delete set items
-        let set_item_ids = RbumSetItemServ::find_id_rbums(
-            &RbumSetItemFilterReq {
-                basic: RbumBasicFilterReq {
-                    with_sub_own_paths: true,
-                    ..Default::default()
-                },
-                rel_rbum_item_ids: Some(vec![id.to_string()]),
-                ..Default::default()
-            },
-            None,
-            None,
-            funs,
-            ctx,
-        )
-        .await?;
-        for set_item_id in set_item_ids {
-            RbumSetItemServ::delete_rbum(&set_item_id, funs, ctx).await?;
+        funs.db().insert_many(item_domains, ctx).await?;
+        funs.db().insert_many(ext_domains, ctx).await?;
+        for id in &resp.succeed_ids {
+            RbumHistoryServ::try_add_history(RbumItemServ::get_table_name(), "c", &[RbumItemServ::get_table_name()], id, None, funs, ctx).await?;
+            Self::after_add_item(id, funs, ctx).await?;
+            RbumHistoryServ::try_add_item_history(Self::get_ext_table_name(), "c", id, None, funs, ctx).await?;
         }
+        rbum_event_helper::try_notify_batch(RbumItemServ::get_table_name(), "c", &resp.succeed_ids, funs, ctx).await?;
+        rbum_event_helper::try_notify_batch(Self::get_ext_table_name(), "c", &resp.succeed_ids, funs, ctx).await?;
+        Ok(resp)
+    }
 
-        // Delete Certs
-        let cert_ids = RbumCertServ::find_id_rbums(
-            &RbumCertFilterReq {
-                basic: RbumBasicFilterReq {
-                    with_sub_own_paths: true,
-                    ..Default::default()
-                },
-                rel_rbum_kind: Some(RbumCertRelKind::Item),
-                rel_rbum_id: Some(id.to_string()),
-                ..Default::default()
-            },
-            None,
-            None,
-            funs,
-            ctx,
-        )
-        .await?;
-        for cert_id in cert_ids {
-            RbumCertServ::delete_rbum(&cert_id, funs, ctx).await?;
+    /// Modify the items, the ownership is checked once for the whole batch, see [add_items](Self::add_items) for how the failed requests are handled.
+    ///
+    /// The versions of the requests without an expected version are increased with one update
+    /// and the items modified the same way are updated together, see [update_many_by_changes].
+    async fn modify_items(modify_reqs: &mut [(String, ModifyReq)], funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<RbumBatchResp> {
+        let ids = modify_reqs.iter().map(|(id, _)| id.to_string()).collect::<Vec<String>>();
+        let owned_ids = RbumItemServ::find_owned_ids(&ids, &Self::get_rbum_kind_id(), funs, ctx).await?;
+        let mut resp = RbumBatchResp::default();
+        let mut checked_ids = HashSet::with_capacity(modify_reqs.len());
+        let mut unversioned_ids = Vec::new();
+        let mut item_domains = Vec::with_capacity(modify_reqs.len());
+        let mut ext_domains = Vec::with_capacity(modify_reqs.len());
+        let mut modified_indexes = Vec::with_capacity(modify_reqs.len());
+        let mut item_befores = Vec::new();
+        for (index, (id, modify_req)) in modify_reqs.iter_mut().enumerate() {
+            let id = id.as_str();
+            if !owned_ids.contains(id) {
+                resp.add_error(
+                    index,
+                    id,
+                    funs.err().not_found(
+                        &Self::get_obj_name(),
+                        "check",
+                        &format!("ownership {}.{} is illegal by {}", Self::get_obj_name(), id, ctx.owner),
+                        "404-rbum-*-ownership-illegal",
+                    ),
+                );
+                continue;
+            }
+            if !checked_ids.insert(id.to_string()) {
+                resp.add_error(
+                    index,
+                    id,
+                    funs.err().conflict(
+                        &Self::get_obj_name(),
+                        "modify",
+                        &format!("id {} is duplicated in the batch", id),
+                        "409-rbum-*-id-duplicated",
+                    ),
+                );
+                continue;
+            }
+            // Only checks before the version check, which is the first write, so a failed request changes nothing
+            let checked: TardisResult<(Option<rbum_item::ActiveModel>, Option<EXT>, Option<u32>, Option<JsonValue>, Option<JsonValue>)> = async {
+                Self::before_modify_item(id, modify_req, funs, ctx).await?;
+                let item_modify_req = Self::package_item_modify(id, modify_req, funs, ctx).await?;
+                let version = item_modify_req.as_ref().and_then(|item_modify_req| item_modify_req.version).or_else(|| Self::get_modify_version(modify_req));
+                let item_domain = match &item_modify_req {
+                    Some(item_modify_req) => Some(RbumItemServ::package_modify(id, item_modify_req, funs, ctx).await?),
+                    None => None,
+                };
+                let ext_domain = Self::package_ext_modify(id, modify_req, funs, ctx).await?;
+                let item_before = RbumHistoryServ::try_snapshot(RbumItemServ::get_table_name(), "u", &[RbumItemServ::get_table_name()], id, funs).await?;
+                let before = RbumHistoryServ::try_snapshot_item(Self::get_ext_table_name(), "u", id, funs).await?;
+                if version.is_some() {
+                    RbumItemServ::check_and_increase_version(id, version, funs).await?;
+                }
+                Ok((item_domain, ext_domain, version, item_before, before))
+            }
+            .await;
+            match checked {
+                Ok((item_domain, ext_domain, version, item_before, before)) => {
+                    if version.is_none() {
+                        unversioned_ids.push(id.to_string());
+                    }
+                    if let Some(item_domain) = item_domain {
+                        item_domains.push(item_domain);
+                        item_befores.push((id.to_string(), item_before));
+                    }
+                    if let Some(ext_domain) = ext_domain {
+                        ext_domains.push(ext_domain);
+                    }
+                    modified_indexes.push((index, before));
+                }
+                Err(e) => resp.add_error(index, id, e),
+            }
+        }
+        RbumItemServ::increase_versions_with_table_name(unversioned_ids, RbumItemServ::get_table_name(), funs).await?;
+        update_many_by_changes(item_domains, funs, ctx).await?;
+        update_many_by_changes(ext_domains, funs, ctx).await?;
+        let mut item_modified_ids = Vec::with_capacity(item_befores.len());
+        for (id, item_before) in item_befores {
+            RbumHistoryServ::try_add_history(RbumItemServ::get_table_name(), "u", &[RbumItemServ::get_table_name()], &id, item_before, funs, ctx).await?;
+            item_modified_ids.push(id);
         }
+        for (index, before) in modified_indexes {
+            let (id, modify_req) = &mut modify_reqs[index];
+            Self::after_modify_item(id, modify_req, funs, ctx).await?;
+            RbumHistoryServ::try_add_item_history(Self::get_ext_table_name(), "u", id, before, funs, ctx).await?;
+            resp.succeed_ids.push(id.to_string());
+        }
+        rbum_event_helper::try_notify_batch(RbumItemServ::get_table_name(), "u", &item_modified_ids, funs, ctx).await?;
+        rbum_event_helper::try_notify_batch(Self::get_ext_table_name(), "u", &resp.succeed_ids, funs, ctx).await?;
+        Ok(resp)
+    }
 
-        // Delete Cert Conf
-        let cert_conf_ids = RbumCertConfServ::find_id_rbums(
-            &RbumCertConfFilterReq {
-                basic: RbumBasicFilterReq {
-                    with_sub_own_paths: true,
-                    ..Default::default()
-                },
-                rel_rbum_item_id: Some(id.to_string()),
-                ..Default::default()
-            },
-            None,
-            None,
-            funs,
-            ctx,
-        )
-        .await?;
-        for cert_conf_id in cert_conf_ids {
-            RbumCertConfServ::delete_rbum(&cert_conf_id, funs, ctx).await?;
+    /// Delete the items, the ownership is checked once for the whole batch, see [add_items](Self::add_items) for how the failed requests are handled.
+    ///
+    /// If the kind has a soft delete retention window, the items are moved into the recycle bin with one update,
+    /// the associated data is checked when they are purged. Otherwise the items and their extension records are deleted with one statement each.
+    async fn delete_items(ids: &[String], funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<RbumBatchResp> {
+        let owned_ids = RbumItemServ::find_owned_ids(ids, &Self::get_rbum_kind_id(), funs, ctx).await?;
+        let soft_delete = RbumItemServ::get_soft_delete_retention_sec_by_kind(&Self::get_rbum_kind_id(), funs).await? > 0;
+        let mut resp = RbumBatchResp::default();
+        let mut checked_ids = HashSet::with_capacity(ids.len());
+        let mut checked_items = Vec::with_capacity(ids.len());
+        for (index, id) in ids.iter().enumerate() {
+            if !owned_ids.contains(id) {
+                resp.add_error(
+                    index,
+                    id,
+                    funs.err().not_found(
+                        &Self::get_obj_name(),
+                        "check",
+                        &format!("ownership {}.{} is illegal by {}", Self::get_obj_name(), id, ctx.owner),
+                        "404-rbum-*-ownership-illegal",
+                    ),
+                );
+                continue;
+            }
+            if !checked_ids.insert(id.to_string()) {
+                resp.add_error(
+                    index,
+                    id,
+                    funs.err().conflict(
+                        &Self::get_obj_name(),
+                        "delete",
+                        &format!("id {} is duplicated in the batch", id),
+                        "409-rbum-*-id-duplicated",
+                    ),
+                );
+                continue;
+            }
+            let checked: TardisResult<Option<DetailResp>> = async {
+                let deleted_item = Self::before_delete_item(id, funs, ctx).await?;
+                if !soft_delete {
+                    RbumItemServ::check_delete_rbum(id, funs, ctx).await?;
+                }
+                Ok(deleted_item)
+            }
+            .await;
+            match checked {
+                Ok(deleted_item) => checked_items.push((id.to_string(), deleted_item)),
+                Err(e) => resp.add_error(index, id, e),
+            }
+        }
+        if !checked_items.is_empty() {
+            let deleted_ids = checked_items.iter().map(|(id, _)| id.to_string()).collect::<Vec<String>>();
+            let mut befores = Vec::with_capacity(checked_items.len());
+            for id in &deleted_ids {
+                befores.push(RbumHistoryServ::try_snapshot_item(Self::get_ext_table_name(), "d", id, funs).await?);
+            }
+            if soft_delete {
+                RbumItemServ::do_soft_delete_rbums(deleted_ids, funs).await?;
+            } else {
+                let select = EXT::Entity::find().filter(Expr::col(ID_FIELD.clone()).is_in(deleted_ids.clone()));
+                #[cfg(feature = "with-mq")]
+                {
+                    let delete_records = funs.db().soft_delete_custom(select, "id").await?;
+                    rbum_event_helper::add_entity_deleted_events(Self::get_ext_table_name(), "", &delete_records, funs, ctx).await?;
+                }
+                #[cfg(not(feature = "with-mq"))]
+                funs.db().soft_delete(select, &ctx.owner).await?;
+                RbumItemServ::do_delete_rbums(&deleted_ids.into_iter().map(|id| (id, None)).collect::<Vec<_>>(), funs, ctx).await?;
+            }
+            for ((id, deleted_item), before) in checked_items.into_iter().zip(befores) {
+                Self::after_delete_item(&id, &deleted_item, funs, ctx).await?;
+                RbumHistoryServ::try_add_item_history(Self::get_ext_table_name(), "d", &id, before, funs, ctx).await?;
+                resp.succeed_ids.push(id);
+            }
         }
+        rbum_event_helper::try_notify_batch(RbumItemServ::get_table_name(), "d", &resp.succeed_ids, funs, ctx).await?;
+        rbum_event_helper::try_notify_batch(Self::get_ext_table_name(), "d", &resp.succeed_ids, funs, ctx).await?;
+        Ok(resp)
+    }
 
-        Self::delete_item(id, funs, ctx).await
+    // ----------------------------- History -------------------------------
+
+    async fn paginate_item_histories(id: &str, page_number: u64, page_size: u64, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<TardisPage<RbumHistoryResp>> {
+        RbumHistoryServ::paginate_histories(Self::get_ext_table_name(), id, page_number, page_size, funs, ctx).await
+    }
+
+    /// State of the item, merged with its extension, as of the time.
+    async fn get_item_snapshot_as_of(id: &str, time: DateTime<Utc>, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<JsonValue>> {
+        RbumHistoryServ::get_snapshot_as_of(Self::get_ext_table_name(), id, time, funs, ctx).await
     }
 
     // ----------------------------- Query -------------------------------
@@ -541,10 +1180,122 @@ where
         if let Some(rbum_item_rel_filter_req) = &filter.rel2() {
             package_rel(&mut query, Alias::new("rbum_rel2"), rbum_item_rel_filter_req);
         }
+        Self::package_item_attr_query(&mut query, filter, funs, ctx).await?;
         query.distinct();
         Ok(query)
     }
 
+    /// Package the attribute value filters and sorts.
+    ///
+    /// Values of main column attributes are read from the extension table, the others from the `rbum_item_attr` table.
+    /// The sorted values are also selected as `attr_sort_<n>` columns, which is required for ordering a distinct query.
+    async fn package_item_attr_query(query: &mut SelectStatement, filter: &ItemFilterReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        let attr_filters = filter.attrs().as_ref().map(|attrs| attrs.iter().collect::<Vec<&RbumItemAttrValueFilterReq>>()).unwrap_or_default();
+        let attr_sorts = filter.attr_sorts().as_ref().map(|sorts| sorts.iter().collect::<Vec<&RbumItemAttrSortReq>>()).unwrap_or_default();
+        if attr_filters.is_empty() && attr_sorts.is_empty() {
+            return Ok(());
+        }
+        let rbum_kind_attrs = RbumKindAttrServ::find_rbums(
+            &RbumKindAttrFilterReq {
+                basic: RbumBasicFilterReq {
+                    rbum_kind_id: Some(filter.basic().rbum_kind_id.clone().unwrap_or_else(Self::get_rbum_kind_id)),
+                    ..Default::default()
+                },
+            },
+            None,
+            None,
+            funs,
+            ctx,
+        )
+        .await?;
+        let backend = funs.db().raw_conn().get_database_backend();
+        // attr name -> (table, column, data type, type to cast the string values to)
+        let mut attr_columns: HashMap<&str, (Alias, Alias, RbumDataTypeKind, Option<&str>)> = HashMap::new();
+        for attr_name in attr_filters.iter().map(|attr_filter| attr_filter.name.as_str()).chain(attr_sorts.iter().map(|attr_sort| attr_sort.name.as_str())) {
+            if attr_columns.contains_key(attr_name) {
+                continue;
+            }
+            let rbum_kind_attr = rbum_kind_attrs.iter().find(|i| i.name == attr_name && i.idx).ok_or_else(|| {
+                funs.err().bad_request(
+                    &Self::get_obj_name(),
+                    "query",
+                    &format!("attribute {} is not found or not indexed", attr_name),
+                    "400-rbum-item-attr-not-queryable",
+                )
+            })?;
+            if rbum_kind_attr.main_column {
+                attr_columns.insert(
+                    attr_name,
+                    (
+                        Alias::new(Self::get_ext_table_name()),
+                        Alias::new(attr_name),
+                        rbum_kind_attr.data_type.clone(),
+                        RbumItemAttrServ::get_value_cast_type(&rbum_kind_attr.data_type, backend),
+                    ),
+                );
+            } else {
+                let attr_table = Alias::new(&format!("rbum_item_attr{}", attr_columns.len() + 1));
+                query.join_as(
+                    JoinType::LeftJoin,
+                    rbum_item_attr::Entity,
+                    attr_table.clone(),
+                    Cond::all()
+                        .add(Expr::tbl(attr_table.clone(), rbum_item_attr::Column::RelRbumItemId).equals(rbum_item::Entity, rbum_item::Column::Id))
+                        .add(Expr::tbl(attr_table.clone(), rbum_item_attr::Column::RelRbumKindAttrId).eq(rbum_kind_attr.id.as_str())),
+                );
+                attr_columns.insert(
+                    attr_name,
+                    (
+                        attr_table,
+                        Alias::new("value"),
+                        rbum_kind_attr.data_type.clone(),
+                        RbumItemAttrServ::get_value_cast_type(&rbum_kind_attr.data_type, backend),
+                    ),
+                );
+            }
+        }
+        for attr_filter in attr_filters {
+            let (attr_table, attr_column, data_type, cast_type) = attr_columns.get(attr_filter.name.as_str()).unwrap();
+            if let Some(eq) = &attr_filter.eq {
+                query.and_where(Expr::tbl(attr_table.clone(), attr_column.clone()).eq(eq.to_string()));
+            }
+            if let Some(like) = &attr_filter.like {
+                query.and_where(Expr::tbl(attr_table.clone(), attr_column.clone()).like(format!("%{}%", like).as_str()));
+            }
+            if let Some(in_values) = &attr_filter.in_values {
+                query.and_where(Expr::tbl(attr_table.clone(), attr_column.clone()).is_in(in_values.clone()));
+            }
+            // Ranges compare by the data type, e.g. `9` is less than `10` as numbers but not as strings
+            for (bound, is_ge) in [(&attr_filter.ge, true), (&attr_filter.le, false)] {
+                let bound = match bound {
+                    Some(bound) => bound,
+                    None => continue,
+                };
+                if !rbum_item_attr_helper::check_data_type(data_type, bound) {
+                    return Err(funs.err().bad_request(
+                        &Self::get_obj_name(),
+                        "query",
+                        &format!("the bound {} of attribute {} is not a valid {}", bound, attr_filter.name, data_type),
+                        "400-rbum-item-attr-bound-invalid",
+                    ));
+                }
+                let column = Expr::expr(RbumItemAttrServ::package_value_expr(Expr::tbl(attr_table.clone(), attr_column.clone()).into(), *cast_type));
+                let bound = RbumItemAttrServ::package_value_expr(Expr::val(bound.trim()).into(), *cast_type);
+                query.and_where(if is_ge { column.greater_or_equal(bound) } else { column.less_or_equal(bound) });
+            }
+        }
+        for (idx, attr_sort) in attr_sorts.into_iter().enumerate() {
+            let (attr_table, attr_column, _, cast_type) = attr_columns.get(attr_sort.name.as_str()).unwrap();
+            let sort_column = Alias::new(&format!("attr_sort_{}", idx + 1));
+            query.expr_as(
+                RbumItemAttrServ::package_value_expr(Expr::tbl(attr_table.clone(), attr_column.clone()).into(), *cast_type),
+                sort_column.clone(),
+            );
+            query.order_by(sort_column, if attr_sort.desc { Order::Desc } else { Order::Asc });
+        }
+        Ok(())
+    }
+
     async fn package_ext_query(query: &mut SelectStatement, is_detail: bool, filter: &ItemFilterReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()>;
 
     async fn peek_item(id: &str, filter: &ItemFilterReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<SummaryResp> {
@@ -721,6 +1472,65 @@ where
         })
     }
 
+    /// Paginate by the cursor of the previous page instead of the page number, see [rbum_cursor_helper] for how it works.
+    ///
+    /// The sort fields are looked up in the columns of the `rbum_item` table first and then the extension table, nullable columns are rejected and the ordering of the filter is ignored.
+    async fn paginate_items_by_cursor(
+        filter: &ItemFilterReq,
+        sorts: &[RbumSortReq],
+        cursor: Option<&str>,
+        page_size: u64,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<RbumCursorPage<SummaryResp>> {
+        Self::do_paginate_items_by_cursor(filter, sorts, cursor, page_size, funs, ctx).await
+    }
+
+    async fn do_paginate_items_by_cursor(
+        filter: &ItemFilterReq,
+        sorts: &[RbumSortReq],
+        cursor: Option<&str>,
+        page_size: u64,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<RbumCursorPage<SummaryResp>> {
+        let sort_columns = sorts
+            .iter()
+            .map(|sort| {
+                if let Some(column) = rbum_item::Column::iter().find(|column| column.as_str() == sort.field) {
+                    rbum_cursor_helper::package_sort_column(RbumItemServ::get_table_name(), column.def(), sort, &Self::get_obj_name(), funs)
+                } else if let Some(column) = <EXT::Entity as EntityTrait>::Column::iter().find(|column| column.as_str() == sort.field) {
+                    rbum_cursor_helper::package_sort_column(Self::get_ext_table_name(), column.def(), sort, &Self::get_obj_name(), funs)
+                } else {
+                    Err(funs.err().bad_request(
+                        &Self::get_obj_name(),
+                        "paginate",
+                        &format!(
+                            "sort field {} is not a column of {} or {}",
+                            sort.field,
+                            RbumItemServ::get_table_name(),
+                            Self::get_ext_table_name()
+                        ),
+                        "400-rbum-*-sort-field-illegal",
+                    ))
+                }
+            })
+            .collect::<TardisResult<Vec<RbumCursorSortColumn>>>()?;
+        let cursor_values = match cursor {
+            Some(cursor) => Some(rbum_cursor_helper::decode_cursor(cursor, &sort_columns, funs)?),
+            None => None,
+        };
+        let mut query = Self::package_item_query(false, filter, funs, ctx).await?;
+        query.inner_join(
+            Alias::new(Self::get_ext_table_name()),
+            Expr::tbl(Alias::new(Self::get_ext_table_name()), ID_FIELD.clone()).equals(rbum_item::Entity, rbum_item::Column::Id),
+        );
+        Self::package_ext_query(&mut query, false, filter, funs, ctx).await?;
+        rbum_cursor_helper::package_cursor_query(&mut query, RbumItemServ::get_table_name(), &sort_columns, cursor_values, page_size);
+        let records = funs.db().find_dtos::<RbumCursorRecord<SummaryResp>>(&query).await?;
+        rbum_cursor_helper::package_cursor_page(records, page_size)
+    }
+
     async fn find_one_item(filter: &ItemFilterReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<SummaryResp>> {
         Self::do_find_one_item(filter, funs, ctx).await
     }
@@ -857,15 +1667,21 @@ where
         funs.db().count(&query).await
     }
 
+    /// Whether the item is disabled, the items in the recycle bin are treated as disabled.
     async fn is_disabled(id: &str, funs: &TardisFunsInst) -> TardisResult<bool> {
         #[derive(Debug, sea_orm::FromQueryResult)]
         pub struct StatusResp {
             pub disabled: bool,
+            pub deleted: bool,
         }
-        let result =
-            funs.db().get_dto::<StatusResp>(Query::select().column(rbum_item::Column::Disabled).from(rbum_item::Entity).and_where(Expr::col(rbum_item::Column::Id).eq(id))).await?;
+        let result = funs
+            .db()
+            .get_dto::<StatusResp>(
+                Query::select().columns(vec![rbum_item::Column::Disabled, rbum_item::Column::Deleted]).from(rbum_item::Entity).and_where(Expr::col(rbum_item::Column::Id).eq(id)),
+            )
+            .await?;
         if let Some(result) = result {
-            Ok(result.disabled)
+            Ok(result.disabled || result.deleted)
         } else {
             Err(funs.err().not_found(
                 &Self::get_obj_name(),
@@ -915,7 +1731,19 @@ impl RbumCrudOperation<rbum_item_attr::ActiveModel, RbumItemAttrAddReq, RbumItem
                 "400-rbum-kind-attr-idx-illegal",
             ));
         }
-        Ok(())
+        if !rbum_kind_attr.default_value.is_empty() && add_req.value.is_empty() {
+            add_req.value = rbum_kind_attr.default_value.clone();
+        }
+        Self::check_item_attr_values(&[(&rbum_kind_attr, &add_req.value)], "add", funs)
+    }
+
+    async fn check_modify_rbum(id: &str, modify_req: &mut RbumItemAttrModifyReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        let rel_rbum_kind_attr_id = Self::peek_rbum(id, &RbumItemAttrFilterReq::default(), funs, ctx).await?.rel_rbum_kind_attr_id;
+        let rbum_kind_attr = RbumKindAttrServ::peek_rbum(&rel_rbum_kind_attr_id, &RbumKindAttrFilterReq::default(), funs, ctx).await?;
+        if !rbum_kind_attr.default_value.is_empty() && modify_req.value.is_empty() {
+            modify_req.value = rbum_kind_attr.default_value.clone();
+        }
+        Self::check_item_attr_values(&[(&rbum_kind_attr, &modify_req.value)], "modify", funs)
     }
 
     async fn package_modify(id: &str, modify_req: &RbumItemAttrModifyReq, _: &TardisFunsInst, _: &TardisContext) -> TardisResult<rbum_item_attr::ActiveModel> {
@@ -938,6 +1766,7 @@ impl RbumCrudOperation<rbum_item_attr::ActiveModel, RbumItemAttrAddReq, RbumItem
                 (rbum_item_attr::Entity, rbum_item_attr::Column::Owner),
                 (rbum_item_attr::Entity, rbum_item_attr::Column::CreateTime),
                 (rbum_item_attr::Entity, rbum_item_attr::Column::UpdateTime),
+                (rbum_item_attr::Entity, rbum_item_attr::Column::Version),
             ])
             .expr_as(Expr::tbl(rbum_item::Entity, rbum_item::Column::Name), Alias::new("rel_rbum_item_name"))
             .expr_as(Expr::tbl(rbum_kind_attr::Entity, rbum_kind_attr::Column::Name), Alias::new("rel_rbum_kind_attr_name"))
@@ -989,11 +1818,38 @@ impl RbumItemAttrServ {
         .await
     }
 
+    /// Add or modify the attribute values of the item.
+    ///
+    /// Empty values are replaced by the default values of the attributes, so are the missing values of the attributes without value yet,
+    /// then the values are validated against the attribute definitions.
     pub async fn add_or_modify_item_attrs(add_req: &RbumItemAttrsAddOrModifyReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
         // Implicit rel_rbum_kind_attr scope check
         let rbum_kind_attrs = Self::find_item_attr_defs_by_item_id(&add_req.rel_rbum_item_id, funs, ctx).await?;
-        let in_main_table_attrs = rbum_kind_attrs.iter().filter(|i| add_req.values.contains_key(&i.name) && i.main_column).collect::<Vec<&RbumKindAttrSummaryResp>>();
-        let in_ext_table_attrs = rbum_kind_attrs.iter().filter(|i| add_req.values.contains_key(&i.name) && !i.main_column).collect::<Vec<&RbumKindAttrSummaryResp>>();
+        let mut values = add_req.values.clone();
+        let absent_attrs =
+            rbum_kind_attrs.iter().filter(|i| !values.contains_key(&i.name) && (i.required || !i.default_value.is_empty())).collect::<Vec<&RbumKindAttrSummaryResp>>();
+        if !absent_attrs.is_empty() {
+            let stored_values = Self::find_item_attr_values(&add_req.rel_rbum_item_id, funs, ctx).await?;
+            for absent_attr in absent_attrs {
+                if stored_values.get(&absent_attr.name).map(|value| value.is_empty()).unwrap_or(true) {
+                    values.insert(absent_attr.name.clone(), "".to_string());
+                }
+            }
+        }
+        for rbum_kind_attr in &rbum_kind_attrs {
+            if let Some(value) = values.get_mut(&rbum_kind_attr.name) {
+                if value.is_empty() && !rbum_kind_attr.default_value.is_empty() {
+                    *value = rbum_kind_attr.default_value.clone();
+                }
+            }
+        }
+        Self::check_item_attr_values(
+            &rbum_kind_attrs.iter().filter_map(|i| values.get(&i.name).map(|value| (i, value))).collect::<Vec<(&RbumKindAttrSummaryResp, &String)>>(),
+            "add_or_modify",
+            funs,
+        )?;
+        let in_main_table_attrs = rbum_kind_attrs.iter().filter(|i| values.contains_key(&i.name) && i.main_column).collect::<Vec<&RbumKindAttrSummaryResp>>();
+        let in_ext_table_attrs = rbum_kind_attrs.iter().filter(|i| values.contains_key(&i.name) && !i.main_column).collect::<Vec<&RbumKindAttrSummaryResp>>();
 
         if !in_main_table_attrs.is_empty() {
             // Implicit rel_rbum_item scope check
@@ -1005,7 +1861,7 @@ impl RbumItemAttrServ {
 
             for in_main_table_attr in in_main_table_attrs {
                 let column_name = Alias::new(&in_main_table_attr.name);
-                let column_val = add_req.values.get(&in_main_table_attr.name).unwrap().clone();
+                let column_val = values.get(&in_main_table_attr.name).unwrap().clone();
                 update_statement.value(column_name, column_val.into());
             }
             update_statement.and_where(Expr::col(ID_FIELD.clone()).eq(add_req.rel_rbum_item_id.as_str()));
@@ -1014,7 +1870,7 @@ impl RbumItemAttrServ {
 
         if !in_ext_table_attrs.is_empty() {
             for in_ext_table_attr in in_ext_table_attrs {
-                let column_val = add_req.values.get(&in_ext_table_attr.name).unwrap().clone();
+                let column_val = values.get(&in_ext_table_attr.name).unwrap().clone();
                 let exist_item_attr_ids = Self::find_id_rbums(
                     &RbumItemAttrFilterReq {
                         basic: Default::default(),
@@ -1047,6 +1903,20 @@ impl RbumItemAttrServ {
         Ok(())
     }
 
+    /// Validate the values against their attributes, the errors of all the invalid fields are reported together.
+    fn check_item_attr_values(values: &[(&RbumKindAttrSummaryResp, &String)], action: &str, funs: &TardisFunsInst) -> TardisResult<()> {
+        let field_errors = values.iter().filter_map(|(rbum_kind_attr, value)| rbum_item_attr_helper::validate(rbum_kind_attr, value)).collect::<Vec<RbumItemAttrFieldErrorResp>>();
+        if !field_errors.is_empty() {
+            return Err(funs.err().bad_request(
+                &Self::get_obj_name(),
+                action,
+                &TardisFuns::json.obj_to_string(&field_errors)?,
+                "400-rbum-item-attr-value-invalid",
+            ));
+        }
+        Ok(())
+    }
+
     pub async fn find_item_attr_values(rbum_item_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<HashMap<String, String>> {
         let rbum_kind_attrs = Self::find_item_attr_defs_by_item_id(rbum_item_id, funs, ctx).await?;
         let in_main_table_attrs = rbum_kind_attrs.iter().filter(|i| i.main_column).collect::<Vec<&RbumKindAttrSummaryResp>>();
@@ -1091,6 +1961,28 @@ impl RbumItemAttrServ {
         }
         Ok(values)
     }
+
+    /// Type to cast the string values of the attribute to, so that ranges and orders follow the data type rather than the characters.
+    ///
+    /// Dates are not cast in sqlite, which has no date types, the `YYYY-MM-DD` strings are already in order.
+    fn get_value_cast_type(data_type: &RbumDataTypeKind, backend: DbBackend) -> Option<&'static str> {
+        match (data_type, backend) {
+            (RbumDataTypeKind::Number, DbBackend::MySql) => Some("DECIMAL(38, 10)"),
+            (RbumDataTypeKind::Number, DbBackend::Postgres) => Some("NUMERIC"),
+            (RbumDataTypeKind::Number, DbBackend::Sqlite) => Some("REAL"),
+            (RbumDataTypeKind::Date, DbBackend::MySql) | (RbumDataTypeKind::Date, DbBackend::Postgres) => Some("DATE"),
+            (RbumDataTypeKind::DateTime, DbBackend::MySql) => Some("DATETIME"),
+            (RbumDataTypeKind::DateTime, DbBackend::Postgres) => Some("TIMESTAMPTZ"),
+            _ => None,
+        }
+    }
+
+    fn package_value_expr(expr: SimpleExpr, cast_type: Option<&str>) -> SimpleExpr {
+        match cast_type {
+            Some(cast_type) => Func::cast_as(expr, Alias::new(cast_type)),
+            None => expr,
+        }
+    }
 }
 
 #[derive(Debug, sea_orm::FromQueryResult)]
@@ -35,6 +35,7 @@ impl RbumCrudOperation<rbum_kind::ActiveModel, RbumKindAddReq, RbumKindModifyReq
             icon: Set(add_req.icon.as_ref().unwrap_or(&"".to_string()).to_string()),
             sort: Set(add_req.sort.unwrap_or(0)),
             ext_table_name: Set(add_req.ext_table_name.as_ref().unwrap_or(&"".to_string()).to_string()),
+            soft_delete_retention_sec: Set(add_req.soft_delete_retention_sec.unwrap_or(0)),
             scope_level: Set(add_req.scope_level.as_ref().unwrap_or(&RbumScopeLevelKind::Private).to_int()),
             ..Default::default()
         })
@@ -72,14 +73,20 @@ impl RbumCrudOperation<rbum_kind::ActiveModel, RbumKindAddReq, RbumKindModifyReq
         if let Some(ext_table_name) = &modify_req.ext_table_name {
             rbum_kind.ext_table_name = Set(ext_table_name.to_string());
         }
+        if let Some(soft_delete_retention_sec) = modify_req.soft_delete_retention_sec {
+            rbum_kind.soft_delete_retention_sec = Set(soft_delete_retention_sec);
+        }
         if let Some(scope_level) = &modify_req.scope_level {
             rbum_kind.scope_level = Set(scope_level.to_int());
         }
         Ok(rbum_kind)
     }
 
-    async fn before_delete_rbum(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<RbumKindDetailResp>> {
-        Self::check_ownership(id, funs, ctx).await?;
+    fn get_modify_version(modify_req: &RbumKindModifyReq) -> Option<u32> {
+        modify_req.version
+    }
+
+    async fn check_delete_rbum(id: &str, funs: &TardisFunsInst, _: &TardisContext) -> TardisResult<Option<RbumKindDetailResp>> {
         Self::check_exist_before_delete(id, RbumKindAttrServ::get_table_name(), rbum_kind_attr::Column::RelRbumKindId.as_str(), funs).await?;
         Self::check_exist_before_delete(id, RbumItemServ::get_table_name(), rbum_item::Column::RelRbumKindId.as_str(), funs).await?;
         Ok(None)
@@ -95,10 +102,12 @@ impl RbumCrudOperation<rbum_kind::ActiveModel, RbumKindAddReq, RbumKindModifyReq
             (rbum_kind::Entity, rbum_kind::Column::Icon),
             (rbum_kind::Entity, rbum_kind::Column::Sort),
             (rbum_kind::Entity, rbum_kind::Column::ExtTableName),
+            (rbum_kind::Entity, rbum_kind::Column::SoftDeleteRetentionSec),
             (rbum_kind::Entity, rbum_kind::Column::OwnPaths),
             (rbum_kind::Entity, rbum_kind::Column::Owner),
             (rbum_kind::Entity, rbum_kind::Column::CreateTime),
             (rbum_kind::Entity, rbum_kind::Column::UpdateTime),
+            (rbum_kind::Entity, rbum_kind::Column::Version),
             (rbum_kind::Entity, rbum_kind::Column::ScopeLevel),
         ]);
         query.from(rbum_kind::Entity).with_filter(Self::get_table_name(), filter, is_detail, true, ctx);
@@ -241,8 +250,11 @@ impl RbumCrudOperation<rbum_kind_attr::ActiveModel, RbumKindAttrAddReq, RbumKind
         Ok(rbum_kind_attr)
     }
 
-    async fn before_delete_rbum(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<RbumKindAttrDetailResp>> {
-        Self::check_ownership(id, funs, ctx).await?;
+    fn get_modify_version(modify_req: &RbumKindAttrModifyReq) -> Option<u32> {
+        modify_req.version
+    }
+
+    async fn check_delete_rbum(id: &str, funs: &TardisFunsInst, _: &TardisContext) -> TardisResult<Option<RbumKindAttrDetailResp>> {
         Self::check_exist_before_delete(id, RbumItemAttrServ::get_table_name(), rbum_item_attr::Column::RelRbumKindAttrId.as_str(), funs).await?;
         Self::check_exist_before_delete(id, RbumRelAttrServ::get_table_name(), rbum_rel_attr::Column::RelRbumKindAttrId.as_str(), funs).await?;
         Ok(None)
@@ -278,6 +290,7 @@ impl RbumCrudOperation<rbum_kind_attr::ActiveModel, RbumKindAttrAddReq, RbumKind
                 (rbum_kind_attr::Entity, rbum_kind_attr::Column::Owner),
                 (rbum_kind_attr::Entity, rbum_kind_attr::Column::CreateTime),
                 (rbum_kind_attr::Entity, rbum_kind_attr::Column::UpdateTime),
+                (rbum_kind_attr::Entity, rbum_kind_attr::Column::Version),
                 (rbum_kind_attr::Entity, rbum_kind_attr::Column::ScopeLevel),
             ])
             .from(rbum_kind_attr::Entity);
@@ -1,6 +1,9 @@
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 
 use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use tardis::basic::dto::TardisContext;
 use tardis::basic::result::TardisResult;
 use tardis::chrono::Utc;
@@ -12,13 +15,15 @@ use tardis::web::web_resp::TardisPage;
 use tardis::TardisFuns;
 use tardis::TardisFunsInst;
 
-use crate::rbum::domain::{rbum_item, rbum_kind_attr, rbum_rel, rbum_rel_attr, rbum_rel_env, rbum_set, rbum_set_cate};
+use crate::rbum::domain::{rbum_item, rbum_kind_attr, rbum_rel, rbum_rel_attr, rbum_rel_env, rbum_set, rbum_set_cate, rbum_set_item};
 use crate::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumRelExtFilterReq, RbumRelFilterReq, RbumSetCateFilterReq, RbumSetItemFilterReq};
 use crate::rbum::dto::rbum_rel_agg_dto::{RbumRelAggAddReq, RbumRelAggResp};
 use crate::rbum::dto::rbum_rel_attr_dto::{RbumRelAttrAddReq, RbumRelAttrDetailResp, RbumRelAttrModifyReq};
 use crate::rbum::dto::rbum_rel_dto::{RbumRelAddReq, RbumRelBoneResp, RbumRelCheckReq, RbumRelDetailResp, RbumRelFindReq, RbumRelModifyReq};
 use crate::rbum::dto::rbum_rel_env_dto::{RbumRelEnvAddReq, RbumRelEnvDetailResp, RbumRelEnvModifyReq};
-use crate::rbum::rbum_enumeration::{RbumRelEnvKind, RbumRelFromKind, RbumSetCateLevelQueryKind};
+use crate::rbum::helper::{rbum_rel_attr_helper, rbum_rel_env_helper};
+use crate::rbum::rbum_config::RbumConfigApi;
+use crate::rbum::rbum_enumeration::{RbumDataTypeKind, RbumRelAttrOperatorKind, RbumRelEnvKind, RbumRelFromKind, RbumSetCateLevelQueryKind};
 use crate::rbum::serv::rbum_crud_serv::{NameResp, RbumCrudOperation, RbumCrudQueryPackage};
 use crate::rbum::serv::rbum_item_serv::RbumItemServ;
 use crate::rbum::serv::rbum_kind_serv::RbumKindAttrServ;
@@ -97,13 +102,7 @@ impl RbumCrudOperation<rbum_rel::ActiveModel, RbumRelAddReq, RbumRelModifyReq, R
 
     async fn before_delete_rbum(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<RbumRelDetailResp>> {
         let mut query = Query::select();
-        query.column(rbum_rel::Column::Id).from(rbum_rel::Entity).and_where(Expr::col(rbum_rel::Column::Id).eq(id)).cond_where(
-            Cond::all().add(
-                Cond::any()
-                    .add(Expr::col(rbum_rel::Column::OwnPaths).like(format!("{}%", ctx.own_paths).as_str()))
-                    .add(Expr::col(rbum_rel::Column::ToOwnPaths).like(format!("{}%", ctx.own_paths).as_str())),
-            ),
-        );
+        query.column(rbum_rel::Column::Id).from(rbum_rel::Entity).and_where(Expr::col(rbum_rel::Column::Id).eq(id)).cond_where(Self::package_deletable_cond(ctx));
         if funs.db().count(&query).await? == 0 {
             return Err(funs.err().not_found(
                 &Self::get_obj_name(),
@@ -112,6 +111,19 @@ impl RbumCrudOperation<rbum_rel::ActiveModel, RbumRelAddReq, RbumRelModifyReq, R
                 "404-rbum-*-ownership-illegal",
             ));
         }
+        Self::check_delete_rbum(id, funs, ctx).await
+    }
+
+    async fn find_deletable_rbum_ids(ids: &[String], funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<HashSet<String>> {
+        if ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+        let mut query = Query::select();
+        query.column(rbum_rel::Column::Id).from(rbum_rel::Entity).and_where(Expr::col(rbum_rel::Column::Id).is_in(ids.to_vec())).cond_where(Self::package_deletable_cond(ctx));
+        Ok(funs.db().find_dtos::<IdResp>(&query).await?.into_iter().map(|resp| resp.id).collect())
+    }
+
+    async fn check_delete_rbum(id: &str, funs: &TardisFunsInst, _: &TardisContext) -> TardisResult<Option<RbumRelDetailResp>> {
         Self::check_exist_before_delete(id, RbumRelAttrServ::get_table_name(), rbum_rel_attr::Column::RelRbumRelId.as_str(), funs).await?;
         Self::check_exist_before_delete(id, RbumRelEnvServ::get_table_name(), rbum_rel_env::Column::RelRbumRelId.as_str(), funs).await?;
         Ok(None)
@@ -135,6 +147,7 @@ impl RbumCrudOperation<rbum_rel::ActiveModel, RbumRelAddReq, RbumRelModifyReq, R
                 (rbum_rel::Entity, rbum_rel::Column::Owner),
                 (rbum_rel::Entity, rbum_rel::Column::CreateTime),
                 (rbum_rel::Entity, rbum_rel::Column::UpdateTime),
+                (rbum_rel::Entity, rbum_rel::Column::Version),
             ])
             .expr_as(
                 Expr::tbl(from_rbum_item_table.clone(), rbum_item::Column::Name).if_null(""),
@@ -207,6 +220,15 @@ impl RbumCrudOperation<rbum_rel::ActiveModel, RbumRelAddReq, RbumRelModifyReq, R
 }
 
 impl RbumRelServ {
+    /// The relationship can be deleted from either side, by the owner of the source or of the target.
+    fn package_deletable_cond(ctx: &TardisContext) -> Condition {
+        Cond::all().add(
+            Cond::any()
+                .add(Expr::col(rbum_rel::Column::OwnPaths).like(format!("{}%", ctx.own_paths).as_str()))
+                .add(Expr::col(rbum_rel::Column::ToOwnPaths).like(format!("{}%", ctx.own_paths).as_str())),
+        )
+    }
+
     pub async fn add_simple_rel(tag: &str, from_rbum_id: &str, to_rbum_item_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
         RbumRelServ::add_rbum(
             &mut RbumRelAddReq {
@@ -233,6 +255,7 @@ impl RbumRelServ {
                 &mut RbumRelAttrAddReq {
                     is_from: attr.is_from,
                     value: attr.value.to_string(),
+                    operator: attr.operator.clone(),
                     name: attr.name.to_string(),
                     rel_rbum_rel_id: rbum_rel_id.to_string(),
                     rel_rbum_kind_attr_id: attr.rel_rbum_kind_attr_id.to_string(),
@@ -772,17 +795,117 @@ impl RbumRelServ {
         query
     }
 
-    // TODO cache
+    /// Check whether the from side is related to the item, directly or through the set and set categories it belongs to.
+    ///
+    /// The related froms and the rels are cached, the attrs and envs are evaluated on every check.
     pub async fn check_rel(check_req: &mut RbumRelCheckReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<bool> {
-        if Self::do_check_rel(check_req, funs, ctx).await? {
-            return Ok(true);
+        Ok(Self::check_rels(std::slice::from_ref(check_req), funs, ctx).await?.pop().unwrap_or(false))
+    }
+
+    /// Check many rels in one call, the results are in the order of the requests.
+    pub async fn check_rels(check_reqs: &[RbumRelCheckReq], funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Vec<bool>> {
+        let cache_key_prefix = format!("{}{}:{}:", funs.rbum_conf_cache_key_rel_check_(), Self::get_check_rel_cache_ver(funs).await?, ctx.own_paths);
+        let mut froms_cache = HashMap::new();
+        let mut candidates_cache = HashMap::new();
+        let mut result = Vec::with_capacity(check_reqs.len());
+        for check_req in check_reqs {
+            let froms_cache_key = format!("{}from:{}:{}", cache_key_prefix, check_req.from_rbum_kind, check_req.from_rbum_id);
+            if !froms_cache.contains_key(&froms_cache_key) {
+                let froms = match Self::get_check_rel_cache::<Vec<RbumRelCheckFrom>>(&froms_cache_key, funs).await? {
+                    Some(froms) => froms,
+                    None => {
+                        let froms = Self::find_check_rel_froms(&check_req.from_rbum_kind, &check_req.from_rbum_id, funs, ctx).await?;
+                        Self::set_check_rel_cache(&froms_cache_key, &froms, funs).await?;
+                        froms
+                    }
+                };
+                froms_cache.insert(froms_cache_key.clone(), froms);
+            }
+            let mut found = false;
+            for from in &froms_cache[&froms_cache_key] {
+                let candidates_cache_key = format!(
+                    "{}rel:{}:{}:{}:{}",
+                    cache_key_prefix, check_req.tag, from.from_rbum_kind, from.from_rbum_id, check_req.to_rbum_item_id
+                );
+                if !candidates_cache.contains_key(&candidates_cache_key) {
+                    let candidates = match Self::get_check_rel_cache::<Vec<RbumRelCheckCandidate>>(&candidates_cache_key, funs).await? {
+                        Some(candidates) => candidates,
+                        None => {
+                            let candidates = Self::find_check_rel_candidates(&check_req.tag, from, &check_req.to_rbum_item_id, funs, ctx).await?;
+                            Self::set_check_rel_cache(&candidates_cache_key, &candidates, funs).await?;
+                            candidates
+                        }
+                    };
+                    candidates_cache.insert(candidates_cache_key.clone(), candidates);
+                }
+                for candidate in &candidates_cache[&candidates_cache_key] {
+                    if Self::match_check_rel_candidate(candidate, check_req, funs)? {
+                        found = true;
+                        break;
+                    }
+                }
+                if found {
+                    break;
+                }
+            }
+            result.push(found);
         }
-        let rel_rbum_set_cate_ids = if check_req.from_rbum_kind == RbumRelFromKind::Item {
+        Ok(result)
+    }
+
+    /// Invalidate all cached rel checks when rels, sets, set categories or set items are changed.
+    ///
+    /// Called when the rbum events are notified and again when they are relayed after committed,
+    /// the cache is stamped with a version so that invalidation is a single increment.
+    pub async fn try_evict_check_rel_cache(table_name: &str, funs: &TardisFunsInst) -> TardisResult<()> {
+        if Self::is_check_rel_cache_table(table_name) {
+            funs.cache().incr(&format!("{}ver", funs.rbum_conf_cache_key_rel_check_()), 1).await?;
+        }
+        Ok(())
+    }
+
+    /// Whether the changes of the table affect the cached rel checks.
+    pub fn is_check_rel_cache_table(table_name: &str) -> bool {
+        [
+            rbum_rel::Entity.table_name(),
+            rbum_rel_attr::Entity.table_name(),
+            rbum_rel_env::Entity.table_name(),
+            rbum_set::Entity.table_name(),
+            rbum_set_cate::Entity.table_name(),
+            rbum_set_item::Entity.table_name(),
+        ]
+        .contains(&table_name)
+    }
+
+    async fn get_check_rel_cache_ver(funs: &TardisFunsInst) -> TardisResult<String> {
+        Ok(funs.cache().get(&format!("{}ver", funs.rbum_conf_cache_key_rel_check_())).await?.unwrap_or_else(|| "0".to_string()))
+    }
+
+    async fn get_check_rel_cache<T: DeserializeOwned>(cache_key: &str, funs: &TardisFunsInst) -> TardisResult<Option<T>> {
+        match funs.cache().get(cache_key).await? {
+            // Entries cached in an outdated format are treated as missing
+            Some(value) => Ok(TardisFuns::json.str_to_obj::<T>(&value).ok()),
+            None => Ok(None),
+        }
+    }
+
+    async fn set_check_rel_cache<T: Serialize>(cache_key: &str, value: &T, funs: &TardisFunsInst) -> TardisResult<()> {
+        funs.cache().set_ex(cache_key, &TardisFuns::json.obj_to_string(value)?, funs.rbum_conf_cache_key_rel_check_expire_sec()).await?;
+        Ok(())
+    }
+
+    /// Find the froms to check, i.e. the from itself and, for items and set categories, the set categories, their sets and parent categories.
+    async fn find_check_rel_froms(from_rbum_kind: &RbumRelFromKind, from_rbum_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Vec<RbumRelCheckFrom>> {
+        let mut froms = vec![RbumRelCheckFrom {
+            from_rbum_kind: from_rbum_kind.clone(),
+            from_rbum_id: from_rbum_id.to_string(),
+        }];
+        let rel_rbum_set_cate_ids = if from_rbum_kind == &RbumRelFromKind::Item {
             // Check set category
             RbumSetItemServ::find_rbums(
                 &RbumSetItemFilterReq {
                     basic: Default::default(),
-                    rel_rbum_item_ids: Some(vec![check_req.from_rbum_id.clone()]),
+                    rel_rbum_item_ids: Some(vec![from_rbum_id.to_string()]),
                     ..Default::default()
                 },
                 None,
@@ -794,26 +917,22 @@ impl RbumRelServ {
             .into_iter()
             .map(|i| i.rel_rbum_set_cate_id)
             .collect::<Vec<String>>()
-        } else if check_req.from_rbum_kind == RbumRelFromKind::SetCate {
-            vec![check_req.from_rbum_id.clone()]
+        } else if from_rbum_kind == &RbumRelFromKind::SetCate {
+            vec![from_rbum_id.to_string()]
         } else {
-            return Ok(false);
+            return Ok(froms);
         };
         for rel_rbum_set_cate_id in rel_rbum_set_cate_ids {
             let rbum_set_cate_base = RbumSetCateServ::peek_rbum(&rel_rbum_set_cate_id, &RbumSetCateFilterReq::default(), funs, ctx).await?;
-            if check_req.from_rbum_kind != RbumRelFromKind::SetCate {
-                check_req.from_rbum_kind = RbumRelFromKind::SetCate;
-                check_req.from_rbum_id = rbum_set_cate_base.id.clone();
-                // Check directly related records
-                if Self::do_check_rel(check_req, funs, ctx).await? {
-                    return Ok(true);
-                }
-            }
-            check_req.from_rbum_kind = RbumRelFromKind::Set;
-            check_req.from_rbum_id = rbum_set_cate_base.rel_rbum_set_id.clone();
-            if Self::do_check_rel(check_req, funs, ctx).await? {
-                return Ok(true);
-            }
+            // Directly related records
+            froms.push(RbumRelCheckFrom {
+                from_rbum_kind: RbumRelFromKind::SetCate,
+                from_rbum_id: rbum_set_cate_base.id.clone(),
+            });
+            froms.push(RbumRelCheckFrom {
+                from_rbum_kind: RbumRelFromKind::Set,
+                from_rbum_id: rbum_set_cate_base.rel_rbum_set_id.clone(),
+            });
             let rbum_set_cate_with_rel_ids = RbumSetCateServ::find_id_rbums(
                 &RbumSetCateFilterReq {
                     basic: Default::default(),
@@ -828,98 +947,108 @@ impl RbumRelServ {
                 ctx,
             )
             .await?;
+            // Indirectly related records
             for rbum_set_cate_with_rel_id in rbum_set_cate_with_rel_ids {
-                check_req.from_rbum_kind = RbumRelFromKind::SetCate;
-                check_req.from_rbum_id = rbum_set_cate_with_rel_id;
-                // Check indirectly related records
-                if Self::do_check_rel(check_req, funs, ctx).await? {
-                    return Ok(true);
-                }
+                froms.push(RbumRelCheckFrom {
+                    from_rbum_kind: RbumRelFromKind::SetCate,
+                    from_rbum_id: rbum_set_cate_with_rel_id,
+                });
+            }
+        }
+        let mut distinct_froms: Vec<RbumRelCheckFrom> = Vec::with_capacity(froms.len());
+        for from in froms {
+            if !distinct_froms.contains(&from) {
+                distinct_froms.push(from);
             }
         }
-        Ok(false)
+        Ok(distinct_froms)
     }
 
-    async fn do_check_rel(check_req: &RbumRelCheckReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<bool> {
+    async fn find_check_rel_candidates(
+        tag: &str,
+        from: &RbumRelCheckFrom,
+        to_rbum_item_id: &str,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<Vec<RbumRelCheckCandidate>> {
         let rbum_rel_ids = Self::find_rel_ids(
             &RbumRelFindReq {
-                tag: Some(check_req.tag.clone()),
-                from_rbum_kind: Some(check_req.from_rbum_kind.clone()),
-                from_rbum_id: Some(check_req.from_rbum_id.clone()),
-                to_rbum_item_id: Some(check_req.to_rbum_item_id.clone()),
+                tag: Some(tag.to_string()),
+                from_rbum_kind: Some(from.from_rbum_kind.clone()),
+                from_rbum_id: Some(from.from_rbum_id.clone()),
+                to_rbum_item_id: Some(to_rbum_item_id.to_string()),
                 ..Default::default()
             },
             funs,
             ctx,
         )
         .await?;
-        for rbum_rel_id in rbum_rel_ids {
-            let mut found = true;
-            let rbum_rel_attrs = funs
-                .db()
-                .find_dtos::<NameAndValueResp>(
-                    Query::select()
-                        .column(rbum_rel_attr::Column::IsFrom)
-                        .column(rbum_rel_attr::Column::Name)
-                        .column(rbum_rel_attr::Column::Value)
-                        .from(rbum_rel_attr::Entity)
-                        .and_where(Expr::col(rbum_rel_attr::Column::RelRbumRelId).eq(rbum_rel_id.clone()))
-                        .and_where(Expr::col(rbum_rel_attr::Column::RecordOnly).eq(false)),
-                )
-                .await?;
-            for rbum_rel_attr in rbum_rel_attrs {
-                if rbum_rel_attr.is_from {
-                    if let Some(value) = check_req.from_attrs.get(&rbum_rel_attr.name) {
-                        if value != rbum_rel_attr.value.as_str() {
-                            found = false;
-                            break;
-                        }
-                    } else {
-                        found = false;
-                        break;
-                    }
-                } else if let Some(value) = check_req.to_attrs.get(&rbum_rel_attr.name) {
-                    if value != rbum_rel_attr.value.as_str() {
-                        found = false;
-                        break;
-                    }
-                } else {
-                    found = false;
-                    break;
-                }
+        if rbum_rel_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let rbum_rel_attrs = funs
+            .db()
+            .find_dtos::<NameAndValueResp>(
+                Query::select()
+                    .column((rbum_rel_attr::Entity, rbum_rel_attr::Column::RelRbumRelId))
+                    .column((rbum_rel_attr::Entity, rbum_rel_attr::Column::IsFrom))
+                    .column((rbum_rel_attr::Entity, rbum_rel_attr::Column::Name))
+                    .column((rbum_rel_attr::Entity, rbum_rel_attr::Column::Value))
+                    .column((rbum_rel_attr::Entity, rbum_rel_attr::Column::Operator))
+                    .column((rbum_kind_attr::Entity, rbum_kind_attr::Column::DataType))
+                    .from(rbum_rel_attr::Entity)
+                    .inner_join(
+                        rbum_kind_attr::Entity,
+                        Expr::tbl(rbum_kind_attr::Entity, rbum_kind_attr::Column::Id).equals(rbum_rel_attr::Entity, rbum_rel_attr::Column::RelRbumKindAttrId),
+                    )
+                    .and_where(Expr::tbl(rbum_rel_attr::Entity, rbum_rel_attr::Column::RelRbumRelId).is_in(rbum_rel_ids.clone()))
+                    .and_where(Expr::tbl(rbum_rel_attr::Entity, rbum_rel_attr::Column::RecordOnly).eq(false)),
+            )
+            .await?;
+        let rbum_rel_envs = funs
+            .db()
+            .find_dtos::<KindAndValueResp>(
+                Query::select()
+                    .column(rbum_rel_env::Column::RelRbumRelId)
+                    .column(rbum_rel_env::Column::Kind)
+                    .column(rbum_rel_env::Column::Value1)
+                    .column(rbum_rel_env::Column::Value2)
+                    .from(rbum_rel_env::Entity)
+                    .and_where(Expr::col(rbum_rel_env::Column::RelRbumRelId).is_in(rbum_rel_ids.clone())),
+            )
+            .await?;
+        Ok(rbum_rel_ids
+            .into_iter()
+            .map(|rbum_rel_id| RbumRelCheckCandidate {
+                attrs: rbum_rel_attrs.iter().filter(|attr| attr.rel_rbum_rel_id == rbum_rel_id).cloned().collect(),
+                envs: rbum_rel_envs.iter().filter(|env| env.rel_rbum_rel_id == rbum_rel_id).cloned().collect(),
+            })
+            .collect())
+    }
+
+    fn match_check_rel_candidate(candidate: &RbumRelCheckCandidate, check_req: &RbumRelCheckReq, funs: &TardisFunsInst) -> TardisResult<bool> {
+        for rbum_rel_attr in &candidate.attrs {
+            let attrs = if rbum_rel_attr.is_from { &check_req.from_attrs } else { &check_req.to_attrs };
+            match attrs.get(&rbum_rel_attr.name) {
+                Some(value) if rbum_rel_attr_helper::check(&rbum_rel_attr.data_type, &rbum_rel_attr.operator, &rbum_rel_attr.value, value) => {}
+                _ => return Ok(false),
             }
-            let rbum_rel_envs = funs
-                .db()
-                .find_dtos::<KindAndValueResp>(
-                    Query::select()
-                        .column(rbum_rel_env::Column::Kind)
-                        .column(rbum_rel_env::Column::Value1)
-                        .column(rbum_rel_env::Column::Value2)
-                        .from(rbum_rel_env::Entity)
-                        .and_where(Expr::col(rbum_rel_env::Column::RelRbumRelId).eq(rbum_rel_id.clone())),
-                )
-                .await?;
-            for rbum_rel_env in rbum_rel_envs {
-                match rbum_rel_env.kind {
-                    RbumRelEnvKind::DatetimeRange => {
-                        if i64::from_str(rbum_rel_env.value1.as_str())? > Utc::now().timestamp() || i64::from_str(rbum_rel_env.value2.as_str())? < Utc::now().timestamp() {
-                            found = false;
-                            break;
-                        }
-                    }
-                    RbumRelEnvKind::TimeRange => {
-                        // TODO
-                    }
-                    RbumRelEnvKind::Ips => {
-                        // TODO
-                    }
+        }
+        for rbum_rel_env in &candidate.envs {
+            let matched = match rbum_rel_env.kind {
+                RbumRelEnvKind::DatetimeRange => {
+                    i64::from_str(rbum_rel_env.value1.as_str())? <= Utc::now().timestamp() && i64::from_str(rbum_rel_env.value2.as_str())? >= Utc::now().timestamp()
                 }
-            }
-            if found {
-                return Ok(true);
+                RbumRelEnvKind::TimeRange => {
+                    rbum_rel_env_helper::check_time_range(&rbum_rel_env.value1, &rbum_rel_env.value2, Utc::now(), funs.rbum_conf_rel_env_time_zone_offset_sec())?
+                }
+                RbumRelEnvKind::Ips => rbum_rel_env_helper::check_ips(&rbum_rel_env.value1, check_req.from_ip.as_deref())?,
+            };
+            if !matched {
+                return Ok(false);
             }
         }
-        Ok(false)
+        Ok(true)
     }
 
     pub async fn delete_rel_with_ext(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<u64> {
@@ -984,6 +1113,7 @@ impl RbumCrudOperation<rbum_rel_attr::ActiveModel, RbumRelAttrAddReq, RbumRelAtt
             id: Set(TardisFuns::field.nanoid()),
             is_from: Set(add_req.is_from),
             value: Set(add_req.value.to_string()),
+            operator: Set(add_req.operator.as_ref().unwrap_or(&RbumRelAttrOperatorKind::Eq).to_string()),
             name: Set(rbum_rel_attr_name),
             record_only: Set(add_req.record_only),
             rel_rbum_kind_attr_id: Set(add_req.rel_rbum_kind_attr_id.to_string()),
@@ -995,6 +1125,20 @@ impl RbumCrudOperation<rbum_rel_attr::ActiveModel, RbumRelAttrAddReq, RbumRelAtt
     async fn before_add_rbum(add_req: &mut RbumRelAttrAddReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
         Self::check_ownership_with_table_name(&add_req.rel_rbum_rel_id, RbumRelServ::get_table_name(), funs, ctx).await?;
         Self::check_scope(&add_req.rel_rbum_kind_attr_id, RbumKindAttrServ::get_table_name(), funs, ctx).await?;
+        let data_type = Self::get_kind_attr_data_type(&add_req.rel_rbum_kind_attr_id, funs).await?;
+        rbum_rel_attr_helper::validate(&data_type, add_req.operator.as_ref().unwrap_or(&RbumRelAttrOperatorKind::Eq), &add_req.value)
+    }
+
+    async fn check_modify_rbum(id: &str, modify_req: &mut RbumRelAttrModifyReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        if modify_req.value.is_some() || modify_req.operator.is_some() {
+            let rbum_rel_attr = Self::peek_rbum(id, &RbumRelExtFilterReq::default(), funs, ctx).await?;
+            let data_type = Self::get_kind_attr_data_type(&rbum_rel_attr.rel_rbum_kind_attr_id, funs).await?;
+            rbum_rel_attr_helper::validate(
+                &data_type,
+                modify_req.operator.as_ref().unwrap_or(&rbum_rel_attr.operator),
+                modify_req.value.as_ref().unwrap_or(&rbum_rel_attr.value),
+            )?;
+        }
         Ok(())
     }
 
@@ -1006,6 +1150,9 @@ impl RbumCrudOperation<rbum_rel_attr::ActiveModel, RbumRelAttrAddReq, RbumRelAtt
         if let Some(value) = &modify_req.value {
             rbum_rel_attr.value = Set(value.to_string());
         }
+        if let Some(operator) = &modify_req.operator {
+            rbum_rel_attr.operator = Set(operator.to_string());
+        }
         if let Some(name) = &modify_req.name {
             rbum_rel_attr.name = Set(name.to_string());
         }
@@ -1019,6 +1166,7 @@ impl RbumCrudOperation<rbum_rel_attr::ActiveModel, RbumRelAttrAddReq, RbumRelAtt
                 (rbum_rel_attr::Entity, rbum_rel_attr::Column::Id),
                 (rbum_rel_attr::Entity, rbum_rel_attr::Column::IsFrom),
                 (rbum_rel_attr::Entity, rbum_rel_attr::Column::Value),
+                (rbum_rel_attr::Entity, rbum_rel_attr::Column::Operator),
                 (rbum_rel_attr::Entity, rbum_rel_attr::Column::Name),
                 (rbum_rel_attr::Entity, rbum_rel_attr::Column::RecordOnly),
                 (rbum_rel_attr::Entity, rbum_rel_attr::Column::RelRbumKindAttrId),
@@ -1027,6 +1175,7 @@ impl RbumCrudOperation<rbum_rel_attr::ActiveModel, RbumRelAttrAddReq, RbumRelAtt
                 (rbum_rel_attr::Entity, rbum_rel_attr::Column::Owner),
                 (rbum_rel_attr::Entity, rbum_rel_attr::Column::CreateTime),
                 (rbum_rel_attr::Entity, rbum_rel_attr::Column::UpdateTime),
+                (rbum_rel_attr::Entity, rbum_rel_attr::Column::Version),
             ])
             .expr_as(Expr::tbl(rbum_kind_attr::Entity, rbum_kind_attr::Column::Name), Alias::new("rel_rbum_kind_attr_name"))
             .from(rbum_rel_attr::Entity)
@@ -1042,6 +1191,26 @@ impl RbumCrudOperation<rbum_rel_attr::ActiveModel, RbumRelAttrAddReq, RbumRelAtt
     }
 }
 
+impl RbumRelAttrServ {
+    async fn get_kind_attr_data_type(rel_rbum_kind_attr_id: &str, funs: &TardisFunsInst) -> TardisResult<RbumDataTypeKind> {
+        Ok(funs
+            .db()
+            .get_dto::<DataTypeResp>(
+                Query::select().column(rbum_kind_attr::Column::DataType).from(rbum_kind_attr::Entity).and_where(Expr::col(rbum_kind_attr::Column::Id).eq(rel_rbum_kind_attr_id)),
+            )
+            .await?
+            .ok_or_else(|| {
+                funs.err().not_found(
+                    &Self::get_obj_name(),
+                    "add",
+                    &format!("not found rbum_kind_attr {}", rel_rbum_kind_attr_id),
+                    "404-rbum-rel-not-exist-kind-attr",
+                )
+            })?
+            .data_type)
+    }
+}
+
 #[async_trait]
 impl RbumCrudOperation<rbum_rel_env::ActiveModel, RbumRelEnvAddReq, RbumRelEnvModifyReq, RbumRelEnvDetailResp, RbumRelEnvDetailResp, RbumRelExtFilterReq> for RbumRelEnvServ {
     fn get_table_name() -> &'static str {
@@ -1060,10 +1229,20 @@ impl RbumCrudOperation<rbum_rel_env::ActiveModel, RbumRelEnvAddReq, RbumRelEnvMo
     }
 
     async fn before_add_rbum(add_req: &mut RbumRelEnvAddReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        rbum_rel_env_helper::validate(&add_req.kind, &add_req.value1, add_req.value2.as_deref().unwrap_or(""))?;
         Self::check_ownership_with_table_name(&add_req.rel_rbum_rel_id, RbumRelServ::get_table_name(), funs, ctx).await?;
         Ok(())
     }
 
+    async fn check_modify_rbum(id: &str, modify_req: &mut RbumRelEnvModifyReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        let rbum_rel_env = Self::peek_rbum(id, &RbumRelExtFilterReq::default(), funs, ctx).await?;
+        rbum_rel_env_helper::validate(
+            &rbum_rel_env.kind,
+            modify_req.value1.as_ref().unwrap_or(&rbum_rel_env.value1),
+            modify_req.value2.as_ref().unwrap_or(&rbum_rel_env.value2),
+        )
+    }
+
     async fn package_modify(id: &str, modify_req: &RbumRelEnvModifyReq, _: &TardisFunsInst, _: &TardisContext) -> TardisResult<rbum_rel_env::ActiveModel> {
         let mut rbum_rel_env = rbum_rel_env::ActiveModel {
             id: Set(id.to_string()),
@@ -1091,6 +1270,7 @@ impl RbumCrudOperation<rbum_rel_env::ActiveModel, RbumRelEnvAddReq, RbumRelEnvMo
                 (rbum_rel_env::Entity, rbum_rel_env::Column::Owner),
                 (rbum_rel_env::Entity, rbum_rel_env::Column::CreateTime),
                 (rbum_rel_env::Entity, rbum_rel_env::Column::UpdateTime),
+                (rbum_rel_env::Entity, rbum_rel_env::Column::Version),
             ])
             .from(rbum_rel_env::Entity);
 
@@ -1102,16 +1282,38 @@ impl RbumCrudOperation<rbum_rel_env::ActiveModel, RbumRelEnvAddReq, RbumRelEnvMo
     }
 }
 
-#[derive(Debug, sea_orm::FromQueryResult)]
+#[derive(Debug, Clone, Serialize, Deserialize, sea_orm::FromQueryResult)]
 struct KindAndValueResp {
+    pub rel_rbum_rel_id: String,
     pub kind: RbumRelEnvKind,
     pub value1: String,
     pub value2: String,
 }
 
-#[derive(Debug, sea_orm::FromQueryResult)]
+#[derive(Debug, Clone, Serialize, Deserialize, sea_orm::FromQueryResult)]
 struct NameAndValueResp {
+    pub rel_rbum_rel_id: String,
     pub is_from: bool,
     pub name: String,
     pub value: String,
+    pub operator: RbumRelAttrOperatorKind,
+    pub data_type: RbumDataTypeKind,
+}
+
+#[derive(Debug, sea_orm::FromQueryResult)]
+struct DataTypeResp {
+    pub data_type: RbumDataTypeKind,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct RbumRelCheckFrom {
+    pub from_rbum_kind: RbumRelFromKind,
+    pub from_rbum_id: String,
+}
+
+/// A rel that may satisfy the check, depending on its attrs and envs.
+#[derive(Debug, Serialize, Deserialize)]
+struct RbumRelCheckCandidate {
+    pub attrs: Vec<NameAndValueResp>,
+    pub envs: Vec<KindAndValueResp>,
 }
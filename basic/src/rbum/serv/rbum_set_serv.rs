@@ -5,6 +5,7 @@ use async_trait::async_trait;
 use itertools::Itertools;
 use tardis::basic::dto::TardisContext;
 use tardis::basic::result::TardisResult;
+use tardis::chrono::Utc;
 use tardis::db::sea_orm;
 use tardis::db::sea_orm::sea_query::*;
 use tardis::db::sea_orm::*;
@@ -13,16 +14,18 @@ use tardis::{TardisFuns, TardisFunsInst};
 
 use crate::rbum::domain::{rbum_cert, rbum_item, rbum_rel, rbum_set, rbum_set_cate, rbum_set_item};
 use crate::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumSetCateFilterReq, RbumSetFilterReq, RbumSetItemFilterReq, RbumSetTreeFilterReq};
-use crate::rbum::dto::rbum_set_cate_dto::{RbumSetCateAddReq, RbumSetCateDetailResp, RbumSetCateModifyReq, RbumSetCateSummaryResp};
+use crate::rbum::dto::rbum_set_cate_dto::{RbumSetCateAddReq, RbumSetCateDetailResp, RbumSetCateModifyReq, RbumSetCateMoveReq, RbumSetCateSummaryResp};
 use crate::rbum::dto::rbum_set_dto::{
     RbumSetAddReq, RbumSetDetailResp, RbumSetModifyReq, RbumSetPathResp, RbumSetSummaryResp, RbumSetTreeExtResp, RbumSetTreeMainResp, RbumSetTreeResp,
 };
 use crate::rbum::dto::rbum_set_item_dto::{RbumSetItemAddReq, RbumSetItemDetailResp, RbumSetItemInfoResp, RbumSetItemModifyReq, RbumSetItemSummaryResp};
+use crate::rbum::helper::rbum_event_helper;
 use crate::rbum::rbum_config::RbumConfigApi;
 use crate::rbum::rbum_enumeration::{RbumCertRelKind, RbumRelFromKind, RbumScopeLevelKind, RbumSetCateLevelQueryKind};
 use crate::rbum::serv::rbum_cert_serv::RbumCertServ;
 use crate::rbum::serv::rbum_crud_serv::{RbumCrudOperation, RbumCrudQueryPackage};
 use crate::rbum::serv::rbum_domain_serv::RbumDomainServ;
+use crate::rbum::serv::rbum_history_serv::RbumHistoryServ;
 use crate::rbum::serv::rbum_item_serv::RbumItemServ;
 use crate::rbum::serv::rbum_kind_serv::RbumKindServ;
 use crate::rbum::serv::rbum_rel_serv::RbumRelServ;
@@ -84,8 +87,11 @@ impl RbumCrudOperation<rbum_set::ActiveModel, RbumSetAddReq, RbumSetModifyReq, R
         Ok(rbum_set)
     }
 
-    async fn before_delete_rbum(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<RbumSetDetailResp>> {
-        Self::check_ownership(id, funs, ctx).await?;
+    fn get_modify_version(modify_req: &RbumSetModifyReq) -> Option<u32> {
+        modify_req.version
+    }
+
+    async fn check_delete_rbum(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<RbumSetDetailResp>> {
         Self::check_exist_before_delete(id, RbumSetCateServ::get_table_name(), rbum_set_cate::Column::RelRbumSetId.as_str(), funs).await?;
         Self::check_exist_before_delete(id, RbumSetItemServ::get_table_name(), rbum_set_item::Column::RelRbumSetId.as_str(), funs).await?;
         Self::check_exist_with_cond_before_delete(
@@ -137,6 +143,7 @@ impl RbumCrudOperation<rbum_set::ActiveModel, RbumSetAddReq, RbumSetModifyReq, R
                 (rbum_set::Entity, rbum_set::Column::Owner),
                 (rbum_set::Entity, rbum_set::Column::CreateTime),
                 (rbum_set::Entity, rbum_set::Column::UpdateTime),
+                (rbum_set::Entity, rbum_set::Column::Version),
                 (rbum_set::Entity, rbum_set::Column::ScopeLevel),
             ])
             .from(rbum_set::Entity);
@@ -450,8 +457,11 @@ impl RbumCrudOperation<rbum_set_cate::ActiveModel, RbumSetCateAddReq, RbumSetCat
         Ok(rbum_set_cate)
     }
 
-    async fn before_delete_rbum(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<RbumSetCateDetailResp>> {
-        Self::check_ownership(id, funs, ctx).await?;
+    fn get_modify_version(modify_req: &RbumSetCateModifyReq) -> Option<u32> {
+        modify_req.version
+    }
+
+    async fn check_delete_rbum(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<RbumSetCateDetailResp>> {
         if funs
             .db()
             .count(
@@ -528,6 +538,7 @@ impl RbumCrudOperation<rbum_set_cate::ActiveModel, RbumSetCateAddReq, RbumSetCat
                 (rbum_set_cate::Entity, rbum_set_cate::Column::Owner),
                 (rbum_set_cate::Entity, rbum_set_cate::Column::CreateTime),
                 (rbum_set_cate::Entity, rbum_set_cate::Column::UpdateTime),
+                (rbum_set_cate::Entity, rbum_set_cate::Column::Version),
                 (rbum_set_cate::Entity, rbum_set_cate::Column::ScopeLevel),
             ])
             .from(rbum_set_cate::Entity);
@@ -640,6 +651,123 @@ impl RbumCrudOperation<rbum_set_cate::ActiveModel, RbumSetCateAddReq, RbumSetCat
 }
 
 impl RbumSetCateServ {
+    /// Move the category with its subtree to a new parent or position.
+    ///
+    /// The subtree gets the sys codes of the new location and its set items are moved along,
+    /// the caller should execute it in one transaction so that the tree is never seen half moved.
+    pub async fn move_rbum(id: &str, move_req: &RbumSetCateMoveReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        Self::check_ownership(id, funs, ctx).await?;
+        let filter = RbumSetCateFilterReq {
+            basic: RbumBasicFilterReq {
+                with_sub_own_paths: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let set_cate = Self::peek_rbum(id, &filter, funs, ctx).await?;
+        let parent_sys_code = if let Some(rbum_parent_cate_id) = &move_req.rbum_parent_cate_id {
+            Self::check_scope(rbum_parent_cate_id, Self::get_table_name(), funs, ctx).await?;
+            let parent_set_cate = Self::peek_rbum(rbum_parent_cate_id, &filter, funs, ctx).await?;
+            if parent_set_cate.rel_rbum_set_id != set_cate.rel_rbum_set_id {
+                return Err(funs.err().bad_request(
+                    &Self::get_obj_name(),
+                    "move",
+                    &format!("can not move {}.{} to another set", Self::get_obj_name(), id),
+                    "400-rbum-set-cate-move-illegal",
+                ));
+            }
+            if parent_set_cate.sys_code.starts_with(&set_cate.sys_code) {
+                return Err(funs.err().bad_request(
+                    &Self::get_obj_name(),
+                    "move",
+                    &format!("can not move {}.{} into its own subtree", Self::get_obj_name(), id),
+                    "400-rbum-set-cate-move-illegal",
+                ));
+            }
+            parent_set_cate.sys_code
+        } else {
+            "".to_string()
+        };
+        let before = RbumHistoryServ::try_snapshot(Self::get_table_name(), "u", &[Self::get_table_name()], id, funs).await?;
+        Self::check_and_increase_version(id, move_req.version, funs).await?;
+        let mut moved_ids = vec![id.to_string()];
+        if set_cate.sys_code[..set_cate.sys_code.len() - funs.rbum_conf_set_cate_sys_code_node_len()] != parent_sys_code {
+            let sys_code = Self::package_sys_code(&set_cate.rel_rbum_set_id, move_req.rbum_parent_cate_id.as_deref(), funs, ctx).await?;
+            moved_ids = Self::replace_sys_code(&set_cate.rel_rbum_set_id, &set_cate.sys_code, &sys_code, funs).await?;
+        }
+        if let Some(sort) = move_req.sort {
+            funs.db()
+                .execute(
+                    Query::update()
+                        .table(rbum_set_cate::Entity)
+                        .values(vec![(rbum_set_cate::Column::Sort, sort.into()), (rbum_set_cate::Column::UpdateTime, Utc::now().into())])
+                        .and_where(Expr::col(rbum_set_cate::Column::Id).eq(id)),
+                )
+                .await?;
+        }
+        RbumHistoryServ::try_add_history(Self::get_table_name(), "u", &[Self::get_table_name()], id, before, funs, ctx).await?;
+        let set = RbumSetServ::peek_rbum(
+            &set_cate.rel_rbum_set_id,
+            &RbumSetFilterReq {
+                basic: RbumBasicFilterReq {
+                    with_sub_own_paths: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            funs,
+            ctx,
+        )
+        .await?;
+        // Lookups of the set by code are cached, they are refreshed once its tree changes
+        funs.cache().del(&format!("{}{}", funs.rbum_conf_cache_key_set_code_(), set.code)).await?;
+        rbum_event_helper::try_notify_batch(Self::get_table_name(), "u", &moved_ids, funs, ctx).await?;
+        Ok(())
+    }
+
+    /// Replace the sys code prefix of the subtree and the category codes of its set items, returns the ids of the categories in the subtree.
+    async fn replace_sys_code(rbum_set_id: &str, old_sys_code: &str, new_sys_code: &str, funs: &TardisFunsInst) -> TardisResult<Vec<String>> {
+        #[derive(Debug, sea_orm::FromQueryResult)]
+        struct IdAndSysCodeResp {
+            pub id: String,
+            pub sys_code: String,
+        }
+        let set_cates = funs
+            .db()
+            .find_dtos::<IdAndSysCodeResp>(
+                Query::select()
+                    .columns(vec![rbum_set_cate::Column::Id, rbum_set_cate::Column::SysCode])
+                    .from(rbum_set_cate::Entity)
+                    .and_where(Expr::col(rbum_set_cate::Column::RelRbumSetId).eq(rbum_set_id))
+                    .and_where(Expr::col(rbum_set_cate::Column::SysCode).like(format!("{}%", old_sys_code).as_str())),
+            )
+            .await?;
+        for set_cate in &set_cates {
+            let sys_code = format!("{}{}", new_sys_code, &set_cate.sys_code[old_sys_code.len()..]);
+            funs.db()
+                .execute(
+                    Query::update()
+                        .table(rbum_set_cate::Entity)
+                        .values(vec![
+                            (rbum_set_cate::Column::SysCode, sys_code.as_str().into()),
+                            (rbum_set_cate::Column::UpdateTime, Utc::now().into()),
+                        ])
+                        .and_where(Expr::col(rbum_set_cate::Column::Id).eq(set_cate.id.as_str())),
+                )
+                .await?;
+            funs.db()
+                .execute(
+                    Query::update()
+                        .table(rbum_set_item::Entity)
+                        .values(vec![(rbum_set_item::Column::RelRbumSetCateCode, sys_code.as_str().into())])
+                        .and_where(Expr::col(rbum_set_item::Column::RelRbumSetId).eq(rbum_set_id))
+                        .and_where(Expr::col(rbum_set_item::Column::RelRbumSetCateCode).eq(set_cate.sys_code.as_str())),
+                )
+                .await?;
+        }
+        Ok(set_cates.into_iter().map(|set_cate| set_cate.id).collect())
+    }
+
     fn get_parent_sys_codes(sys_code: &str, funs: &TardisFunsInst) -> TardisResult<Vec<String>> {
         let set_cate_sys_code_node_len = funs.rbum_conf_set_cate_sys_code_node_len();
         let mut level = sys_code.len() / set_cate_sys_code_node_len - 1;
@@ -804,6 +932,7 @@ impl RbumCrudOperation<rbum_set_item::ActiveModel, RbumSetItemAddReq, RbumSetIte
                 (rbum_set_item::Entity, rbum_set_item::Column::Owner),
                 (rbum_set_item::Entity, rbum_set_item::Column::CreateTime),
                 (rbum_set_item::Entity, rbum_set_item::Column::UpdateTime),
+                (rbum_set_item::Entity, rbum_set_item::Column::Version),
             ])
             .expr_as(Expr::tbl(rbum_set_cate::Entity, rbum_set_cate::Column::Id), Alias::new("rel_rbum_set_cate_id"))
             .expr_as(Expr::tbl(rbum_set_cate::Entity, rbum_set_cate::Column::SysCode), Alias::new("rel_rbum_set_cate_sys_code"))
@@ -13,6 +13,7 @@ use tardis::TardisFuns;
 use bios_basic::rbum::dto::rbum_domain_dto::RbumDomainAddReq;
 use bios_basic::rbum::dto::rbum_item_dto::RbumItemAddReq;
 use bios_basic::rbum::dto::rbum_kind_dto::RbumKindAddReq;
+use bios_basic::rbum::helper::rbum_event_helper;
 use bios_basic::rbum::rbum_config::RbumConfig;
 use bios_basic::rbum::rbum_enumeration::RbumScopeLevelKind;
 use bios_basic::rbum::serv::rbum_crud_serv::RbumCrudOperation;
@@ -52,7 +53,16 @@ pub async fn init(docker: &Cli) -> TardisResult<LifeHold<'_>> {
     env::set_var("RUST_LOG", "debug,test_rbum=trace,sqlx::query=off");
     TardisFuns::init("tests/config").await?;
 
-    bios_basic::rbum::rbum_initializer::init("", RbumConfig::default()).await?;
+    bios_basic::rbum::rbum_initializer::init(
+        "",
+        RbumConfig {
+            // short enough to test the heartbeat of the long running tasks
+            task_timeout_sec: 4,
+            ..Default::default()
+        },
+    )
+    .await?;
+    rbum_event_helper::start_relay_task("");
 
     Ok(LifeHold {
         mysql: mysql_container,
@@ -82,6 +92,7 @@ pub async fn init_test_data() -> TardisResult<TardisContext> {
             icon: None,
             sort: None,
             ext_table_name: Some(RBUM_KIND_SCHEME_IAM_TENANT.to_string().to_lowercase()),
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::Root),
         },
         &funs,
@@ -97,6 +108,7 @@ pub async fn init_test_data() -> TardisResult<TardisContext> {
             icon: None,
             sort: None,
             ext_table_name: Some(RBUM_KIND_SCHEME_IAM_APP.to_string().to_lowercase()),
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::Root),
         },
         &funs,
@@ -112,6 +124,7 @@ pub async fn init_test_data() -> TardisResult<TardisContext> {
             icon: None,
             sort: None,
             ext_table_name: Some(RBUM_KIND_SCHEME_IAM_ACCOUNT.to_string().to_lowercase()),
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::Root),
         },
         &funs,
@@ -10,6 +10,7 @@ mod test_rbum_kind;
 mod test_rbum_rel;
 mod test_rbum_set;
 mod test_scope;
+mod test_task_processor;
 
 #[tokio::test]
 async fn test_rbum() -> TardisResult<()> {
@@ -24,5 +25,6 @@ async fn test_rbum() -> TardisResult<()> {
     test_rbum_rel::test(&ctx).await?;
     test_rbum_set::test(&ctx).await?;
     test_rbum_event::test(&ctx).await?;
+    test_task_processor::test(&ctx).await?;
     Ok(())
 }
@@ -724,7 +724,7 @@ async fn test_rbum_cert_basic(context: &TardisContext) -> TardisResult<()> {
             start_time: None,
             end_time: None,
             conn_uri: None,
-            status: None
+            status: None,
         },
         &funs,
         context
@@ -46,6 +46,7 @@ pub async fn test(context: &TardisContext) -> TardisResult<()> {
             icon: Some(".".to_string()),
             sort: None,
             scope_level: None,
+            version: None,
         },
         &funs,
         context,
@@ -1,37 +1,71 @@
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
 
+use lazy_static::lazy_static;
 use tardis::basic::dto::TardisContext;
 use tardis::basic::field::TrimString;
 use tardis::basic::result::TardisResult;
 use tardis::log::info;
-use tardis::TardisFuns;
+use tardis::{tokio, TardisFuns, TardisFunsInst};
 
 use bios_basic::rbum::dto::rbum_set_dto::RbumSetAddReq;
 use bios_basic::rbum::helper::rbum_event_helper;
+use bios_basic::rbum::helper::rbum_event_helper::RbumEventMessage;
 use bios_basic::rbum::rbum_enumeration::RbumScopeLevelKind;
 use bios_basic::rbum::serv::rbum_crud_serv::RbumCrudOperation;
 use bios_basic::rbum::serv::rbum_set_serv::RbumSetServ;
 
-static COUNTER: AtomicUsize = AtomicUsize::new(0);
+lazy_static! {
+    static ref MESSAGES: Mutex<Vec<RbumEventMessage>> = Mutex::new(Vec::new());
+}
 
 pub async fn test(context: &TardisContext) -> TardisResult<()> {
-    let funs = TardisFuns::inst_with_db_conn("".to_string(), None);
+    let mut funs = TardisFuns::inst_with_db_conn("".to_string(), None);
     info!("【test_rbum_event】 : receive events");
     rbum_event_helper::receive(
         |(_, msg)| async move {
             let message = rbum_event_helper::parse_message(msg)?;
-            assert_eq!(message.table_name, "rbum_set");
-            assert_eq!(message.operate, "c");
-            COUNTER.fetch_add(1, Ordering::SeqCst);
+            if message.table_name == "rbum_set" && message.operate == "c" {
+                MESSAGES.lock().unwrap().push(message);
+            }
             Ok(())
         },
         &funs,
     )
     .await?;
 
+    info!("【test_rbum_event】 : Test Rollback : RbumSetServ::add_rbum");
+    funs.begin().await?;
+    let rollback_set_id = add_set("test_rbum_event_rollback", &funs, context).await?;
+    funs.rollback().await?;
+
+    info!("【test_rbum_event】 : Test Commit : RbumSetServ::add_rbum");
+    funs.begin().await?;
+    let set_id = add_set("test_rbum_set_code", &funs, context).await?;
+    let other_set_id = add_set("test_rbum_event_other", &funs, context).await?;
+    funs.commit().await?;
+
+    loop {
+        if MESSAGES.lock().unwrap().iter().filter(|message| message.record_id == set_id || message.record_id == other_set_id).count() >= 2 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    tokio::time::sleep(Duration::from_millis(1000)).await;
+    let messages = MESSAGES.lock().unwrap();
+    assert!(messages.iter().all(|message| message.record_id != rollback_set_id));
+    let messages = messages.iter().filter(|message| message.record_id == set_id || message.record_id == other_set_id).collect::<Vec<_>>();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0].record_id, set_id);
+    assert!(!messages[0].id.is_empty());
+    assert_ne!(messages[0].id, messages[1].id);
+    Ok(())
+}
+
+async fn add_set(code: &str, funs: &TardisFunsInst, context: &TardisContext) -> TardisResult<String> {
     RbumSetServ::add_rbum(
         &mut RbumSetAddReq {
-            code: TrimString("test_rbum_set_code".to_string()),
+            code: TrimString(code.to_string()),
             kind: TrimString("".to_string()),
             name: TrimString(" 测试集合 ".to_string()),
             note: None,
@@ -41,15 +75,8 @@ pub async fn test(context: &TardisContext) -> TardisResult<()> {
             ext: None,
             disabled: None,
         },
-        &funs,
+        funs,
         context,
     )
-    .await?;
-
-    loop {
-        if COUNTER.load(Ordering::SeqCst) > 0 {
-            break;
-        }
-    }
-    Ok(())
+    .await
 }
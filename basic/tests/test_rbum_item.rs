@@ -3,30 +3,37 @@ use std::collections::HashMap;
 use tardis::basic::dto::TardisContext;
 use tardis::basic::field::TrimString;
 use tardis::basic::result::TardisResult;
+use tardis::chrono::{Duration, Utc};
 use tardis::db::reldb_client::TardisActiveModel;
 use tardis::db::sea_orm;
+use tardis::db::sea_orm::sea_query::Alias;
 use tardis::db::sea_orm::sea_query::Expr;
 use tardis::db::sea_orm::sea_query::Query;
 use tardis::log::info;
 use tardis::TardisFuns;
 
 use bios_basic::rbum::dto::rbum_domain_dto::RbumDomainAddReq;
-use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumItemAttrFilterReq, RbumKindAttrFilterReq};
+use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumItemAttrFilterReq, RbumKindAttrFilterReq, RbumRelFilterReq, RbumSortReq};
 use bios_basic::rbum::dto::rbum_item_attr_dto::{RbumItemAttrAddReq, RbumItemAttrModifyReq, RbumItemAttrsAddOrModifyReq};
 use bios_basic::rbum::dto::rbum_item_dto::{RbumItemAddReq, RbumItemModifyReq};
 use bios_basic::rbum::dto::rbum_kind_attr_dto::RbumKindAttrAddReq;
 use bios_basic::rbum::dto::rbum_kind_dto::RbumKindAddReq;
-use bios_basic::rbum::rbum_enumeration::{RbumDataTypeKind, RbumScopeLevelKind, RbumWidgetTypeKind};
+use bios_basic::rbum::dto::rbum_rel_dto::RbumRelAddReq;
+use bios_basic::rbum::rbum_enumeration::{RbumDataTypeKind, RbumRelFromKind, RbumScopeLevelKind, RbumWidgetTypeKind};
 use bios_basic::rbum::serv::rbum_crud_serv::RbumCrudOperation;
 use bios_basic::rbum::serv::rbum_domain_serv::RbumDomainServ;
 use bios_basic::rbum::serv::rbum_item_serv::{RbumItemAttrServ, RbumItemServ};
 use bios_basic::rbum::serv::rbum_kind_serv::{RbumKindAttrServ, RbumKindServ};
+use bios_basic::rbum::serv::rbum_rel_serv::RbumRelServ;
 
 pub async fn test(context: &TardisContext) -> TardisResult<()> {
     test_rbum_item(context).await?;
     test_rbum_item_attr(context).await?;
     test_rbum_item_attr_has_main_table(context).await?;
     test_rbum_item_with_none_owner_name(context).await?;
+    test_rbum_item_recycle(context).await?;
+    test_rbum_item_batch(context).await?;
+    test_rbum_item_cursor(context).await?;
     Ok(())
 }
 
@@ -43,6 +50,7 @@ async fn test_rbum_item(context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             ext_table_name: None,
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::L2),
         },
         &funs,
@@ -144,11 +152,46 @@ async fn test_rbum_item(context: &TardisContext) -> TardisResult<()> {
             name: Some(TrimString("数据库实例1".to_string())),
             disabled: None,
             scope_level: None,
+            version: None,
         },
         &funs,
         context,
     )
     .await?;
+    let rbum = RbumItemServ::get_rbum(&id, &RbumBasicFilterReq::default(), &funs, context).await?;
+    assert_eq!(rbum.version, 1);
+
+    info!("【test_rbum_item】 : Test Modify With Version : RbumItemServ::modify_rbum");
+    assert!(RbumItemServ::modify_rbum(
+        &id,
+        &mut RbumItemModifyReq {
+            code: None,
+            name: Some(TrimString("数据库实例2".to_string())),
+            disabled: None,
+            scope_level: None,
+            version: Some(0),
+        },
+        &funs,
+        context,
+    )
+    .await
+    .is_err());
+    RbumItemServ::modify_rbum(
+        &id,
+        &mut RbumItemModifyReq {
+            code: None,
+            name: Some(TrimString("数据库实例1".to_string())),
+            disabled: None,
+            scope_level: None,
+            version: Some(1),
+        },
+        &funs,
+        context,
+    )
+    .await?;
+    let rbum = RbumItemServ::get_rbum(&id, &RbumBasicFilterReq::default(), &funs, context).await?;
+    assert_eq!(rbum.name, "数据库实例1");
+    assert_eq!(rbum.version, 2);
 
     info!("【test_rbum_item】 : Test Find : RbumItemServ::paginate_rbums");
     let rbums = RbumItemServ::paginate_rbums(
@@ -192,6 +235,7 @@ async fn test_rbum_item_attr(context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             ext_table_name: None,
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::L2),
         },
         &funs,
@@ -357,6 +401,7 @@ async fn test_rbum_item_attr_has_main_table(context: &TardisContext) -> TardisRe
             icon: None,
             sort: None,
             ext_table_name: Some("iam_account".to_string()),
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::L2),
         },
         &funs,
@@ -516,6 +561,17 @@ async fn test_rbum_item_attr_has_main_table(context: &TardisContext) -> TardisRe
     assert_eq!(attr_defs.len(), 3);
 
     info!("【test_rbum_item_attr】 : Test Add : RbumItemAttrServ::add_or_modify_item_attrs");
+    assert!(RbumItemAttrServ::add_or_modify_item_attrs(
+        &RbumItemAttrsAddOrModifyReq {
+            values: HashMap::from([("ext1_idx".to_string(), "yes".to_string()), ("addr".to_string(), "中国杭州".to_string()),]),
+            rel_rbum_item_id: item_id.to_string(),
+        },
+        &funs,
+        context,
+    )
+    .await
+    .is_err());
+
     RbumItemAttrServ::add_or_modify_item_attrs(
         &RbumItemAttrsAddOrModifyReq {
             values: HashMap::from([
@@ -627,6 +683,7 @@ async fn test_rbum_item_with_none_owner_name(context: &TardisContext) -> TardisR
             icon: None,
             sort: None,
             ext_table_name: None,
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::L2),
         },
         &funs,
@@ -732,3 +789,432 @@ mod test_iam_account {
     #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
     pub enum Relation {}
 }
+
+async fn test_rbum_item_recycle(context: &TardisContext) -> TardisResult<()> {
+    let mut funs = TardisFuns::inst_with_db_conn("".to_string(), None);
+    funs.begin().await?;
+
+    info!("【test_rbum_item_recycle】 : Prepare : RbumKindServ::add_rbum");
+    let kind_id = RbumKindServ::add_rbum(
+        &mut RbumKindAddReq {
+            code: TrimString("recycle".to_string()),
+            name: TrimString("回收站".to_string()),
+            note: None,
+            icon: None,
+            sort: None,
+            ext_table_name: None,
+            soft_delete_retention_sec: Some(3600),
+            scope_level: Some(RbumScopeLevelKind::L2),
+        },
+        &funs,
+        context,
+    )
+    .await?;
+
+    info!("【test_rbum_item_recycle】 : Prepare Domain : RbumDomainServ::add_rbum");
+    let domain_id = RbumDomainServ::add_rbum(
+        &mut RbumDomainAddReq {
+            code: TrimString("recycle-dev".to_string()),
+            name: TrimString("回收站测试".to_string()),
+            note: None,
+            icon: None,
+            sort: None,
+            scope_level: Some(RbumScopeLevelKind::L2),
+        },
+        &funs,
+        context,
+    )
+    .await?;
+
+    let id = RbumItemServ::add_rbum(
+        &mut RbumItemAddReq {
+            id: None,
+            code: Some(TrimString("recycle-item".to_string())),
+            name: TrimString("实例1".to_string()),
+            disabled: None,
+            rel_rbum_kind_id: kind_id.to_string(),
+            rel_rbum_domain_id: domain_id.to_string(),
+            scope_level: Some(RbumScopeLevelKind::L2),
+        },
+        &funs,
+        context,
+    )
+    .await?;
+
+    // -----------------------------------
+
+    info!("【test_rbum_item_recycle】 : Test Soft Delete : RbumItemServ::soft_delete_rbum");
+    RbumItemServ::soft_delete_rbum(&id, &funs, context).await?;
+    assert!(RbumItemServ::get_rbum(&id, &RbumBasicFilterReq::default(), &funs, context).await.is_err());
+    assert!(RbumItemServ::soft_delete_rbum(&id, &funs, context).await.is_err());
+    let rbums = RbumItemServ::paginate_rbums(
+        &RbumBasicFilterReq {
+            rbum_kind_id: Some(kind_id.to_string()),
+            ..Default::default()
+        },
+        1,
+        10,
+        None,
+        None,
+        &funs,
+        context,
+    )
+    .await?;
+    assert_eq!(rbums.total_size, 0);
+
+    info!("【test_rbum_item_recycle】 : Test Find : RbumItemServ::paginate_recycled_rbums");
+    let recycled_rbums = RbumItemServ::paginate_recycled_rbums(
+        &RbumBasicFilterReq {
+            rbum_kind_id: Some(kind_id.to_string()),
+            ..Default::default()
+        },
+        1,
+        10,
+        &funs,
+        context,
+    )
+    .await?;
+    assert_eq!(recycled_rbums.total_size, 1);
+    assert_eq!(recycled_rbums.records.get(0).unwrap().id, id);
+    assert_eq!(
+        recycled_rbums.records.get(0).unwrap().expire_time.timestamp() - recycled_rbums.records.get(0).unwrap().delete_time.timestamp(),
+        3600
+    );
+
+    info!("【test_rbum_item_recycle】 : Test Code Conflict : RbumItemServ::add_rbum");
+    assert!(RbumItemServ::add_rbum(
+        &mut RbumItemAddReq {
+            id: None,
+            code: Some(TrimString("recycle-item".to_string())),
+            name: TrimString("实例2".to_string()),
+            disabled: None,
+            rel_rbum_kind_id: kind_id.to_string(),
+            rel_rbum_domain_id: domain_id.to_string(),
+            scope_level: Some(RbumScopeLevelKind::L2),
+        },
+        &funs,
+        context,
+    )
+    .await
+    .is_err());
+
+    info!("【test_rbum_item_recycle】 : Test Purge : RbumItemServ::purge_expired_rbums");
+    assert_eq!(RbumItemServ::purge_expired_rbums(&funs, context).await?, 0);
+
+    info!("【test_rbum_item_recycle】 : Test Restore : RbumItemServ::restore_rbum");
+    RbumItemServ::restore_rbum(&id, &funs, context).await?;
+    assert_eq!(RbumItemServ::get_rbum(&id, &RbumBasicFilterReq::default(), &funs, context).await?.name, "实例1");
+    assert!(RbumItemServ::restore_rbum(&id, &funs, context).await.is_err());
+    let recycled_rbums = RbumItemServ::paginate_recycled_rbums(
+        &RbumBasicFilterReq {
+            rbum_kind_id: Some(kind_id.to_string()),
+            ..Default::default()
+        },
+        1,
+        10,
+        &funs,
+        context,
+    )
+    .await?;
+    assert_eq!(recycled_rbums.total_size, 0);
+
+    info!("【test_rbum_item_recycle】 : Test Purge Expired : RbumItemServ::purge_expired_rbums");
+    // Associated data does not prevent soft delete, it is removed together with the item when purging
+    let rel_id = RbumRelServ::add_rbum(
+        &mut RbumRelAddReq {
+            tag: "recycle".to_string(),
+            note: None,
+            from_rbum_kind: RbumRelFromKind::Item,
+            from_rbum_id: id.to_string(),
+            to_rbum_item_id: id.to_string(),
+            to_own_paths: context.own_paths.to_string(),
+            to_is_outside: false,
+            ext: None,
+        },
+        &funs,
+        context,
+    )
+    .await?;
+    RbumItemServ::soft_delete_rbum(&id, &funs, context).await?;
+    funs.db()
+        .execute(
+            Query::update()
+                .table(Alias::new("rbum_item"))
+                .value(Alias::new("delete_time"), (Utc::now() - Duration::seconds(3601)).into())
+                .and_where(Expr::col(Alias::new("id")).eq(id.as_str())),
+        )
+        .await?;
+    assert!(RbumItemServ::restore_rbum(&id, &funs, context).await.is_err());
+    assert_eq!(RbumItemServ::purge_expired_rbums(&funs, context).await?, 1);
+    assert!(RbumRelServ::get_rbum(&rel_id, &RbumRelFilterReq::default(), &funs, context).await.is_err());
+    let recycled_rbums = RbumItemServ::paginate_recycled_rbums(
+        &RbumBasicFilterReq {
+            rbum_kind_id: Some(kind_id.to_string()),
+            ..Default::default()
+        },
+        1,
+        10,
+        &funs,
+        context,
+    )
+    .await?;
+    assert_eq!(recycled_rbums.total_size, 0);
+
+    funs.rollback().await?;
+
+    Ok(())
+}
+
+async fn test_rbum_item_batch(context: &TardisContext) -> TardisResult<()> {
+    let mut funs = TardisFuns::inst_with_db_conn("".to_string(), None);
+    funs.begin().await?;
+
+    info!("【test_rbum_item_batch】 : Prepare : RbumKindServ::add_rbum");
+    let kind_id = RbumKindServ::add_rbum(
+        &mut RbumKindAddReq {
+            code: TrimString("batch".to_string()),
+            name: TrimString("批量".to_string()),
+            note: None,
+            icon: None,
+            sort: None,
+            ext_table_name: None,
+            soft_delete_retention_sec: None,
+            scope_level: Some(RbumScopeLevelKind::L2),
+        },
+        &funs,
+        context,
+    )
+    .await?;
+
+    info!("【test_rbum_item_batch】 : Prepare Domain : RbumDomainServ::add_rbum");
+    let domain_id = RbumDomainServ::add_rbum(
+        &mut RbumDomainAddReq {
+            code: TrimString("batch-dev".to_string()),
+            name: TrimString("批量测试".to_string()),
+            note: None,
+            icon: None,
+            sort: None,
+            scope_level: Some(RbumScopeLevelKind::L2),
+        },
+        &funs,
+        context,
+    )
+    .await?;
+
+    let new_add_req = |code: &str| RbumItemAddReq {
+        id: None,
+        code: Some(TrimString(code.to_string())),
+        name: TrimString(code.to_string()),
+        disabled: None,
+        rel_rbum_kind_id: kind_id.to_string(),
+        rel_rbum_domain_id: domain_id.to_string(),
+        scope_level: Some(RbumScopeLevelKind::L2),
+    };
+    RbumItemServ::add_rbum(&mut new_add_req("batch-exist"), &funs, context).await?;
+
+    // -----------------------------------
+
+    info!("【test_rbum_item_batch】 : Test Add : RbumItemServ::add_rbums");
+    let resp = RbumItemServ::add_rbums(&mut [new_add_req("batch-1"), new_add_req("batch-exist"), new_add_req("batch-2")], &funs, context).await?;
+    assert_eq!(resp.succeed_ids.len(), 2);
+    assert_eq!(resp.errors.len(), 1);
+    assert_eq!(resp.errors[0].index, 1);
+    let rbums = RbumItemServ::find_rbums(
+        &RbumBasicFilterReq {
+            rbum_kind_id: Some(kind_id.to_string()),
+            ..Default::default()
+        },
+        None,
+        None,
+        &funs,
+        context,
+    )
+    .await?;
+    assert_eq!(rbums.len(), 3);
+    assert!(rbums.iter().any(|rbum| rbum.id == resp.succeed_ids[0] && rbum.code == "batch-1"));
+    assert!(rbums.iter().any(|rbum| rbum.id == resp.succeed_ids[1] && rbum.code == "batch-2"));
+    let ids = resp.succeed_ids;
+
+    info!("【test_rbum_item_batch】 : Test Modify : RbumItemServ::modify_rbums");
+    let new_modify_req = |name: &str| RbumItemModifyReq {
+        code: None,
+        name: Some(TrimString(name.to_string())),
+        scope_level: None,
+        disabled: None,
+        version: None,
+    };
+    let resp = RbumItemServ::modify_rbums(
+        &mut [(ids[0].to_string(), new_modify_req("批量1")), ("not-exist".to_string(), new_modify_req("批量2"))],
+        &funs,
+        context,
+    )
+    .await?;
+    assert_eq!(resp.succeed_ids, vec![ids[0].to_string()]);
+    assert_eq!(resp.errors.len(), 1);
+    assert_eq!(resp.errors[0].index, 1);
+    assert_eq!(resp.errors[0].id, "not-exist");
+    assert_eq!(RbumItemServ::get_rbum(&ids[0], &RbumBasicFilterReq::default(), &funs, context).await?.name, "批量1");
+    // The records modified the same way are updated together, with or without an expected version
+    let version0 = RbumItemServ::get_rbum(&ids[0], &RbumBasicFilterReq::default(), &funs, context).await?.version;
+    let version1 = RbumItemServ::get_rbum(&ids[1], &RbumBasicFilterReq::default(), &funs, context).await?.version;
+    let resp = RbumItemServ::modify_rbums(
+        &mut [
+            (ids[0].to_string(), new_modify_req("批量同名")),
+            (
+                ids[1].to_string(),
+                RbumItemModifyReq {
+                    version: Some(version1),
+                    ..new_modify_req("批量同名")
+                },
+            ),
+            (ids[0].to_string(), new_modify_req("批量重复")),
+        ],
+        &funs,
+        context,
+    )
+    .await?;
+    assert_eq!(resp.succeed_ids, ids);
+    assert_eq!(resp.errors.len(), 1);
+    assert_eq!(resp.errors[0].index, 2);
+    assert_eq!(resp.errors[0].code, "409-rbum-*-id-duplicated");
+    let rbum = RbumItemServ::get_rbum(&ids[0], &RbumBasicFilterReq::default(), &funs, context).await?;
+    assert_eq!(rbum.name, "批量同名");
+    assert_eq!(rbum.version, version0 + 1);
+    let rbum = RbumItemServ::get_rbum(&ids[1], &RbumBasicFilterReq::default(), &funs, context).await?;
+    assert_eq!(rbum.name, "批量同名");
+    assert_eq!(rbum.version, version1 + 1);
+    let resp = RbumItemServ::modify_rbums(
+        &mut [(
+            ids[1].to_string(),
+            RbumItemModifyReq {
+                version: Some(version1),
+                ..new_modify_req("批量过期")
+            },
+        )],
+        &funs,
+        context,
+    )
+    .await?;
+    assert!(resp.succeed_ids.is_empty());
+    assert_eq!(resp.errors[0].code, "409-rbum-*-version-conflict");
+    assert_eq!(RbumItemServ::get_rbum(&ids[1], &RbumBasicFilterReq::default(), &funs, context).await?.name, "批量同名");
+
+    info!("【test_rbum_item_batch】 : Test Delete : RbumItemServ::delete_rbums");
+    let resp = RbumItemServ::delete_rbums(&[ids[0].to_string(), "not-exist".to_string(), ids[1].to_string()], &funs, context).await?;
+    assert_eq!(resp.succeed_ids, ids);
+    assert_eq!(resp.errors.len(), 1);
+    assert_eq!(resp.errors[0].index, 1);
+    assert_eq!(
+        RbumItemServ::count_rbums(
+            &RbumBasicFilterReq {
+                rbum_kind_id: Some(kind_id.to_string()),
+                ..Default::default()
+            },
+            &funs,
+            context,
+        )
+        .await?,
+        1
+    );
+
+    funs.rollback().await?;
+
+    Ok(())
+}
+
+async fn test_rbum_item_cursor(context: &TardisContext) -> TardisResult<()> {
+    let mut funs = TardisFuns::inst_with_db_conn("".to_string(), None);
+    funs.begin().await?;
+
+    info!("【test_rbum_item_cursor】 : Prepare : RbumKindServ::add_rbum");
+    let kind_id = RbumKindServ::add_rbum(
+        &mut RbumKindAddReq {
+            code: TrimString("cursor".to_string()),
+            name: TrimString("游标".to_string()),
+            note: None,
+            icon: None,
+            sort: None,
+            ext_table_name: None,
+            soft_delete_retention_sec: None,
+            scope_level: Some(RbumScopeLevelKind::L2),
+        },
+        &funs,
+        context,
+    )
+    .await?;
+
+    info!("【test_rbum_item_cursor】 : Prepare Domain : RbumDomainServ::add_rbum");
+    let domain_id = RbumDomainServ::add_rbum(
+        &mut RbumDomainAddReq {
+            code: TrimString("cursor-dev".to_string()),
+            name: TrimString("游标测试".to_string()),
+            note: None,
+            icon: None,
+            sort: None,
+            scope_level: Some(RbumScopeLevelKind::L2),
+        },
+        &funs,
+        context,
+    )
+    .await?;
+
+    let new_add_req = |code: &str, name: &str| RbumItemAddReq {
+        id: None,
+        code: Some(TrimString(code.to_string())),
+        name: TrimString(name.to_string()),
+        disabled: None,
+        rel_rbum_kind_id: kind_id.to_string(),
+        rel_rbum_domain_id: domain_id.to_string(),
+        scope_level: Some(RbumScopeLevelKind::L2),
+    };
+    RbumItemServ::add_rbum(&mut new_add_req("cursor-1", "cursor-b"), &funs, context).await?;
+    RbumItemServ::add_rbum(&mut new_add_req("cursor-2", "cursor-b"), &funs, context).await?;
+    RbumItemServ::add_rbum(&mut new_add_req("cursor-3", "cursor-c"), &funs, context).await?;
+    let filter = RbumBasicFilterReq {
+        rbum_kind_id: Some(kind_id.to_string()),
+        ..Default::default()
+    };
+
+    // -----------------------------------
+
+    info!("【test_rbum_item_cursor】 : Test Sort : RbumSortReq::parse");
+    let sorts = RbumSortReq::parse("name, -create_time,");
+    assert_eq!(sorts.len(), 2);
+    assert!(!sorts[0].desc);
+    assert_eq!(sorts[1].field, "create_time");
+    assert!(sorts[1].desc);
+    assert!(RbumItemServ::paginate_rbums_by_cursor(&filter, &RbumSortReq::parse("not_exist"), None, 2, &funs, context).await.is_err());
+    assert!(RbumItemServ::paginate_rbums_by_cursor(&filter, &[], Some("not-exist"), 2, &funs, context).await.is_err());
+    // Nullable columns can not be sorted by
+    assert!(RbumItemServ::paginate_rbums_by_cursor(&filter, &RbumSortReq::parse("delete_time"), None, 2, &funs, context).await.is_err());
+
+    info!("【test_rbum_item_cursor】 : Test Paginate : RbumItemServ::paginate_rbums_by_cursor");
+    let sorts = RbumSortReq::parse("name");
+    let page = RbumItemServ::paginate_rbums_by_cursor(&filter, &sorts, None, 2, &funs, context).await?;
+    assert_eq!(page.records.len(), 2);
+    assert!(page.records.iter().all(|rbum| rbum.name == "cursor-b"));
+    assert!(page.next_cursor.is_some());
+    // Records added before the cursor do not shift the next page
+    RbumItemServ::add_rbum(&mut new_add_req("cursor-4", "cursor-a"), &funs, context).await?;
+    RbumItemServ::add_rbum(&mut new_add_req("cursor-5", "cursor-d"), &funs, context).await?;
+    let page = RbumItemServ::paginate_rbums_by_cursor(&filter, &sorts, page.next_cursor.as_deref(), 2, &funs, context).await?;
+    assert_eq!(page.records.iter().map(|rbum| rbum.code.as_str()).collect::<Vec<&str>>(), vec!["cursor-3", "cursor-5"]);
+    assert!(page.next_cursor.is_none());
+
+    let page = RbumItemServ::paginate_rbums_by_cursor(&filter, &RbumSortReq::parse("-name,code"), None, 3, &funs, context).await?;
+    assert_eq!(
+        page.records.iter().map(|rbum| rbum.code.as_str()).collect::<Vec<&str>>(),
+        vec!["cursor-5", "cursor-3", "cursor-1"]
+    );
+    // The cursor does not match other sort fields
+    assert!(RbumItemServ::paginate_rbums_by_cursor(&filter, &RbumSortReq::parse("name"), page.next_cursor.as_deref(), 3, &funs, context).await.is_err());
+    // The cursor carries the sort values, so deleting the last record of the page does not break the pagination
+    RbumItemServ::delete_rbum(&page.records[2].id, &funs, context).await?;
+    let page = RbumItemServ::paginate_rbums_by_cursor(&filter, &RbumSortReq::parse("-name,code"), page.next_cursor.as_deref(), 3, &funs, context).await?;
+    assert_eq!(page.records.iter().map(|rbum| rbum.code.as_str()).collect::<Vec<&str>>(), vec!["cursor-2", "cursor-4"]);
+    assert!(page.next_cursor.is_none());
+
+    funs.rollback().await?;
+
+    Ok(())
+}
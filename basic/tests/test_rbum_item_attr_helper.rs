@@ -0,0 +1,102 @@
+use tardis::chrono::Utc;
+use tardis::tokio;
+
+use bios_basic::rbum::dto::rbum_kind_attr_dto::RbumKindAttrSummaryResp;
+use bios_basic::rbum::helper::rbum_item_attr_helper::validate;
+use bios_basic::rbum::rbum_enumeration::{RbumDataTypeKind, RbumScopeLevelKind, RbumWidgetTypeKind};
+
+fn package_attr(data_type: RbumDataTypeKind, widget_type: RbumWidgetTypeKind, options: &str, required: bool, min_length: u32, max_length: u32) -> RbumKindAttrSummaryResp {
+    RbumKindAttrSummaryResp {
+        id: "".to_string(),
+        name: "db_type".to_string(),
+        module: "".to_string(),
+        label: "数据库类型".to_string(),
+        note: "".to_string(),
+        sort: 0,
+        main_column: false,
+        position: false,
+        capacity: false,
+        overload: false,
+        hide: false,
+        idx: false,
+        data_type,
+        widget_type,
+        default_value: "".to_string(),
+        options: options.to_string(),
+        required,
+        min_length,
+        max_length,
+        action: "".to_string(),
+        ext: "".to_string(),
+        rel_rbum_kind_id: "".to_string(),
+        own_paths: "".to_string(),
+        owner: "".to_string(),
+        create_time: Utc::now(),
+        update_time: Utc::now(),
+        scope_level: RbumScopeLevelKind::Private,
+    }
+}
+
+#[tokio::test]
+pub async fn test_validate_required_and_length() {
+    let attr = package_attr(RbumDataTypeKind::String, RbumWidgetTypeKind::Input, "", true, 2, 5);
+    assert_eq!(validate(&attr, "").unwrap().code, "required");
+    assert_eq!(validate(&attr, "").unwrap().name, "db_type");
+    assert_eq!(validate(&attr, "m").unwrap().code, "min_length");
+    assert_eq!(validate(&attr, "mysql8").unwrap().code, "max_length");
+    assert!(validate(&attr, "mysql").is_none());
+    assert!(validate(&attr, "数据库").is_none());
+
+    let attr = package_attr(RbumDataTypeKind::Number, RbumWidgetTypeKind::InputNum, "", false, 0, 0);
+    assert!(validate(&attr, "").is_none());
+}
+
+#[tokio::test]
+pub async fn test_validate_data_type() {
+    let attr = package_attr(RbumDataTypeKind::Number, RbumWidgetTypeKind::InputNum, "", false, 0, 0);
+    assert!(validate(&attr, "3306").is_none());
+    assert!(validate(&attr, "1.5").is_none());
+    assert_eq!(validate(&attr, "port").unwrap().code, "data_type");
+
+    let attr = package_attr(RbumDataTypeKind::Boolean, RbumWidgetTypeKind::Switch, "", false, 0, 0);
+    assert!(validate(&attr, "true").is_none());
+    assert!(validate(&attr, "yes").is_some());
+
+    let attr = package_attr(RbumDataTypeKind::Date, RbumWidgetTypeKind::Date, "", false, 0, 0);
+    assert!(validate(&attr, "2022-02-28").is_none());
+    assert!(validate(&attr, "2022-02-30").is_some());
+
+    let attr = package_attr(RbumDataTypeKind::DateTime, RbumWidgetTypeKind::DateTime, "", false, 0, 0);
+    assert!(validate(&attr, "2022-01-01 08:00:00").is_none());
+    assert!(validate(&attr, "2022-01-01T08:00:00+08:00").is_none());
+    assert!(validate(&attr, "2022-01-01").is_some());
+
+    let attr = package_attr(RbumDataTypeKind::Json, RbumWidgetTypeKind::Input, "", false, 0, 0);
+    assert!(validate(&attr, r#"{"host":"127.0.0.1"}"#).is_none());
+    assert!(validate(&attr, r#"{"host":"#).is_some());
+
+    let attr = package_attr(RbumDataTypeKind::Numbers, RbumWidgetTypeKind::Input, "", false, 0, 0);
+    assert!(validate(&attr, "[1,2]").is_none());
+    assert!(validate(&attr, r#"[1,"2"]"#).is_some());
+    assert!(validate(&attr, "1").is_some());
+}
+
+#[tokio::test]
+pub async fn test_validate_options() {
+    let options = r#"[{"mysql":"MySQL"},{"postgres":"PostgreSQL"}]"#;
+    let attr = package_attr(RbumDataTypeKind::String, RbumWidgetTypeKind::Select, options, false, 0, 0);
+    assert!(validate(&attr, "mysql").is_none());
+    assert_eq!(validate(&attr, "oracle").unwrap().code, "options");
+
+    let attr = package_attr(RbumDataTypeKind::Strings, RbumWidgetTypeKind::Checkbox, options, false, 0, 0);
+    assert!(validate(&attr, r#"["mysql","postgres"]"#).is_none());
+    assert!(validate(&attr, r#"["mysql","oracle"]"#).is_some());
+
+    // Options are not enforced by free input widgets
+    let attr = package_attr(RbumDataTypeKind::String, RbumWidgetTypeKind::Input, options, false, 0, 0);
+    assert!(validate(&attr, "oracle").is_none());
+
+    // Unparseable options are not a constraint
+    let attr = package_attr(RbumDataTypeKind::String, RbumWidgetTypeKind::Select, "mysql,postgres", false, 0, 0);
+    assert!(validate(&attr, "oracle").is_none());
+}
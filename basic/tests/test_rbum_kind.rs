@@ -30,6 +30,7 @@ async fn test_rbum_kind(context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             ext_table_name: Some("reldb_mgr".to_string()),
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::L2),
         },
         &funs,
@@ -45,6 +46,7 @@ async fn test_rbum_kind(context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             ext_table_name: Some("reldb_mgr".to_string()),
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::L2),
         },
         &funs,
@@ -60,6 +62,7 @@ async fn test_rbum_kind(context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             ext_table_name: Some("reldb_mgr".to_string()),
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::L2),
         },
         &funs,
@@ -75,6 +78,7 @@ async fn test_rbum_kind(context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             ext_table_name: Some("reldb_mgr".to_string()),
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::L2),
         },
         &funs,
@@ -97,7 +101,9 @@ async fn test_rbum_kind(context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             ext_table_name: None,
+            soft_delete_retention_sec: None,
             scope_level: None,
+            version: None,
         },
         &funs,
         context,
@@ -145,6 +151,7 @@ async fn test_rbum_kind_attr(context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             ext_table_name: None,
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::L2),
         },
         &funs,
@@ -278,7 +285,8 @@ async fn test_rbum_kind_attr(context: &TardisContext) -> TardisResult<()> {
             ext: None,
             scope_level: None,
             idx: None,
-            hide: None
+            hide: None,
+            version: None,
         },
         &funs,
         context
@@ -308,12 +316,53 @@ async fn test_rbum_kind_attr(context: &TardisContext) -> TardisResult<()> {
             scope_level: None,
             idx: None,
             hide: None,
+            version: None,
         },
         &funs,
         context,
     )
     .await?;
 
+    info!("【test_rbum_kind_attr】 : Test Modify With Version : RbumKindAttrServ::modify_rbum");
+    let rbum = RbumKindAttrServ::get_rbum(&kind_attr_id, &RbumKindAttrFilterReq::default(), &funs, context).await?;
+    assert_eq!(rbum.version, 1);
+    let mut modify_req = RbumKindAttrModifyReq {
+        label: Some("数据库类型2".to_string()),
+        data_type: None,
+        widget_type: None,
+        note: None,
+        sort: None,
+        main_column: None,
+        position: None,
+        capacity: None,
+        overload: None,
+        default_value: None,
+        options: None,
+        required: None,
+        min_length: None,
+        max_length: None,
+        action: None,
+        ext: None,
+        scope_level: None,
+        idx: None,
+        hide: None,
+        version: Some(0),
+    };
+    assert_eq!(
+        RbumKindAttrServ::modify_rbum(&kind_attr_id, &mut modify_req, &funs, context).await.unwrap_err().code,
+        "409-rbum-*-version-conflict"
+    );
+    modify_req.version = Some(1);
+    RbumKindAttrServ::modify_rbum(&kind_attr_id, &mut modify_req, &funs, context).await?;
+    let rbum = RbumKindAttrServ::get_rbum(&kind_attr_id, &RbumKindAttrFilterReq::default(), &funs, context).await?;
+    assert_eq!(rbum.label, "数据库类型2");
+    assert_eq!(rbum.version, 2);
+    // A missing record is not a conflict
+    assert_eq!(
+        RbumKindAttrServ::check_and_increase_version("111", Some(0), &funs).await.unwrap_err().code,
+        "404-rbum-*-obj-not-exist"
+    );
+
     info!("【test_rbum_kind_attr】 : Test Find : RbumKindAttrServ::paginate_rbums");
     let rbums = RbumKindAttrServ::paginate_rbums(&RbumKindAttrFilterReq::default(), 1, 10, None, None, &funs, context).await?;
     assert_eq!(rbums.page_number, 1);
@@ -325,6 +374,15 @@ async fn test_rbum_kind_attr(context: &TardisContext) -> TardisResult<()> {
     RbumKindAttrServ::delete_rbum(&kind_attr_id, &funs, context).await?;
     assert!(RbumKindAttrServ::get_rbum(&kind_attr_id, &RbumKindAttrFilterReq::default(), &funs, context).await.is_err());
 
+    info!("【test_rbum_kind_attr】 : Test Batch Delete : RbumKindServ::delete_rbums");
+    let resp = RbumKindServ::delete_rbums(&["not-exist".to_string(), kind_id.to_string(), kind_id.to_string()], &funs, context).await?;
+    assert_eq!(resp.succeed_ids, vec![kind_id.to_string()]);
+    assert_eq!(resp.errors.len(), 2);
+    assert_eq!(resp.errors[0].code, "404-rbum-*-ownership-illegal");
+    assert_eq!(resp.errors[1].index, 2);
+    assert_eq!(resp.errors[1].code, "409-rbum-*-id-duplicated");
+    assert!(RbumKindServ::get_rbum(&kind_id, &RbumBasicFilterReq::default(), &funs, context).await.is_err());
+
     funs.rollback().await?;
 
     Ok(())
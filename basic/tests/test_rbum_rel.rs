@@ -20,7 +20,7 @@ use bios_basic::rbum::dto::rbum_rel_env_dto::{RbumRelEnvAddReq, RbumRelEnvModify
 use bios_basic::rbum::dto::rbum_set_cate_dto::RbumSetCateAddReq;
 use bios_basic::rbum::dto::rbum_set_dto::RbumSetAddReq;
 use bios_basic::rbum::dto::rbum_set_item_dto::RbumSetItemAddReq;
-use bios_basic::rbum::rbum_enumeration::{RbumDataTypeKind, RbumRelEnvKind, RbumRelFromKind, RbumScopeLevelKind, RbumWidgetTypeKind};
+use bios_basic::rbum::rbum_enumeration::{RbumDataTypeKind, RbumRelAttrOperatorKind, RbumRelEnvKind, RbumRelFromKind, RbumScopeLevelKind, RbumWidgetTypeKind};
 use bios_basic::rbum::serv::rbum_crud_serv::RbumCrudOperation;
 use bios_basic::rbum::serv::rbum_domain_serv::RbumDomainServ;
 use bios_basic::rbum::serv::rbum_item_serv::RbumItemServ;
@@ -34,6 +34,7 @@ pub async fn test(context: &TardisContext) -> TardisResult<()> {
     test_rbum_rel_attr(context).await?;
     test_rbum_rel_env(context).await?;
     test_rbum_rel_use(context).await?;
+    test_rbum_rel_cache(context).await?;
     Ok(())
 }
 
@@ -50,6 +51,7 @@ async fn test_rbum_rel(context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             ext_table_name: None,
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::L2),
         },
         &funs,
@@ -65,6 +67,7 @@ async fn test_rbum_rel(context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             ext_table_name: None,
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::L2),
         },
         &funs,
@@ -329,7 +332,8 @@ async fn test_rbum_rel_with_set(context: &TardisContext) -> TardisResult<()> {
                 from_rbum_id: context.owner.to_string(),
                 to_rbum_item_id: "xxxx".to_string(),
                 from_attrs: Default::default(),
-                to_attrs: Default::default()
+                to_attrs: Default::default(),
+                from_ip: None
             },
             &funs,
             context
@@ -363,7 +367,8 @@ async fn test_rbum_rel_with_set(context: &TardisContext) -> TardisResult<()> {
                 from_rbum_id: context.owner.to_string(),
                 to_rbum_item_id: "xxxx".to_string(),
                 from_attrs: Default::default(),
-                to_attrs: Default::default()
+                to_attrs: Default::default(),
+                from_ip: None
             },
             &funs,
             context
@@ -383,7 +388,8 @@ async fn test_rbum_rel_with_set(context: &TardisContext) -> TardisResult<()> {
                 from_rbum_id: context.owner.to_string(),
                 to_rbum_item_id: "xxxx".to_string(),
                 from_attrs: Default::default(),
-                to_attrs: Default::default()
+                to_attrs: Default::default(),
+                from_ip: None
             },
             &funs,
             context
@@ -415,7 +421,8 @@ async fn test_rbum_rel_with_set(context: &TardisContext) -> TardisResult<()> {
                 from_rbum_id: context.owner.to_string(),
                 to_rbum_item_id: "xxxx".to_string(),
                 from_attrs: Default::default(),
-                to_attrs: Default::default()
+                to_attrs: Default::default(),
+                from_ip: None
             },
             &funs,
             context
@@ -441,6 +448,7 @@ async fn test_rbum_rel_attr(context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             ext_table_name: None,
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::L2),
         },
         &funs,
@@ -456,6 +464,7 @@ async fn test_rbum_rel_attr(context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             ext_table_name: None,
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::L2),
         },
         &funs,
@@ -578,6 +587,7 @@ async fn test_rbum_rel_attr(context: &TardisContext) -> TardisResult<()> {
         &mut RbumRelAttrAddReq {
             is_from: true,
             value: "mysql".to_string(),
+            operator: None,
             name: "".to_string(),
             rel_rbum_rel_id: "".to_string(),
             rel_rbum_kind_attr_id: "".to_string(),
@@ -593,6 +603,7 @@ async fn test_rbum_rel_attr(context: &TardisContext) -> TardisResult<()> {
         &mut RbumRelAttrAddReq {
             is_from: true,
             value: "mysql".to_string(),
+            operator: None,
             name: "".to_string(),
             rel_rbum_rel_id: rel_id.to_string(),
             rel_rbum_kind_attr_id: "".to_string(),
@@ -608,6 +619,7 @@ async fn test_rbum_rel_attr(context: &TardisContext) -> TardisResult<()> {
         &mut RbumRelAttrAddReq {
             is_from: true,
             value: "mysql".to_string(),
+            operator: None,
             name: "".to_string(),
             rel_rbum_rel_id: rel_id.to_string(),
             rel_rbum_kind_attr_id: kind_attr_db_type_id.to_string(),
@@ -618,10 +630,27 @@ async fn test_rbum_rel_attr(context: &TardisContext) -> TardisResult<()> {
     )
     .await?;
 
+    assert!(RbumRelAttrServ::add_rbum(
+        &mut RbumRelAttrAddReq {
+            is_from: true,
+            value: "^(mysql".to_string(),
+            operator: Some(RbumRelAttrOperatorKind::Regex),
+            name: "".to_string(),
+            rel_rbum_rel_id: rel_id.to_string(),
+            rel_rbum_kind_attr_id: kind_attr_db_type_id.to_string(),
+            record_only: false
+        },
+        &funs,
+        context,
+    )
+    .await
+    .is_err());
+
     info!("【test_rbum_rel_attr】 : Test Get : RbumRelAttrServ::get_rbum");
     let rbum = RbumRelAttrServ::get_rbum(&id, &RbumRelExtFilterReq::default(), &funs, context).await?;
     assert_eq!(rbum.id, id);
     assert_eq!(rbum.value, "mysql");
+    assert_eq!(rbum.operator, RbumRelAttrOperatorKind::Eq);
     assert_eq!(rbum.name, "db_type");
 
     info!("【test_rbum_rel_attr】 : Test Modify : RbumRelAttrServ::modify_rbum");
@@ -629,6 +658,7 @@ async fn test_rbum_rel_attr(context: &TardisContext) -> TardisResult<()> {
         &id,
         &mut RbumRelAttrModifyReq {
             value: Some("tidb".to_string()),
+            operator: None,
             name: None,
         },
         &funs,
@@ -667,6 +697,7 @@ async fn test_rbum_rel_env(context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             ext_table_name: None,
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::L2),
         },
         &funs,
@@ -682,6 +713,7 @@ async fn test_rbum_rel_env(context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             ext_table_name: None,
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::L2),
         },
         &funs,
@@ -782,6 +814,32 @@ async fn test_rbum_rel_env(context: &TardisContext) -> TardisResult<()> {
     .await
     .is_err());
 
+    assert!(RbumRelEnvServ::add_rbum(
+        &mut RbumRelEnvAddReq {
+            kind: RbumRelEnvKind::TimeRange,
+            value1: "25:00".to_string(),
+            value2: Some("18:00".to_string()),
+            rel_rbum_rel_id: rel_id.to_string()
+        },
+        &funs,
+        context,
+    )
+    .await
+    .is_err());
+
+    assert!(RbumRelEnvServ::add_rbum(
+        &mut RbumRelEnvAddReq {
+            kind: RbumRelEnvKind::Ips,
+            value1: "192.168.0.1,10.0.0.0/33".to_string(),
+            value2: None,
+            rel_rbum_rel_id: rel_id.to_string()
+        },
+        &funs,
+        context,
+    )
+    .await
+    .is_err());
+
     let start_time = Utc::now().timestamp().to_string();
     let end_time = (Utc::now().timestamp() + 2000).to_string();
     let id = RbumRelEnvServ::add_rbum(
@@ -847,6 +905,7 @@ async fn test_rbum_rel_use(context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             ext_table_name: None,
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::L2),
         },
         &funs,
@@ -893,6 +952,7 @@ async fn test_rbum_rel_use(context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             ext_table_name: None,
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::L2),
         },
         &funs,
@@ -980,6 +1040,7 @@ async fn test_rbum_rel_use(context: &TardisContext) -> TardisResult<()> {
             attrs: vec![RbumRelAttrAggAddReq {
                 is_from: true,
                 value: "mysql".to_string(),
+                operator: None,
                 name: "".to_string(),
                 record_only: false,
                 rel_rbum_kind_attr_id: kind_attr_db_type_id.to_string(),
@@ -1036,7 +1097,8 @@ async fn test_rbum_rel_use(context: &TardisContext) -> TardisResult<()> {
                 from_rbum_id: "".to_string(),
                 to_rbum_item_id: "".to_string(),
                 from_attrs: Default::default(),
-                to_attrs: Default::default()
+                to_attrs: Default::default(),
+                from_ip: None
             },
             &funs,
             context
@@ -1052,7 +1114,8 @@ async fn test_rbum_rel_use(context: &TardisContext) -> TardisResult<()> {
                 from_rbum_id: item_reldb_inst1_id.to_string(),
                 to_rbum_item_id: item_account_a1_id.to_string(),
                 from_attrs: Default::default(),
-                to_attrs: Default::default()
+                to_attrs: Default::default(),
+                from_ip: None
             },
             &funs,
             context
@@ -1068,7 +1131,8 @@ async fn test_rbum_rel_use(context: &TardisContext) -> TardisResult<()> {
                 from_rbum_id: item_reldb_inst1_id.to_string(),
                 to_rbum_item_id: item_account_a1_id.to_string(),
                 from_attrs: HashMap::from([("db_type".to_string(), "tidb".to_string()),]),
-                to_attrs: Default::default()
+                to_attrs: Default::default(),
+                from_ip: None
             },
             &funs,
             context
@@ -1084,7 +1148,8 @@ async fn test_rbum_rel_use(context: &TardisContext) -> TardisResult<()> {
                 from_rbum_id: item_reldb_inst1_id.to_string(),
                 to_rbum_item_id: item_account_a1_id.to_string(),
                 from_attrs: HashMap::from([("db_type".to_string(), "mysql".to_string()),]),
-                to_attrs: Default::default()
+                to_attrs: Default::default(),
+                from_ip: None
             },
             &funs,
             context
@@ -1092,6 +1157,45 @@ async fn test_rbum_rel_use(context: &TardisContext) -> TardisResult<()> {
         .await?
     );
 
+    info!("【test_rbum_rel_use】 : Test Check Rels : RbumRelServ::check_rels");
+    assert_eq!(
+        RbumRelServ::check_rels(
+            &[
+                RbumRelCheckReq {
+                    tag: "bind".to_string(),
+                    from_rbum_kind: RbumRelFromKind::Item,
+                    from_rbum_id: item_reldb_inst1_id.to_string(),
+                    to_rbum_item_id: item_account_a1_id.to_string(),
+                    from_attrs: HashMap::from([("db_type".to_string(), "mysql".to_string()),]),
+                    to_attrs: Default::default(),
+                    from_ip: None
+                },
+                RbumRelCheckReq {
+                    tag: "bind".to_string(),
+                    from_rbum_kind: RbumRelFromKind::Item,
+                    from_rbum_id: item_reldb_inst1_id.to_string(),
+                    to_rbum_item_id: item_account_a1_id.to_string(),
+                    from_attrs: HashMap::from([("db_type".to_string(), "tidb".to_string()),]),
+                    to_attrs: Default::default(),
+                    from_ip: None
+                },
+                RbumRelCheckReq {
+                    tag: "bind".to_string(),
+                    from_rbum_kind: RbumRelFromKind::Item,
+                    from_rbum_id: item_reldb_inst1_id.to_string(),
+                    to_rbum_item_id: "".to_string(),
+                    from_attrs: HashMap::from([("db_type".to_string(), "mysql".to_string()),]),
+                    to_attrs: Default::default(),
+                    from_ip: None
+                },
+            ],
+            &funs,
+            context
+        )
+        .await?,
+        vec![true, false, false]
+    );
+
     tardis::tokio::time::sleep(Duration::from_secs(3)).await;
 
     assert!(
@@ -1102,7 +1206,8 @@ async fn test_rbum_rel_use(context: &TardisContext) -> TardisResult<()> {
                 from_rbum_id: item_reldb_inst1_id.to_string(),
                 to_rbum_item_id: item_account_a1_id.to_string(),
                 from_attrs: HashMap::from([("db_type".to_string(), "mysql".to_string()),]),
-                to_attrs: Default::default()
+                to_attrs: Default::default(),
+                from_ip: None
             },
             &funs,
             context
@@ -1114,3 +1219,55 @@ async fn test_rbum_rel_use(context: &TardisContext) -> TardisResult<()> {
 
     Ok(())
 }
+
+async fn test_rbum_rel_cache(context: &TardisContext) -> TardisResult<()> {
+    let funs = TardisFuns::inst_with_db_conn("".to_string(), None);
+    info!("【test_rbum_rel_cache】 : Prepare : RbumRelServ::add_rbum");
+    let rel_id = RbumRelServ::add_rbum(
+        &mut RbumRelAddReq {
+            tag: "cache".to_string(),
+            note: None,
+            from_rbum_kind: RbumRelFromKind::Item,
+            from_rbum_id: context.owner.to_string(),
+            to_rbum_item_id: "cache_xxxx".to_string(),
+            to_own_paths: context.own_paths.to_string(),
+            to_is_outside: true,
+            ext: None,
+        },
+        &funs,
+        context,
+    )
+    .await?;
+    assert!(RbumRelServ::check_rel(&mut cache_check_req(context), &funs, context).await?);
+
+    info!("【test_rbum_rel_cache】 : Test Evict After Commit : RbumRelServ::delete_rbum");
+    let mut tx_funs = TardisFuns::inst_with_db_conn("".to_string(), None);
+    tx_funs.begin().await?;
+    RbumRelServ::delete_rbum(&rel_id, &tx_funs, context).await?;
+    // Checked by others before committed, the uncommitted state is cached
+    assert!(RbumRelServ::check_rel(&mut cache_check_req(context), &funs, context).await?);
+    tx_funs.commit().await?;
+    let mut checked = true;
+    for _ in 0..50 {
+        checked = RbumRelServ::check_rel(&mut cache_check_req(context), &funs, context).await?;
+        if !checked {
+            break;
+        }
+        tardis::tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert!(!checked);
+
+    Ok(())
+}
+
+fn cache_check_req(context: &TardisContext) -> RbumRelCheckReq {
+    RbumRelCheckReq {
+        tag: "cache".to_string(),
+        from_rbum_kind: RbumRelFromKind::Item,
+        from_rbum_id: context.owner.to_string(),
+        to_rbum_item_id: "cache_xxxx".to_string(),
+        from_attrs: Default::default(),
+        to_attrs: Default::default(),
+        from_ip: None,
+    }
+}
@@ -0,0 +1,64 @@
+use tardis::tokio;
+
+use bios_basic::rbum::helper::rbum_rel_attr_helper::{check, validate};
+use bios_basic::rbum::rbum_enumeration::{RbumDataTypeKind, RbumRelAttrOperatorKind};
+
+#[tokio::test]
+pub async fn test_validate() {
+    assert!(validate(&RbumDataTypeKind::Number, &RbumRelAttrOperatorKind::Le, "10000").is_ok());
+    assert!(validate(&RbumDataTypeKind::Number, &RbumRelAttrOperatorKind::Le, "ten").is_err());
+    assert!(validate(&RbumDataTypeKind::Number, &RbumRelAttrOperatorKind::Prefix, "1").is_err());
+    assert!(validate(&RbumDataTypeKind::Boolean, &RbumRelAttrOperatorKind::Gt, "true").is_err());
+    assert!(validate(&RbumDataTypeKind::Date, &RbumRelAttrOperatorKind::Lt, "2022-02-30").is_err());
+    assert!(validate(&RbumDataTypeKind::DateTime, &RbumRelAttrOperatorKind::Ge, "2022-01-01T00:00:00+08:00").is_ok());
+    assert!(validate(&RbumDataTypeKind::String, &RbumRelAttrOperatorKind::In, "north,east").is_ok());
+    assert!(validate(&RbumDataTypeKind::Number, &RbumRelAttrOperatorKind::In, "1,two").is_err());
+    assert!(validate(&RbumDataTypeKind::String, &RbumRelAttrOperatorKind::Regex, "^(north").is_err());
+    assert!(validate(&RbumDataTypeKind::String, &RbumRelAttrOperatorKind::Regex, "north)|(east").is_err());
+}
+
+#[tokio::test]
+pub async fn test_check() {
+    assert!(check(&RbumDataTypeKind::String, &RbumRelAttrOperatorKind::Eq, "mysql", "mysql"));
+    assert!(!check(&RbumDataTypeKind::String, &RbumRelAttrOperatorKind::Eq, "mysql", "tidb"));
+    assert!(check(&RbumDataTypeKind::String, &RbumRelAttrOperatorKind::Ne, "mysql", "tidb"));
+
+    assert!(check(&RbumDataTypeKind::Number, &RbumRelAttrOperatorKind::Eq, "10", "10.0"));
+    assert!(check(&RbumDataTypeKind::Number, &RbumRelAttrOperatorKind::Le, "10000", "9999.5"));
+    assert!(check(&RbumDataTypeKind::Number, &RbumRelAttrOperatorKind::Le, "10000", "10000"));
+    assert!(!check(&RbumDataTypeKind::Number, &RbumRelAttrOperatorKind::Le, "10000", "10001"));
+    assert!(!check(&RbumDataTypeKind::Number, &RbumRelAttrOperatorKind::Le, "10000", "unknown"));
+    assert!(check(&RbumDataTypeKind::Number, &RbumRelAttrOperatorKind::Gt, "0", "1"));
+
+    assert!(check(&RbumDataTypeKind::Date, &RbumRelAttrOperatorKind::Lt, "2022-06-01", "2022-05-31"));
+    assert!(!check(&RbumDataTypeKind::Date, &RbumRelAttrOperatorKind::Lt, "2022-06-01", "2022-06-01"));
+    assert!(check(
+        &RbumDataTypeKind::DateTime,
+        &RbumRelAttrOperatorKind::Ge,
+        "2022-01-01T00:00:00+08:00",
+        "2021-12-31 16:00:00"
+    ));
+    assert!(!check(
+        &RbumDataTypeKind::DateTime,
+        &RbumRelAttrOperatorKind::Ge,
+        "2022-01-01T00:00:00+08:00",
+        "2021-12-31 15:59:59"
+    ));
+
+    assert!(check(&RbumDataTypeKind::Boolean, &RbumRelAttrOperatorKind::Eq, "true", "true"));
+    assert!(check(&RbumDataTypeKind::Boolean, &RbumRelAttrOperatorKind::Ne, "true", "false"));
+
+    assert!(check(&RbumDataTypeKind::String, &RbumRelAttrOperatorKind::In, "north, east", "east"));
+    assert!(!check(&RbumDataTypeKind::String, &RbumRelAttrOperatorKind::In, "north, east", "west"));
+    assert!(check(&RbumDataTypeKind::String, &RbumRelAttrOperatorKind::NotIn, "north, east", "west"));
+    assert!(check(&RbumDataTypeKind::Number, &RbumRelAttrOperatorKind::In, "1,2,3", "2.0"));
+    assert!(!check(&RbumDataTypeKind::Number, &RbumRelAttrOperatorKind::NotIn, "1,2,3", "unknown"));
+
+    assert!(check(&RbumDataTypeKind::String, &RbumRelAttrOperatorKind::Prefix, "/iam/", "/iam/ct/account"));
+    assert!(!check(&RbumDataTypeKind::String, &RbumRelAttrOperatorKind::Prefix, "/iam/", "/reldb/"));
+    assert!(check(&RbumDataTypeKind::String, &RbumRelAttrOperatorKind::Regex, "^cn-(north|east)$", "cn-north"));
+    assert!(!check(&RbumDataTypeKind::String, &RbumRelAttrOperatorKind::Regex, "^cn-(north|east)$", "cn-south"));
+    assert!(check(&RbumDataTypeKind::String, &RbumRelAttrOperatorKind::Regex, "cn-north|cn-east", "cn-east"));
+    assert!(!check(&RbumDataTypeKind::String, &RbumRelAttrOperatorKind::Regex, "cn-north|cn-east", "cn-east-1"));
+    assert!(!check(&RbumDataTypeKind::String, &RbumRelAttrOperatorKind::Regex, "north", "cn-north"));
+}
@@ -0,0 +1,51 @@
+use tardis::chrono::{DateTime, Utc};
+use tardis::tokio;
+
+use bios_basic::rbum::helper::rbum_rel_env_helper::{check_ips, check_time_range, validate};
+use bios_basic::rbum::rbum_enumeration::RbumRelEnvKind;
+
+#[tokio::test]
+pub async fn test_validate() {
+    assert!(validate(&RbumRelEnvKind::DatetimeRange, "1000", "2000").is_ok());
+    assert!(validate(&RbumRelEnvKind::DatetimeRange, "2000", "1000").is_err());
+    assert!(validate(&RbumRelEnvKind::DatetimeRange, "1000", "").is_err());
+
+    assert!(validate(&RbumRelEnvKind::TimeRange, "09:00", "18:00:30").is_ok());
+    assert!(validate(&RbumRelEnvKind::TimeRange, "22:00", "06:00").is_ok());
+    assert!(validate(&RbumRelEnvKind::TimeRange, "24:00", "06:00").is_err());
+    assert!(validate(&RbumRelEnvKind::TimeRange, "09:00", "").is_err());
+
+    assert!(validate(&RbumRelEnvKind::Ips, "192.168.0.1, 10.0.0.0/8,::1,fe80::/10", "").is_ok());
+    assert!(validate(&RbumRelEnvKind::Ips, "192.168.0.256", "").is_err());
+    assert!(validate(&RbumRelEnvKind::Ips, "10.0.0.0/33", "").is_err());
+    assert!(validate(&RbumRelEnvKind::Ips, " , ", "").is_err());
+}
+
+#[tokio::test]
+pub async fn test_check_time_range() {
+    let now = DateTime::parse_from_rfc3339("2022-01-01T10:00:00Z").unwrap().with_timezone(&Utc);
+    assert!(check_time_range("09:00", "18:00", now, 0).unwrap());
+    assert!(!check_time_range("11:00", "18:00", now, 0).unwrap());
+    assert!(check_time_range("10:00:00", "10:00:00", now, 0).unwrap());
+    // 18:00 at UTC+8
+    assert!(!check_time_range("09:00", "17:59", now, 8 * 3600).unwrap());
+    assert!(check_time_range("18:00", "18:30", now, 8 * 3600).unwrap());
+    // Crossing midnight
+    assert!(check_time_range("22:00", "11:00", now, 0).unwrap());
+    assert!(!check_time_range("22:00", "06:00", now, 0).unwrap());
+    assert!(check_time_range("22:00", "06:00", now, -5 * 3600).unwrap());
+}
+
+#[tokio::test]
+pub async fn test_check_ips() {
+    assert!(check_ips("192.168.0.1", Some("192.168.0.1")).unwrap());
+    assert!(!check_ips("192.168.0.1", Some("192.168.0.2")).unwrap());
+    assert!(check_ips("192.168.0.1,10.0.0.0/8", Some("10.20.30.40")).unwrap());
+    assert!(!check_ips("10.0.0.0/8", Some("11.0.0.1")).unwrap());
+    assert!(check_ips("0.0.0.0/0", Some("8.8.8.8")).unwrap());
+    assert!(check_ips("10.0.0.0/8", Some("::ffff:10.0.0.1")).unwrap());
+    assert!(check_ips("fe80::/10", Some("fe80::1")).unwrap());
+    assert!(!check_ips("fe80::/10", Some("10.0.0.1")).unwrap());
+    assert!(!check_ips("10.0.0.0/8", None).unwrap());
+    assert!(!check_ips("10.0.0.0/8", Some("unknown")).unwrap());
+}
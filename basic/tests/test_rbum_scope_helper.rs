@@ -1,6 +1,9 @@
 use tardis::tokio;
 
-use bios_basic::rbum::helper::rbum_scope_helper::get_pre_paths;
+use bios_basic::rbum::dto::rbum_filer_dto::RbumBasicFilterReq;
+use bios_basic::rbum::helper::rbum_scope_helper::{check_scope, get_all_pre_paths, get_pre_paths, get_scope_level_by_context};
+use bios_basic::rbum::rbum_enumeration::RbumScopeLevelKind;
+use tardis::basic::dto::TardisContext;
 
 #[tokio::test]
 pub async fn test_get_pre_paths() {
@@ -20,4 +23,53 @@ pub async fn test_get_pre_paths() {
     assert_eq!(get_pre_paths(3, "aaaa"), None);
     assert_eq!(get_pre_paths(3, "aaaa/bbbb"), None);
     assert_eq!(get_pre_paths(3, "aaaa/bbbb/cccc"), Some("aaaa/bbbb/cccc".to_string()));
+
+    assert_eq!(get_pre_paths(4, "aaaa/bbbb/cccc"), None);
+    assert_eq!(get_pre_paths(4, "aaaa/bbbb/cccc/dddd/eeee"), Some("aaaa/bbbb/cccc/dddd".to_string()));
+}
+
+#[tokio::test]
+pub async fn test_get_all_pre_paths() {
+    assert!(get_all_pre_paths("").is_empty());
+    assert_eq!(get_all_pre_paths("aaaa/"), vec!["aaaa".to_string()]);
+    assert_eq!(
+        get_all_pre_paths("aaaa/bbbb/cccc/dddd"),
+        vec!["aaaa".to_string(), "aaaa/bbbb".to_string(), "aaaa/bbbb/cccc".to_string(), "aaaa/bbbb/cccc/dddd".to_string()]
+    );
+}
+
+#[tokio::test]
+pub async fn test_check_scope() {
+    let ctx = TardisContext {
+        own_paths: "aaaa/bbbb/cccc/dddd".to_string(),
+        ..Default::default()
+    };
+    let filter = RbumBasicFilterReq::default();
+    assert!(check_scope("aaaa/bbbb/cccc/dddd", Some(-1), &filter, &ctx));
+    assert!(!check_scope("aaaa/bbbb/cccc/xxxx", Some(-1), &filter, &ctx));
+
+    assert!(check_scope("", Some(4), &filter, &ctx));
+    assert!(check_scope("aaaa", Some(4), &filter, &ctx));
+    assert!(check_scope("aaaa/bbbb/cccc", Some(4), &filter, &ctx));
+    assert!(check_scope("aaaa/bbbb/cccc/dddd/eeee", Some(4), &filter, &ctx));
+    assert!(!check_scope("aaaa/bbbb/cccc/xxxx", Some(4), &filter, &ctx));
+    assert!(!check_scope("aaaa/xxxx", Some(4), &filter, &ctx));
+
+    assert!(check_scope("aaaa/bbbb/cccc/xxxx", Some(3), &filter, &ctx));
+    assert!(!check_scope("aaaa/xxxx/cccc", Some(3), &filter, &ctx));
+    assert!(!check_scope("", Some(5), &filter, &ctx));
+}
+
+#[tokio::test]
+pub async fn test_scope_level() {
+    assert_eq!(RbumScopeLevelKind::from_int(-1).unwrap(), RbumScopeLevelKind::Private);
+    assert_eq!(RbumScopeLevelKind::from_int(3).unwrap(), RbumScopeLevelKind::L3);
+    assert_eq!(RbumScopeLevelKind::from_int(7).unwrap().to_int(), 7);
+    assert_eq!(RbumScopeLevelKind::from_int(-2).unwrap_err().code, "406-rbum-*-enum-init-error");
+
+    let ctx = TardisContext {
+        own_paths: "aaaa/bbbb/cccc/dddd/eeee/ffff/gggg".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(get_scope_level_by_context(&ctx).unwrap().to_int(), 6);
 }
@@ -2,7 +2,7 @@ use bios_basic::rbum::dto::rbum_domain_dto::RbumDomainAddReq;
 use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumSetCateFilterReq, RbumSetFilterReq, RbumSetItemFilterReq, RbumSetTreeFilterReq};
 use bios_basic::rbum::dto::rbum_item_dto::RbumItemAddReq;
 use bios_basic::rbum::dto::rbum_kind_dto::RbumKindAddReq;
-use bios_basic::rbum::dto::rbum_set_cate_dto::{RbumSetCateAddReq, RbumSetCateModifyReq};
+use bios_basic::rbum::dto::rbum_set_cate_dto::{RbumSetCateAddReq, RbumSetCateModifyReq, RbumSetCateMoveReq};
 use bios_basic::rbum::dto::rbum_set_dto::{RbumSetAddReq, RbumSetModifyReq};
 use bios_basic::rbum::dto::rbum_set_item_dto::{RbumSetItemAddReq, RbumSetItemModifyReq};
 use bios_basic::rbum::rbum_enumeration::{RbumScopeLevelKind, RbumSetCateLevelQueryKind};
@@ -63,6 +63,7 @@ async fn test_rbum_set(context: &TardisContext) -> TardisResult<()> {
             scope_level: None,
             ext: None,
             disabled: None,
+            version: None,
         },
         &funs,
         context,
@@ -115,6 +116,7 @@ async fn test_rbum_set_cate(context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             ext_table_name: None,
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::L2),
         },
         &funs,
@@ -322,11 +324,28 @@ async fn test_rbum_set_cate(context: &TardisContext) -> TardisResult<()> {
             sort: None,
             ext: None,
             scope_level: None,
+            version: None,
         },
         &funs,
         context,
     )
     .await?;
+    assert!(RbumSetCateServ::modify_rbum(
+        &l2_1_2_id,
+        &mut RbumSetCateModifyReq {
+            bus_code: None,
+            name: Some(TrimString("l2_1_2_stale".to_string())),
+            icon: None,
+            sort: None,
+            ext: None,
+            scope_level: None,
+            version: Some(rbum.version),
+        },
+        &funs,
+        context,
+    )
+    .await
+    .is_err());
 
     info!("【test_rbum_set_cate】 : Test Find : RbumSetCateServ::paginate_rbums");
     let rbums = RbumSetCateServ::paginate_rbums(
@@ -721,6 +740,65 @@ async fn test_rbum_set_cate(context: &TardisContext) -> TardisResult<()> {
     assert_eq!(rbums.main[0].id, l2_1_1_id);
     assert_eq!(rbums.main[1].id, l2_1_2_id);
 
+    info!("【test_rbum_set_cate】 : Test Move : RbumSetCateServ::move_rbum");
+    assert!(RbumSetCateServ::move_rbum(
+        &l2_id,
+        &RbumSetCateMoveReq {
+            rbum_parent_cate_id: Some(l2_1_1_id.clone()),
+            sort: None,
+            version: None,
+        },
+        &funs,
+        context,
+    )
+    .await
+    .is_err());
+    RbumSetCateServ::move_rbum(
+        &l2_1_id,
+        &RbumSetCateMoveReq {
+            rbum_parent_cate_id: Some(l3_id.clone()),
+            sort: Some(1),
+            version: None,
+        },
+        &funs,
+        context,
+    )
+    .await?;
+    let l2_1 = RbumSetCateServ::get_rbum(&l2_1_id, &RbumSetCateFilterReq::default(), &funs, context).await?;
+    assert_eq!(l2_1.sort, 1);
+    assert_eq!(l2_1.sys_code.len(), 8);
+    assert!(l2_1.sys_code.starts_with(&l3_sys_code));
+    let l2_1_1 = RbumSetCateServ::get_rbum(&l2_1_1_id, &RbumSetCateFilterReq::default(), &funs, context).await?;
+    assert_eq!(l2_1_1.sys_code.len(), 12);
+    assert!(l2_1_1.sys_code.starts_with(&l2_1.sys_code));
+    let set_items = RbumSetItemServ::find_rbums(
+        &RbumSetItemFilterReq {
+            rel_rbum_set_cate_ids: Some(vec![l2_1_id.clone(), l2_1_1_id.clone()]),
+            ..Default::default()
+        },
+        None,
+        None,
+        &funs,
+        context,
+    )
+    .await?;
+    assert_eq!(set_items.len(), 2);
+    assert!(set_items.iter().all(|item| item.rel_rbum_set_cate_sys_code.starts_with(&l2_1.sys_code)));
+    let rbums = RbumSetServ::get_tree(
+        &set_id,
+        &RbumSetTreeFilterReq {
+            fetch_cate_item: true,
+            sys_codes: Some(vec![l2_sys_code.clone()]),
+            sys_code_query_kind: Some(RbumSetCateLevelQueryKind::Sub),
+            sys_code_query_depth: Some(1),
+            ..Default::default()
+        },
+        &funs,
+        context,
+    )
+    .await?;
+    assert!(rbums.main.is_empty());
+
     info!("【test_rbum_set_cate】 : Test Delete : RbumSetCateServ::delete_rbum");
     assert!(RbumSetCateServ::delete_rbum(&l2_1_id, &funs, context).await.is_err());
     RbumSetCateServ::delete_rbum(&l2_1_2_id, &funs, context).await?;
@@ -744,6 +822,7 @@ async fn test_rbum_set_item(context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             ext_table_name: None,
+            soft_delete_retention_sec: None,
             scope_level: Some(RbumScopeLevelKind::L2),
         },
         &funs,
@@ -394,6 +394,126 @@ pub async fn test() -> TardisResult<()> {
     );
     assert_eq!(rbums.len(), 16);
 
+    info!("【test_scope】 : Test Deep Level");
+    let s4 = TardisContext {
+        own_paths: format!("{}/{}", s3.own_paths, TardisFuns::field.nanoid_len(4)),
+        ak: "".to_string(),
+        owner: "".to_string(),
+        roles: vec![],
+        groups: vec![],
+        ..Default::default()
+    };
+
+    RbumDomainServ::add_rbum(
+        &mut RbumDomainAddReq {
+            code: TrimString("s0-l4".to_string()),
+            name: TrimString("scope_test_deep_s0-l4".to_string()),
+            note: None,
+            icon: None,
+            sort: None,
+            scope_level: Some(RbumScopeLevelKind::from_int(4)?),
+        },
+        &funs,
+        &s0,
+    )
+    .await?;
+
+    RbumDomainServ::add_rbum(
+        &mut RbumDomainAddReq {
+            code: TrimString("s1-l4".to_string()),
+            name: TrimString("scope_test_deep_s1-l4".to_string()),
+            note: None,
+            icon: None,
+            sort: None,
+            scope_level: Some(RbumScopeLevelKind::from_int(4)?),
+        },
+        &funs,
+        &s1,
+    )
+    .await?;
+
+    RbumDomainServ::add_rbum(
+        &mut RbumDomainAddReq {
+            code: TrimString("s3-l4".to_string()),
+            name: TrimString("scope_test_deep_s3-l4".to_string()),
+            note: None,
+            icon: None,
+            sort: None,
+            scope_level: Some(RbumScopeLevelKind::from_int(4)?),
+        },
+        &funs,
+        &s3,
+    )
+    .await?;
+
+    RbumDomainServ::add_rbum(
+        &mut RbumDomainAddReq {
+            code: TrimString("s4-l4".to_string()),
+            name: TrimString("scope_test_deep_s4-l4".to_string()),
+            note: None,
+            icon: None,
+            sort: None,
+            scope_level: Some(RbumScopeLevelKind::from_int(4)?),
+        },
+        &funs,
+        &s4,
+    )
+    .await?;
+
+    RbumDomainServ::add_rbum(
+        &mut RbumDomainAddReq {
+            code: TrimString("s4-l3".to_string()),
+            name: TrimString("scope_test_deep_s4-l3".to_string()),
+            note: None,
+            icon: None,
+            sort: None,
+            scope_level: Some(RbumScopeLevelKind::L3),
+        },
+        &funs,
+        &s4,
+    )
+    .await?;
+
+    let rbums = RbumDomainServ::find_rbums(
+        &RbumBasicFilterReq {
+            name: Some("scope_test_deep_".to_string()),
+            ..Default::default()
+        },
+        None,
+        None,
+        &funs,
+        &s4,
+    )
+    .await?;
+    assert_eq!(rbums.len(), 5);
+
+    let rbums = RbumDomainServ::find_rbums(
+        &RbumBasicFilterReq {
+            name: Some("scope_test_deep_".to_string()),
+            ..Default::default()
+        },
+        None,
+        None,
+        &funs,
+        &s3,
+    )
+    .await?;
+    assert_eq!(rbums.len(), 2);
+
+    let rbums = RbumDomainServ::find_rbums(
+        &RbumBasicFilterReq {
+            name: Some("scope_test_deep_".to_string()),
+            with_sub_own_paths: true,
+            ..Default::default()
+        },
+        None,
+        None,
+        &funs,
+        &s3,
+    )
+    .await?;
+    assert_eq!(rbums.len(), 5);
+
     funs.rollback().await?;
 
     Ok(())
@@ -0,0 +1,216 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tardis::basic::dto::TardisContext;
+use tardis::basic::error::TardisError;
+use tardis::basic::result::TardisResult;
+use tardis::log::info;
+use tardis::serde_json::{json, Value as JsonValue};
+use tardis::{tokio, TardisFuns, TardisFunsInst};
+
+use bios_basic::process::task_processor::TaskProcessor;
+use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumTaskFilterReq};
+use bios_basic::rbum::dto::rbum_task_dto::{RbumTaskDetailResp, RbumTaskRetryPolicy};
+use bios_basic::rbum::rbum_enumeration::RbumTaskStatusKind;
+
+pub async fn test(context: &TardisContext) -> TardisResult<()> {
+    let funs = TardisFuns::inst_with_db_conn("".to_string(), None);
+
+    info!("【test_task_processor】 : Test Succeeded : TaskProcessor::execute_task");
+    let task_id = TaskProcessor::execute_task(
+        "test_succeeded",
+        None,
+        |task| async move {
+            task.set_progress(50).await?;
+            Ok(Some(json!({"count": 1})))
+        },
+        &funs,
+        context,
+    )
+    .await?;
+    let task = wait_task_finished(&task_id, &funs, context).await?;
+    assert_eq!(task.kind, "test_succeeded");
+    assert_eq!(task.status, RbumTaskStatusKind::Succeeded);
+    assert_eq!(task.progress, 100);
+    assert_eq!(task.result, r#"{"count":1}"#);
+    assert_eq!(task.own_paths, context.own_paths);
+
+    info!("【test_task_processor】 : Test Retry : TaskProcessor::execute_task");
+    let counter = Arc::new(AtomicUsize::new(0));
+    let process_counter = counter.clone();
+    let task_id = TaskProcessor::execute_task(
+        "test_retry",
+        Some(RbumTaskRetryPolicy {
+            max_retry_times: 2,
+            retry_interval_sec: 0,
+        }),
+        move |_| {
+            let counter = process_counter.clone();
+            async move {
+                if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                    return Err(TardisError::internal_error("first execution error", ""));
+                }
+                Ok(None)
+            }
+        },
+        &funs,
+        context,
+    )
+    .await?;
+    let task = wait_task_finished(&task_id, &funs, context).await?;
+    assert_eq!(task.status, RbumTaskStatusKind::Succeeded);
+    assert_eq!(task.retry_times, 1);
+    assert_eq!(task.max_retry_times, 2);
+    assert!(task.error.contains("first execution error"));
+    assert_eq!(counter.load(Ordering::SeqCst), 2);
+
+    info!("【test_task_processor】 : Test Failed : TaskProcessor::execute_task");
+    let task_id = TaskProcessor::execute_task(
+        "test_failed",
+        Some(RbumTaskRetryPolicy {
+            max_retry_times: 1,
+            retry_interval_sec: 0,
+        }),
+        |_| async move { Err(TardisError::internal_error("execution error", "")) },
+        &funs,
+        context,
+    )
+    .await?;
+    let task = wait_task_finished(&task_id, &funs, context).await?;
+    assert_eq!(task.status, RbumTaskStatusKind::Failed);
+    assert_eq!(task.retry_times, 1);
+    assert!(task.error.contains("execution error"));
+    assert!(TaskProcessor::cancel_task(&task_id, &funs, context).await.is_err());
+
+    let task_id = TaskProcessor::execute_task("test_failed", None, |_| async move { panic_process() }, &funs, context).await?;
+    let task = wait_task_finished(&task_id, &funs, context).await?;
+    assert_eq!(task.status, RbumTaskStatusKind::Failed);
+    assert!(task.error.contains("panic"));
+
+    info!("【test_task_processor】 : Test Cancel : TaskProcessor::cancel_task");
+    let task_id = TaskProcessor::execute_task(
+        "test_cancel",
+        None,
+        |task| async move {
+            while !task.is_cancelled().await? {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            Ok(Some(json!("ignored")))
+        },
+        &funs,
+        context,
+    )
+    .await?;
+    TaskProcessor::cancel_task(&task_id, &funs, context).await?;
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let task = TaskProcessor::get_task(&task_id, &funs, context).await?;
+    assert_eq!(task.status, RbumTaskStatusKind::Cancelled);
+    assert_eq!(task.result, "");
+    assert!(TaskProcessor::cancel_task(&task_id, &funs, context).await.is_err());
+
+    info!("【test_task_processor】 : Test Context : TaskProcessor::execute_task_with_ctx");
+    let ctx = context.clone();
+    TaskProcessor::execute_task_with_ctx("test_ctx", None, |_| async move { Ok(None) }, &funs, &ctx).await?;
+    TaskProcessor::execute_task_with_ctx("test_ctx", None, |_| async move { Ok(None) }, &funs, &ctx).await?;
+    let task_ids = TaskProcessor::get_task_id_with_ctx(&ctx)?.unwrap();
+    assert_eq!(task_ids.split(',').count(), 2);
+    for task_id in task_ids.split(',') {
+        wait_task_finished(task_id, &funs, context).await?;
+    }
+
+    info!("【test_task_processor】 : Test Timeout : TaskProcessor::start_timeout_task");
+    TaskProcessor::start_timeout_task("");
+    let counter = Arc::new(AtomicUsize::new(0));
+    let process_counter = counter.clone();
+    let task_id = TaskProcessor::execute_task(
+        "test_timeout",
+        Some(RbumTaskRetryPolicy {
+            max_retry_times: 1,
+            retry_interval_sec: 6,
+        }),
+        move |_| {
+            let counter = process_counter.clone();
+            async move {
+                // Running and sleeping between the retries both last longer than the timeout
+                if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                    tokio::time::sleep(Duration::from_secs(6)).await;
+                    return Err(TardisError::internal_error("first execution error", ""));
+                }
+                Ok(None)
+            }
+        },
+        &funs,
+        context,
+    )
+    .await?;
+    let task = wait_task_finished(&task_id, &funs, context).await?;
+    assert_eq!(task.status, RbumTaskStatusKind::Succeeded);
+    assert_eq!(task.retry_times, 1);
+    assert_eq!(counter.load(Ordering::SeqCst), 2);
+
+    info!("【test_task_processor】 : Test Find : TaskProcessor::paginate_tasks");
+    let tasks = TaskProcessor::paginate_tasks(
+        &RbumTaskFilterReq {
+            kind: Some("test_failed".to_string()),
+            ..Default::default()
+        },
+        1,
+        10,
+        None,
+        &funs,
+        context,
+    )
+    .await?;
+    assert_eq!(tasks.total_size, 2);
+    assert!(tasks.records.iter().all(|task| task.status == RbumTaskStatusKind::Failed));
+    let tasks = TaskProcessor::find_tasks(
+        &RbumTaskFilterReq {
+            status: Some(RbumTaskStatusKind::Succeeded),
+            ..Default::default()
+        },
+        &funs,
+        context,
+    )
+    .await?;
+    assert_eq!(tasks.len(), 5);
+    assert!(TaskProcessor::get_task(
+        &tasks[0].id,
+        &funs,
+        &TardisContext {
+            own_paths: "xxx".to_string(),
+            ..Default::default()
+        }
+    )
+    .await
+    .is_err());
+    let tasks = TaskProcessor::find_tasks(
+        &RbumTaskFilterReq {
+            basic: RbumBasicFilterReq {
+                own_paths: Some("xxx".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        &funs,
+        context,
+    )
+    .await?;
+    assert!(tasks.is_empty());
+
+    Ok(())
+}
+
+fn panic_process() -> TardisResult<Option<JsonValue>> {
+    panic!("execution panic")
+}
+
+async fn wait_task_finished(task_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<RbumTaskDetailResp> {
+    loop {
+        let task = TaskProcessor::get_task(task_id, funs, ctx).await?;
+        if task.status.is_finished() {
+            return Ok(task);
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
@@ -1,3 +1,4 @@
 pub mod domain;
 pub mod dto;
+pub mod helper;
 pub mod serv;
@@ -1 +1,2 @@
+pub mod reldb_audit;
 pub mod reldb_config;
@@ -0,0 +1,73 @@
+use tardis::basic::dto::TardisContext;
+use tardis::chrono::{self, Utc};
+use tardis::db::reldb_client::TardisActiveModel;
+use tardis::db::sea_orm;
+use tardis::db::sea_orm::sea_query::{ColumnDef, Index, IndexCreateStatement, Table, TableCreateStatement};
+use tardis::db::sea_orm::*;
+
+/// Audit record of the statements executed on the managed instances
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "db_reldb_audit")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub inst_id: String,
+    /// `query` or `execute`
+    pub kind: String,
+    /// Statement with normalized whitespaces and keywords, parameter values are not included
+    pub normalized_sql: String,
+    /// Digest of the parameters
+    pub params_digest: String,
+    pub duration_ms: u64,
+    pub row_count: u64,
+    /// Error message, empty when succeeded
+    pub error: String,
+
+    pub own_paths: String,
+    pub owner: String,
+    pub create_time: chrono::DateTime<Utc>,
+}
+
+impl TardisActiveModel for ActiveModel {
+    fn fill_ctx(&mut self, ctx: &TardisContext, is_insert: bool) {
+        if is_insert {
+            self.own_paths = Set(ctx.own_paths.to_string());
+            self.owner = Set(ctx.owner.to_string());
+        }
+    }
+
+    fn create_table_statement(_: DbBackend) -> TableCreateStatement {
+        Table::create()
+            .table(Entity.table_ref())
+            .if_not_exists()
+            .engine("InnoDB")
+            .character_set("utf8mb4")
+            .collate("utf8mb4_0900_as_cs")
+            .col(ColumnDef::new(Column::Id).not_null().string().primary_key())
+            // Specific
+            .col(ColumnDef::new(Column::InstId).not_null().string())
+            .col(ColumnDef::new(Column::Kind).not_null().string())
+            .col(ColumnDef::new(Column::NormalizedSql).not_null().text())
+            .col(ColumnDef::new(Column::ParamsDigest).not_null().string())
+            .col(ColumnDef::new(Column::DurationMs).not_null().big_unsigned())
+            .col(ColumnDef::new(Column::RowCount).not_null().big_unsigned())
+            .col(ColumnDef::new(Column::Error).not_null().text())
+            // Basic
+            .col(ColumnDef::new(Column::OwnPaths).not_null().string())
+            .col(ColumnDef::new(Column::Owner).not_null().string())
+            .col(ColumnDef::new(Column::CreateTime).extra("DEFAULT CURRENT_TIMESTAMP".to_string()).timestamp())
+            .to_owned()
+    }
+
+    fn create_index_statement() -> Vec<IndexCreateStatement> {
+        vec![
+            Index::create().name(&format!("idx-{}-{}", Entity.table_name(), Column::InstId.to_string())).table(Entity).col(Column::InstId).col(Column::CreateTime).to_owned(),
+            Index::create().name(&format!("idx-{}-{}", Entity.table_name(), Column::Owner.to_string())).table(Entity).col(Column::Owner).col(Column::CreateTime).to_owned(),
+        ]
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
@@ -10,6 +10,10 @@ pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: String,
     pub icon: String,
+    // comma separated allow-list of tables, e.g. `t1,s1.t2,s2.*`
+    pub allowed_tables: String,
+    // 0 means the default timeout in the component config
+    pub statement_timeout_sec: u32,
     pub ext1_idx: String,
     pub ext2_idx: String,
     pub ext3_idx: String,
@@ -39,6 +43,8 @@ impl TardisActiveModel for ActiveModel {
             .collate("utf8mb4_0900_as_cs")
             .col(ColumnDef::new(Column::Id).not_null().string().primary_key())
             .col(ColumnDef::new(Column::Icon).not_null().string())
+            .col(ColumnDef::new(Column::AllowedTables).not_null().text())
+            .col(ColumnDef::new(Column::StatementTimeoutSec).not_null().unsigned())
             .col(ColumnDef::new(Column::Ext1Idx).not_null().string())
             .col(ColumnDef::new(Column::Ext2Idx).not_null().string())
             .col(ColumnDef::new(Column::Ext3Idx).not_null().string())
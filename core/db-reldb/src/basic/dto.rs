@@ -1,2 +1,3 @@
+pub mod reldb_audit_dto;
 pub mod reldb_config_dto;
 pub mod reldb_process_dto;
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use tardis::chrono::{DateTime, Utc};
+use tardis::db::sea_orm;
+use tardis::web::poem_openapi;
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct RelDbAuditFilterReq {
+    pub inst_id: Option<String>,
+    pub owner: Option<String>,
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+}
+
+#[derive(poem_openapi::Object, sea_orm::FromQueryResult, Serialize, Deserialize, Debug)]
+pub struct RelDbAuditSummaryResp {
+    pub id: String,
+    pub inst_id: String,
+    pub kind: String,
+    pub normalized_sql: String,
+    pub params_digest: String,
+    pub duration_ms: u64,
+    pub row_count: u64,
+    pub error: String,
+
+    pub own_paths: String,
+    pub owner: String,
+    pub create_time: DateTime<Utc>,
+}
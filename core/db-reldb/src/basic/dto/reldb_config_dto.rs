@@ -4,6 +4,7 @@ use tardis::chrono::{DateTime, Utc};
 use tardis::db::sea_orm;
 use tardis::web::poem_openapi;
 
+use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumItemFilterFetcher, RbumItemRelFilterReq};
 use bios_basic::rbum::rbum_enumeration::RbumScopeLevelKind;
 
 #[derive(poem_openapi::Object, Serialize, Deserialize, Debug)]
@@ -18,6 +19,69 @@ pub struct RelDbConfigAggAddOrModifyReq {
     pub connect_uri: TrimString,
 
     pub icon: Option<String>,
+    pub allowed_tables: Option<Vec<String>>,
+    #[oai(validator(maximum(value = "3600")))]
+    pub statement_timeout_sec: Option<u32>,
+
+    // e.g. `L1` shares a tenant config with all apps of the tenant
+    pub scope_level: Option<RbumScopeLevelKind>,
+    pub disabled: Option<bool>,
+    // version of the config that the modification is based on, ignored when adding
+    pub version: Option<u32>,
+}
+
+#[derive(poem_openapi::Object, Serialize, Deserialize, Debug)]
+pub struct RelDbConfigConnTestReq {
+    #[oai(validator(min_length = "2", max_length = "255"))]
+    pub cert_user_name: TrimString,
+    #[oai(validator(min_length = "2", max_length = "255"))]
+    pub cert_password: TrimString,
+    #[oai(validator(min_length = "2", max_length = "2048"))]
+    pub connect_uri: TrimString,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RelDbConfigAddReq {
+    pub name: TrimString,
+    pub scope_level: Option<RbumScopeLevelKind>,
+    pub disabled: Option<bool>,
+
+    pub icon: Option<String>,
+    pub allowed_tables: String,
+    pub statement_timeout_sec: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RelDbConfigModifyReq {
+    pub name: Option<TrimString>,
+    pub scope_level: Option<RbumScopeLevelKind>,
+    pub disabled: Option<bool>,
+
+    pub icon: Option<String>,
+    pub allowed_tables: Option<String>,
+    pub statement_timeout_sec: Option<u32>,
+
+    pub version: Option<u32>,
+}
+
+#[derive(poem_openapi::Object, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct RelDbConfigFilterReq {
+    pub basic: RbumBasicFilterReq,
+    pub rel: Option<RbumItemRelFilterReq>,
+    pub rel2: Option<RbumItemRelFilterReq>,
+}
+
+impl RbumItemFilterFetcher for RelDbConfigFilterReq {
+    fn basic(&self) -> &RbumBasicFilterReq {
+        &self.basic
+    }
+    fn rel(&self) -> &Option<RbumItemRelFilterReq> {
+        &self.rel
+    }
+    fn rel2(&self) -> &Option<RbumItemRelFilterReq> {
+        &self.rel2
+    }
 }
 
 #[derive(poem_openapi::Object, sea_orm::FromQueryResult, Serialize, Deserialize, Debug)]
@@ -46,6 +110,7 @@ pub struct RelDbConfigDetailResp {
     pub owner_name: Option<String>,
     pub create_time: DateTime<Utc>,
     pub update_time: DateTime<Utc>,
+    pub version: u32,
 
     pub scope_level: RbumScopeLevelKind,
     pub disabled: bool,
@@ -63,12 +128,16 @@ pub struct RelDbConfigAggResp {
     pub owner_name: Option<String>,
     pub create_time: DateTime<Utc>,
     pub update_time: DateTime<Utc>,
+    pub version: u32,
 
     pub scope_level: RbumScopeLevelKind,
     pub disabled: bool,
 
     pub icon: String,
 
+    pub allowed_tables: Vec<String>,
+    pub statement_timeout_sec: u32,
+
     pub cert_user_name: String,
     pub cert_password: String,
     pub connect_uri: String,
@@ -1,13 +1,40 @@
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
+use tardis::chrono::{DateTime, Utc};
+use tardis::serde_json::Value;
 use tardis::web::poem_openapi;
 
+use crate::reldb_enumeration::RelDbColumnDataTypeKind;
+
 #[derive(poem_openapi::Object, Serialize, Deserialize, Debug)]
 pub struct RelDbQueryReq {
     #[oai(validator(min_length = "2"))]
     pub sql: String,
     pub params: HashMap<String, String>,
+    #[oai(validator(minimum(value = "1")))]
+    pub page_number: Option<u64>,
+    #[oai(validator(minimum(value = "1")))]
+    pub page_size: Option<u64>,
+}
+
+#[derive(poem_openapi::Object, Serialize, Deserialize, Debug)]
+pub struct RelDbQueryResp {
+    pub columns: Vec<RelDbColumnResp>,
+    // values of each row, in the same order as columns
+    pub records: Vec<Vec<Value>>,
+    pub page_number: u64,
+    pub page_size: u64,
+    // whether there are more rows after this page
+    pub has_more: bool,
+}
+
+#[derive(poem_openapi::Object, Serialize, Deserialize, Debug)]
+pub struct RelDbColumnResp {
+    pub name: String,
+    pub data_type: RelDbColumnDataTypeKind,
+    // type name reported by the driver
+    pub db_type: String,
 }
 
 #[derive(poem_openapi::Object, Serialize, Deserialize, Debug)]
@@ -16,3 +43,12 @@ pub struct RelDbExecuteReq {
     pub sql: String,
     pub params: HashMap<String, String>,
 }
+
+#[derive(poem_openapi::Object, Serialize, Deserialize, Debug)]
+pub struct RelDbPoolHealthResp {
+    pub inst_id: String,
+    pub healthy: bool,
+    pub message: Option<String>,
+    pub opened_time: DateTime<Utc>,
+    pub last_used_time: DateTime<Utc>,
+}
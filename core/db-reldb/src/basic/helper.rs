@@ -0,0 +1,2 @@
+pub mod reldb_crypto_helper;
+pub mod reldb_sql_helper;
@@ -0,0 +1,72 @@
+//! Encryption of the stored connection passwords.
+//!
+//! Encrypted values have the format `<key id>:<iv>:<cipher>`, the key id is the prefix of the key digest,
+//! so that values encrypted with an old key can still be decrypted during a key rotation.
+use tardis::basic::result::TardisResult;
+use tardis::{TardisFuns, TardisFunsInst};
+
+use crate::reldb_config::RelDbConfig;
+
+const OBJ_NAME: &str = "reldb_crypto";
+const KEY_ID_LEN: usize = 8;
+const IV_LEN: usize = 16;
+pub const MASKED_PASSWORD: &str = "******";
+
+/// Check the configured keys, the component refuses to start without a valid current key.
+pub fn check_password_crypto_keys(funs: &TardisFunsInst) -> TardisResult<()> {
+    let conf = funs.conf::<RelDbConfig>();
+    if conf.password_crypto_key.is_empty() {
+        return Err(funs.err().internal_error(OBJ_NAME, "check", "password_crypto_key is not configured", "500-reldb-password-crypto-key-missing"));
+    }
+    for key in std::iter::once(&conf.password_crypto_key).chain(conf.password_crypto_old_keys.iter()) {
+        if ![16, 24, 32].contains(&key.len()) {
+            return Err(funs.err().internal_error(
+                OBJ_NAME,
+                "check",
+                "the length of the password crypto key must be 16, 24 or 32",
+                "500-reldb-password-crypto-key-invalid",
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn encrypt_password(password: &str, funs: &TardisFunsInst) -> TardisResult<String> {
+    let key = funs.conf::<RelDbConfig>().password_crypto_key.clone();
+    let iv = TardisFuns::field.nanoid_len(IV_LEN);
+    let cipher = TardisFuns::crypto.aes.encrypt_cbc(password, &key, &iv)?;
+    Ok(format!("{}:{}:{}", get_key_id(&key)?, iv, cipher))
+}
+
+pub fn decrypt_password(encrypted_password: &str, funs: &TardisFunsInst) -> TardisResult<String> {
+    let (key_id, iv, cipher) =
+        parse(encrypted_password).ok_or_else(|| funs.err().internal_error(OBJ_NAME, "decrypt", "invalid format of encrypted password", "500-reldb-password-decrypt-error"))?;
+    let conf = funs.conf::<RelDbConfig>();
+    for key in std::iter::once(&conf.password_crypto_key).chain(conf.password_crypto_old_keys.iter()) {
+        if get_key_id(key)? == key_id {
+            return TardisFuns::crypto.aes.decrypt_cbc(cipher, key, iv);
+        }
+    }
+    Err(funs.err().internal_error(OBJ_NAME, "decrypt", &format!("not found crypto key {}", key_id), "500-reldb-password-decrypt-error"))
+}
+
+/// Whether the value is encrypted with the current key.
+pub fn is_encrypted_with_current_key(encrypted_password: &str, funs: &TardisFunsInst) -> TardisResult<bool> {
+    if let Some((key_id, _, _)) = parse(encrypted_password) {
+        Ok(get_key_id(&funs.conf::<RelDbConfig>().password_crypto_key)? == key_id)
+    } else {
+        Ok(false)
+    }
+}
+
+fn parse(encrypted_password: &str) -> Option<(&str, &str, &str)> {
+    let mut parts = encrypted_password.splitn(3, ':');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(key_id), Some(iv), Some(cipher)) if key_id.len() == KEY_ID_LEN && iv.len() == IV_LEN && !cipher.is_empty() => Some((key_id, iv, cipher)),
+        _ => None,
+    }
+}
+
+fn get_key_id(key: &str) -> TardisResult<String> {
+    Ok(TardisFuns::crypto.digest.sha256(key)?[..KEY_ID_LEN].to_string())
+}
@@ -0,0 +1,104 @@
+//! SQL guard of the interface console.
+//!
+//! Each payload is parsed with the dialect of the target instance and must contain exactly one statement:
+//!
+//! | api       | accepted statements        |
+//! | --------- | -------------------------- |
+//! | `query`   | SELECT (including UNION, WITH) |
+//! | `execute` | INSERT, UPDATE, DELETE     |
+//!
+//! DDL and other statements are always rejected.
+//! When the config declares an allow-list, every relation referenced by the statement must match one of its entries:
+//! `table`, `schema.table` or `schema.*`.
+use std::ops::ControlFlow;
+
+use sqlparser::ast::{visit_relations, ObjectName, Statement};
+use sqlparser::dialect::{Dialect, GenericDialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect};
+use sqlparser::parser::Parser;
+use tardis::basic::result::TardisResult;
+use tardis::db::sea_orm::DbBackend;
+use tardis::TardisFunsInst;
+
+const OBJ_NAME: &str = "reldb_sql";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RelDbSqlKind {
+    Read,
+    Dml,
+    Ddl,
+}
+
+pub fn check_query(sql: &str, allowed_tables: &[String], backend: DbBackend, funs: &TardisFunsInst) -> TardisResult<()> {
+    check(sql, RelDbSqlKind::Read, allowed_tables, backend, funs)
+}
+
+pub fn check_execute(sql: &str, allowed_tables: &[String], backend: DbBackend, funs: &TardisFunsInst) -> TardisResult<()> {
+    check(sql, RelDbSqlKind::Dml, allowed_tables, backend, funs)
+}
+
+fn check(sql: &str, expected_kind: RelDbSqlKind, allowed_tables: &[String], backend: DbBackend, funs: &TardisFunsInst) -> TardisResult<()> {
+    let dialect: Box<dyn Dialect> = match backend {
+        DbBackend::MySql => Box::new(MySqlDialect {}),
+        DbBackend::Postgres => Box::new(PostgreSqlDialect {}),
+        DbBackend::Sqlite => Box::new(SQLiteDialect {}),
+        #[allow(unreachable_patterns)]
+        _ => Box::new(GenericDialect {}),
+    };
+    let statements =
+        Parser::parse_sql(dialect.as_ref(), sql).map_err(|e| funs.err().forbidden(OBJ_NAME, "check", &format!("sql parse error: {}", e), "403-reldb-sql-unparsable"))?;
+    if statements.len() != 1 {
+        return Err(funs.err().forbidden(OBJ_NAME, "check", "only one statement is allowed", "403-reldb-sql-multi-statement"));
+    }
+    let statement = &statements[0];
+    let kind = classify(statement);
+    if kind != expected_kind {
+        return Err(match expected_kind {
+            RelDbSqlKind::Read => funs.err().forbidden(OBJ_NAME, "check", "only read statements are allowed", "403-reldb-sql-not-read"),
+            _ => funs.err().forbidden(OBJ_NAME, "check", "only insert, update and delete statements are allowed", "403-reldb-sql-not-dml"),
+        });
+    }
+    if allowed_tables.is_empty() {
+        return Ok(());
+    }
+    // Common table expressions are referenced as relations too
+    let cte_names = if let Statement::Query(query) = statement {
+        query.with.as_ref().map(|with| with.cte_tables.iter().map(|cte| cte.alias.name.value.to_lowercase()).collect::<Vec<String>>()).unwrap_or_default()
+    } else {
+        vec![]
+    };
+    let not_allowed = visit_relations(statement, |relation| {
+        if (relation.0.len() == 1 && cte_names.contains(&relation.0[0].value.to_lowercase())) || is_allowed(relation, allowed_tables) {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(relation.to_string())
+        }
+    });
+    if let ControlFlow::Break(relation) = not_allowed {
+        return Err(funs.err().forbidden(OBJ_NAME, "check", &format!("table {} is not allowed", relation), "403-reldb-sql-table-not-allowed"));
+    }
+    Ok(())
+}
+
+pub fn classify(statement: &Statement) -> RelDbSqlKind {
+    match statement {
+        Statement::Query(_) => RelDbSqlKind::Read,
+        Statement::Insert { .. } | Statement::Update { .. } | Statement::Delete { .. } => RelDbSqlKind::Dml,
+        _ => RelDbSqlKind::Ddl,
+    }
+}
+
+fn is_allowed(relation: &ObjectName, allowed_tables: &[String]) -> bool {
+    let idents = relation.0.iter().map(|ident| ident.value.to_lowercase()).collect::<Vec<String>>();
+    let full_name = idents.join(".");
+    let table_name = idents.last().cloned().unwrap_or_default();
+    let schema_name = if idents.len() > 1 { Some(idents[idents.len() - 2].clone()) } else { None };
+    allowed_tables.iter().map(|allowed| allowed.trim().to_lowercase()).any(|allowed| {
+        if let Some(schema) = allowed.strip_suffix(".*") {
+            schema_name.as_deref() == Some(schema)
+        } else if allowed.contains('.') {
+            allowed == full_name
+        } else {
+            schema_name.is_none() && allowed == table_name
+        }
+    })
+}
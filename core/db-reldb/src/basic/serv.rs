@@ -1 +1,4 @@
-
+pub mod reldb_audit_serv;
+pub mod reldb_config_serv;
+pub mod reldb_pool_serv;
+pub mod reldb_process_serv;
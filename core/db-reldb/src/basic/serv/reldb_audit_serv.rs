@@ -0,0 +1,105 @@
+use std::collections::{BTreeMap, HashMap};
+
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
+use tardis::basic::dto::TardisContext;
+use tardis::basic::result::TardisResult;
+use tardis::db::sea_orm::sea_query::{Expr, Order, Query};
+use tardis::db::sea_orm::*;
+use tardis::web::web_resp::TardisPage;
+use tardis::{TardisFuns, TardisFunsInst};
+
+use crate::basic::domain::reldb_audit;
+use crate::basic::dto::reldb_audit_dto::{RelDbAuditFilterReq, RelDbAuditSummaryResp};
+
+pub struct RelDbAuditServ;
+
+impl RelDbAuditServ {
+    pub async fn add_audit(
+        inst_id: &str,
+        kind: &str,
+        sql: &str,
+        params: &HashMap<String, String>,
+        duration_ms: u64,
+        row_count: u64,
+        error: Option<String>,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<()> {
+        // Sort the parameters to get a stable digest
+        let params = params.iter().collect::<BTreeMap<&String, &String>>();
+        funs.db()
+            .insert_one(
+                reldb_audit::ActiveModel {
+                    id: Set(TardisFuns::field.nanoid()),
+                    inst_id: Set(inst_id.to_string()),
+                    kind: Set(kind.to_string()),
+                    normalized_sql: Set(Self::normalize_sql(sql)),
+                    params_digest: Set(TardisFuns::crypto.digest.sha256(&TardisFuns::json.obj_to_string(&params)?)?),
+                    duration_ms: Set(duration_ms),
+                    row_count: Set(row_count),
+                    error: Set(error.unwrap_or_default()),
+                    ..Default::default()
+                },
+                ctx,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Find the audit records of the current and sub own paths.
+    pub async fn paginate_audits(
+        filter: &RelDbAuditFilterReq,
+        page_number: u64,
+        page_size: u64,
+        desc_by_create: Option<bool>,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<TardisPage<RelDbAuditSummaryResp>> {
+        let mut query = Query::select();
+        query
+            .columns(vec![
+                reldb_audit::Column::Id,
+                reldb_audit::Column::InstId,
+                reldb_audit::Column::Kind,
+                reldb_audit::Column::NormalizedSql,
+                reldb_audit::Column::ParamsDigest,
+                reldb_audit::Column::DurationMs,
+                reldb_audit::Column::RowCount,
+                reldb_audit::Column::Error,
+                reldb_audit::Column::OwnPaths,
+                reldb_audit::Column::Owner,
+                reldb_audit::Column::CreateTime,
+            ])
+            .from(reldb_audit::Entity)
+            .and_where(Expr::col(reldb_audit::Column::OwnPaths).like(format!("{}%", ctx.own_paths).as_str()));
+        if let Some(inst_id) = &filter.inst_id {
+            query.and_where(Expr::col(reldb_audit::Column::InstId).eq(inst_id.as_str()));
+        }
+        if let Some(owner) = &filter.owner {
+            query.and_where(Expr::col(reldb_audit::Column::Owner).eq(owner.as_str()));
+        }
+        if let Some(start_time) = filter.start_time {
+            query.and_where(Expr::col(reldb_audit::Column::CreateTime).gte(start_time));
+        }
+        if let Some(end_time) = filter.end_time {
+            query.and_where(Expr::col(reldb_audit::Column::CreateTime).lte(end_time));
+        }
+        query.order_by(reldb_audit::Column::CreateTime, if desc_by_create.unwrap_or(true) { Order::Desc } else { Order::Asc });
+        let (records, total_size) = funs.db().paginate_dtos(&query, page_number, page_size).await?;
+        Ok(TardisPage {
+            page_size,
+            page_number,
+            total_size,
+            records,
+        })
+    }
+
+    /// Re-render the statement to unify whitespaces and keyword cases, fall back to collapsing whitespaces.
+    fn normalize_sql(sql: &str) -> String {
+        match Parser::parse_sql(&GenericDialect {}, sql) {
+            Ok(statements) if !statements.is_empty() => statements.iter().map(|statement| statement.to_string()).collect::<Vec<String>>().join("; "),
+            _ => sql.split_whitespace().collect::<Vec<&str>>().join(" "),
+        }
+    }
+}
@@ -0,0 +1,430 @@
+use async_trait::async_trait;
+use tardis::basic::dto::TardisContext;
+use tardis::basic::field::TrimString;
+use tardis::basic::result::TardisResult;
+use tardis::db::sea_orm;
+use tardis::db::sea_orm::sea_query::{Expr, Query, SelectStatement};
+use tardis::db::sea_orm::*;
+use tardis::log::info;
+use tardis::web::web_resp::TardisPage;
+use tardis::TardisFunsInst;
+
+use bios_basic::rbum::dto::rbum_cert_conf_dto::RbumCertConfAddReq;
+use bios_basic::rbum::dto::rbum_cert_dto::{RbumCertAddReq, RbumCertModifyReq};
+use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumCertConfFilterReq, RbumCertFilterReq};
+use bios_basic::rbum::dto::rbum_item_dto::{RbumItemKernelAddReq, RbumItemModifyReq};
+use bios_basic::rbum::rbum_enumeration::{RbumCertRelKind, RbumCertStatusKind};
+use bios_basic::rbum::serv::rbum_cert_serv::{RbumCertConfServ, RbumCertServ};
+use bios_basic::rbum::serv::rbum_crud_serv::RbumCrudOperation;
+use bios_basic::rbum::serv::rbum_item_serv::{RbumItemCrudOperation, RbumItemServ};
+
+use crate::basic::domain::reldb_config;
+use crate::basic::dto::reldb_config_dto::{
+    RelDbConfigAddReq, RelDbConfigAggAddOrModifyReq, RelDbConfigAggResp, RelDbConfigConnTestReq, RelDbConfigDetailResp, RelDbConfigFilterReq, RelDbConfigModifyReq,
+    RelDbConfigSummaryResp,
+};
+use crate::basic::helper::reldb_crypto_helper;
+use crate::basic::serv::reldb_pool_serv::RelDbPoolServ;
+use crate::reldb_config::RelDbBasicInfoManager;
+use crate::reldb_constants;
+
+pub struct RelDbConfigServ;
+
+#[async_trait]
+impl RbumItemCrudOperation<reldb_config::ActiveModel, RelDbConfigAddReq, RelDbConfigModifyReq, RelDbConfigSummaryResp, RelDbConfigDetailResp, RelDbConfigFilterReq>
+    for RelDbConfigServ
+{
+    fn get_ext_table_name() -> &'static str {
+        reldb_config::Entity.table_name()
+    }
+
+    fn get_rbum_kind_id() -> String {
+        RelDbBasicInfoManager::get_config(|conf| conf.kind_config_id.clone())
+    }
+
+    fn get_rbum_domain_id() -> String {
+        RelDbBasicInfoManager::get_config(|conf| conf.domain_reldb_id.clone())
+    }
+
+    async fn package_item_add(add_req: &RelDbConfigAddReq, _: &TardisFunsInst, _: &TardisContext) -> TardisResult<RbumItemKernelAddReq> {
+        Ok(RbumItemKernelAddReq {
+            id: None,
+            code: None,
+            name: add_req.name.clone(),
+            disabled: add_req.disabled,
+            scope_level: add_req.scope_level.clone(),
+        })
+    }
+
+    async fn package_ext_add(id: &str, add_req: &RelDbConfigAddReq, _: &TardisFunsInst, _: &TardisContext) -> TardisResult<reldb_config::ActiveModel> {
+        Ok(reldb_config::ActiveModel {
+            id: Set(id.to_string()),
+            icon: Set(add_req.icon.as_ref().unwrap_or(&"".to_string()).to_string()),
+            allowed_tables: Set(add_req.allowed_tables.to_string()),
+            statement_timeout_sec: Set(add_req.statement_timeout_sec),
+            ext1_idx: Set("".to_string()),
+            ext2_idx: Set("".to_string()),
+            ext3_idx: Set("".to_string()),
+            ext4: Set("".to_string()),
+            ext5: Set("".to_string()),
+            ext6: Set("".to_string()),
+            ext7: Set("".to_string()),
+            ext8: Set("".to_string()),
+            ext9: Set("".to_string()),
+            ..Default::default()
+        })
+    }
+
+    async fn package_item_modify(_: &str, modify_req: &RelDbConfigModifyReq, _: &TardisFunsInst, _: &TardisContext) -> TardisResult<Option<RbumItemModifyReq>> {
+        if modify_req.name.is_none() && modify_req.scope_level.is_none() && modify_req.disabled.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(RbumItemModifyReq {
+            code: None,
+            name: modify_req.name.clone(),
+            scope_level: modify_req.scope_level.clone(),
+            disabled: modify_req.disabled,
+            version: None,
+        }))
+    }
+
+    fn get_modify_version(modify_req: &RelDbConfigModifyReq) -> Option<u32> {
+        modify_req.version
+    }
+
+    async fn package_ext_modify(id: &str, modify_req: &RelDbConfigModifyReq, _: &TardisFunsInst, _: &TardisContext) -> TardisResult<Option<reldb_config::ActiveModel>> {
+        if modify_req.icon.is_none() && modify_req.allowed_tables.is_none() && modify_req.statement_timeout_sec.is_none() {
+            return Ok(None);
+        }
+        let mut reldb_config = reldb_config::ActiveModel {
+            id: Set(id.to_string()),
+            ..Default::default()
+        };
+        if let Some(icon) = &modify_req.icon {
+            reldb_config.icon = Set(icon.to_string());
+        }
+        if let Some(allowed_tables) = &modify_req.allowed_tables {
+            reldb_config.allowed_tables = Set(allowed_tables.to_string());
+        }
+        if let Some(statement_timeout_sec) = modify_req.statement_timeout_sec {
+            reldb_config.statement_timeout_sec = Set(statement_timeout_sec);
+        }
+        Ok(Some(reldb_config))
+    }
+
+    async fn package_ext_query(query: &mut SelectStatement, _: bool, _: &RelDbConfigFilterReq, _: &TardisFunsInst, _: &TardisContext) -> TardisResult<()> {
+        query.column((reldb_config::Entity, reldb_config::Column::Icon));
+        Ok(())
+    }
+}
+
+impl RelDbConfigServ {
+    /// Add the config with the connection credentials, the connection is tested before saving.
+    pub async fn add_config_agg(add_req: &RelDbConfigAggAddOrModifyReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<String> {
+        RelDbPoolServ::test_connection(&add_req.connect_uri.0, &add_req.cert_user_name.0, &add_req.cert_password.0, funs).await?;
+        let inst_id = Self::add_item(
+            &mut RelDbConfigAddReq {
+                name: add_req.name.clone(),
+                scope_level: add_req.scope_level.clone(),
+                disabled: add_req.disabled,
+                icon: add_req.icon.clone(),
+                allowed_tables: Self::package_allowed_tables(add_req.allowed_tables.as_ref().unwrap_or(&vec![])),
+                statement_timeout_sec: add_req.statement_timeout_sec.unwrap_or(0),
+            },
+            funs,
+            ctx,
+        )
+        .await?;
+        // Each config has its own cert conf, so the cert lives in the same own paths as the config
+        let cert_conf_id = RbumCertConfServ::add_rbum(
+            &mut RbumCertConfAddReq {
+                code: TrimString(reldb_constants::RBUM_CERT_CONF_CODE_RELDB_CONN.to_string()),
+                name: TrimString(reldb_constants::RBUM_CERT_CONF_CODE_RELDB_CONN.to_string()),
+                note: None,
+                ak_note: None,
+                ak_rule: None,
+                sk_note: None,
+                sk_rule: None,
+                ext: None,
+                sk_need: Some(true),
+                sk_dynamic: Some(false),
+                // Encrypted by reldb_crypto_helper, so that the key can be rotated
+                sk_encrypted: Some(false),
+                repeatable: None,
+                is_basic: Some(false),
+                is_ak_repeatable: None,
+                rest_by_kinds: None,
+                expire_sec: None,
+                sk_lock_cycle_sec: None,
+                sk_lock_err_times: None,
+                sk_lock_duration_sec: None,
+                coexist_num: Some(1),
+                conn_uri: None,
+                rel_rbum_domain_id: Self::get_rbum_domain_id(),
+                rel_rbum_item_id: Some(inst_id.clone()),
+            },
+            funs,
+            ctx,
+        )
+        .await?;
+        RbumCertServ::add_rbum(
+            &mut RbumCertAddReq {
+                ak: add_req.cert_user_name.clone(),
+                sk: Some(TrimString(reldb_crypto_helper::encrypt_password(&add_req.cert_password.0, funs)?)),
+                vcode: None,
+                ext: None,
+                start_time: None,
+                end_time: None,
+                conn_uri: Some(add_req.connect_uri.to_string()),
+                status: RbumCertStatusKind::Enabled,
+                rel_rbum_cert_conf_id: Some(cert_conf_id),
+                rel_rbum_kind: RbumCertRelKind::Item,
+                rel_rbum_id: inst_id.clone(),
+                is_outside: false,
+            },
+            funs,
+            ctx,
+        )
+        .await?;
+        Ok(inst_id)
+    }
+
+    /// Modify the config, the masked password means not changed.
+    ///
+    /// The connection is tested again if the uri or credentials are changed.
+    pub async fn modify_config_agg(inst_id: &str, modify_req: &RelDbConfigAggAddOrModifyReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        let config = Self::get_config_agg(inst_id, funs, ctx).await?;
+        let password_changed = modify_req.cert_password.0 != reldb_crypto_helper::MASKED_PASSWORD;
+        if password_changed || modify_req.cert_user_name.0 != config.cert_user_name || modify_req.connect_uri.0 != config.connect_uri {
+            let password = if password_changed {
+                modify_req.cert_password.to_string()
+            } else {
+                reldb_crypto_helper::decrypt_password(&config.cert_password, funs)?
+            };
+            RelDbPoolServ::test_connection(&modify_req.connect_uri.0, &modify_req.cert_user_name.0, &password, funs).await?;
+        }
+        Self::modify_item(
+            inst_id,
+            &mut RelDbConfigModifyReq {
+                name: Some(modify_req.name.clone()),
+                scope_level: modify_req.scope_level.clone(),
+                disabled: modify_req.disabled,
+                icon: modify_req.icon.clone(),
+                allowed_tables: modify_req.allowed_tables.as_ref().map(|allowed_tables| Self::package_allowed_tables(allowed_tables)),
+                statement_timeout_sec: modify_req.statement_timeout_sec,
+                version: modify_req.version,
+            },
+            funs,
+            ctx,
+        )
+        .await?;
+        let cert_filter = Self::package_cert_filter(inst_id, &config.own_paths);
+        if let Some(rbum_cert) = RbumCertServ::find_one_rbum(&cert_filter, funs, ctx).await? {
+            RbumCertServ::modify_rbum(
+                &rbum_cert.id,
+                &mut RbumCertModifyReq {
+                    ak: Some(modify_req.cert_user_name.clone()),
+                    sk: if password_changed {
+                        Some(TrimString(reldb_crypto_helper::encrypt_password(&modify_req.cert_password.0, funs)?))
+                    } else {
+                        None
+                    },
+                    ext: None,
+                    start_time: None,
+                    end_time: None,
+                    conn_uri: Some(modify_req.connect_uri.to_string()),
+                    status: None,
+                },
+                funs,
+                ctx,
+            )
+            .await?;
+        }
+        RelDbPoolServ::evict(inst_id).await;
+        Ok(())
+    }
+
+    /// Delete the config with its cert and cert conf.
+    pub async fn delete_config_agg(inst_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<u64> {
+        let rbum_item = RbumItemServ::get_rbum(inst_id, &RbumBasicFilterReq::default(), funs, ctx).await?;
+        RbumItemServ::check_ownership(inst_id, funs, ctx).await?;
+        let cert_filter = Self::package_cert_filter(inst_id, &rbum_item.own_paths);
+        for cert_id in RbumCertServ::find_id_rbums(&cert_filter, None, None, funs, ctx).await? {
+            RbumCertServ::delete_rbum(&cert_id, funs, ctx).await?;
+        }
+        let cert_conf_filter = RbumCertConfFilterReq {
+            basic: RbumBasicFilterReq {
+                own_paths: Some(rbum_item.own_paths.clone()),
+                ..Default::default()
+            },
+            rel_rbum_domain_id: Some(Self::get_rbum_domain_id()),
+            rel_rbum_item_id: Some(inst_id.to_string()),
+        };
+        for cert_conf_id in RbumCertConfServ::find_id_rbums(&cert_conf_filter, None, None, funs, ctx).await? {
+            RbumCertConfServ::delete_rbum(&cert_conf_id, funs, ctx).await?;
+        }
+        let result = Self::delete_item(inst_id, funs, ctx).await?;
+        RelDbPoolServ::evict(inst_id).await;
+        Ok(result)
+    }
+
+    /// Find the configs of the current own paths and the ones shared to it by scope level.
+    pub async fn paginate_configs(
+        inst_id: Option<String>,
+        name: Option<String>,
+        page_number: u64,
+        page_size: u64,
+        desc_by_create: Option<bool>,
+        desc_by_update: Option<bool>,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<TardisPage<RelDbConfigSummaryResp>> {
+        Self::paginate_items(
+            &RelDbConfigFilterReq {
+                basic: RbumBasicFilterReq {
+                    ids: inst_id.map(|inst_id| vec![inst_id]),
+                    name,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            page_number,
+            page_size,
+            desc_by_create,
+            desc_by_update,
+            funs,
+            ctx,
+        )
+        .await
+    }
+
+    pub async fn test_connection(test_req: &RelDbConfigConnTestReq, funs: &TardisFunsInst) -> TardisResult<()> {
+        RelDbPoolServ::test_connection(&test_req.connect_uri.0, &test_req.cert_user_name.0, &test_req.cert_password.0, funs).await
+    }
+
+    /// Resolve the instance id to the full config, including the connection credentials.
+    pub async fn get_config_agg(inst_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<RelDbConfigAggResp> {
+        #[derive(sea_orm::FromQueryResult)]
+        struct ExtResp {
+            pub icon: String,
+            pub allowed_tables: String,
+            pub statement_timeout_sec: u32,
+        }
+        let rbum_item = RbumItemServ::get_rbum(inst_id, &RbumBasicFilterReq::default(), funs, ctx).await?;
+        if rbum_item.rel_rbum_kind_id != Self::get_rbum_kind_id() {
+            return Err(funs.err().not_found(
+                &Self::get_obj_name(),
+                "get_config_agg",
+                &format!("not found config {}", inst_id),
+                "404-reldb-config-obj-not-exist",
+            ));
+        }
+        let mut query = Query::select();
+        query
+            .columns(vec![
+                (reldb_config::Entity, reldb_config::Column::Icon),
+                (reldb_config::Entity, reldb_config::Column::AllowedTables),
+                (reldb_config::Entity, reldb_config::Column::StatementTimeoutSec),
+            ])
+            .from(reldb_config::Entity)
+            .and_where(Expr::tbl(reldb_config::Entity, reldb_config::Column::Id).eq(inst_id));
+        let ext = funs.db().get_dto::<ExtResp>(&query).await?.ok_or_else(|| {
+            funs.err().not_found(
+                &Self::get_obj_name(),
+                "get_config_agg",
+                &format!("not found config {}", inst_id),
+                "404-reldb-config-obj-not-exist",
+            )
+        })?;
+        // The config may be shared from the parent own paths, so find the cert from the own paths of the config
+        let cert_filter = Self::package_cert_filter(inst_id, &rbum_item.own_paths);
+        let rbum_cert = RbumCertServ::find_one_detail_rbum(&cert_filter, funs, ctx).await?.ok_or_else(|| {
+            funs.err().not_found(
+                &Self::get_obj_name(),
+                "get_config_agg",
+                &format!("not found cert of config {}", inst_id),
+                "404-reldb-config-cert-not-exist",
+            )
+        })?;
+        let cert_password = RbumCertServ::show_sk(&rbum_cert.id, &cert_filter, funs, ctx).await?;
+        Ok(RelDbConfigAggResp {
+            id: rbum_item.id,
+            name: rbum_item.name,
+            own_paths: rbum_item.own_paths,
+            owner: rbum_item.owner,
+            owner_name: rbum_item.owner_name,
+            create_time: rbum_item.create_time,
+            update_time: rbum_item.update_time,
+            version: rbum_item.version,
+            scope_level: rbum_item.scope_level,
+            disabled: rbum_item.disabled,
+            icon: ext.icon,
+            allowed_tables: ext.allowed_tables.split(',').map(|t| t.trim()).filter(|t| !t.is_empty()).map(|t| t.to_string()).collect(),
+            statement_timeout_sec: ext.statement_timeout_sec,
+            cert_user_name: rbum_cert.ak,
+            cert_password,
+            connect_uri: rbum_cert.conn_uri,
+        })
+    }
+
+    /// Same as [`Self::get_config_agg`], but the password is masked, used for api responses.
+    pub async fn get_masked_config_agg(inst_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<RelDbConfigAggResp> {
+        let mut config = Self::get_config_agg(inst_id, funs, ctx).await?;
+        config.cert_password = reldb_crypto_helper::MASKED_PASSWORD.to_string();
+        Ok(config)
+    }
+
+    /// Re-encrypt the passwords not encrypted with the current key.
+    pub async fn rotate_password_crypto_key(funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<u64> {
+        #[derive(sea_orm::FromQueryResult)]
+        struct IdResp {
+            pub id: String,
+        }
+        let mut query = Query::select();
+        query.column((reldb_config::Entity, reldb_config::Column::Id)).from(reldb_config::Entity);
+        let inst_ids = funs.db().find_dtos::<IdResp>(&query).await?;
+        let mut rotated = 0;
+        for inst_id in inst_ids {
+            let cert_filter = RbumCertFilterReq {
+                basic: RbumBasicFilterReq {
+                    ignore_scope: true,
+                    own_paths: Some("".to_string()),
+                    with_sub_own_paths: true,
+                    ..Default::default()
+                },
+                rel_rbum_kind: Some(RbumCertRelKind::Item),
+                rel_rbum_id: Some(inst_id.id.clone()),
+                ..Default::default()
+            };
+            if let Some(rbum_cert) = RbumCertServ::find_one_rbum(&cert_filter, funs, ctx).await? {
+                let stored_password = RbumCertServ::show_sk(&rbum_cert.id, &cert_filter, funs, ctx).await?;
+                if reldb_crypto_helper::is_encrypted_with_current_key(&stored_password, funs)? {
+                    continue;
+                }
+                let password = reldb_crypto_helper::decrypt_password(&stored_password, funs)?;
+                RbumCertServ::reset_sk(&rbum_cert.id, &reldb_crypto_helper::encrypt_password(&password, funs)?, &cert_filter, funs, ctx).await?;
+                rotated += 1;
+            }
+        }
+        if rotated > 0 {
+            info!("[BIOS.RelDb] re-encrypted {} passwords with the current key", rotated);
+        }
+        Ok(rotated)
+    }
+
+    fn package_cert_filter(inst_id: &str, own_paths: &str) -> RbumCertFilterReq {
+        RbumCertFilterReq {
+            basic: RbumBasicFilterReq {
+                own_paths: Some(own_paths.to_string()),
+                ..Default::default()
+            },
+            rel_rbum_kind: Some(RbumCertRelKind::Item),
+            rel_rbum_id: Some(inst_id.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn package_allowed_tables(allowed_tables: &[String]) -> String {
+        allowed_tables.iter().map(|t| t.trim()).filter(|t| !t.is_empty()).collect::<Vec<&str>>().join(",")
+    }
+}
@@ -0,0 +1,361 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tardis::basic::dto::TardisContext;
+use tardis::basic::error::TardisError;
+use tardis::basic::result::TardisResult;
+use tardis::db::reldb_client::TardisRelDBClient;
+use tardis::db::sea_orm::sqlx::{self, Column, Executor, TypeInfo};
+use tardis::db::sea_orm::{ConnectionTrait, DbBackend, ExecResult, FromQueryResult, QueryResult, Statement, TransactionTrait, Value};
+use tardis::serde_json::{Map, Value as JsonValue};
+use tardis::tokio::time::timeout;
+use tardis::TardisFunsInst;
+
+use bios_basic::rbum::serv::rbum_item_serv::RbumItemCrudOperation;
+
+use crate::basic::dto::reldb_config_dto::RelDbConfigAggResp;
+use crate::basic::dto::reldb_process_dto::{RelDbColumnResp, RelDbExecuteReq, RelDbQueryReq, RelDbQueryResp};
+use crate::basic::helper::reldb_sql_helper;
+use crate::basic::serv::reldb_audit_serv::RelDbAuditServ;
+use crate::basic::serv::reldb_config_serv::RelDbConfigServ;
+use crate::basic::serv::reldb_pool_serv::RelDbPoolServ;
+use crate::reldb_config::RelDbConfig;
+use crate::reldb_enumeration::RelDbColumnDataTypeKind;
+
+pub struct RelDbProcessServ;
+
+impl RelDbProcessServ {
+    pub async fn query(inst_id: &str, query_req: &RelDbQueryReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<RelDbQueryResp> {
+        let start = Instant::now();
+        let result = Self::do_query(inst_id, query_req, funs, ctx).await;
+        let row_count = result.as_ref().map(|resp| resp.records.len() as u64).unwrap_or(0);
+        Self::audit(inst_id, "query", &query_req.sql, &query_req.params, start, row_count, result.as_ref().err(), funs, ctx).await?;
+        result
+    }
+
+    pub async fn execute(inst_id: &str, execute_req: &RelDbExecuteReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<String>> {
+        let start = Instant::now();
+        let result = Self::do_execute(inst_id, execute_req, funs, ctx).await;
+        let row_count = result.as_ref().map(|(rows_affected, _)| *rows_affected).unwrap_or(0);
+        Self::audit(
+            inst_id,
+            "execute",
+            &execute_req.sql,
+            &execute_req.params,
+            start,
+            row_count,
+            result.as_ref().err(),
+            funs,
+            ctx,
+        )
+        .await?;
+        result.map(|(_, resp)| resp)
+    }
+
+    async fn audit(
+        inst_id: &str,
+        kind: &str,
+        sql: &str,
+        params: &HashMap<String, String>,
+        start: Instant,
+        row_count: u64,
+        error: Option<&TardisError>,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<()> {
+        RelDbAuditServ::add_audit(
+            inst_id,
+            kind,
+            sql,
+            params,
+            start.elapsed().as_millis() as u64,
+            row_count,
+            error.map(|e| e.to_string()),
+            funs,
+            ctx,
+        )
+        .await
+    }
+
+    async fn do_query(inst_id: &str, query_req: &RelDbQueryReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<RelDbQueryResp> {
+        let conf = funs.conf::<RelDbConfig>();
+        let (config, client) = Self::get_client(inst_id, funs, ctx).await?;
+        let (sql, params) = Self::bind_named_params(&query_req.sql, &query_req.params, client.backend(), funs)?;
+        reldb_sql_helper::check_query(&sql, &config.allowed_tables, client.backend(), funs)?;
+        let page_number = query_req.page_number.unwrap_or(1);
+        let page_size = query_req.page_size.unwrap_or(conf.query_default_page_size).min(conf.query_max_rows);
+        // Offsets beyond the signed 64-bit range are rejected by the databases as well
+        let offset = (page_number.max(1) - 1).checked_mul(page_size).filter(|offset| *offset <= i64::MAX as u64).ok_or_else(|| {
+            funs.err().bad_request(
+                &RelDbConfigServ::get_obj_name(),
+                "query",
+                &format!("page {} of size {} is out of range", page_number, page_size),
+                "400-reldb-process-page-invalid",
+            )
+        })?;
+        let timeout_sec = Self::get_timeout_sec(&config, funs);
+        let sql = Self::package_page_sql(&sql, offset, page_size, timeout_sec, client.backend());
+        // The timeout covers the whole query, describing the statement included
+        let (columns, rows) = Self::with_timeout(
+            async {
+                let columns = Self::describe_columns(&sql, &client, funs).await?;
+                let rows = Self::query_all(&sql, params, timeout_sec, &client).await?;
+                Ok((columns, rows))
+            },
+            timeout_sec,
+            funs,
+        )
+        .await?;
+        let has_more = rows.len() as u64 > page_size;
+        let mut records = Vec::with_capacity(rows.len());
+        for row in rows.iter().take(page_size as usize) {
+            let value = match JsonValue::from_query_result(row, "")? {
+                JsonValue::Object(obj) => obj,
+                _ => Map::new(),
+            };
+            records.push(columns.iter().map(|column| value.get(&column.name).cloned().unwrap_or(JsonValue::Null)).collect());
+        }
+        Ok(RelDbQueryResp {
+            columns,
+            records,
+            page_number,
+            page_size,
+            has_more,
+        })
+    }
+
+    async fn do_execute(inst_id: &str, execute_req: &RelDbExecuteReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<(u64, Option<String>)> {
+        let (config, client) = Self::get_client(inst_id, funs, ctx).await?;
+        let (sql, params) = Self::bind_named_params(&execute_req.sql, &execute_req.params, client.backend(), funs)?;
+        reldb_sql_helper::check_execute(&sql, &config.allowed_tables, client.backend(), funs)?;
+        let timeout_sec = Self::get_timeout_sec(&config, funs);
+        let result = Self::with_timeout(Self::execute_one(&sql, params, timeout_sec, &client), timeout_sec, funs).await?;
+        Ok((result.rows_affected(), Some(result.rows_affected().to_string())))
+    }
+
+    /// Wrap the statement with the paging clause, one more row than the page size is fetched.
+    ///
+    /// For mysql, the timeout is set by the `MAX_EXECUTION_TIME` hint, which only applies to reads.
+    fn package_page_sql(sql: &str, offset: u64, page_size: u64, timeout_sec: u32, backend: DbBackend) -> String {
+        let sql = sql.trim().trim_end_matches(';');
+        let limit = page_size.saturating_add(1);
+        match backend {
+            DbBackend::MySql => format!(
+                "SELECT /*+ MAX_EXECUTION_TIME({}) */ * FROM ({}) AS t_page LIMIT {}, {}",
+                timeout_sec as u64 * 1000,
+                sql,
+                offset,
+                limit
+            ),
+            _ => format!("SELECT * FROM ({}) AS t_page LIMIT {} OFFSET {}", sql, limit, offset),
+        }
+    }
+
+    async fn query_all(sql: &str, params: Vec<Value>, timeout_sec: u32, client: &TardisRelDBClient) -> TardisResult<Vec<QueryResult>> {
+        let conn = client.conn();
+        let statement = Statement::from_sql_and_values(client.backend(), sql, params);
+        if client.backend() == DbBackend::Postgres {
+            let tx = conn.raw_conn().begin().await?;
+            tx.execute(Self::package_pg_timeout_statement(timeout_sec)).await?;
+            let rows = tx.query_all(statement).await?;
+            tx.commit().await?;
+            Ok(rows)
+        } else {
+            Ok(conn.raw_conn().query_all(statement).await?)
+        }
+    }
+
+    /// Mysql has no database side timeout of writes, they are only limited by [with_timeout](Self::with_timeout).
+    async fn execute_one(sql: &str, params: Vec<Value>, timeout_sec: u32, client: &TardisRelDBClient) -> TardisResult<ExecResult> {
+        let conn = client.conn();
+        let statement = Statement::from_sql_and_values(client.backend(), sql, params);
+        if client.backend() == DbBackend::Postgres {
+            let tx = conn.raw_conn().begin().await?;
+            tx.execute(Self::package_pg_timeout_statement(timeout_sec)).await?;
+            let result = tx.execute(statement).await?;
+            tx.commit().await?;
+            Ok(result)
+        } else {
+            Ok(conn.raw_conn().execute(statement).await?)
+        }
+    }
+
+    // Only applies to the transaction, the pooled connection is not affected
+    fn package_pg_timeout_statement(timeout_sec: u32) -> Statement {
+        Statement::from_string(DbBackend::Postgres, format!("SET LOCAL statement_timeout = {}", timeout_sec as u64 * 1000))
+    }
+
+    fn get_timeout_sec(config: &RelDbConfigAggResp, funs: &TardisFunsInst) -> u32 {
+        if config.statement_timeout_sec > 0 {
+            config.statement_timeout_sec
+        } else {
+            funs.conf::<RelDbConfig>().statement_timeout_sec
+        }
+    }
+
+    /// Stop waiting for the statement when the timeout is reached, the statements cancelled by the database for the same timeout are reported the same way.
+    async fn with_timeout<F, T>(fut: F, timeout_sec: u32, funs: &TardisFunsInst) -> TardisResult<T>
+    where
+        F: Future<Output = TardisResult<T>>,
+    {
+        match timeout(Duration::from_secs(timeout_sec as u64), fut).await {
+            Ok(Err(e)) if !Self::is_statement_timeout(&e) => Err(e),
+            Ok(Ok(result)) => Ok(result),
+            _ => Err(funs.err().timeout(
+                &RelDbConfigServ::get_obj_name(),
+                "process",
+                &format!("statement timeout after {} seconds", timeout_sec),
+                "408-reldb-process-timeout",
+            )),
+        }
+    }
+
+    fn is_statement_timeout(error: &TardisError) -> bool {
+        // mysql error 3024 and postgres sqlstate 57014
+        error.message.contains("maximum statement execution time exceeded") || error.message.contains("canceling statement due to statement timeout")
+    }
+
+    async fn get_client(inst_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<(RelDbConfigAggResp, Arc<TardisRelDBClient>)> {
+        // Check the visibility of the config on every call, the pool itself is shared.
+        let config = RelDbConfigServ::get_config_agg(inst_id, funs, ctx).await?;
+        if config.disabled {
+            return Err(funs.err().conflict(
+                &RelDbConfigServ::get_obj_name(),
+                "get_client",
+                &format!("config {} is disabled", inst_id),
+                "409-reldb-config-disabled",
+            ));
+        }
+        let client = RelDbPoolServ::get_client(&config, funs).await?;
+        Ok((config, client))
+    }
+
+    /// Replace named placeholders (`:name`) with the positional placeholders of the backend.
+    ///
+    /// Placeholders inside quoted literals and postgres casts (`::type`) are left untouched.
+    /// Comments (`-- ...` and `/* ... */`) are replaced by a space, so they cannot hide placeholders or the paging clause wrapped around the statement.
+    fn bind_named_params(sql: &str, params: &HashMap<String, String>, backend: DbBackend, funs: &TardisFunsInst) -> TardisResult<(String, Vec<Value>)> {
+        let chars = sql.chars().collect::<Vec<char>>();
+        let mut result = String::with_capacity(sql.len());
+        let mut values = Vec::new();
+        let mut quote: Option<char> = None;
+        let mut idx = 0;
+        while idx < chars.len() {
+            let c = chars[idx];
+            if let Some(q) = quote {
+                if c == q {
+                    quote = None;
+                }
+                result.push(c);
+                idx += 1;
+                continue;
+            }
+            if c == '\'' || c == '"' || c == '`' {
+                quote = Some(c);
+                result.push(c);
+                idx += 1;
+                continue;
+            }
+            if c == '-' && idx + 1 < chars.len() && chars[idx + 1] == '-' {
+                // The line break is kept as it ends the comment
+                while idx < chars.len() && chars[idx] != '\n' {
+                    idx += 1;
+                }
+                result.push(' ');
+                continue;
+            }
+            if c == '/' && idx + 1 < chars.len() && chars[idx + 1] == '*' {
+                idx += 2;
+                while idx < chars.len() && !(chars[idx] == '*' && idx + 1 < chars.len() && chars[idx + 1] == '/') {
+                    idx += 1;
+                }
+                idx = (idx + 2).min(chars.len());
+                result.push(' ');
+                continue;
+            }
+            if c == ':' && idx + 1 < chars.len() && chars[idx + 1] == ':' {
+                result.push_str("::");
+                idx += 2;
+                continue;
+            }
+            if c == ':' && idx + 1 < chars.len() && (chars[idx + 1].is_ascii_alphabetic() || chars[idx + 1] == '_') {
+                let start = idx + 1;
+                let mut end = start;
+                while end < chars.len() && (chars[end].is_ascii_alphanumeric() || chars[end] == '_') {
+                    end += 1;
+                }
+                let name = chars[start..end].iter().collect::<String>();
+                let value = params.get(&name).ok_or_else(|| {
+                    funs.err().bad_request(
+                        &RelDbConfigServ::get_obj_name(),
+                        "bind_params",
+                        &format!("missing value of parameter {}", name),
+                        "400-reldb-process-param-missing",
+                    )
+                })?;
+                values.push(Value::from(value.as_str()));
+                match backend {
+                    DbBackend::Postgres => result.push_str(&format!("${}", values.len())),
+                    _ => result.push('?'),
+                }
+                idx = end;
+                continue;
+            }
+            result.push(c);
+            idx += 1;
+        }
+        Ok((result, values))
+    }
+
+    /// Get the columns of the statement from the driver, in the order of the statement.
+    async fn describe_columns(sql: &str, client: &TardisRelDBClient, funs: &TardisFunsInst) -> TardisResult<Vec<RelDbColumnResp>> {
+        let conn = client.conn();
+        let columns = match client.backend() {
+            DbBackend::MySql => Self::do_describe_columns(conn.raw_conn().get_mysql_connection_pool(), sql).await,
+            DbBackend::Postgres => Self::do_describe_columns(conn.raw_conn().get_postgres_connection_pool(), sql).await,
+            DbBackend::Sqlite => Self::do_describe_columns(conn.raw_conn().get_sqlite_connection_pool(), sql).await,
+        }
+        .map_err(|e| {
+            funs.err().bad_request(
+                &RelDbConfigServ::get_obj_name(),
+                "describe",
+                &format!("describe statement error: {}", e),
+                "400-reldb-process-describe-error",
+            )
+        })?;
+        Ok(columns
+            .into_iter()
+            .map(|(name, db_type)| RelDbColumnResp {
+                name,
+                data_type: Self::get_data_type(&db_type),
+                db_type,
+            })
+            .collect())
+    }
+
+    async fn do_describe_columns<'e, E>(executor: E, sql: &'e str) -> Result<Vec<(String, String)>, sqlx::Error>
+    where
+        E: Executor<'e>,
+    {
+        let describe = executor.describe(sql).await?;
+        Ok(describe.columns().iter().map(|column| (column.name().to_string(), column.type_info().name().to_string())).collect())
+    }
+
+    /// Map the type name reported by the driver, e.g. `VARCHAR`, `INT8` or `BIGINT UNSIGNED`.
+    fn get_data_type(db_type: &str) -> RelDbColumnDataTypeKind {
+        let db_type = db_type.to_uppercase();
+        if db_type.starts_with("BOOL") {
+            RelDbColumnDataTypeKind::Boolean
+        } else if db_type.contains("INT") || ["FLOAT", "DOUBLE", "REAL", "DECIMAL", "NUMERIC", "SERIAL"].iter().any(|number_type| db_type.starts_with(number_type)) {
+            RelDbColumnDataTypeKind::Number
+        } else if db_type.starts_with("JSON") {
+            RelDbColumnDataTypeKind::Json
+        } else if ["CHAR", "TEXT", "DATE", "TIME", "UUID", "ENUM", "SET", "NAME", "YEAR"].iter().any(|string_type| db_type.contains(string_type)) {
+            RelDbColumnDataTypeKind::String
+        } else {
+            RelDbColumnDataTypeKind::Unknown
+        }
+    }
+}
@@ -1 +1,2 @@
+pub mod reldb_ca_audit;
 pub mod reldb_ca_config;
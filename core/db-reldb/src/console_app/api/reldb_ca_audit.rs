@@ -0,0 +1,46 @@
+use tardis::chrono::{DateTime, Utc};
+use tardis::web::context_extractor::TardisContextExtractor;
+use tardis::web::poem_openapi;
+use tardis::web::poem_openapi::param::Query;
+use tardis::web::web_resp::{TardisApiResult, TardisPage, TardisResp};
+
+use crate::basic::dto::reldb_audit_dto::{RelDbAuditFilterReq, RelDbAuditSummaryResp};
+use crate::basic::serv::reldb_audit_serv::RelDbAuditServ;
+use crate::reldb_constants;
+
+pub struct RelDbCaAuditApi;
+
+/// App Console RelDb Audit API
+#[poem_openapi::OpenApi(prefix_path = "/ca/audit", tag = "bios_basic::ApiTag::App")]
+impl RelDbCaAuditApi {
+    /// Find Audit Records
+    #[oai(path = "/", method = "get")]
+    async fn paginate_audit(
+        &self,
+        inst_id: Query<Option<String>>,
+        owner: Query<Option<String>>,
+        start_time: Query<Option<DateTime<Utc>>>,
+        end_time: Query<Option<DateTime<Utc>>>,
+        page_number: Query<u64>,
+        page_size: Query<u64>,
+        desc_by_create: Query<Option<bool>>,
+        ctx: TardisContextExtractor,
+    ) -> TardisApiResult<TardisPage<RelDbAuditSummaryResp>> {
+        let funs = reldb_constants::get_tardis_inst();
+        let result = RelDbAuditServ::paginate_audits(
+            &RelDbAuditFilterReq {
+                inst_id: inst_id.0,
+                owner: owner.0,
+                start_time: start_time.0,
+                end_time: end_time.0,
+            },
+            page_number.0,
+            page_size.0,
+            desc_by_create.0,
+            &funs,
+            &ctx.0,
+        )
+        .await?;
+        TardisResp::ok(result)
+    }
+}
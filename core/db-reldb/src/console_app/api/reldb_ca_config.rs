@@ -4,7 +4,8 @@ use tardis::web::poem_openapi::param::{Path, Query};
 use tardis::web::poem_openapi::payload::Json;
 use tardis::web::web_resp::{TardisApiResult, TardisPage, TardisResp, Void};
 
-use crate::basic::dto::reldb_config_dto::{RelDbConfigAggAddOrModifyReq, RelDbConfigAggResp, RelDbConfigSummaryResp};
+use crate::basic::dto::reldb_config_dto::{RelDbConfigAggAddOrModifyReq, RelDbConfigAggResp, RelDbConfigConnTestReq, RelDbConfigSummaryResp};
+use crate::basic::serv::reldb_config_serv::RelDbConfigServ;
 use crate::reldb_constants;
 
 pub struct RelDbCaConfigApi;
@@ -17,9 +18,9 @@ impl RelDbCaConfigApi {
     async fn add_config(&self, add_req: Json<RelDbConfigAggAddOrModifyReq>, ctx: TardisContextExtractor) -> TardisApiResult<String> {
         let mut funs = reldb_constants::get_tardis_inst();
         funs.begin().await?;
-        // TODO
+        let result = RelDbConfigServ::add_config_agg(&add_req.0, &funs, &ctx.0).await?;
         funs.commit().await?;
-        TardisResp::ok("".to_string())
+        TardisResp::ok(result)
     }
 
     /// Modify RelDb Config By Instance Id
@@ -27,11 +28,21 @@ impl RelDbCaConfigApi {
     async fn modify_config(&self, inst_id: Path<String>, modify_req: Json<RelDbConfigAggAddOrModifyReq>, ctx: TardisContextExtractor) -> TardisApiResult<Void> {
         let mut funs = reldb_constants::get_tardis_inst();
         funs.begin().await?;
-        // TODO
+        RelDbConfigServ::modify_config_agg(&inst_id.0, &modify_req.0, &funs, &ctx.0).await?;
         funs.commit().await?;
         TardisResp::ok(Void {})
     }
 
+    /// Test RelDb Connection
+    ///
+    /// Check the connect uri and credentials before saving.
+    #[oai(path = "/test", method = "post")]
+    async fn test_connection(&self, test_req: Json<RelDbConfigConnTestReq>, _ctx: TardisContextExtractor) -> TardisApiResult<Void> {
+        let funs = reldb_constants::get_tardis_inst();
+        RelDbConfigServ::test_connection(&test_req.0, &funs).await?;
+        TardisResp::ok(Void {})
+    }
+
     /// Find RelDb Config
     #[oai(path = "/", method = "get")]
     async fn paginate_config(
@@ -45,35 +56,18 @@ impl RelDbCaConfigApi {
         ctx: TardisContextExtractor,
     ) -> TardisApiResult<TardisPage<RelDbConfigSummaryResp>> {
         let funs = reldb_constants::get_tardis_inst();
-        // TODO
-        TardisResp::ok(TardisPage {
-            page_size: todo!(),
-            page_number: todo!(),
-            total_size: todo!(),
-            records: todo!(),
-        })
+        let result = RelDbConfigServ::paginate_configs(inst_id.0, name.0, page_number.0, page_size.0, desc_by_create.0, desc_by_update.0, &funs, &ctx.0).await?;
+        TardisResp::ok(result)
     }
 
     /// Get RelDb Config By Instance Id
+    ///
+    /// The password is always masked.
     #[oai(path = "/:inst_id", method = "get")]
     async fn get_config(&self, inst_id: Path<String>, ctx: TardisContextExtractor) -> TardisApiResult<RelDbConfigAggResp> {
         let funs = reldb_constants::get_tardis_inst();
-        // TODO
-        TardisResp::ok(RelDbConfigAggResp {
-            id: todo!(),
-            name: todo!(),
-            own_paths: todo!(),
-            owner: todo!(),
-            owner_name: todo!(),
-            create_time: todo!(),
-            update_time: todo!(),
-            scope_level: todo!(),
-            disabled: todo!(),
-            icon: todo!(),
-            cert_user_name: todo!(),
-            cert_password: todo!(),
-            connect_uri: todo!(),
-        })
+        let result = RelDbConfigServ::get_masked_config_agg(&inst_id.0, &funs, &ctx.0).await?;
+        TardisResp::ok(result)
     }
 
     /// Delete RelDb Config By Instance Id
@@ -81,7 +75,7 @@ impl RelDbCaConfigApi {
     async fn delete_config(&self, inst_id: Path<String>, ctx: TardisContextExtractor) -> TardisApiResult<Void> {
         let mut funs = reldb_constants::get_tardis_inst();
         funs.begin().await?;
-        // TODO
+        RelDbConfigServ::delete_config_agg(&inst_id.0, &funs, &ctx.0).await?;
         funs.commit().await?;
         TardisResp::ok(Void {})
     }
@@ -1,12 +1,12 @@
-use std::collections::HashMap;
-
 use tardis::web::context_extractor::TardisContextExtractor;
 use tardis::web::poem_openapi;
 use tardis::web::poem_openapi::param::Path;
 use tardis::web::poem_openapi::payload::Json;
 use tardis::web::web_resp::{TardisApiResult, TardisResp};
 
-use crate::basic::dto::reldb_process_dto::{RelDbExecuteReq, RelDbQueryReq};
+use crate::basic::dto::reldb_process_dto::{RelDbExecuteReq, RelDbPoolHealthResp, RelDbQueryReq, RelDbQueryResp};
+use crate::basic::serv::reldb_pool_serv::RelDbPoolServ;
+use crate::basic::serv::reldb_process_serv::RelDbProcessServ;
 use crate::reldb_constants;
 
 pub struct RelDbCiProcessApi;
@@ -15,18 +15,27 @@ pub struct RelDbCiProcessApi;
 #[poem_openapi::OpenApi(prefix_path = "/ci/proc", tag = "bios_basic::ApiTag::Interface")]
 impl RelDbCiProcessApi {
     /// Query
+    ///
+    /// The rows are paged, the page size is limited by the server side max rows.
     #[oai(path = "/:inst_id/query", method = "post")]
-    async fn query(&self, inst_id: Path<String>, query_req: Json<RelDbQueryReq>, ctx: TardisContextExtractor) -> TardisApiResult<Vec<HashMap<String, String>>> {
+    async fn query(&self, inst_id: Path<String>, query_req: Json<RelDbQueryReq>, ctx: TardisContextExtractor) -> TardisApiResult<RelDbQueryResp> {
         let funs = reldb_constants::get_tardis_inst();
-        // TODO
-        TardisResp::ok(Vec::new())
+        let result = RelDbProcessServ::query(&inst_id.0, &query_req.0, &funs, &ctx.0).await?;
+        TardisResp::ok(result)
     }
 
     /// Execute
     #[oai(path = "/:inst_id/execute", method = "post")]
     async fn execute(&self, inst_id: Path<String>, execute_req: Json<RelDbExecuteReq>, ctx: TardisContextExtractor) -> TardisApiResult<Option<String>> {
         let funs = reldb_constants::get_tardis_inst();
-        // TODO
-        TardisResp::ok(None)
+        let result = RelDbProcessServ::execute(&inst_id.0, &execute_req.0, &funs, &ctx.0).await?;
+        TardisResp::ok(result)
+    }
+
+    /// Get Health Of Opened Pools
+    #[oai(path = "/pool/health", method = "get")]
+    async fn pool_health(&self, ctx: TardisContextExtractor) -> TardisApiResult<Vec<RelDbPoolHealthResp>> {
+        let result = RelDbPoolServ::health(&ctx.0).await?;
+        TardisResp::ok(result)
     }
 }
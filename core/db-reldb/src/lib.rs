@@ -1,6 +1,9 @@
-mod basic;
-mod console_app;
-mod console_interface;
-mod reldb_config;
-mod reldb_constants;
-mod reldb_initializer;
+#[macro_use]
+extern crate lazy_static;
+pub mod basic;
+pub mod console_app;
+pub mod console_interface;
+pub mod reldb_config;
+pub mod reldb_constants;
+pub mod reldb_enumeration;
+pub mod reldb_initializer;
@@ -1,15 +1,81 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Mutex;
+
 use bios_basic::rbum::rbum_config::RbumConfig;
 use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
+use tardis::basic::error::TardisError;
+use tardis::basic::result::TardisResult;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(default)]
 pub struct RelDbConfig {
     pub rbum: RbumConfig,
+    // connection pool settings of each managed instance
+    pub inst_max_connections: u32,
+    pub inst_min_connections: u32,
+    pub inst_connect_timeout_sec: Option<u64>,
+    pub inst_idle_timeout_sec: Option<u64>,
+    // max number of opened pools per tenant, the least recently used ones are evicted beyond it
+    pub tenant_max_pools: usize,
+    // hard limit of the rows returned by a query
+    pub query_max_rows: u64,
+    pub query_default_page_size: u64,
+    // used when the config does not specify a timeout, also applied by postgres and by mysql for reads on the database side
+    pub statement_timeout_sec: u32,
+    // aes key (16/24/32 chars) to encrypt the stored connection passwords, required
+    pub password_crypto_key: String,
+    // keys used before, records encrypted by them are re-encrypted with the current key at startup
+    pub password_crypto_old_keys: Vec<String>,
 }
 
 impl Default for RelDbConfig {
     fn default() -> Self {
-        RelDbConfig { rbum: Default::default() }
+        RelDbConfig {
+            rbum: RbumConfig {
+                // notify config changes to evict the opened pools
+                event_domains: HashMap::from([("rbum_".to_string(), "cud".to_string()), ("db_reldb_config".to_string(), "ud".to_string())]),
+                ..Default::default()
+            },
+            inst_max_connections: 20,
+            inst_min_connections: 1,
+            inst_connect_timeout_sec: Some(10),
+            inst_idle_timeout_sec: Some(600),
+            tenant_max_pools: 10,
+            query_max_rows: 10000,
+            query_default_page_size: 100,
+            statement_timeout_sec: 30,
+            password_crypto_key: "".to_string(),
+            password_crypto_old_keys: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BasicInfo {
+    pub kind_config_id: String,
+    pub domain_reldb_id: String,
+}
+
+lazy_static! {
+    static ref BASIC_INFO: Mutex<Option<BasicInfo>> = Mutex::new(None);
+}
+
+pub struct RelDbBasicInfoManager;
+
+impl RelDbBasicInfoManager {
+    pub fn set(basic_info: BasicInfo) -> TardisResult<()> {
+        let mut conf = BASIC_INFO.lock().map_err(|e| TardisError::internal_error(&format!("{:?}", e), ""))?;
+        *conf = Some(basic_info);
+        Ok(())
+    }
+
+    pub fn get_config<F, T>(fun: F) -> T
+    where
+        F: Fn(&BasicInfo) -> T,
+    {
+        let conf = BASIC_INFO.lock().unwrap_or_else(|e| panic!("reldb basic config lock error: {:?}", e));
+        let conf = conf.as_ref().unwrap_or_else(|| panic!("reldb basic config not set"));
+        fun(conf)
     }
 }
@@ -1,7 +1,16 @@
 use tardis::TardisFuns;
 use tardis::TardisFunsInst;
 
+use bios_basic::rbum::rbum_enumeration::RbumScopeLevelKind;
+
 pub const COMPONENT_CODE: &str = "reldb";
+pub const RBUM_KIND_CODE_RELDB_CONFIG: &str = "reldb-config";
+pub const RBUM_CERT_CONF_CODE_RELDB_CONN: &str = "reldb-conn";
+
+pub const RBUM_EXT_TABLE_RELDB_CONFIG: &str = "db_reldb_config";
+
+pub const RBUM_SCOPE_LEVEL_GLOBAL: RbumScopeLevelKind = RbumScopeLevelKind::Root;
+pub const RBUM_SCOPE_LEVEL_TENANT: RbumScopeLevelKind = RbumScopeLevelKind::L1;
 
 pub fn get_tardis_inst() -> TardisFunsInst {
     TardisFuns::inst_with_db_conn(COMPONENT_CODE.to_string(), None)
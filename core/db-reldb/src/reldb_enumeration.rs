@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+use tardis::derive_more::Display;
+use tardis::web::poem_openapi;
+
+#[derive(Display, Clone, Debug, PartialEq, Eq, Deserialize, Serialize, poem_openapi::Enum)]
+pub enum RelDbColumnDataTypeKind {
+    String,
+    Number,
+    Boolean,
+    Json,
+    // type not mapped to the kinds above, e.g. binary types
+    Unknown,
+}
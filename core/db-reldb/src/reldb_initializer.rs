@@ -1,22 +1,95 @@
-use tardis::{basic::result::TardisResult, web::web_server::TardisWebServer, TardisFunsInst};
+use bios_basic::rbum::dto::rbum_domain_dto::RbumDomainAddReq;
+use bios_basic::rbum::dto::rbum_kind_dto::RbumKindAddReq;
+use bios_basic::rbum::helper::rbum_event_helper;
+use bios_basic::rbum::serv::rbum_crud_serv::RbumCrudOperation;
+use bios_basic::rbum::serv::rbum_domain_serv::RbumDomainServ;
+use bios_basic::rbum::serv::rbum_kind_serv::RbumKindServ;
+use tardis::basic::dto::TardisContext;
+use tardis::basic::field::TrimString;
+use tardis::db::reldb_client::TardisActiveModel;
+use tardis::{basic::result::TardisResult, web::web_server::TardisWebServer, TardisFuns, TardisFunsInst};
 
-use crate::{console_app::api::reldb_ca_config, console_interface::api::reldb_ci_process, reldb_config::RelDbConfig, reldb_constants};
+use crate::basic::domain::{reldb_audit, reldb_config};
+use crate::basic::helper::reldb_crypto_helper;
+use crate::basic::serv::reldb_config_serv::RelDbConfigServ;
+use crate::basic::serv::reldb_pool_serv::RelDbPoolServ;
+use crate::{
+    console_app::api::{reldb_ca_audit, reldb_ca_config},
+    console_interface::api::reldb_ci_process,
+    reldb_config::{BasicInfo, RelDbBasicInfoManager, RelDbConfig},
+    reldb_constants,
+};
 
 pub async fn init(web_server: &TardisWebServer) -> TardisResult<()> {
     let funs = reldb_constants::get_tardis_inst();
     init_db(funs).await?;
+    rbum_event_helper::start_relay_task(reldb_constants::COMPONENT_CODE);
+    RelDbPoolServ::init_event_listener(&reldb_constants::get_tardis_inst()).await?;
     init_api(web_server).await
 }
 
 async fn init_api(web_server: &TardisWebServer) -> TardisResult<()> {
-    web_server.add_module(reldb_constants::COMPONENT_CODE, (reldb_ca_config::RelDbCaConfigApi, reldb_ci_process::RelDbCiProcessApi)).await;
+    web_server
+        .add_module(
+            reldb_constants::COMPONENT_CODE,
+            (reldb_ca_config::RelDbCaConfigApi, reldb_ca_audit::RelDbCaAuditApi, reldb_ci_process::RelDbCiProcessApi),
+        )
+        .await;
     Ok(())
 }
 
 pub async fn init_db(mut funs: TardisFunsInst) -> TardisResult<()> {
+    reldb_crypto_helper::check_password_crypto_keys(&funs)?;
     bios_basic::rbum::rbum_initializer::init(funs.module_code(), funs.conf::<RelDbConfig>().rbum.clone()).await?;
     funs.begin().await?;
-    // TODO
+    funs.db().create_table_and_index(&reldb_config::ActiveModel::create_table_and_index_statement(TardisFuns::reldb().backend())).await?;
+    funs.db().create_table_and_index(&reldb_audit::ActiveModel::create_table_and_index_statement(TardisFuns::reldb().backend())).await?;
+    init_basic_info(&funs).await?;
+    if !funs.conf::<RelDbConfig>().password_crypto_old_keys.is_empty() {
+        RelDbConfigServ::rotate_password_crypto_key(&funs, &TardisContext::default()).await?;
+    }
     funs.commit().await?;
     Ok(())
 }
+
+/// Find the rbum kind and domain of the component, add them at the first startup.
+async fn init_basic_info(funs: &TardisFunsInst) -> TardisResult<()> {
+    let ctx = TardisContext::default();
+    let kind_config_id = if let Some(kind_config_id) = RbumKindServ::get_rbum_kind_id_by_code(reldb_constants::RBUM_KIND_CODE_RELDB_CONFIG, funs).await? {
+        kind_config_id
+    } else {
+        RbumKindServ::add_rbum(
+            &mut RbumKindAddReq {
+                code: TrimString(reldb_constants::RBUM_KIND_CODE_RELDB_CONFIG.to_string()),
+                name: TrimString(reldb_constants::RBUM_KIND_CODE_RELDB_CONFIG.to_string()),
+                note: None,
+                icon: None,
+                sort: None,
+                ext_table_name: Some(reldb_constants::RBUM_EXT_TABLE_RELDB_CONFIG.to_string()),
+                soft_delete_retention_sec: None,
+                scope_level: Some(reldb_constants::RBUM_SCOPE_LEVEL_GLOBAL),
+            },
+            funs,
+            &ctx,
+        )
+        .await?
+    };
+    let domain_reldb_id = if let Some(domain_reldb_id) = RbumDomainServ::get_rbum_domain_id_by_code(reldb_constants::COMPONENT_CODE, funs).await? {
+        domain_reldb_id
+    } else {
+        RbumDomainServ::add_rbum(
+            &mut RbumDomainAddReq {
+                code: TrimString(reldb_constants::COMPONENT_CODE.to_string()),
+                name: TrimString(reldb_constants::COMPONENT_CODE.to_string()),
+                note: None,
+                icon: None,
+                sort: None,
+                scope_level: Some(reldb_constants::RBUM_SCOPE_LEVEL_GLOBAL),
+            },
+            funs,
+            &ctx,
+        )
+        .await?
+    };
+    RelDbBasicInfoManager::set(BasicInfo { kind_config_id, domain_reldb_id })
+}
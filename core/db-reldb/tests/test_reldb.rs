@@ -0,0 +1,28 @@
+use tardis::basic::dto::TardisContext;
+use tardis::basic::result::TardisResult;
+use tardis::{testcontainers, tokio};
+
+use bios_db_reldb::{reldb_constants, reldb_initializer};
+
+mod test_basic;
+mod test_reldb_audit;
+mod test_reldb_config;
+mod test_reldb_pool;
+mod test_reldb_process;
+
+#[tokio::test]
+async fn test_reldb() -> TardisResult<()> {
+    let docker = testcontainers::clients::Cli::default();
+    let (_x, connect_uri) = test_basic::init(&docker).await?;
+    reldb_initializer::init_db(reldb_constants::get_tardis_inst()).await?;
+    let ctx = TardisContext {
+        own_paths: "t1/a1".to_string(),
+        owner: "u001".to_string(),
+        ..Default::default()
+    };
+    test_reldb_config::test(&connect_uri, &ctx).await?;
+    let inst_id = test_reldb_process::test(&connect_uri, &ctx).await?;
+    test_reldb_audit::test(&inst_id, &ctx).await?;
+    test_reldb_pool::test(&connect_uri, &ctx).await?;
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use tardis::basic::dto::TardisContext;
+use tardis::basic::result::TardisResult;
+use tardis::log::info;
+
+use bios_db_reldb::basic::dto::reldb_audit_dto::RelDbAuditFilterReq;
+use bios_db_reldb::basic::dto::reldb_process_dto::{RelDbExecuteReq, RelDbQueryReq};
+use bios_db_reldb::basic::serv::reldb_audit_serv::RelDbAuditServ;
+use bios_db_reldb::basic::serv::reldb_process_serv::RelDbProcessServ;
+use bios_db_reldb::reldb_constants;
+
+pub async fn test(inst_id: &str, ctx: &TardisContext) -> TardisResult<()> {
+    let funs = reldb_constants::get_tardis_inst();
+
+    info!("【test_reldb_audit】 : Prepare : RelDbProcessServ::query & RelDbProcessServ::execute");
+    for name in ["user1", "user1", "user2"] {
+        RelDbProcessServ::query(
+            inst_id,
+            &RelDbQueryReq {
+                sql: "SELECT  id FROM reldb_test_user\n WHERE name = :name AND age >= 0".to_string(),
+                params: HashMap::from([("name".to_string(), name.to_string())]),
+                page_number: None,
+                page_size: None,
+            },
+            &funs,
+            ctx,
+        )
+        .await?;
+    }
+    assert!(RelDbProcessServ::query(
+        inst_id,
+        &RelDbQueryReq {
+            sql: "SELECT id FROM reldb_test_user WHERE name = :name AND age >= 0".to_string(),
+            params: HashMap::new(),
+            page_number: None,
+            page_size: None,
+        },
+        &funs,
+        ctx,
+    )
+    .await
+    .is_err());
+    RelDbProcessServ::execute(
+        inst_id,
+        &RelDbExecuteReq {
+            sql: "UPDATE reldb_test_user SET age = age + :age WHERE age >= 0".to_string(),
+            params: HashMap::from([("age".to_string(), "1".to_string())]),
+        },
+        &funs,
+        ctx,
+    )
+    .await?;
+
+    info!("【test_reldb_audit】 : Test Find : RelDbAuditServ::paginate_audits");
+    let audits = RelDbAuditServ::paginate_audits(
+        &RelDbAuditFilterReq {
+            inst_id: Some(inst_id.to_string()),
+            ..Default::default()
+        },
+        1,
+        100,
+        None,
+        &funs,
+        ctx,
+    )
+    .await?;
+    let audits = audits.records.into_iter().filter(|audit| audit.normalized_sql.contains("age >= 0")).collect::<Vec<_>>();
+    assert_eq!(audits.len(), 5);
+    assert!(audits.iter().all(|audit| audit.inst_id == inst_id && audit.owner == ctx.owner && audit.own_paths == ctx.own_paths));
+
+    let query_audits = audits.iter().filter(|audit| audit.kind == "query").collect::<Vec<_>>();
+    assert_eq!(query_audits.len(), 4);
+    // Whitespaces are normalized, so the statements are grouped together
+    assert!(query_audits.iter().all(|audit| audit.normalized_sql == query_audits[0].normalized_sql));
+    assert!(query_audits[0].normalized_sql.starts_with("SELECT id FROM reldb_test_user WHERE"));
+    // The parameter values are not recorded, only their digests
+    assert!(audits.iter().all(|audit| !audit.normalized_sql.contains("user1") && !audit.normalized_sql.contains("user2")));
+    let succeeded_audits = query_audits.iter().filter(|audit| audit.error.is_empty()).collect::<Vec<_>>();
+    assert_eq!(succeeded_audits.len(), 3);
+    assert!(succeeded_audits.iter().all(|audit| audit.row_count == 1));
+    let mut digests = succeeded_audits.iter().map(|audit| audit.params_digest.as_str()).collect::<Vec<&str>>();
+    digests.sort();
+    digests.dedup();
+    assert_eq!(digests.len(), 2);
+    let failed_audits = query_audits.iter().filter(|audit| !audit.error.is_empty()).collect::<Vec<_>>();
+    assert_eq!(failed_audits.len(), 1);
+    assert_eq!(failed_audits[0].row_count, 0);
+
+    let execute_audits = audits.iter().filter(|audit| audit.kind == "execute").collect::<Vec<_>>();
+    assert_eq!(execute_audits.len(), 1);
+    assert!(execute_audits[0].error.is_empty());
+    assert_eq!(execute_audits[0].row_count, 7);
+
+    info!("【test_reldb_audit】 : Test Find By Other Context : RelDbAuditServ::paginate_audits");
+    let audits = RelDbAuditServ::paginate_audits(
+        &RelDbAuditFilterReq {
+            inst_id: Some(inst_id.to_string()),
+            owner: Some("u000".to_string()),
+            ..Default::default()
+        },
+        1,
+        100,
+        None,
+        &funs,
+        ctx,
+    )
+    .await?;
+    assert_eq!(audits.total_size, 0);
+    let audits = RelDbAuditServ::paginate_audits(
+        &RelDbAuditFilterReq {
+            inst_id: Some(inst_id.to_string()),
+            ..Default::default()
+        },
+        1,
+        100,
+        None,
+        &funs,
+        &TardisContext {
+            own_paths: "t0".to_string(),
+            owner: "u000".to_string(),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert_eq!(audits.total_size, 0);
+
+    Ok(())
+}
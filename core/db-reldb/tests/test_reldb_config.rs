@@ -0,0 +1,221 @@
+use tardis::basic::dto::TardisContext;
+use tardis::basic::field::TrimString;
+use tardis::basic::result::TardisResult;
+use tardis::log::info;
+use tardis::TardisFuns;
+
+use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumCertFilterReq};
+use bios_basic::rbum::rbum_enumeration::RbumCertRelKind;
+use bios_basic::rbum::serv::rbum_cert_serv::RbumCertServ;
+use bios_basic::rbum::serv::rbum_crud_serv::RbumCrudOperation;
+use bios_db_reldb::basic::dto::reldb_config_dto::{RelDbConfigAggAddOrModifyReq, RelDbConfigConnTestReq};
+use bios_db_reldb::basic::helper::reldb_crypto_helper;
+use bios_db_reldb::basic::serv::reldb_config_serv::RelDbConfigServ;
+use bios_db_reldb::reldb_constants;
+
+// Same as the old key in tests/config
+const OLD_PASSWORD_CRYPTO_KEY: &str = "fedcba9876543210";
+
+pub async fn test(connect_uri: &str, ctx: &TardisContext) -> TardisResult<()> {
+    let funs = reldb_constants::get_tardis_inst();
+
+    info!("【test_reldb_config】 : Test Connection : RelDbConfigServ::test_connection");
+    RelDbConfigServ::test_connection(
+        &RelDbConfigConnTestReq {
+            cert_user_name: TrimString("root".to_string()),
+            cert_password: TrimString("123456".to_string()),
+            connect_uri: TrimString(connect_uri.to_string()),
+        },
+        &funs,
+    )
+    .await?;
+    let err = RelDbConfigServ::test_connection(
+        &RelDbConfigConnTestReq {
+            cert_user_name: TrimString("root".to_string()),
+            cert_password: TrimString("123456".to_string()),
+            connect_uri: TrimString(connect_uri.replace("mysql://", "mongodb://")),
+        },
+        &funs,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code, "400-reldb-config-uri-invalid");
+
+    info!("【test_reldb_config】 : Test Add : RelDbConfigServ::add_config_agg");
+    let err = RelDbConfigServ::add_config_agg(
+        &RelDbConfigAggAddOrModifyReq {
+            name: TrimString("test db".to_string()),
+            cert_user_name: TrimString("root".to_string()),
+            cert_password: TrimString("wrong password".to_string()),
+            connect_uri: TrimString(connect_uri.to_string()),
+            icon: None,
+            allowed_tables: None,
+            statement_timeout_sec: None,
+            scope_level: None,
+            disabled: None,
+            version: None,
+        },
+        &funs,
+        ctx,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code, "400-reldb-config-connect-failed");
+    let inst_id = RelDbConfigServ::add_config_agg(
+        &RelDbConfigAggAddOrModifyReq {
+            name: TrimString("test db".to_string()),
+            cert_user_name: TrimString("root".to_string()),
+            cert_password: TrimString("123456".to_string()),
+            connect_uri: TrimString(connect_uri.to_string()),
+            icon: None,
+            allowed_tables: Some(vec![" reldb_test_user ".to_string(), "".to_string()]),
+            statement_timeout_sec: Some(10),
+            scope_level: None,
+            disabled: None,
+            version: None,
+        },
+        &funs,
+        ctx,
+    )
+    .await?;
+
+    info!("【test_reldb_config】 : Test Get : RelDbConfigServ::get_config_agg");
+    let config = RelDbConfigServ::get_config_agg(&inst_id, &funs, ctx).await?;
+    assert_eq!(config.name, "test db");
+    assert_eq!(config.own_paths, ctx.own_paths);
+    assert_eq!(config.allowed_tables, vec!["reldb_test_user".to_string()]);
+    assert_eq!(config.statement_timeout_sec, 10);
+    assert_eq!(config.cert_user_name, "root");
+    assert_eq!(config.connect_uri, connect_uri);
+    // The password is stored encrypted
+    assert_ne!(config.cert_password, "123456");
+    assert!(reldb_crypto_helper::is_encrypted_with_current_key(&config.cert_password, &funs)?);
+    assert_eq!(reldb_crypto_helper::decrypt_password(&config.cert_password, &funs)?, "123456");
+    let encrypted_password = config.cert_password;
+
+    info!("【test_reldb_config】 : Test Get Masked : RelDbConfigServ::get_masked_config_agg");
+    let config = RelDbConfigServ::get_masked_config_agg(&inst_id, &funs, ctx).await?;
+    assert_eq!(config.cert_password, reldb_crypto_helper::MASKED_PASSWORD);
+
+    info!("【test_reldb_config】 : Test Get By Other Tenant : RelDbConfigServ::get_config_agg");
+    let other_ctx = TardisContext {
+        own_paths: "t0".to_string(),
+        owner: "u000".to_string(),
+        ..Default::default()
+    };
+    assert!(RelDbConfigServ::get_config_agg(&inst_id, &funs, &other_ctx).await.is_err());
+
+    info!("【test_reldb_config】 : Test Modify : RelDbConfigServ::modify_config_agg");
+    // The masked password keeps the stored one
+    RelDbConfigServ::modify_config_agg(
+        &inst_id,
+        &RelDbConfigAggAddOrModifyReq {
+            name: TrimString("test db modified".to_string()),
+            cert_user_name: TrimString("root".to_string()),
+            cert_password: TrimString(reldb_crypto_helper::MASKED_PASSWORD.to_string()),
+            connect_uri: TrimString(connect_uri.to_string()),
+            icon: Some("icon".to_string()),
+            allowed_tables: Some(vec![]),
+            statement_timeout_sec: Some(20),
+            scope_level: None,
+            disabled: None,
+            version: None,
+        },
+        &funs,
+        ctx,
+    )
+    .await?;
+    let config = RelDbConfigServ::get_config_agg(&inst_id, &funs, ctx).await?;
+    assert_eq!(config.name, "test db modified");
+    assert_eq!(config.icon, "icon");
+    assert!(config.allowed_tables.is_empty());
+    assert_eq!(config.statement_timeout_sec, 20);
+    assert_eq!(config.cert_password, encrypted_password);
+    // The connection is tested again with the new password
+    let err = RelDbConfigServ::modify_config_agg(
+        &inst_id,
+        &RelDbConfigAggAddOrModifyReq {
+            name: TrimString("test db modified".to_string()),
+            cert_user_name: TrimString("root".to_string()),
+            cert_password: TrimString("wrong password".to_string()),
+            connect_uri: TrimString(connect_uri.to_string()),
+            icon: None,
+            allowed_tables: None,
+            statement_timeout_sec: None,
+            scope_level: None,
+            disabled: None,
+            version: None,
+        },
+        &funs,
+        ctx,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code, "400-reldb-config-connect-failed");
+    RelDbConfigServ::modify_config_agg(
+        &inst_id,
+        &RelDbConfigAggAddOrModifyReq {
+            name: TrimString("test db modified".to_string()),
+            cert_user_name: TrimString("root".to_string()),
+            cert_password: TrimString("123456".to_string()),
+            connect_uri: TrimString(connect_uri.to_string()),
+            icon: None,
+            allowed_tables: None,
+            statement_timeout_sec: None,
+            scope_level: None,
+            disabled: None,
+            version: None,
+        },
+        &funs,
+        ctx,
+    )
+    .await?;
+    let config = RelDbConfigServ::get_config_agg(&inst_id, &funs, ctx).await?;
+    // Encrypted again with a new iv
+    assert_ne!(config.cert_password, encrypted_password);
+    assert_eq!(reldb_crypto_helper::decrypt_password(&config.cert_password, &funs)?, "123456");
+
+    info!("【test_reldb_config】 : Test Find : RelDbConfigServ::paginate_configs");
+    let configs = RelDbConfigServ::paginate_configs(None, Some("test db modified".to_string()), 1, 10, None, None, &funs, ctx).await?;
+    assert_eq!(configs.total_size, 1);
+    assert_eq!(configs.records[0].id, inst_id);
+    let configs = RelDbConfigServ::paginate_configs(None, Some("test db modified".to_string()), 1, 10, None, None, &funs, &other_ctx).await?;
+    assert_eq!(configs.total_size, 0);
+
+    info!("【test_reldb_config】 : Test Rotate Key : RelDbConfigServ::rotate_password_crypto_key");
+    let cert_filter = RbumCertFilterReq {
+        basic: RbumBasicFilterReq {
+            own_paths: Some(ctx.own_paths.clone()),
+            ..Default::default()
+        },
+        rel_rbum_kind: Some(RbumCertRelKind::Item),
+        rel_rbum_id: Some(inst_id.clone()),
+        ..Default::default()
+    };
+    let cert_id = RbumCertServ::find_one_rbum(&cert_filter, &funs, ctx).await?.unwrap().id;
+    // Simulate a password stored before the key rotation
+    let iv = TardisFuns::field.nanoid_len(16);
+    let old_encrypted_password = format!(
+        "{}:{}:{}",
+        &TardisFuns::crypto.digest.sha256(OLD_PASSWORD_CRYPTO_KEY)?[..8],
+        iv,
+        TardisFuns::crypto.aes.encrypt_cbc("123456", OLD_PASSWORD_CRYPTO_KEY, &iv)?
+    );
+    RbumCertServ::reset_sk(&cert_id, &old_encrypted_password, &cert_filter, &funs, ctx).await?;
+    let config = RelDbConfigServ::get_config_agg(&inst_id, &funs, ctx).await?;
+    assert!(!reldb_crypto_helper::is_encrypted_with_current_key(&config.cert_password, &funs)?);
+    assert_eq!(reldb_crypto_helper::decrypt_password(&config.cert_password, &funs)?, "123456");
+    assert_eq!(RelDbConfigServ::rotate_password_crypto_key(&funs, ctx).await?, 1);
+    let config = RelDbConfigServ::get_config_agg(&inst_id, &funs, ctx).await?;
+    assert!(reldb_crypto_helper::is_encrypted_with_current_key(&config.cert_password, &funs)?);
+    assert_eq!(reldb_crypto_helper::decrypt_password(&config.cert_password, &funs)?, "123456");
+    assert_eq!(RelDbConfigServ::rotate_password_crypto_key(&funs, ctx).await?, 0);
+
+    info!("【test_reldb_config】 : Test Delete : RelDbConfigServ::delete_config_agg");
+    assert!(RelDbConfigServ::delete_config_agg(&inst_id, &funs, &other_ctx).await.is_err());
+    assert_eq!(RelDbConfigServ::delete_config_agg(&inst_id, &funs, ctx).await?, 1);
+    assert!(RelDbConfigServ::get_config_agg(&inst_id, &funs, ctx).await.is_err());
+    assert!(RbumCertServ::find_one_rbum(&cert_filter, &funs, ctx).await?.is_none());
+
+    Ok(())
+}
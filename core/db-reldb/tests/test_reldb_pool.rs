@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use tardis::basic::dto::TardisContext;
+use tardis::basic::field::TrimString;
+use tardis::basic::result::TardisResult;
+use tardis::log::info;
+use tardis::TardisFunsInst;
+
+use bios_db_reldb::basic::dto::reldb_config_dto::RelDbConfigAggAddOrModifyReq;
+use bios_db_reldb::basic::dto::reldb_process_dto::RelDbQueryReq;
+use bios_db_reldb::basic::helper::reldb_crypto_helper;
+use bios_db_reldb::basic::serv::reldb_config_serv::RelDbConfigServ;
+use bios_db_reldb::basic::serv::reldb_pool_serv::RelDbPoolServ;
+use bios_db_reldb::basic::serv::reldb_process_serv::RelDbProcessServ;
+use bios_db_reldb::reldb_constants;
+
+pub async fn test(connect_uri: &str, ctx: &TardisContext) -> TardisResult<()> {
+    let funs = reldb_constants::get_tardis_inst();
+    // The pools of other tenants are not counted
+    let ctx_t2 = TardisContext {
+        own_paths: "t2".to_string(),
+        owner: "u002".to_string(),
+        ..Default::default()
+    };
+    let inst_id1 = add_config("pool db 1", connect_uri, &funs, &ctx_t2).await?;
+    let inst_id2 = add_config("pool db 2", connect_uri, &funs, &ctx_t2).await?;
+    let inst_id3 = add_config("pool db 3", connect_uri, &funs, &ctx_t2).await?;
+
+    info!("【test_reldb_pool】 : Test Open : RelDbPoolServ::get_client");
+    assert!(get_opened_inst_ids(&ctx_t2).await?.is_empty());
+    query(&inst_id1, &funs, &ctx_t2).await?;
+    assert_eq!(get_opened_inst_ids(&ctx_t2).await?, vec![inst_id1.clone()]);
+    // The opened pool is reused
+    query(&inst_id1, &funs, &ctx_t2).await?;
+    assert_eq!(get_opened_inst_ids(&ctx_t2).await?, vec![inst_id1.clone()]);
+    query(&inst_id2, &funs, &ctx_t2).await?;
+    assert_eq!(get_opened_inst_ids(&ctx_t2).await?, sorted(vec![inst_id1.clone(), inst_id2.clone()]));
+    let health = RelDbPoolServ::health(&ctx_t2).await?;
+    assert!(health.iter().all(|pool| pool.healthy && pool.message.is_none()));
+    // Pools of other own paths are invisible
+    assert!(RelDbPoolServ::health(ctx).await?.iter().all(|pool| pool.inst_id != inst_id1 && pool.inst_id != inst_id2));
+
+    info!("【test_reldb_pool】 : Test Tenant Limit : RelDbPoolServ::get_client");
+    // The max pools per tenant is 2 in tests/config, the least recently used pool is evicted
+    query(&inst_id3, &funs, &ctx_t2).await?;
+    assert_eq!(get_opened_inst_ids(&ctx_t2).await?, sorted(vec![inst_id2.clone(), inst_id3.clone()]));
+    query(&inst_id2, &funs, &ctx_t2).await?;
+    query(&inst_id1, &funs, &ctx_t2).await?;
+    assert_eq!(get_opened_inst_ids(&ctx_t2).await?, sorted(vec![inst_id1.clone(), inst_id2.clone()]));
+
+    info!("【test_reldb_pool】 : Test Evict By Modify : RelDbConfigServ::modify_config_agg");
+    RelDbConfigServ::modify_config_agg(
+        &inst_id1,
+        &RelDbConfigAggAddOrModifyReq {
+            name: TrimString("pool db 1 modified".to_string()),
+            cert_user_name: TrimString("root".to_string()),
+            cert_password: TrimString(reldb_crypto_helper::MASKED_PASSWORD.to_string()),
+            connect_uri: TrimString(connect_uri.to_string()),
+            icon: None,
+            allowed_tables: None,
+            statement_timeout_sec: None,
+            scope_level: None,
+            disabled: None,
+            version: None,
+        },
+        &funs,
+        &ctx_t2,
+    )
+    .await?;
+    assert_eq!(get_opened_inst_ids(&ctx_t2).await?, vec![inst_id2.clone()]);
+    // The evicted pool frees its slot
+    query(&inst_id3, &funs, &ctx_t2).await?;
+    assert_eq!(get_opened_inst_ids(&ctx_t2).await?, sorted(vec![inst_id2.clone(), inst_id3.clone()]));
+
+    info!("【test_reldb_pool】 : Test Evict By Delete : RelDbConfigServ::delete_config_agg");
+    RelDbConfigServ::delete_config_agg(&inst_id2, &funs, &ctx_t2).await?;
+    assert_eq!(get_opened_inst_ids(&ctx_t2).await?, vec![inst_id3.clone()]);
+    assert!(query(&inst_id2, &funs, &ctx_t2).await.is_err());
+    // The modified config is opened again
+    query(&inst_id1, &funs, &ctx_t2).await?;
+    assert_eq!(get_opened_inst_ids(&ctx_t2).await?, sorted(vec![inst_id1.clone(), inst_id3.clone()]));
+
+    info!("【test_reldb_pool】 : Test Evict : RelDbPoolServ::evict");
+    assert!(RelDbPoolServ::evict(&inst_id1).await);
+    assert!(!RelDbPoolServ::evict(&inst_id1).await);
+    assert!(RelDbPoolServ::evict(&inst_id3).await);
+    assert!(get_opened_inst_ids(&ctx_t2).await?.is_empty());
+
+    Ok(())
+}
+
+async fn add_config(name: &str, connect_uri: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<String> {
+    RelDbConfigServ::add_config_agg(
+        &RelDbConfigAggAddOrModifyReq {
+            name: TrimString(name.to_string()),
+            cert_user_name: TrimString("root".to_string()),
+            cert_password: TrimString("123456".to_string()),
+            connect_uri: TrimString(connect_uri.to_string()),
+            icon: None,
+            allowed_tables: None,
+            statement_timeout_sec: None,
+            scope_level: None,
+            disabled: None,
+            version: None,
+        },
+        funs,
+        ctx,
+    )
+    .await
+}
+
+async fn query(inst_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+    RelDbProcessServ::query(
+        inst_id,
+        &RelDbQueryReq {
+            sql: "SELECT 1 AS v".to_string(),
+            params: HashMap::new(),
+            page_number: None,
+            page_size: None,
+        },
+        funs,
+        ctx,
+    )
+    .await?;
+    Ok(())
+}
+
+async fn get_opened_inst_ids(ctx: &TardisContext) -> TardisResult<Vec<String>> {
+    Ok(sorted(RelDbPoolServ::health(ctx).await?.into_iter().map(|pool| pool.inst_id).collect()))
+}
+
+fn sorted(mut inst_ids: Vec<String>) -> Vec<String> {
+    inst_ids.sort();
+    inst_ids
+}
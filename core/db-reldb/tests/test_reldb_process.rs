@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+
+use tardis::basic::dto::TardisContext;
+use tardis::basic::field::TrimString;
+use tardis::basic::result::TardisResult;
+use tardis::log::info;
+use tardis::serde_json::json;
+use tardis::TardisFuns;
+
+use bios_db_reldb::basic::dto::reldb_config_dto::RelDbConfigAggAddOrModifyReq;
+use bios_db_reldb::basic::dto::reldb_process_dto::{RelDbExecuteReq, RelDbQueryReq};
+use bios_db_reldb::basic::serv::reldb_config_serv::RelDbConfigServ;
+use bios_db_reldb::basic::serv::reldb_process_serv::RelDbProcessServ;
+use bios_db_reldb::reldb_constants;
+use bios_db_reldb::reldb_enumeration::RelDbColumnDataTypeKind;
+
+pub async fn test(connect_uri: &str, ctx: &TardisContext) -> TardisResult<String> {
+    let funs = reldb_constants::get_tardis_inst();
+    TardisFuns::reldb()
+        .conn()
+        .execute_one(
+            "CREATE TABLE IF NOT EXISTS reldb_test_user (id VARCHAR(32) NOT NULL PRIMARY KEY, name VARCHAR(255) NOT NULL, age INT NOT NULL)",
+            vec![],
+        )
+        .await?;
+    let inst_id = RelDbConfigServ::add_config_agg(
+        &RelDbConfigAggAddOrModifyReq {
+            name: TrimString("test process db".to_string()),
+            cert_user_name: TrimString("root".to_string()),
+            cert_password: TrimString("123456".to_string()),
+            connect_uri: TrimString(connect_uri.to_string()),
+            icon: None,
+            allowed_tables: Some(vec!["reldb_test_user".to_string()]),
+            statement_timeout_sec: None,
+            scope_level: None,
+            disabled: None,
+            version: None,
+        },
+        &funs,
+        ctx,
+    )
+    .await?;
+
+    info!("【test_reldb_process】 : Test Execute : RelDbProcessServ::execute");
+    for i in 1..=7 {
+        let result = RelDbProcessServ::execute(
+            &inst_id,
+            &RelDbExecuteReq {
+                sql: "INSERT INTO reldb_test_user (id, name, age) VALUES (:id, :name, :age)".to_string(),
+                params: HashMap::from([
+                    ("id".to_string(), format!("u{}", i)),
+                    ("name".to_string(), format!("user{}", i)),
+                    ("age".to_string(), i.to_string()),
+                ]),
+            },
+            &funs,
+            ctx,
+        )
+        .await?;
+        assert_eq!(result, Some("1".to_string()));
+    }
+    // The same parameter can be referenced more than once
+    let result = RelDbProcessServ::execute(
+        &inst_id,
+        &RelDbExecuteReq {
+            sql: "UPDATE reldb_test_user SET age = :age WHERE id = :id AND age <> :age".to_string(),
+            params: HashMap::from([("id".to_string(), "u7".to_string()), ("age".to_string(), "70".to_string())]),
+        },
+        &funs,
+        ctx,
+    )
+    .await?;
+    assert_eq!(result, Some("1".to_string()));
+
+    info!("【test_reldb_process】 : Test Query With Params : RelDbProcessServ::query");
+    let resp = RelDbProcessServ::query(
+        &inst_id,
+        &RelDbQueryReq {
+            sql: "SELECT name, id, age FROM reldb_test_user WHERE name = :name".to_string(),
+            params: HashMap::from([("name".to_string(), "user3".to_string())]),
+            page_number: None,
+            page_size: None,
+        },
+        &funs,
+        ctx,
+    )
+    .await?;
+    // Columns are in the order of the statement
+    assert_eq!(resp.columns.iter().map(|column| column.name.as_str()).collect::<Vec<&str>>(), vec!["name", "id", "age"]);
+    assert_eq!(resp.columns[0].data_type, RelDbColumnDataTypeKind::String);
+    assert_eq!(resp.columns[1].data_type, RelDbColumnDataTypeKind::String);
+    assert_eq!(resp.columns[2].data_type, RelDbColumnDataTypeKind::Number);
+    assert_eq!(resp.records, vec![vec![json!("user3"), json!("u3"), json!(3)]]);
+    assert!(!resp.has_more);
+    let resp = RelDbProcessServ::query(
+        &inst_id,
+        &RelDbQueryReq {
+            sql: "SELECT age FROM reldb_test_user WHERE id = :id".to_string(),
+            params: HashMap::from([("id".to_string(), "u7".to_string())]),
+            page_number: None,
+            page_size: None,
+        },
+        &funs,
+        ctx,
+    )
+    .await?;
+    assert_eq!(resp.records, vec![vec![json!(70)]]);
+    // The values are bound, not spliced into the statement
+    let resp = RelDbProcessServ::query(
+        &inst_id,
+        &RelDbQueryReq {
+            sql: "SELECT id FROM reldb_test_user WHERE name = :name".to_string(),
+            params: HashMap::from([("name".to_string(), "user3' OR '1'='1".to_string())]),
+            page_number: None,
+            page_size: None,
+        },
+        &funs,
+        ctx,
+    )
+    .await?;
+    assert!(resp.records.is_empty());
+    // Placeholders inside literals are not parameters
+    let resp = RelDbProcessServ::query(
+        &inst_id,
+        &RelDbQueryReq {
+            sql: "SELECT id FROM reldb_test_user WHERE name = ':name'".to_string(),
+            params: HashMap::new(),
+            page_number: None,
+            page_size: None,
+        },
+        &funs,
+        ctx,
+    )
+    .await?;
+    assert!(resp.records.is_empty());
+    // Placeholders inside comments are not parameters, and a trailing comment does not hide the paging
+    let resp = RelDbProcessServ::query(
+        &inst_id,
+        &RelDbQueryReq {
+            sql: "SELECT /* :name */ age FROM reldb_test_user -- :name\nWHERE id = :id -- or id = :other".to_string(),
+            params: HashMap::from([("id".to_string(), "u7".to_string())]),
+            page_number: None,
+            page_size: None,
+        },
+        &funs,
+        ctx,
+    )
+    .await?;
+    assert_eq!(resp.records, vec![vec![json!(70)]]);
+    let resp = RelDbProcessServ::query(
+        &inst_id,
+        &RelDbQueryReq {
+            sql: "SELECT id FROM reldb_test_user ORDER BY id -- LIMIT :limit".to_string(),
+            params: HashMap::new(),
+            page_number: None,
+            page_size: None,
+        },
+        &funs,
+        ctx,
+    )
+    .await?;
+    assert_eq!(resp.records.len(), 3);
+    assert!(resp.has_more);
+    let err = RelDbProcessServ::query(
+        &inst_id,
+        &RelDbQueryReq {
+            sql: "SELECT id FROM reldb_test_user WHERE name = :name".to_string(),
+            params: HashMap::new(),
+            page_number: None,
+            page_size: None,
+        },
+        &funs,
+        ctx,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code, "400-reldb-process-param-missing");
+
+    info!("【test_reldb_process】 : Test Query Paging : RelDbProcessServ::query");
+    // The default page size is 3 and the max rows is 5 in tests/config
+    let resp = RelDbProcessServ::query(
+        &inst_id,
+        &RelDbQueryReq {
+            sql: "SELECT id FROM reldb_test_user ORDER BY id".to_string(),
+            params: HashMap::new(),
+            page_number: None,
+            page_size: None,
+        },
+        &funs,
+        ctx,
+    )
+    .await?;
+    assert_eq!(resp.page_number, 1);
+    assert_eq!(resp.page_size, 3);
+    assert_eq!(resp.records, vec![vec![json!("u1")], vec![json!("u2")], vec![json!("u3")]]);
+    assert!(resp.has_more);
+    let resp = RelDbProcessServ::query(
+        &inst_id,
+        &RelDbQueryReq {
+            sql: "SELECT id FROM reldb_test_user ORDER BY id;".to_string(),
+            params: HashMap::new(),
+            page_number: Some(1),
+            page_size: Some(100),
+        },
+        &funs,
+        ctx,
+    )
+    .await?;
+    assert_eq!(resp.page_size, 5);
+    assert_eq!(resp.records.len(), 5);
+    assert!(resp.has_more);
+    let resp = RelDbProcessServ::query(
+        &inst_id,
+        &RelDbQueryReq {
+            sql: "SELECT id FROM reldb_test_user ORDER BY id".to_string(),
+            params: HashMap::new(),
+            page_number: Some(2),
+            page_size: Some(100),
+        },
+        &funs,
+        ctx,
+    )
+    .await?;
+    assert_eq!(resp.page_number, 2);
+    assert_eq!(resp.records, vec![vec![json!("u6")], vec![json!("u7")]]);
+    assert!(!resp.has_more);
+    let err = RelDbProcessServ::query(
+        &inst_id,
+        &RelDbQueryReq {
+            sql: "SELECT id FROM reldb_test_user ORDER BY id".to_string(),
+            params: HashMap::new(),
+            page_number: Some(u64::MAX),
+            page_size: Some(5),
+        },
+        &funs,
+        ctx,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code, "400-reldb-process-page-invalid");
+
+    info!("【test_reldb_process】 : Test Sql Guard : RelDbProcessServ::query & RelDbProcessServ::execute");
+    for (sql, code) in [
+        ("SELEC id FROM reldb_test_user", "403-reldb-sql-unparsable"),
+        ("SELECT id FROM reldb_test_user; SELECT name FROM reldb_test_user", "403-reldb-sql-multi-statement"),
+        ("DELETE FROM reldb_test_user", "403-reldb-sql-not-read"),
+        ("SELECT id FROM rbum_item", "403-reldb-sql-table-not-allowed"),
+        ("SELECT id FROM reldb_test_user WHERE id IN (SELECT id FROM rbum_item)", "403-reldb-sql-table-not-allowed"),
+    ] {
+        let err = RelDbProcessServ::query(
+            &inst_id,
+            &RelDbQueryReq {
+                sql: sql.to_string(),
+                params: HashMap::new(),
+                page_number: None,
+                page_size: None,
+            },
+            &funs,
+            ctx,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.code, code, "{}", sql);
+    }
+    for (sql, code) in [
+        ("SELECT id FROM reldb_test_user", "403-reldb-sql-not-dml"),
+        ("DROP TABLE reldb_test_user", "403-reldb-sql-not-dml"),
+        ("DELETE FROM reldb_test_user; DROP TABLE reldb_test_user", "403-reldb-sql-multi-statement"),
+        ("DELETE FROM rbum_item", "403-reldb-sql-table-not-allowed"),
+    ] {
+        let err = RelDbProcessServ::execute(
+            &inst_id,
+            &RelDbExecuteReq {
+                sql: sql.to_string(),
+                params: HashMap::new(),
+            },
+            &funs,
+            ctx,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.code, code, "{}", sql);
+    }
+    // Nothing is changed by the rejected statements
+    let resp = RelDbProcessServ::query(
+        &inst_id,
+        &RelDbQueryReq {
+            sql: "SELECT COUNT(1) AS cnt FROM reldb_test_user".to_string(),
+            params: HashMap::new(),
+            page_number: None,
+            page_size: None,
+        },
+        &funs,
+        ctx,
+    )
+    .await?;
+    assert_eq!(resp.records, vec![vec![json!(7)]]);
+
+    Ok(inst_id)
+}
@@ -22,6 +22,7 @@ local schema = {
         head_key_app = { type = "string", default = "Bios-App" },
         head_key_protocol = { type = "string", default = "Bios-Proto" },
         head_key_context = { type = "string", default = "Tardis-Context" },
+        head_key_client_ip = { type = "string", default = "Tardis-Client-Ip" },
 
         cache_key_token_info = { type = "string", default = "iam:cache:token:info:" },
         cache_key_account_info = { type = "string", default = "iam:cache:account:info:" },
@@ -80,6 +81,8 @@ local function cors(conf)
 end
 
 function _M.rewrite(conf, ctx)
+    -- Overwrite the header sent by the client, so that the services can trust it
+    core.request.set_header(ctx, conf.head_key_client_ip, core.request.get_remote_client_ip(ctx))
     local path = ngx.var.request_uri
     for _, prefix_path in pairs(conf.exclude_prefix_paths) do
         if string.sub(path, 1, string.len(prefix_path)) == prefix_path then
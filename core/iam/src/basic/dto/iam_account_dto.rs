@@ -62,6 +62,8 @@ pub struct IamAccountAggModifyReq {
     pub org_cate_ids: Option<Vec<String>>,
 
     pub exts: Option<HashMap<String, String>>,
+
+    pub version: Option<u32>,
 }
 
 #[derive(poem_openapi::Object, Serialize, Deserialize, Debug)]
@@ -73,6 +75,8 @@ pub struct IamAccountModifyReq {
 
     #[oai(validator(min_length = "2", max_length = "1000"))]
     pub icon: Option<String>,
+
+    pub version: Option<u32>,
 }
 
 #[derive(poem_openapi::Object, Serialize, Deserialize, Debug)]
@@ -120,6 +124,7 @@ pub struct IamAccountDetailResp {
     pub owner_name: Option<String>,
     pub create_time: DateTime<Utc>,
     pub update_time: DateTime<Utc>,
+    pub version: u32,
 
     pub scope_level: RbumScopeLevelKind,
     pub disabled: bool,
@@ -54,6 +54,8 @@ pub struct IamAppAggModifyReq {
     pub contact_phone: Option<String>,
 
     pub admin_ids: Option<Vec<String>>,
+
+    pub version: Option<u32>,
 }
 
 #[derive(poem_openapi::Object, Serialize, Deserialize, Debug)]
@@ -69,6 +71,8 @@ pub struct IamAppModifyReq {
     pub sort: Option<u32>,
     #[oai(validator(min_length = "2", max_length = "255"))]
     pub contact_phone: Option<String>,
+
+    pub version: Option<u32>,
 }
 
 #[derive(poem_openapi::Object, sea_orm::FromQueryResult, Serialize, Deserialize, Debug)]
@@ -99,6 +103,7 @@ pub struct IamAppDetailResp {
     pub owner_name: Option<String>,
     pub create_time: DateTime<Utc>,
     pub update_time: DateTime<Utc>,
+    pub version: u32,
 
     pub scope_level: RbumScopeLevelKind,
     pub disabled: bool,
@@ -3,7 +3,7 @@ use std::default::Default;
 use serde::{Deserialize, Serialize};
 use tardis::web::poem_openapi;
 
-use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumItemFilterFetcher, RbumItemRelFilterReq};
+use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumItemAttrSortReq, RbumItemAttrValueFilterReq, RbumItemFilterFetcher, RbumItemRelFilterReq};
 
 use crate::iam_enumeration::{IamResKind, IamRoleKind};
 
@@ -14,6 +14,8 @@ pub struct IamAccountFilterReq {
     pub rel: Option<RbumItemRelFilterReq>,
     pub rel2: Option<RbumItemRelFilterReq>,
     pub icon: Option<String>,
+    pub attrs: Option<Vec<RbumItemAttrValueFilterReq>>,
+    pub attr_sorts: Option<Vec<RbumItemAttrSortReq>>,
 }
 
 impl RbumItemFilterFetcher for IamAccountFilterReq {
@@ -26,6 +28,12 @@ impl RbumItemFilterFetcher for IamAccountFilterReq {
     fn rel2(&self) -> &Option<RbumItemRelFilterReq> {
         &self.rel2
     }
+    fn attrs(&self) -> &Option<Vec<RbumItemAttrValueFilterReq>> {
+        &self.attrs
+    }
+    fn attr_sorts(&self) -> &Option<Vec<RbumItemAttrSortReq>> {
+        &self.attr_sorts
+    }
 }
 
 #[derive(poem_openapi::Object, Serialize, Deserialize, Debug, Clone, Default)]
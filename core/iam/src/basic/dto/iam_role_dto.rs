@@ -47,6 +47,8 @@ pub struct IamRoleModifyReq {
     #[oai(validator(min_length = "2", max_length = "255"))]
     pub icon: Option<String>,
     pub sort: Option<u32>,
+
+    pub version: Option<u32>,
 }
 
 #[derive(poem_openapi::Object, sea_orm::FromQueryResult, Serialize, Deserialize, Debug)]
@@ -88,6 +90,7 @@ pub struct IamRoleDetailResp {
     pub owner_name: Option<String>,
     pub create_time: DateTime<Utc>,
     pub update_time: DateTime<Utc>,
+    pub version: u32,
 
     pub kind: IamRoleKind,
     pub scope_level: RbumScopeLevelKind,
@@ -32,6 +32,17 @@ pub struct IamSetCateModifyReq {
     pub sort: Option<u32>,
     #[oai(validator(min_length = "2", max_length = "1000"))]
     pub ext: Option<String>,
+
+    pub version: Option<u32>,
+}
+
+#[derive(poem_openapi::Object, Serialize, Deserialize, Debug)]
+pub struct IamSetCateMoveReq {
+    #[oai(validator(min_length = "2", max_length = "1000"))]
+    pub rbum_parent_cate_id: Option<String>,
+    pub sort: Option<u32>,
+
+    pub version: Option<u32>,
 }
 
 #[derive(poem_openapi::Object, Serialize, Deserialize, Debug)]
@@ -44,6 +44,8 @@ pub struct IamTenantModifyReq {
     #[oai(validator(min_length = "2", max_length = "2000"))]
     pub note: Option<String>,
     pub account_self_reg: Option<bool>,
+
+    pub version: Option<u32>,
 }
 
 #[derive(poem_openapi::Object, Serialize, Deserialize, Debug)]
@@ -87,6 +89,7 @@ pub struct IamTenantAggModifyReq {
     pub note: Option<String>,
     pub account_self_reg: Option<bool>,
     pub disabled: Option<bool>,
+    pub version: Option<u32>,
 
     pub cert_conf_by_user_pwd: Option<IamCertConfUserPwdAddOrModifyReq>,
     pub cert_conf_by_phone_vcode: Option<bool>,
@@ -106,6 +109,7 @@ pub struct IamTenantAggDetailResp {
     pub owner_name: Option<String>,
     pub create_time: DateTime<Utc>,
     pub update_time: DateTime<Utc>,
+    pub version: u32,
 
     pub disabled: bool,
 
@@ -159,6 +163,7 @@ pub struct IamTenantDetailResp {
     pub owner_name: Option<String>,
     pub create_time: DateTime<Utc>,
     pub update_time: DateTime<Utc>,
+    pub version: u32,
 
     pub scope_level: RbumScopeLevelKind,
     pub disabled: bool,
@@ -10,7 +10,8 @@ use tardis::db::sea_orm::*;
 use tardis::web::web_resp::{TardisPage, Void};
 use tardis::TardisFunsInst;
 
-use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumCertFilterReq, RbumItemRelFilterReq};
+use bios_basic::rbum::dto::rbum_cursor_dto::RbumCursorPage;
+use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumCertFilterReq, RbumItemRelFilterReq, RbumSortReq};
 use bios_basic::rbum::dto::rbum_item_dto::{RbumItemKernelAddReq, RbumItemModifyReq};
 use bios_basic::rbum::dto::rbum_rel_dto::RbumRelBoneResp;
 use bios_basic::rbum::serv::rbum_crud_serv::RbumCrudOperation;
@@ -91,9 +92,14 @@ impl RbumItemCrudOperation<iam_account::ActiveModel, IamAccountAddReq, IamAccoun
             name: modify_req.name.clone(),
             scope_level: modify_req.scope_level.clone(),
             disabled: modify_req.disabled,
+            version: None,
         }))
     }
 
+    fn get_modify_version(modify_req: &IamAccountModifyReq) -> Option<u32> {
+        modify_req.version
+    }
+
     async fn package_ext_modify(id: &str, modify_req: &IamAccountModifyReq, _: &TardisFunsInst, _: &TardisContext) -> TardisResult<Option<iam_account::ActiveModel>> {
         if modify_req.icon.is_none() {
             return Ok(None);
@@ -230,6 +236,7 @@ impl IamAccountServ {
                 scope_level: modify_req.scope_level.clone(),
                 disabled: modify_req.disabled,
                 icon: modify_req.icon.clone(),
+                version: modify_req.version,
             },
             funs,
             ctx,
@@ -308,6 +315,7 @@ impl IamAccountServ {
                 icon: modify_req.icon.clone(),
                 disabled: modify_req.disabled,
                 scope_level: None,
+                version: None,
             },
             funs,
             &mock_ctx,
@@ -439,13 +447,46 @@ impl IamAccountServ {
         ctx: &TardisContext,
     ) -> TardisResult<TardisPage<IamAccountSummaryAggResp>> {
         let accounts = IamAccountServ::paginate_items(filter, page_number, page_size, desc_sort_by_create, desc_sort_by_update, funs, ctx).await?;
-        let mut account_aggs = Vec::with_capacity(accounts.total_size as usize);
+        Ok(TardisPage {
+            page_size: accounts.page_size,
+            page_number: accounts.page_number,
+            total_size: accounts.total_size,
+            records: Self::package_account_summary_aggs(accounts.records, use_sys_org, use_sys_cert, funs, ctx).await?,
+        })
+    }
+
+    pub async fn paginate_account_summary_aggs_by_cursor(
+        filter: &IamAccountFilterReq,
+        use_sys_org: bool,
+        use_sys_cert: bool,
+        sorts: &[RbumSortReq],
+        cursor: Option<&str>,
+        page_size: u64,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<RbumCursorPage<IamAccountSummaryAggResp>> {
+        let accounts = IamAccountServ::paginate_items_by_cursor(filter, sorts, cursor, page_size, funs, ctx).await?;
+        Ok(RbumCursorPage {
+            page_size: accounts.page_size,
+            next_cursor: accounts.next_cursor,
+            records: Self::package_account_summary_aggs(accounts.records, use_sys_org, use_sys_cert, funs, ctx).await?,
+        })
+    }
+
+    async fn package_account_summary_aggs(
+        accounts: Vec<IamAccountSummaryResp>,
+        use_sys_org: bool,
+        use_sys_cert: bool,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<Vec<IamAccountSummaryAggResp>> {
+        let mut account_aggs = Vec::with_capacity(accounts.len());
         let set_id = if use_sys_org {
             IamSetServ::get_set_id_by_code(&IamSetServ::get_default_code(&IamSetKind::Org, ""), true, funs, ctx).await?
         } else {
             IamSetServ::get_default_set_id_by_ctx(&IamSetKind::Org, funs, ctx).await?
         };
-        for account in accounts.records {
+        for account in accounts {
             account_aggs.push(IamAccountSummaryAggResp {
                 id: account.id.clone(),
                 name: account.name,
@@ -483,12 +524,7 @@ impl IamAccountServ {
                 is_locked: funs.cache().exists(&format!("{}{}", funs.rbum_conf_cache_key_cert_locked_(), &account.id)).await?,
             });
         }
-        Ok(TardisPage {
-            page_size: accounts.page_size,
-            page_number: accounts.page_number,
-            total_size: accounts.total_size,
-            records: account_aggs,
-        })
+        Ok(account_aggs)
     }
 
     pub async fn find_name_by_ids(ids: Vec<String>, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Vec<String>> {
@@ -72,9 +72,14 @@ impl RbumItemCrudOperation<iam_app::ActiveModel, IamAppAddReq, IamAppModifyReq,
             name: modify_req.name.clone(),
             scope_level: modify_req.scope_level.clone(),
             disabled: modify_req.disabled,
+            version: None,
         }))
     }
 
+    fn get_modify_version(modify_req: &IamAppModifyReq) -> Option<u32> {
+        modify_req.version
+    }
+
     async fn package_ext_modify(id: &str, modify_req: &IamAppModifyReq, _: &TardisFunsInst, _: &TardisContext) -> TardisResult<Option<iam_app::ActiveModel>> {
         if modify_req.icon.is_none() && modify_req.sort.is_none() && modify_req.contact_phone.is_none() {
             return Ok(None);
@@ -186,6 +191,7 @@ impl IamAppServ {
                 icon: modify_req.icon.clone(),
                 sort: modify_req.sort,
                 contact_phone: modify_req.contact_phone.clone(),
+                version: modify_req.version,
             },
             funs,
             ctx,
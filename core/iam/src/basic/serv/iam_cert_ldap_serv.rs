@@ -1,8 +1,9 @@
+use itertools::Itertools;
 use ldap3::log::{error, warn};
 use std::collections::HashMap;
 
-use self::ldap::LdapClient;
-use crate::basic::dto::iam_account_dto::{IamAccountAddByLdapResp, IamAccountExtSysAddReq, IamAccountExtSysBatchAddReq};
+use self::ldap::{LdapClient, LdapSearchResp};
+use crate::basic::dto::iam_account_dto::{IamAccountAddByLdapResp, IamAccountAddReq, IamAccountExtSysAddReq, IamAccountExtSysBatchAddReq};
 use crate::console_passport::dto::iam_cp_cert_dto::IamCpUserPwdBindWithLdapReq;
 use crate::console_passport::serv::iam_cp_cert_user_pwd_serv::IamCpCertUserPwdServ;
 use crate::iam_enumeration::IamCertKernelKind;
@@ -36,7 +37,7 @@ use tardis::{
     TardisFuns, TardisFunsInst,
 };
 
-use super::{iam_account_serv::IamAccountServ, iam_cert_serv::IamCertServ, iam_tenant_serv::IamTenantServ};
+use super::{iam_account_serv::IamAccountServ, iam_attr_serv::IamAttrServ, iam_cert_serv::IamCertServ, iam_tenant_serv::IamTenantServ};
 
 pub struct IamCertLdapServ;
 
@@ -211,41 +212,52 @@ impl IamCertLdapServ {
         Ok(result)
     }
 
+    /// Get or add the accounts of the dns in one batch.
+    ///
+    /// The existing accounts are found with one query, the absent ones are fetched through one ldap connection and added by the batch API.
+    /// A dn that fails is reported in `fail` and does not prevent the others from being added.
     pub async fn batch_get_or_add_account_without_verify(
         add_req: IamAccountExtSysBatchAddReq,
         tenant_id: Option<String>,
         funs: &TardisFunsInst,
         ctx: &TardisContext,
     ) -> TardisResult<IamAccountAddByLdapResp> {
+        let mut fail: HashMap<String, String> = HashMap::new();
         if add_req.account_id.is_empty() {
-            Ok(IamAccountAddByLdapResp {
-                result: vec![],
-                fail: HashMap::new(),
-            })
-        } else {
-            let mut result: Vec<String> = Vec::new();
-            let mut fail: HashMap<String, String> = HashMap::new();
-            for account_id in add_req.account_id {
-                let verify = Self::get_or_add_account_without_verify(
-                    IamAccountExtSysAddReq {
-                        account_id: account_id.clone(),
-                        code: add_req.code.clone(),
-                    },
-                    tenant_id.clone(),
-                    funs,
-                    ctx,
-                )
-                .await;
-                if verify.is_ok() {
-                    result.push(verify.unwrap().0);
-                } else {
-                    let err_msg = if let Err(tardis_error) = verify { tardis_error.message } else { "".to_string() };
-                    warn!("get_or_add_account_without_verify resp is err:{}", err_msg);
-                    fail.insert(account_id, err_msg);
+            return Ok(IamAccountAddByLdapResp { result: vec![], fail });
+        }
+        let cert_conf_id = IamCertServ::get_cert_conf_id_by_code(&format!("{}{}", IamCertExtKind::Ldap, add_req.code), tenant_id, funs).await?;
+        let cert_conf = Self::get_cert_conf(&cert_conf_id, funs, ctx).await?;
+        let mut dn_account_ids = Self::find_cert_rel_accounts_by_dns(&add_req.account_id, &cert_conf_id, funs, ctx).await?;
+        let absent_dns = add_req.account_id.iter().filter(|dn| !dn_account_ids.contains_key(*dn)).unique().cloned().collect::<Vec<String>>();
+        if !absent_dns.is_empty() {
+            match Self::get_ldap_accounts_by_dns(&absent_dns, &cert_conf, funs).await {
+                Ok(ldap_accounts) => {
+                    let mut absent_accounts = Vec::with_capacity(ldap_accounts.len());
+                    for (dn, account) in ldap_accounts {
+                        if let Some(account) = account {
+                            absent_accounts.push((dn, account.get_simple_attr(&cert_conf.field_display_name).unwrap_or_default()));
+                        } else {
+                            fail.insert(dn.clone(), format!("not found ldap cert(openid): {}", dn));
+                        }
+                    }
+                    let mock_ctx = TardisContext {
+                        own_paths: ctx.own_paths.clone(),
+                        owner: TardisFuns::field.nanoid(),
+                        ..Default::default()
+                    };
+                    let (added_account_ids, add_fail) = Self::do_add_accounts(&absent_accounts, &cert_conf_id, funs, &mock_ctx).await?;
+                    dn_account_ids.extend(added_account_ids);
+                    fail.extend(add_fail);
                 }
+                Err(e) => fail.extend(absent_dns.into_iter().map(|dn| (dn, e.message.clone()))),
             }
-            Ok(IamAccountAddByLdapResp { result, fail })
         }
+        for (dn, err_msg) in &fail {
+            warn!("get_or_add_account_without_verify of {} resp is err:{}", dn, err_msg);
+        }
+        let result = add_req.account_id.iter().filter_map(|dn| dn_account_ids.get(dn).cloned()).collect();
+        Ok(IamAccountAddByLdapResp { result, fail })
     }
 
     pub async fn get_or_add_account_without_verify(
@@ -529,6 +541,109 @@ impl IamCertLdapServ {
         Ok(account_id)
     }
 
+    /// Batch version of [do_add_account](Self::do_add_account), the accounts and their certs are added by the batch API.
+    ///
+    /// The accounts are given as dn and name, return the ids of the added accounts and the error messages of the failed ones, both by dn.
+    async fn do_add_accounts(
+        accounts: &[(String, String)],
+        ldap_cert_conf_id: &str,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<(HashMap<String, String>, HashMap<String, String>)> {
+        let mut added_account_ids = HashMap::with_capacity(accounts.len());
+        let mut fail = HashMap::new();
+        if accounts.is_empty() {
+            return Ok((added_account_ids, fail));
+        }
+        // No ext values are given, so any required attribute fails all of them like `add_account_agg`
+        if IamAttrServ::find_account_attrs(funs, ctx).await?.iter().any(|i| i.required) {
+            fail.extend(accounts.iter().map(|(dn, _)| (dn.to_string(), "missing required field".to_string())));
+            return Ok((added_account_ids, fail));
+        }
+        let mut account_add_reqs = accounts
+            .iter()
+            .map(|(_, account_name)| IamAccountAddReq {
+                id: None,
+                name: TrimString(account_name.to_string()),
+                scope_level: Some(RbumScopeLevelKind::Root),
+                disabled: None,
+                icon: None,
+            })
+            .collect::<Vec<IamAccountAddReq>>();
+        let account_resp = IamAccountServ::add_items(&mut account_add_reqs, funs, ctx).await?;
+        let mut account_errors = account_resp.errors.into_iter().map(|error| (error.index, error.message)).collect::<HashMap<usize, String>>();
+        // The succeeded ids are in the order of the requests
+        let mut succeed_ids = account_resp.succeed_ids.into_iter();
+        let mut added_accounts = Vec::with_capacity(accounts.len());
+        for (index, (dn, _)) in accounts.iter().enumerate() {
+            if let Some(err_msg) = account_errors.remove(&index) {
+                fail.insert(dn.to_string(), err_msg);
+            } else if let Some(account_id) = succeed_ids.next() {
+                added_accounts.push((dn.to_string(), account_id));
+            }
+        }
+
+        let user_pwd_cert_conf_id =
+            IamCertServ::get_cert_conf_id_and_ext_opt_by_code(&IamCertKernelKind::UserPwd.to_string(), Some(ctx.own_paths.clone()), funs).await?.map(|cert_conf| cert_conf.id);
+        let mut cert_add_reqs = Vec::with_capacity(added_accounts.len() * 2);
+        let mut cert_dns = Vec::with_capacity(added_accounts.len() * 2);
+        for (dn, account_id) in &added_accounts {
+            if let Some(user_pwd_cert_conf_id) = &user_pwd_cert_conf_id {
+                // TODO Auto match rule
+                cert_add_reqs.push(RbumCertAddReq {
+                    ak: TrimString(TardisFuns::field.nanoid_len(8).to_lowercase()),
+                    sk: Some(TrimString(format!("{}0Pw$", TardisFuns::field.nanoid_len(6)))),
+                    vcode: None,
+                    ext: None,
+                    start_time: None,
+                    end_time: None,
+                    conn_uri: None,
+                    status: RbumCertStatusKind::Pending,
+                    rel_rbum_cert_conf_id: Some(user_pwd_cert_conf_id.to_string()),
+                    rel_rbum_kind: RbumCertRelKind::Item,
+                    rel_rbum_id: account_id.to_string(),
+                    is_outside: false,
+                });
+                cert_dns.push(dn.as_str());
+            }
+            cert_add_reqs.push(RbumCertAddReq {
+                ak: TrimString(dn.to_string()),
+                sk: None,
+                vcode: None,
+                ext: None,
+                start_time: None,
+                end_time: None,
+                conn_uri: None,
+                status: RbumCertStatusKind::Enabled,
+                rel_rbum_cert_conf_id: Some(ldap_cert_conf_id.to_string()),
+                rel_rbum_kind: RbumCertRelKind::Item,
+                rel_rbum_id: account_id.to_string(),
+                is_outside: false,
+            });
+            cert_dns.push(dn.as_str());
+        }
+        let cert_resp = RbumCertServ::add_rbums(&mut cert_add_reqs, funs, ctx).await?;
+        for error in cert_resp.errors {
+            fail.insert(cert_dns[error.index].to_string(), error.message);
+        }
+
+        for (dn, account_id) in added_accounts {
+            if fail.contains_key(&dn) {
+                continue;
+            }
+            // Fill the default values of the attributes
+            match IamAttrServ::add_or_modify_account_attr_values(&account_id, HashMap::new(), funs, ctx).await {
+                Ok(_) => {
+                    added_account_ids.insert(dn, account_id);
+                }
+                Err(e) => {
+                    fail.insert(dn, e.message);
+                }
+            }
+        }
+        Ok((added_account_ids, fail))
+    }
+
     async fn get_ldap_client(tenant_id: Option<String>, code: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<(LdapClient, IamCertConfLdapResp, String)> {
         let cert_conf_id = IamCertServ::get_cert_conf_id_by_code(&format!("{}{}", IamCertExtKind::Ldap, code), tenant_id, funs).await?;
         let cert_conf = Self::get_cert_conf(&cert_conf_id, funs, ctx).await?;
@@ -536,6 +651,39 @@ impl IamCertLdapServ {
         Ok((client, cert_conf, cert_conf_id))
     }
 
+    /// Fetch the ldap accounts of the dns through one connection.
+    async fn get_ldap_accounts_by_dns(dns: &[String], cert_conf: &IamCertConfLdapResp, funs: &TardisFunsInst) -> TardisResult<Vec<(String, Option<LdapSearchResp>)>> {
+        let mut ldap_client = LdapClient::new(&cert_conf.conn_uri, cert_conf.is_tls, &cert_conf.base_dn).await?;
+        if ldap_client.bind(&cert_conf.principal, &cert_conf.credentials).await?.is_none() {
+            ldap_client.unbind().await?;
+            return Err(funs.err().unauthorized("rbum_cert", "search_accounts", "ldap admin validation error", "401-rbum-cert-valid-error"));
+        };
+        let mut accounts = Vec::with_capacity(dns.len());
+        for dn in dns {
+            let account = ldap_client.get_by_dn(dn, &vec!["dn", "cn", &cert_conf.field_display_name]).await?;
+            accounts.push((dn.to_string(), account));
+        }
+        ldap_client.unbind().await?;
+        Ok(accounts)
+    }
+
+    /// Ids of the accounts related to the dns by their ldap certs in one query, by dn.
+    async fn find_cert_rel_accounts_by_dns(dns: &[String], rel_rbum_cert_conf_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<HashMap<String, String>> {
+        let certs = RbumCertServ::find_rbums(
+            &RbumCertFilterReq {
+                rel_rbum_cert_conf_ids: Some(vec![rel_rbum_cert_conf_id.to_string()]),
+                aks: Some(dns.to_vec()),
+                ..Default::default()
+            },
+            None,
+            None,
+            funs,
+            ctx,
+        )
+        .await?;
+        Ok(certs.into_iter().map(|cert| (cert.ak, cert.rel_rbum_id)).collect())
+    }
+
     async fn get_ldap_cert_account_by_account(account_id: &str, rel_rbum_cert_conf_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<String>> {
         let result = RbumCertServ::find_rbums(
             &RbumCertFilterReq {
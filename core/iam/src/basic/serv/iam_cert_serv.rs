@@ -3,12 +3,15 @@ use bios_basic::rbum::serv::rbum_rel_serv::RbumRelServ;
 use tardis::basic::dto::TardisContext;
 use tardis::basic::field::TrimString;
 use tardis::basic::result::TardisResult;
+use tardis::chrono::{DateTime, Utc};
+use tardis::serde_json::Value as JsonValue;
 use tardis::web::web_resp::TardisPage;
 use tardis::{TardisFuns, TardisFunsInst};
 
 use bios_basic::rbum::dto::rbum_cert_conf_dto::{RbumCertConfAddReq, RbumCertConfDetailResp, RbumCertConfIdAndExtResp, RbumCertConfSummaryResp};
 use bios_basic::rbum::dto::rbum_cert_dto::{RbumCertAddReq, RbumCertDetailResp, RbumCertModifyReq, RbumCertSummaryResp, RbumCertSummaryWithSkResp};
 use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumCertConfFilterReq, RbumCertFilterReq, RbumRelFilterReq};
+use bios_basic::rbum::dto::rbum_history_dto::RbumHistoryResp;
 use bios_basic::rbum::dto::rbum_rel_dto::{RbumRelAddReq, RbumRelBoneResp};
 use bios_basic::rbum::helper::rbum_scope_helper;
 use bios_basic::rbum::rbum_enumeration::{RbumCertRelKind, RbumCertStatusKind, RbumRelFromKind};
@@ -312,6 +315,14 @@ impl IamCertServ {
         Ok(result)
     }
 
+    pub async fn paginate_cert_conf_histories(id: &str, page_number: u64, page_size: u64, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<TardisPage<RbumHistoryResp>> {
+        RbumCertConfServ::paginate_rbum_histories(id, page_number, page_size, funs, ctx).await
+    }
+
+    pub async fn get_cert_conf_as_of(id: &str, time: DateTime<Utc>, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Option<JsonValue>> {
+        RbumCertConfServ::get_rbum_snapshot_as_of(id, time, funs, ctx).await
+    }
+
     pub async fn clean_cache_by_cert_conf(id: &str, fetched_cert_conf: Option<RbumCertConfSummaryResp>, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
         let rbum_cert_conf = if let Some(rbum_cert_conf) = fetched_cert_conf {
             rbum_cert_conf
@@ -848,6 +859,8 @@ impl IamCertServ {
                 rel: None,
                 rel2: None,
                 icon: None,
+                attrs: None,
+                attr_sorts: None,
             },
             funs,
             &mock_ctx,
@@ -106,40 +106,30 @@ impl IamIdentCacheServ {
         } else {
             tenant_or_app_id.clone()
         };
-        let ctx_clone = ctx.clone();
+        let task_ctx = ctx.clone();
         TaskProcessor::execute_task_with_ctx(
-            &funs.conf::<IamConfig>().cache_key_async_task_status,
-            move || async move {
-                let funs = iam_constants::get_tardis_inst();
-                let filter = IamAccountFilterReq {
-                    basic: RbumBasicFilterReq {
-                        own_paths: Some(own_paths),
-                        with_sub_own_paths: true,
+            "iam_account_context_refresh",
+            None,
+            move |_| {
+                let tenant_or_app_id = tenant_or_app_id.clone();
+                let own_paths = own_paths.clone();
+                let ctx_clone = task_ctx.clone();
+                async move {
+                    let funs = iam_constants::get_tardis_inst();
+                    let filter = IamAccountFilterReq {
+                        basic: RbumBasicFilterReq {
+                            own_paths: Some(own_paths),
+                            with_sub_own_paths: true,
+                            ..Default::default()
+                        },
                         ..Default::default()
-                    },
-                    ..Default::default()
-                };
-                let mut count = IamAccountServ::count_items(&filter, &funs, &ctx_clone).await.unwrap() as isize;
-                let mut page_number = 1;
-                while count > 0 {
-                    let ids = IamAccountServ::paginate_id_items(&filter, page_number, 100, None, None, &funs, &ctx_clone).await.unwrap().records;
-                    for id in ids {
-                        // Self::delete_tokens_and_contexts_by_account_id(&id, &funs).await.unwrap();
-                        // TODO reset account cache
-                        let tenant_ctx = IamCertServ::use_sys_or_tenant_ctx_unsafe(ctx_clone.clone())?;
-                        IamCertServ::package_tardis_account_context_and_resp(&id, &tenant_ctx.own_paths, "".to_string(), None, &funs, &tenant_ctx).await?;
-                    }
-                    page_number += 1;
-                    count -= 100;
-                }
-                if is_app {
-                    let mut count = IamRelServ::count_to_rels(&IamRelKind::IamAccountApp, &tenant_or_app_id, &funs, &ctx_clone).await.unwrap() as isize;
+                    };
+                    let mut count = IamAccountServ::count_items(&filter, &funs, &ctx_clone).await.unwrap() as isize;
                     let mut page_number = 1;
                     while count > 0 {
-                        let ids =
-                            IamRelServ::paginate_to_id_rels(&IamRelKind::IamAccountApp, &tenant_or_app_id, page_number, 100, None, None, &funs, &ctx_clone).await.unwrap().records;
+                        let ids = IamAccountServ::paginate_id_items(&filter, page_number, 100, None, None, &funs, &ctx_clone).await.unwrap().records;
                         for id in ids {
-                            // IamIdentCacheServ::delete_tokens_and_contexts_by_account_id(&id, &funs).await.unwrap();
+                            // Self::delete_tokens_and_contexts_by_account_id(&id, &funs).await.unwrap();
                             // TODO reset account cache
                             let tenant_ctx = IamCertServ::use_sys_or_tenant_ctx_unsafe(ctx_clone.clone())?;
                             IamCertServ::package_tardis_account_context_and_resp(&id, &tenant_ctx.own_paths, "".to_string(), None, &funs, &tenant_ctx).await?;
@@ -147,8 +137,26 @@ impl IamIdentCacheServ {
                         page_number += 1;
                         count -= 100;
                     }
+                    if is_app {
+                        let mut count = IamRelServ::count_to_rels(&IamRelKind::IamAccountApp, &tenant_or_app_id, &funs, &ctx_clone).await.unwrap() as isize;
+                        let mut page_number = 1;
+                        while count > 0 {
+                            let ids = IamRelServ::paginate_to_id_rels(&IamRelKind::IamAccountApp, &tenant_or_app_id, page_number, 100, None, None, &funs, &ctx_clone)
+                                .await
+                                .unwrap()
+                                .records;
+                            for id in ids {
+                                // IamIdentCacheServ::delete_tokens_and_contexts_by_account_id(&id, &funs).await.unwrap();
+                                // TODO reset account cache
+                                let tenant_ctx = IamCertServ::use_sys_or_tenant_ctx_unsafe(ctx_clone.clone())?;
+                                IamCertServ::package_tardis_account_context_and_resp(&id, &tenant_ctx.own_paths, "".to_string(), None, &funs, &tenant_ctx).await?;
+                            }
+                            page_number += 1;
+                            count -= 100;
+                        }
+                    }
+                    Ok(None)
                 }
-                Ok(())
             },
             funs,
             ctx,
@@ -11,7 +11,7 @@ use tardis::basic::result::TardisResult;
 use tardis::db::sea_orm::sea_query::{Expr, SelectStatement};
 use tardis::db::sea_orm::*;
 use tardis::web::web_resp::TardisPage;
-use tardis::TardisFunsInst;
+use tardis::{TardisFuns, TardisFunsInst};
 
 use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumSetItemFilterReq};
 use bios_basic::rbum::dto::rbum_item_dto::{RbumItemKernelAddReq, RbumItemModifyReq};
@@ -21,7 +21,7 @@ use bios_basic::rbum::serv::rbum_item_serv::RbumItemCrudOperation;
 use crate::basic::domain::iam_res;
 use crate::basic::dto::iam_filer_dto::IamResFilterReq;
 use crate::basic::dto::iam_res_dto::{IamResAddReq, IamResAggAddReq, IamResDetailResp, IamResModifyReq, IamResSummaryResp};
-use crate::basic::dto::iam_set_dto::IamSetItemAddReq;
+use crate::basic::dto::iam_set_dto::{IamSetCateMoveReq, IamSetItemAddReq};
 use crate::basic::serv::iam_key_cache_serv::IamResCacheServ;
 use crate::basic::serv::iam_rel_serv::IamRelServ;
 use crate::basic::serv::iam_set_serv::IamSetServ;
@@ -105,9 +105,14 @@ impl RbumItemCrudOperation<iam_res::ActiveModel, IamResAddReq, IamResModifyReq,
             name: modify_req.name.clone(),
             scope_level: modify_req.scope_level.clone(),
             disabled: modify_req.disabled,
+            version: None,
         }))
     }
 
+    fn get_modify_version(modify_req: &IamResModifyReq) -> Option<u32> {
+        modify_req.version
+    }
+
     async fn package_ext_modify(id: &str, modify_req: &IamResModifyReq, _: &TardisFunsInst, _: &TardisContext) -> TardisResult<Option<iam_res::ActiveModel>> {
         if modify_req.icon.is_none() && modify_req.sort.is_none() && modify_req.hide.is_none() && modify_req.action.is_none() {
             return Ok(None);
@@ -352,6 +357,21 @@ impl IamResServ {
         IamRelServ::paginate_to_simple_rels(rel_kind, res_id, page_number, page_size, desc_by_create, desc_by_update, funs, ctx).await
     }
 
+    /// Move the res cate, which is moved under the api cate if no parent cate is specified, the menu and api cates themselves can not be moved.
+    pub async fn move_res_cate(set_id: &str, set_cate_id: &str, move_req: &mut IamSetCateMoveReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        let menu_sys_code = String::from_utf8(vec![b'0'; funs.rbum_conf_set_cate_sys_code_node_len()])?;
+        let api_sys_code = TardisFuns::field.incr_by_base36(&menu_sys_code).unwrap();
+        let menu_cate_id = IamSetServ::get_cate_id_with_sys_code(set_id, &menu_sys_code, funs, ctx).await?;
+        let api_cate_id = IamSetServ::get_cate_id_with_sys_code(set_id, &api_sys_code, funs, ctx).await?;
+        if set_cate_id == menu_cate_id || set_cate_id == api_cate_id {
+            return Err(funs.err().bad_request(&Self::get_obj_name(), "move_cate", "the menu or api cate can not be moved", "400-iam-res-cate-move-illegal"));
+        }
+        if move_req.rbum_parent_cate_id.is_none() {
+            move_req.rbum_parent_cate_id = Some(api_cate_id);
+        }
+        IamSetServ::move_set_cate(set_cate_id, move_req, funs, ctx).await
+    }
+
     pub async fn add_res_agg(add_req: &mut IamResAggAddReq, set_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<String> {
         if add_req.res.kind == IamResKind::Menu {
             let set_cate_sys_code_node_len = funs.rbum_conf_set_cate_sys_code_node_len();
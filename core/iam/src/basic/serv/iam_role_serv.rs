@@ -9,24 +9,27 @@ use tardis::web::web_resp::TardisPage;
 use tardis::{TardisFuns, TardisFunsInst};
 
 use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumRelFilterReq};
-use bios_basic::rbum::dto::rbum_item_dto::{RbumItemKernelAddReq, RbumItemModifyReq};
+use bios_basic::rbum::dto::rbum_item_dto::{RbumItemKernelAddReq, RbumItemModifyReq, RbumItemRecycleSummaryResp};
 use bios_basic::rbum::dto::rbum_rel_dto::{RbumRelBoneResp, RbumRelCheckReq};
+use bios_basic::rbum::helper::rbum_rel_env_helper;
 use bios_basic::rbum::helper::rbum_scope_helper;
 use bios_basic::rbum::helper::rbum_scope_helper::get_scope_level_by_context;
 use bios_basic::rbum::rbum_enumeration::{RbumRelFromKind, RbumScopeLevelKind};
-use bios_basic::rbum::serv::rbum_item_serv::RbumItemCrudOperation;
+use bios_basic::rbum::serv::rbum_item_serv::{RbumItemCrudOperation, RbumItemServ};
 use bios_basic::rbum::serv::rbum_rel_serv::RbumRelServ;
 
 use super::iam_cert_serv::IamCertServ;
 use crate::basic::domain::iam_role;
-use crate::basic::dto::iam_filer_dto::IamRoleFilterReq;
+use crate::basic::dto::iam_filer_dto::{IamResFilterReq, IamRoleFilterReq};
+use crate::basic::dto::iam_res_dto::IamResSummaryResp;
 use crate::basic::dto::iam_role_dto::{IamRoleAddReq, IamRoleAggAddReq, IamRoleAggModifyReq, IamRoleDetailResp, IamRoleModifyReq, IamRoleSummaryResp};
-use crate::basic::serv::iam_key_cache_serv::IamIdentCacheServ;
+use crate::basic::serv::iam_key_cache_serv::{IamCacheResRelAddOrModifyReq, IamCacheResRelDeleteReq, IamIdentCacheServ, IamResCacheServ};
 use crate::basic::serv::iam_rel_serv::IamRelServ;
+use crate::basic::serv::iam_res_serv::IamResServ;
 use crate::iam_config::{IamBasicConfigApi, IamBasicInfoManager, IamConfig};
 use crate::iam_constants;
 use crate::iam_constants::{RBUM_SCOPE_LEVEL_APP, RBUM_SCOPE_LEVEL_TENANT};
-use crate::iam_enumeration::{IamRelKind, IamRoleKind};
+use crate::iam_enumeration::{IamRelKind, IamResKind, IamRoleKind};
 
 pub struct IamRoleServ;
 
@@ -96,9 +99,14 @@ impl RbumItemCrudOperation<iam_role::ActiveModel, IamRoleAddReq, IamRoleModifyRe
             name: modify_req.name.clone(),
             scope_level: modify_req.scope_level.clone(),
             disabled: modify_req.disabled,
+            version: None,
         }))
     }
 
+    fn get_modify_version(modify_req: &IamRoleModifyReq) -> Option<u32> {
+        modify_req.version
+    }
+
     async fn package_ext_modify(id: &str, modify_req: &IamRoleModifyReq, _: &TardisFunsInst, _: &TardisContext) -> TardisResult<Option<iam_role::ActiveModel>> {
         if modify_req.icon.is_none() && modify_req.sort.is_none() {
             return Ok(None);
@@ -139,58 +147,21 @@ impl RbumItemCrudOperation<iam_role::ActiveModel, IamRoleAddReq, IamRoleModifyRe
                 TardisFuns::json.obj_to_string(&role)?.as_str(),
             )
             .await?;
-        let role_id = id.to_string();
-        let ctx_clone = ctx.clone();
         if modify_req.disabled.unwrap_or(false) {
-            TaskProcessor::execute_task_with_ctx(
-                &funs.conf::<IamConfig>().cache_key_async_task_status,
-                || async move {
-                    let funs = iam_constants::get_tardis_inst();
-                    let mut count = IamRoleServ::count_rel_accounts(&role_id, &funs, &ctx_clone).await.unwrap() as isize;
-                    let mut page_number = 1;
-                    while count > 0 {
-                        let ids = IamRoleServ::paginate_id_rel_accounts(&role_id, page_number, 100, None, None, &funs, &ctx_clone).await.unwrap().records;
-                        for id in ids {
-                            IamIdentCacheServ::delete_tokens_and_contexts_by_account_id(&id, &funs).await.unwrap();
-                        }
-                        page_number += 1;
-                        count -= 100;
-                    }
-                    Ok(())
-                },
-                funs,
-                ctx,
-            )
-            .await?;
+            Self::delete_rel_accounts_tokens_and_contexts(id, funs, ctx).await?;
         }
         Ok(())
     }
 
     async fn after_delete_item(id: &str, _: &Option<IamRoleDetailResp>, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
         funs.cache().del(&format!("{}{}", funs.conf::<IamConfig>().cache_key_role_info_, id)).await?;
-        let role_id = id.to_string();
-        let ctx_clone = ctx.clone();
-        TaskProcessor::execute_task_with_ctx(
-            &funs.conf::<IamConfig>().cache_key_async_task_status,
-            || async move {
-                let funs = iam_constants::get_tardis_inst();
-                let mut count = IamRoleServ::count_rel_accounts(&role_id, &funs, &ctx_clone).await.unwrap() as isize;
-                let mut page_number = 1;
-                while count > 0 {
-                    let ids = IamRoleServ::paginate_id_rel_accounts(&role_id, page_number, 100, None, None, &funs, &ctx_clone).await.unwrap().records;
-                    for id in ids {
-                        IamIdentCacheServ::delete_tokens_and_contexts_by_account_id(&id, &funs).await.unwrap();
-                    }
-                    page_number += 1;
-                    count -= 100;
-                }
-                Ok(())
-            },
-            funs,
-            ctx,
-        )
-        .await?;
-        Ok(())
+        // The rels of a role in the recycle bin are kept, but the role must not grant its resources any more
+        Self::bind_rel_res_apis_in_cache(id, false, funs, ctx).await?;
+        Self::delete_rel_accounts_tokens_and_contexts(id, funs, ctx).await
+    }
+
+    async fn after_restore_item(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        Self::bind_rel_res_apis_in_cache(id, true, funs, ctx).await
     }
 
     async fn package_ext_query(query: &mut SelectStatement, _: bool, filter: &IamRoleFilterReq, _: &TardisFunsInst, _: &TardisContext) -> TardisResult<()> {
@@ -222,6 +193,21 @@ impl RbumItemCrudOperation<iam_role::ActiveModel, IamRoleAddReq, IamRoleModifyRe
 }
 
 impl IamRoleServ {
+    pub async fn paginate_recycled_roles(page_number: u64, page_size: u64, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<TardisPage<RbumItemRecycleSummaryResp>> {
+        RbumItemServ::paginate_recycled_rbums(
+            &RbumBasicFilterReq {
+                with_sub_own_paths: true,
+                rbum_kind_id: Some(funs.iam_basic_kind_role_id()),
+                ..Default::default()
+            },
+            page_number,
+            page_size,
+            funs,
+            ctx,
+        )
+        .await
+    }
+
     pub async fn add_role_agg(add_req: &mut IamRoleAggAddReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<String> {
         let role_id = Self::add_item(&mut add_req.role, funs, ctx).await?;
         if let Some(res_ids) = &add_req.res_ids {
@@ -303,6 +289,116 @@ impl IamRoleServ {
         IamRelServ::count_to_rels(&IamRelKind::IamAccountRole, role_id, funs, ctx).await
     }
 
+    /// Bind or unbind the role to the api resources it grants in the cache, directly or through the related menu and element resources.
+    async fn bind_rel_res_apis_in_cache(role_id: &str, is_bind: bool, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        let sys_ctx = IamCertServ::use_sys_ctx_unsafe(ctx.clone())?;
+        let rel_res_ids = IamRelServ::find_to_id_rels(&IamRelKind::IamResRole, role_id, None, None, funs, &sys_ctx).await?;
+        if rel_res_ids.is_empty() {
+            return Ok(());
+        }
+        let mut rel_res_api_ids = Vec::new();
+        for rel_res in Self::find_res_by_ids(rel_res_ids, funs, &sys_ctx).await? {
+            if rel_res.kind == IamResKind::Api {
+                rel_res_api_ids.push(rel_res.id);
+            } else {
+                rel_res_api_ids.extend(IamRelServ::find_to_id_rels(&IamRelKind::IamResApi, &rel_res.id, None, None, funs, &sys_ctx).await?);
+            }
+        }
+        rel_res_api_ids.sort();
+        rel_res_api_ids.dedup();
+        for rel_res_api in Self::find_res_by_ids(rel_res_api_ids, funs, &sys_ctx).await? {
+            if is_bind {
+                IamResCacheServ::add_or_modify_res_rel(
+                    &rel_res_api.code,
+                    &rel_res_api.method,
+                    &IamCacheResRelAddOrModifyReq {
+                        st: None,
+                        et: None,
+                        accounts: vec![],
+                        roles: vec![role_id.to_string()],
+                        groups: vec![],
+                        apps: vec![],
+                        tenants: vec![],
+                    },
+                    funs,
+                )
+                .await?;
+            } else {
+                IamResCacheServ::delete_res_rel(
+                    &rel_res_api.code,
+                    &rel_res_api.method,
+                    &IamCacheResRelDeleteReq {
+                        accounts: vec![],
+                        roles: vec![role_id.to_string()],
+                        groups: vec![],
+                        apps: vec![],
+                        tenants: vec![],
+                    },
+                    funs,
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn find_res_by_ids(res_ids: Vec<String>, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Vec<IamResSummaryResp>> {
+        if res_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        IamResServ::find_items(
+            &IamResFilterReq {
+                basic: RbumBasicFilterReq {
+                    ids: Some(res_ids),
+                    with_sub_own_paths: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+            None,
+            funs,
+            ctx,
+        )
+        .await
+    }
+
+    /// Delete the tokens and contexts of the accounts with the role in an asynchronous task.
+    async fn delete_rel_accounts_tokens_and_contexts(role_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        let role_id = role_id.to_string();
+        let task_ctx = ctx.clone();
+        TaskProcessor::execute_task_with_ctx(
+            "iam_role_account_token_delete",
+            None,
+            move |task| {
+                let role_id = role_id.clone();
+                let ctx = task_ctx.clone();
+                async move {
+                    let funs = iam_constants::get_tardis_inst();
+                    let total = IamRoleServ::count_rel_accounts(&role_id, &funs, &ctx).await?;
+                    let mut processed = 0;
+                    let mut page_number = 1;
+                    while processed < total {
+                        let ids = IamRoleServ::paginate_id_rel_accounts(&role_id, page_number, 100, None, None, &funs, &ctx).await?.records;
+                        if ids.is_empty() {
+                            break;
+                        }
+                        processed += ids.len() as u64;
+                        for id in ids {
+                            IamIdentCacheServ::delete_tokens_and_contexts_by_account_id(&id, &funs).await?;
+                        }
+                        task.set_progress((processed * 100 / total) as u8).await?;
+                        page_number += 1;
+                    }
+                    Ok(None)
+                }
+            },
+            funs,
+            ctx,
+        )
+        .await
+    }
+
     pub async fn find_id_rel_accounts(
         role_id: &str,
         desc_by_create: Option<bool>,
@@ -455,6 +551,7 @@ impl IamRoleServ {
                 to_rbum_item_id: role_id.to_string(),
                 from_attrs: Default::default(),
                 to_attrs: Default::default(),
+                from_ip: rbum_rel_env_helper::get_client_ip(ctx)?,
             },
             funs,
             ctx,
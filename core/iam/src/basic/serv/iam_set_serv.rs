@@ -6,7 +6,7 @@ use tardis::basic::result::TardisResult;
 use tardis::{TardisFuns, TardisFunsInst};
 
 use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumSetCateFilterReq, RbumSetItemFilterReq, RbumSetTreeFilterReq};
-use bios_basic::rbum::dto::rbum_set_cate_dto::{RbumSetCateAddReq, RbumSetCateModifyReq};
+use bios_basic::rbum::dto::rbum_set_cate_dto::{RbumSetCateAddReq, RbumSetCateModifyReq, RbumSetCateMoveReq};
 use bios_basic::rbum::dto::rbum_set_dto::{RbumSetAddReq, RbumSetPathResp, RbumSetTreeResp};
 use bios_basic::rbum::dto::rbum_set_item_dto::{RbumSetItemAddReq, RbumSetItemDetailResp, RbumSetItemModifyReq};
 use bios_basic::rbum::helper::rbum_scope_helper;
@@ -15,7 +15,7 @@ use bios_basic::rbum::rbum_enumeration::{RbumScopeLevelKind, RbumSetCateLevelQue
 use bios_basic::rbum::serv::rbum_crud_serv::RbumCrudOperation;
 use bios_basic::rbum::serv::rbum_set_serv::{RbumSetCateServ, RbumSetItemServ, RbumSetServ};
 
-use crate::basic::dto::iam_set_dto::{IamSetCateAddReq, IamSetCateModifyReq, IamSetItemAddReq};
+use crate::basic::dto::iam_set_dto::{IamSetCateAddReq, IamSetCateModifyReq, IamSetCateMoveReq, IamSetItemAddReq};
 use crate::iam_config::IamBasicConfigApi;
 use crate::iam_constants::{RBUM_SCOPE_LEVEL_APP, RBUM_SCOPE_LEVEL_TENANT};
 use crate::iam_enumeration::{IamRelKind, IamSetCateKind, IamSetKind};
@@ -145,6 +145,21 @@ impl IamSetServ {
                 sort: modify_req.sort,
                 ext: modify_req.ext.clone(),
                 scope_level: modify_req.scope_level.clone(),
+                version: modify_req.version,
+            },
+            funs,
+            ctx,
+        )
+        .await
+    }
+
+    pub async fn move_set_cate(set_cate_id: &str, move_req: &IamSetCateMoveReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        RbumSetCateServ::move_rbum(
+            set_cate_id,
+            &RbumSetCateMoveReq {
+                rbum_parent_cate_id: move_req.rbum_parent_cate_id.clone(),
+                sort: move_req.sort,
+                version: move_req.version,
             },
             funs,
             ctx,
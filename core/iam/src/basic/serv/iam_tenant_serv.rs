@@ -80,9 +80,14 @@ impl RbumItemCrudOperation<iam_tenant::ActiveModel, IamTenantAddReq, IamTenantMo
             name: modify_req.name.clone(),
             scope_level: modify_req.scope_level.clone(),
             disabled: modify_req.disabled,
+            version: None,
         }))
     }
 
+    fn get_modify_version(modify_req: &IamTenantModifyReq) -> Option<u32> {
+        modify_req.version
+    }
+
     async fn package_ext_modify(id: &str, modify_req: &IamTenantModifyReq, _: &TardisFunsInst, _: &TardisContext) -> TardisResult<Option<iam_tenant::ActiveModel>> {
         if modify_req.icon.is_none() && modify_req.sort.is_none() && modify_req.contact_phone.is_none() && modify_req.note.is_none() {
             return Ok(None);
@@ -252,6 +257,7 @@ impl IamTenantServ {
                 contact_phone: modify_req.contact_phone.clone(),
                 note: modify_req.note.clone(),
                 account_self_reg: modify_req.account_self_reg,
+                version: modify_req.version,
             },
             funs,
             ctx,
@@ -338,6 +344,7 @@ impl IamTenantServ {
             owner_name: tenant.owner_name.clone(),
             create_time: tenant.create_time,
             update_time: tenant.update_time,
+            version: tenant.version,
             disabled: tenant.disabled,
             icon: tenant.icon.clone(),
             sort: tenant.sort,
@@ -90,12 +90,8 @@ impl IamCcRoleApi {
             &id.0,
             desc_by_create.0,
             desc_by_update.0,
-            Some(vec![
-                RbumScopeLevelKind::Root.to_int().try_into().unwrap(),
-                RbumScopeLevelKind::L1.to_int().try_into().unwrap(),
-                RbumScopeLevelKind::L2.to_int().try_into().unwrap(),
-                RbumScopeLevelKind::L3.to_int().try_into().unwrap(),
-            ]),
+            // All levels except private
+            Some((RbumScopeLevelKind::Root.to_int()..=i8::MAX).map(|scope_level| scope_level as u8).collect()),
             &funs,
             &ctx,
         )
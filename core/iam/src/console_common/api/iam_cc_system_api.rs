@@ -1,30 +1,87 @@
 use bios_basic::process::task_processor::TaskProcessor;
+use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumTaskFilterReq};
+use bios_basic::rbum::dto::rbum_task_dto::RbumTaskDetailResp;
+use bios_basic::rbum::rbum_enumeration::RbumTaskStatusKind;
+use itertools::Itertools;
+use tardis::web::context_extractor::TardisContextExtractor;
 use tardis::web::poem_openapi;
-use tardis::web::poem_openapi::param::Path;
-use tardis::web::web_resp::{TardisApiResult, TardisResp};
+use tardis::web::poem_openapi::param::{Path, Query};
+use tardis::web::web_resp::{TardisApiResult, TardisPage, TardisResp, Void};
 
-use crate::iam_config::IamConfig;
 use crate::iam_constants;
 
 pub struct IamCcSystemApi;
 
 /// Common Console System API
-///
-/// Use commas to separate multiple task ids
 #[poem_openapi::OpenApi(prefix_path = "/cc/system", tag = "bios_basic::ApiTag::Common")]
 impl IamCcSystemApi {
     /// Get Async Task Status
+    ///
+    /// Use commas to separate multiple task ids, returns whether all the tasks are finished
     #[oai(path = "/task/:task_ids", method = "get")]
-    async fn task_check_finished(&self, task_ids: Path<String>) -> TardisApiResult<bool> {
+    async fn task_check_finished(&self, task_ids: Path<String>, ctx: TardisContextExtractor) -> TardisApiResult<bool> {
         let funs = iam_constants::get_tardis_inst();
-        let task_ids = task_ids.0.split(',');
-        for task_id in task_ids {
-            let task_id = task_id.parse().map_err(|_| funs.err().format_error("system", "task", "task id format error", "406-iam-task-id-foramt"))?;
-            let is_finished = TaskProcessor::check_status(&funs.conf::<IamConfig>().cache_key_async_task_status, task_id, funs.cache()).await?;
-            if !is_finished {
-                return TardisResp::ok(false);
-            }
-        }
-        TardisResp::ok(true)
+        let task_ids = task_ids.0.split(',').map(|task_id| task_id.to_string()).unique().collect::<Vec<String>>();
+        let tasks = TaskProcessor::find_tasks(
+            &RbumTaskFilterReq {
+                basic: RbumBasicFilterReq {
+                    ids: Some(task_ids.clone()),
+                    with_sub_own_paths: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            &funs,
+            &ctx.0,
+        )
+        .await?;
+        TardisResp::ok(tasks.len() == task_ids.len() && tasks.iter().all(|task| task.status.is_finished()))
+    }
+
+    /// Get Async Task
+    #[oai(path = "/task/:task_id/detail", method = "get")]
+    async fn get_task(&self, task_id: Path<String>, ctx: TardisContextExtractor) -> TardisApiResult<RbumTaskDetailResp> {
+        let funs = iam_constants::get_tardis_inst();
+        let result = TaskProcessor::get_task(&task_id.0, &funs, &ctx.0).await?;
+        TardisResp::ok(result)
+    }
+
+    /// Find Async Tasks
+    #[oai(path = "/task", method = "get")]
+    async fn paginate_tasks(
+        &self,
+        kind: Query<Option<String>>,
+        status: Query<Option<RbumTaskStatusKind>>,
+        page_number: Query<u64>,
+        page_size: Query<u64>,
+        desc_by_create: Query<Option<bool>>,
+        ctx: TardisContextExtractor,
+    ) -> TardisApiResult<TardisPage<RbumTaskDetailResp>> {
+        let funs = iam_constants::get_tardis_inst();
+        let result = TaskProcessor::paginate_tasks(
+            &RbumTaskFilterReq {
+                basic: RbumBasicFilterReq {
+                    with_sub_own_paths: true,
+                    ..Default::default()
+                },
+                kind: kind.0,
+                status: status.0,
+            },
+            page_number.0,
+            page_size.0,
+            desc_by_create.0,
+            &funs,
+            &ctx.0,
+        )
+        .await?;
+        TardisResp::ok(result)
+    }
+
+    /// Cancel Async Task
+    #[oai(path = "/task/:task_id/cancel", method = "put")]
+    async fn cancel_task(&self, task_id: Path<String>, ctx: TardisContextExtractor) -> TardisApiResult<Void> {
+        let funs = iam_constants::get_tardis_inst();
+        TaskProcessor::cancel_task(&task_id.0, &funs, &ctx.0).await?;
+        TardisResp::ok(Void {})
     }
 }
@@ -1,9 +1,12 @@
+use tardis::chrono::{DateTime, Utc};
+use tardis::serde_json::Value as JsonValue;
 use tardis::web::context_extractor::TardisContextExtractor;
 use tardis::web::poem_openapi;
 use tardis::web::poem_openapi::{param::Path, param::Query, payload::Json};
 use tardis::web::web_resp::{TardisApiResult, TardisPage, TardisResp, Void};
 
 use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumItemRelFilterReq};
+use bios_basic::rbum::dto::rbum_history_dto::RbumHistoryResp;
 use bios_basic::rbum::rbum_enumeration::RbumRelFromKind;
 use bios_basic::rbum::serv::rbum_item_serv::RbumItemCrudOperation;
 
@@ -123,6 +126,31 @@ impl IamCsAccountApi {
         TardisResp::ok(Void {})
     }
 
+    /// Find Change Histories By Account Id
+    #[oai(path = "/:id/history", method = "get")]
+    async fn paginate_histories(
+        &self,
+        id: Path<String>,
+        tenant_id: Query<Option<String>>,
+        page_number: Query<u64>,
+        page_size: Query<u64>,
+        ctx: TardisContextExtractor,
+    ) -> TardisApiResult<TardisPage<RbumHistoryResp>> {
+        let ctx = IamCertServ::try_use_tenant_ctx(ctx.0, tenant_id.0)?;
+        let funs = iam_constants::get_tardis_inst();
+        let result = IamAccountServ::paginate_item_histories(&id.0, page_number.0, page_size.0, &funs, &ctx).await?;
+        TardisResp::ok(result)
+    }
+
+    /// Get Account As Of Time By Account Id
+    #[oai(path = "/:id/history/as-of", method = "get")]
+    async fn get_as_of(&self, id: Path<String>, tenant_id: Query<Option<String>>, time: Query<DateTime<Utc>>, ctx: TardisContextExtractor) -> TardisApiResult<Option<JsonValue>> {
+        let ctx = IamCertServ::try_use_tenant_ctx(ctx.0, tenant_id.0)?;
+        let funs = iam_constants::get_tardis_inst();
+        let result = IamAccountServ::get_item_snapshot_as_of(&id.0, time.0, &funs, &ctx).await?;
+        TardisResp::ok(result)
+    }
+
     /// Delete Token By Account Id
     #[oai(path = "/:id/token", method = "delete")]
     async fn offline(&self, id: Path<String>, tenant_id: Query<Option<String>>, ctx: TardisContextExtractor) -> TardisApiResult<Void> {
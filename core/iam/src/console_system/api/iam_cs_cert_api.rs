@@ -1,10 +1,13 @@
+use tardis::chrono::{DateTime, Utc};
+use tardis::serde_json::Value as JsonValue;
 use tardis::web::context_extractor::TardisContextExtractor;
 use tardis::web::poem_openapi;
-use tardis::web::poem_openapi::{param::Query, payload::Json};
-use tardis::web::web_resp::{TardisApiResult, TardisResp, Void};
+use tardis::web::poem_openapi::{param::Path, param::Query, payload::Json};
+use tardis::web::web_resp::{TardisApiResult, TardisPage, TardisResp, Void};
 
 use bios_basic::rbum::dto::rbum_cert_dto::{RbumCertSummaryResp, RbumCertSummaryWithSkResp};
 use bios_basic::rbum::dto::rbum_filer_dto::RbumCertFilterReq;
+use bios_basic::rbum::dto::rbum_history_dto::RbumHistoryResp;
 use bios_basic::rbum::helper::rbum_scope_helper::get_max_level_id_by_context;
 
 use crate::basic::dto::iam_cert_dto::{IamCertExtAddReq, IamCertUserPwdRestReq};
@@ -96,4 +99,35 @@ impl IamCsCertApi {
         };
         TardisResp::ok(rbum_cert)
     }
+
+    /// Find Change Histories By Cert Conf Id
+    #[oai(path = "/conf/:id/history", method = "get")]
+    async fn paginate_cert_conf_histories(
+        &self,
+        id: Path<String>,
+        tenant_id: Query<Option<String>>,
+        page_number: Query<u64>,
+        page_size: Query<u64>,
+        ctx: TardisContextExtractor,
+    ) -> TardisApiResult<TardisPage<RbumHistoryResp>> {
+        let ctx = IamCertServ::try_use_tenant_ctx(ctx.0, tenant_id.0)?;
+        let funs = iam_constants::get_tardis_inst();
+        let result = IamCertServ::paginate_cert_conf_histories(&id.0, page_number.0, page_size.0, &funs, &ctx).await?;
+        TardisResp::ok(result)
+    }
+
+    /// Get Cert Conf As Of Time By Cert Conf Id
+    #[oai(path = "/conf/:id/history/as-of", method = "get")]
+    async fn get_cert_conf_as_of(
+        &self,
+        id: Path<String>,
+        tenant_id: Query<Option<String>>,
+        time: Query<DateTime<Utc>>,
+        ctx: TardisContextExtractor,
+    ) -> TardisApiResult<Option<JsonValue>> {
+        let ctx = IamCertServ::try_use_tenant_ctx(ctx.0, tenant_id.0)?;
+        let funs = iam_constants::get_tardis_inst();
+        let result = IamCertServ::get_cert_conf_as_of(&id.0, time.0, &funs, &ctx).await?;
+        TardisResp::ok(result)
+    }
 }
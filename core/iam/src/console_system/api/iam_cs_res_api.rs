@@ -13,7 +13,7 @@ use bios_basic::rbum::serv::rbum_item_serv::RbumItemCrudOperation;
 
 use crate::basic::dto::iam_filer_dto::IamResFilterReq;
 use crate::basic::dto::iam_res_dto::{IamResAggAddReq, IamResDetailResp, IamResModifyReq, IamResSummaryResp};
-use crate::basic::dto::iam_set_dto::{IamSetCateAddReq, IamSetCateModifyReq};
+use crate::basic::dto::iam_set_dto::{IamSetCateAddReq, IamSetCateModifyReq, IamSetCateMoveReq};
 use crate::basic::serv::iam_rel_serv::IamRelServ;
 use crate::basic::serv::iam_res_serv::IamResServ;
 use crate::basic::serv::iam_set_serv::IamSetServ;
@@ -261,4 +261,18 @@ impl IamCsResApi {
         funs.commit().await?;
         TardisResp::ok(Void {})
     }
+
+    /// Move Res Cate By Res Cate Id
+    ///
+    /// The sub cates and the res of the cate are moved along, the cate is moved under the api cate if no parent cate is specified.
+    /// The menu and api cates themselves can not be moved.
+    #[oai(path = "/cate/:id/move", method = "put")]
+    async fn move_set_cate(&self, id: Path<String>, mut move_req: Json<IamSetCateMoveReq>, ctx: TardisContextExtractor) -> TardisApiResult<Void> {
+        let mut funs = iam_constants::get_tardis_inst();
+        funs.begin().await?;
+        let set_id = IamSetServ::get_default_set_id_by_ctx(&IamSetKind::Res, &funs, &ctx.0).await?;
+        IamResServ::move_res_cate(&set_id, &id.0, &mut move_req.0, &funs, &ctx.0).await?;
+        funs.commit().await?;
+        TardisResp::ok(Void {})
+    }
 }
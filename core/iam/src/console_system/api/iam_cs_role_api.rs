@@ -1,10 +1,14 @@
 use bios_basic::process::task_processor::TaskProcessor;
+use tardis::chrono::{DateTime, Utc};
+use tardis::serde_json::Value as JsonValue;
 use tardis::web::context_extractor::TardisContextExtractor;
 use tardis::web::poem_openapi;
 use tardis::web::poem_openapi::{param::Path, param::Query, payload::Json};
 use tardis::web::web_resp::{TardisApiResult, TardisPage, TardisResp, Void};
 
 use bios_basic::rbum::dto::rbum_filer_dto::RbumBasicFilterReq;
+use bios_basic::rbum::dto::rbum_history_dto::RbumHistoryResp;
+use bios_basic::rbum::dto::rbum_item_dto::RbumItemRecycleSummaryResp;
 use bios_basic::rbum::dto::rbum_rel_dto::RbumRelBoneResp;
 use bios_basic::rbum::serv::rbum_item_serv::RbumItemCrudOperation;
 
@@ -112,6 +116,57 @@ impl IamCsRoleApi {
         TardisResp::ok(Void {})
     }
 
+    /// Find Deleted Roles
+    #[oai(path = "/recycle", method = "get")]
+    async fn paginate_recycled(
+        &self,
+        tenant_id: Query<Option<String>>,
+        page_number: Query<u64>,
+        page_size: Query<u64>,
+        ctx: TardisContextExtractor,
+    ) -> TardisApiResult<TardisPage<RbumItemRecycleSummaryResp>> {
+        let ctx = IamCertServ::try_use_tenant_ctx(ctx.0, tenant_id.0)?;
+        let funs = iam_constants::get_tardis_inst();
+        let result = IamRoleServ::paginate_recycled_roles(page_number.0, page_size.0, &funs, &ctx).await?;
+        TardisResp::ok(result)
+    }
+
+    /// Restore Deleted Role By Role Id
+    #[oai(path = "/:id/restore", method = "put")]
+    async fn restore(&self, id: Path<String>, tenant_id: Query<Option<String>>, ctx: TardisContextExtractor) -> TardisApiResult<Void> {
+        let ctx = IamCertServ::try_use_tenant_ctx(ctx.0, tenant_id.0)?;
+        let mut funs = iam_constants::get_tardis_inst();
+        funs.begin().await?;
+        IamRoleServ::restore_item(&id.0, &funs, &ctx).await?;
+        funs.commit().await?;
+        TardisResp::ok(Void {})
+    }
+
+    /// Find Change Histories By Role Id
+    #[oai(path = "/:id/history", method = "get")]
+    async fn paginate_histories(
+        &self,
+        id: Path<String>,
+        tenant_id: Query<Option<String>>,
+        page_number: Query<u64>,
+        page_size: Query<u64>,
+        ctx: TardisContextExtractor,
+    ) -> TardisApiResult<TardisPage<RbumHistoryResp>> {
+        let ctx = IamCertServ::try_use_tenant_ctx(ctx.0, tenant_id.0)?;
+        let funs = iam_constants::get_tardis_inst();
+        let result = IamRoleServ::paginate_item_histories(&id.0, page_number.0, page_size.0, &funs, &ctx).await?;
+        TardisResp::ok(result)
+    }
+
+    /// Get Role As Of Time By Role Id
+    #[oai(path = "/:id/history/as-of", method = "get")]
+    async fn get_as_of(&self, id: Path<String>, tenant_id: Query<Option<String>>, time: Query<DateTime<Utc>>, ctx: TardisContextExtractor) -> TardisApiResult<Option<JsonValue>> {
+        let ctx = IamCertServ::try_use_tenant_ctx(ctx.0, tenant_id.0)?;
+        let funs = iam_constants::get_tardis_inst();
+        let result = IamRoleServ::get_item_snapshot_as_of(&id.0, time.0, &funs, &ctx).await?;
+        TardisResp::ok(result)
+    }
+
     /// Add Role Rel Account
     #[oai(path = "/:id/account/:account_id", method = "put")]
     async fn add_rel_account(&self, id: Path<String>, account_id: Path<String>, tenant_id: Query<Option<String>>, ctx: TardisContextExtractor) -> TardisApiResult<Void> {
@@ -1,9 +1,13 @@
+use tardis::chrono::{DateTime, Utc};
+use tardis::serde_json::Value as JsonValue;
 use tardis::web::context_extractor::TardisContextExtractor;
 use tardis::web::poem_openapi;
 use tardis::web::poem_openapi::{param::Path, param::Query, payload::Json};
 use tardis::web::web_resp::{TardisApiResult, TardisPage, TardisResp, Void};
 
-use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumItemRelFilterReq};
+use bios_basic::rbum::dto::rbum_cursor_dto::RbumCursorPage;
+use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumItemRelFilterReq, RbumSortReq};
+use bios_basic::rbum::dto::rbum_history_dto::RbumHistoryResp;
 use bios_basic::rbum::rbum_enumeration::RbumRelFromKind;
 use bios_basic::rbum::serv::rbum_item_serv::RbumItemCrudOperation;
 
@@ -113,6 +117,54 @@ impl IamCtAccountApi {
         TardisResp::ok(result)
     }
 
+    /// Find Accounts By Cursor
+    ///
+    /// `sort_by` is a comma separated list of columns, a column prefixed with `-` is sorted in descending order, e.g. `-create_time,name`.
+    #[oai(path = "/cursor", method = "get")]
+    async fn paginate_by_cursor(
+        &self,
+        name: Query<Option<String>>,
+        role_id: Query<Option<String>>,
+        app_id: Query<Option<String>>,
+        with_sub: Query<Option<bool>>,
+        cursor: Query<Option<String>>,
+        page_size: Query<u64>,
+        sort_by: Query<Option<String>>,
+        ctx: TardisContextExtractor,
+    ) -> TardisApiResult<RbumCursorPage<IamAccountSummaryAggResp>> {
+        let ctx = IamCertServ::try_use_app_ctx(ctx.0, app_id.0)?;
+        let funs = iam_constants::get_tardis_inst();
+        let rel = role_id.0.map(|role_id| RbumItemRelFilterReq {
+            rel_by_from: true,
+            tag: Some(IamRelKind::IamAccountRole.to_string()),
+            from_rbum_kind: Some(RbumRelFromKind::Item),
+            rel_item_id: Some(role_id),
+            own_paths: Some(ctx.own_paths.clone()),
+            ..Default::default()
+        });
+        let result = IamAccountServ::paginate_account_summary_aggs_by_cursor(
+            &IamAccountFilterReq {
+                basic: RbumBasicFilterReq {
+                    name: name.0,
+                    with_sub_own_paths: with_sub.0.unwrap_or(false),
+                    enabled: Some(true),
+                    ..Default::default()
+                },
+                rel,
+                ..Default::default()
+            },
+            false,
+            false,
+            &sort_by.0.map(|sort_by| RbumSortReq::parse(&sort_by)).unwrap_or_default(),
+            cursor.0.as_deref(),
+            page_size.0,
+            &funs,
+            &ctx,
+        )
+        .await?;
+        TardisResp::ok(result)
+    }
+
     /// Delete Account
     #[oai(path = "/:id", method = "delete")]
     async fn delete(&self, id: Path<String>, ctx: TardisContextExtractor) -> TardisApiResult<Void> {
@@ -123,6 +175,31 @@ impl IamCtAccountApi {
         TardisResp::ok(Void {})
     }
 
+    /// Find Change Histories By Account Id
+    #[oai(path = "/:id/history", method = "get")]
+    async fn paginate_histories(
+        &self,
+        id: Path<String>,
+        app_id: Query<Option<String>>,
+        page_number: Query<u64>,
+        page_size: Query<u64>,
+        ctx: TardisContextExtractor,
+    ) -> TardisApiResult<TardisPage<RbumHistoryResp>> {
+        let ctx = IamCertServ::try_use_app_ctx(ctx.0, app_id.0)?;
+        let funs = iam_constants::get_tardis_inst();
+        let result = IamAccountServ::paginate_item_histories(&id.0, page_number.0, page_size.0, &funs, &ctx).await?;
+        TardisResp::ok(result)
+    }
+
+    /// Get Account As Of Time By Account Id
+    #[oai(path = "/:id/history/as-of", method = "get")]
+    async fn get_as_of(&self, id: Path<String>, app_id: Query<Option<String>>, time: Query<DateTime<Utc>>, ctx: TardisContextExtractor) -> TardisApiResult<Option<JsonValue>> {
+        let ctx = IamCertServ::try_use_app_ctx(ctx.0, app_id.0)?;
+        let funs = iam_constants::get_tardis_inst();
+        let result = IamAccountServ::get_item_snapshot_as_of(&id.0, time.0, &funs, &ctx).await?;
+        TardisResp::ok(result)
+    }
+
     /// Count Accounts
     #[oai(path = "/total", method = "get")]
     async fn count(&self, app_id: Query<Option<String>>, ctx: TardisContextExtractor) -> TardisApiResult<u64> {
@@ -8,7 +8,7 @@ use bios_basic::rbum::dto::rbum_set_dto::RbumSetTreeResp;
 use bios_basic::rbum::dto::rbum_set_item_dto::RbumSetItemDetailResp;
 use bios_basic::rbum::rbum_enumeration::RbumSetCateLevelQueryKind;
 
-use crate::basic::dto::iam_set_dto::{IamSetCateAddReq, IamSetCateModifyReq, IamSetItemAddReq, IamSetItemWithDefaultSetAddReq};
+use crate::basic::dto::iam_set_dto::{IamSetCateAddReq, IamSetCateModifyReq, IamSetCateMoveReq, IamSetItemAddReq, IamSetItemWithDefaultSetAddReq};
 use crate::basic::serv::iam_set_serv::IamSetServ;
 use crate::iam_constants;
 use crate::iam_enumeration::IamSetKind;
@@ -41,6 +41,18 @@ impl IamCtOrgApi {
         TardisResp::ok(Void {})
     }
 
+    /// Move Org Cate By Org Cate Id
+    ///
+    /// The sub cates and the accounts of the cate are moved along.
+    #[oai(path = "/cate/:id/move", method = "put")]
+    async fn move_set_cate(&self, id: Path<String>, move_req: Json<IamSetCateMoveReq>, ctx: TardisContextExtractor) -> TardisApiResult<Void> {
+        let mut funs = iam_constants::get_tardis_inst();
+        funs.begin().await?;
+        IamSetServ::move_set_cate(&id.0, &move_req.0, &funs, &ctx.0).await?;
+        funs.commit().await?;
+        TardisResp::ok(Void {})
+    }
+
     /// Find Org Tree By Current Tenant
     ///
     /// * Without parameters: Query the whole tree
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::Mutex;
 
@@ -30,7 +31,8 @@ pub struct IamConfig {
     // time_stamp -> res_uri##action
     pub cache_key_res_changed_info_: String,
     pub cache_key_res_changed_expire_sec: usize,
-    pub cache_key_async_task_status: String,
+    // seconds that the deleted tenants and roles are kept in the recycle bin
+    pub soft_delete_retention_sec: u32,
     pub mail_template_cert_activate_title: String,
     pub mail_template_cert_activate_content: String,
     pub mail_template_cert_login_title: String,
@@ -82,7 +84,14 @@ impl Default for IamLdapConfig {
 impl Default for IamConfig {
     fn default() -> Self {
         IamConfig {
-            rbum: Default::default(),
+            rbum: RbumConfig {
+                history_domains: HashMap::from([
+                    ("iam_account".to_string(), "cud".to_string()),
+                    ("iam_role".to_string(), "cud".to_string()),
+                    ("rbum_cert_conf".to_string(), "cud".to_string()),
+                ]),
+                ..Default::default()
+            },
             cache_key_token_info_: "iam:cache:token:info:".to_string(),
             cache_key_account_rel_: "iam:cache:account:rel:".to_string(),
             cache_key_account_info_: "iam:cache:account:info:".to_string(),
@@ -99,7 +108,7 @@ impl Default for IamConfig {
             phone_template_cert_login_title: "Your account is trying to login, verification code: {vcode}".to_string(),
             phone_template_cert_login_content: "IAM Service Phone Credentials Activation".to_string(),
             ldap: IamLdapConfig::default(),
-            cache_key_async_task_status: "iam:cache:task:status".to_string(),
+            soft_delete_retention_sec: 60 * 60 * 24 * 30,
         }
     }
 }
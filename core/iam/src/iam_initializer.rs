@@ -1,3 +1,4 @@
+use bios_basic::process::task_processor::TaskProcessor;
 use bios_basic::rbum::dto::rbum_set_cate_dto::RbumSetCateAddReq;
 use bios_basic::rbum::serv::rbum_set_serv::RbumSetCateServ;
 use tardis::basic::dto::TardisContext;
@@ -12,6 +13,7 @@ use tardis::{TardisFuns, TardisFunsInst};
 use bios_basic::rbum::dto::rbum_domain_dto::RbumDomainAddReq;
 use bios_basic::rbum::dto::rbum_filer_dto::RbumBasicFilterReq;
 use bios_basic::rbum::dto::rbum_kind_dto::RbumKindAddReq;
+use bios_basic::rbum::helper::rbum_event_helper;
 use bios_basic::rbum::rbum_initializer;
 use bios_basic::rbum::rbum_initializer::get_first_account_context;
 use bios_basic::rbum::serv::rbum_crud_serv::RbumCrudOperation;
@@ -48,6 +50,9 @@ use crate::iam_enumeration::{IamResKind, IamRoleKind, IamSetCateKind, IamSetKind
 pub async fn init(web_server: &TardisWebServer) -> TardisResult<()> {
     let funs = iam_constants::get_tardis_inst();
     init_db(funs).await?;
+    RbumItemServ::start_purge_task(iam_constants::COMPONENT_CODE);
+    TaskProcessor::start_timeout_task(iam_constants::COMPONENT_CODE);
+    rbum_event_helper::start_relay_task(iam_constants::COMPONENT_CODE);
     init_api(web_server).await
 }
 
@@ -202,11 +207,26 @@ pub async fn init_rbum_data(funs: &TardisFunsInst) -> TardisResult<(String, Stri
         ..Default::default()
     };
 
-    let kind_tenant_id = add_kind(iam_constants::RBUM_KIND_CODE_IAM_TENANT, iam_constants::RBUM_EXT_TABLE_IAM_TENANT, funs, &ctx).await?;
-    let kind_app_id = add_kind(iam_constants::RBUM_KIND_CODE_IAM_APP, iam_constants::RBUM_EXT_TABLE_IAM_APP, funs, &ctx).await?;
-    let kind_role_id = add_kind(iam_constants::RBUM_KIND_CODE_IAM_ROLE, iam_constants::RBUM_EXT_TABLE_IAM_ROLE, funs, &ctx).await?;
-    let kind_account_id = add_kind(iam_constants::RBUM_KIND_CODE_IAM_ACCOUNT, iam_constants::RBUM_EXT_TABLE_IAM_ACCOUNT, funs, &ctx).await?;
-    let kind_res_id = add_kind(iam_constants::RBUM_KIND_CODE_IAM_RES, iam_constants::RBUM_EXT_TABLE_IAM_RES, funs, &ctx).await?;
+    let soft_delete_retention_sec = funs.conf::<IamConfig>().soft_delete_retention_sec;
+    let kind_tenant_id = add_kind(
+        iam_constants::RBUM_KIND_CODE_IAM_TENANT,
+        iam_constants::RBUM_EXT_TABLE_IAM_TENANT,
+        Some(soft_delete_retention_sec),
+        funs,
+        &ctx,
+    )
+    .await?;
+    let kind_app_id = add_kind(iam_constants::RBUM_KIND_CODE_IAM_APP, iam_constants::RBUM_EXT_TABLE_IAM_APP, None, funs, &ctx).await?;
+    let kind_role_id = add_kind(
+        iam_constants::RBUM_KIND_CODE_IAM_ROLE,
+        iam_constants::RBUM_EXT_TABLE_IAM_ROLE,
+        Some(soft_delete_retention_sec),
+        funs,
+        &ctx,
+    )
+    .await?;
+    let kind_account_id = add_kind(iam_constants::RBUM_KIND_CODE_IAM_ACCOUNT, iam_constants::RBUM_EXT_TABLE_IAM_ACCOUNT, None, funs, &ctx).await?;
+    let kind_res_id = add_kind(iam_constants::RBUM_KIND_CODE_IAM_RES, iam_constants::RBUM_EXT_TABLE_IAM_RES, None, funs, &ctx).await?;
 
     let domain_iam_id = add_domain(funs, &ctx).await?;
 
@@ -320,6 +340,7 @@ pub async fn init_rbum_data(funs: &TardisFunsInst) -> TardisResult<(String, Stri
             role_ids: Some(vec![role_sys_admin_id.clone()]),
             org_cate_ids: None,
             exts: None,
+            version: None,
         },
         funs,
         &ctx,
@@ -385,7 +406,7 @@ System administrator name: {} ,Initial password: {}
     Ok((iam_constants::RBUM_ITEM_NAME_SYS_ADMIN_ACCOUNT.to_string(), pwd))
 }
 
-async fn add_kind<'a>(scheme: &str, ext_table: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<String> {
+async fn add_kind<'a>(scheme: &str, ext_table: &str, soft_delete_retention_sec: Option<u32>, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<String> {
     RbumKindServ::add_rbum(
         &mut RbumKindAddReq {
             code: TrimString(scheme.to_string()),
@@ -394,6 +415,7 @@ async fn add_kind<'a>(scheme: &str, ext_table: &str, funs: &TardisFunsInst, ctx:
             icon: None,
             sort: None,
             ext_table_name: Some(ext_table.to_string().to_lowercase()),
+            soft_delete_retention_sec,
             scope_level: Some(iam_constants::RBUM_SCOPE_LEVEL_GLOBAL),
         },
         funs,
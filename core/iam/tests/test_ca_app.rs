@@ -24,6 +24,7 @@ pub async fn test(context1: &TardisContext, _context2: &TardisContext) -> Tardis
             disabled: None,
             scope_level: None,
             admin_ids: None,
+            version: None,
         },
         &funs,
         context1,
@@ -41,6 +42,7 @@ pub async fn test(context1: &TardisContext, _context2: &TardisContext) -> Tardis
             disabled: None,
             scope_level: None,
             admin_ids: Some(vec![_context2.owner.to_string()]),
+            version: None,
         },
         &funs,
         context1,
@@ -58,6 +60,7 @@ pub async fn test(context1: &TardisContext, _context2: &TardisContext) -> Tardis
             disabled: None,
             scope_level: None,
             admin_ids: Some(vec![context1.owner.to_string()]),
+            version: None,
         },
         &funs,
         context1,
@@ -71,6 +71,7 @@ async fn test_single_level(context: &TardisContext, account_name: &str, role_nam
             icon: Some("/icon/icon.png".to_string()),
             scope_level: None,
             disabled: None
+            version: None,
         },
         &funs,
         another_context
@@ -84,6 +85,7 @@ async fn test_single_level(context: &TardisContext, account_name: &str, role_nam
             icon: Some("/icon/icon.png".to_string()),
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         context,
@@ -245,6 +247,7 @@ pub async fn test_multi_level_by_sys_context(
             icon: Some("/icon/icon.png".to_string()),
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         sys_context,
@@ -257,6 +260,7 @@ pub async fn test_multi_level_by_sys_context(
             icon: Some("/icon/icon.png".to_string()),
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         sys_context,
@@ -269,6 +273,7 @@ pub async fn test_multi_level_by_sys_context(
             icon: Some("/icon/icon.png".to_string()),
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         sys_context,
@@ -346,6 +351,7 @@ pub async fn test_multi_level_by_tenant_context(
             icon: Some("/icon/icon.png".to_string()),
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t1_context,
@@ -359,6 +365,7 @@ pub async fn test_multi_level_by_tenant_context(
             icon: Some("/icon/icon.png".to_string()),
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t2_context,
@@ -372,6 +379,7 @@ pub async fn test_multi_level_by_tenant_context(
             icon: Some("/icon/icon.png".to_string()),
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t2_context,
@@ -384,6 +392,7 @@ pub async fn test_multi_level_by_tenant_context(
             icon: Some("/icon/icon.png".to_string()),
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t2_context,
@@ -446,6 +455,7 @@ pub async fn test_multi_level_by_app_context(
             icon: Some("/icon/icon.png".to_string()),
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -459,6 +469,7 @@ pub async fn test_multi_level_by_app_context(
             icon: Some("/icon/icon.png".to_string()),
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -472,6 +483,7 @@ pub async fn test_multi_level_by_app_context(
             icon: Some("/icon/icon.png".to_string()),
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -485,6 +497,7 @@ pub async fn test_multi_level_by_app_context(
             icon: Some("/icon/icon.png".to_string()),
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -498,6 +511,7 @@ pub async fn test_multi_level_by_app_context(
             icon: Some("/icon/icon.png".to_string()),
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t2_a1_context,
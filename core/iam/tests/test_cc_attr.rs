@@ -1,12 +1,18 @@
+use std::collections::HashMap;
+
 use tardis::basic::dto::TardisContext;
 use tardis::basic::field::TrimString;
 use tardis::basic::result::TardisResult;
 use tardis::log::info;
 use tardis::TardisFunsInst;
 
+use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumItemAttrSortReq, RbumItemAttrValueFilterReq};
 use bios_basic::rbum::dto::rbum_kind_attr_dto::RbumKindAttrModifyReq;
 use bios_basic::rbum::rbum_enumeration::{RbumDataTypeKind, RbumScopeLevelKind, RbumWidgetTypeKind};
+use bios_basic::rbum::serv::rbum_item_serv::RbumItemCrudOperation;
 use bios_iam::basic::dto::iam_attr_dto::IamKindAttrAddReq;
+use bios_iam::basic::dto::iam_filer_dto::IamAccountFilterReq;
+use bios_iam::basic::serv::iam_account_serv::IamAccountServ;
 use bios_iam::basic::serv::iam_attr_serv::IamAttrServ;
 use bios_iam::iam_constants;
 
@@ -211,6 +217,147 @@ pub async fn test_multi_level_by_sys_context(
         "t2_a1_attr3_global_value_modify"
     );
 
+    info!("【test_cc_attr】 : test_multi_level : Find Accounts By Attr value By sys_context");
+    assert!(IamAccountServ::find_id_items(
+        &IamAccountFilterReq {
+            attrs: Some(vec![RbumItemAttrValueFilterReq {
+                name: "sys_attr1".to_string(),
+                eq: Some("sys_attr1_value_modify".to_string()),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        },
+        None,
+        None,
+        &funs,
+        sys_context,
+    )
+    .await
+    .is_err());
+    IamAttrServ::add_account_attr(
+        &IamKindAttrAddReq {
+            main_column: Some(true),
+            idx: Some(true),
+            ..package_test_attr_add_req("ext1_idx", None)
+        },
+        &funs,
+        sys_context,
+    )
+    .await?;
+    IamAttrServ::add_or_modify_account_attr_values(&sys_context.owner, HashMap::from([("ext1_idx".to_string(), "00001".to_string())]), &funs, sys_context).await?;
+    assert!(find_account_ids_by_attr(
+        RbumItemAttrValueFilterReq {
+            name: "ext1_idx".to_string(),
+            eq: Some("00001".to_string()),
+            ..Default::default()
+        },
+        &funs,
+        sys_context,
+    )
+    .await?
+    .contains(&sys_context.owner));
+    assert!(find_account_ids_by_attr(
+        RbumItemAttrValueFilterReq {
+            name: "ext1_idx".to_string(),
+            like: Some("0000".to_string()),
+            ..Default::default()
+        },
+        &funs,
+        sys_context,
+    )
+    .await?
+    .contains(&sys_context.owner));
+    assert!(!find_account_ids_by_attr(
+        RbumItemAttrValueFilterReq {
+            name: "ext1_idx".to_string(),
+            in_values: Some(vec!["00002".to_string(), "00003".to_string()]),
+            ..Default::default()
+        },
+        &funs,
+        sys_context,
+    )
+    .await?
+    .contains(&sys_context.owner));
+    assert!(!find_account_ids_by_attr(
+        RbumItemAttrValueFilterReq {
+            name: "ext1_idx".to_string(),
+            ge: Some("00002".to_string()),
+            ..Default::default()
+        },
+        &funs,
+        sys_context,
+    )
+    .await?
+    .contains(&sys_context.owner));
+    let account_ids = IamAccountServ::find_id_items(
+        &IamAccountFilterReq {
+            basic: RbumBasicFilterReq {
+                with_sub_own_paths: true,
+                ..Default::default()
+            },
+            attr_sorts: Some(vec![RbumItemAttrSortReq {
+                name: "ext1_idx".to_string(),
+                desc: true,
+            }]),
+            ..Default::default()
+        },
+        None,
+        None,
+        &funs,
+        sys_context,
+    )
+    .await?;
+    assert_eq!(account_ids.first(), Some(&sys_context.owner));
+    // Ranges of number attributes are compared as numbers, `9` is less than `10`
+    IamAttrServ::add_account_attr(
+        &IamKindAttrAddReq {
+            data_type: RbumDataTypeKind::Number,
+            ..package_test_attr_add_req("num_attr", None)
+        },
+        &funs,
+        sys_context,
+    )
+    .await?;
+    IamAttrServ::add_or_modify_account_attr_values(&sys_context.owner, HashMap::from([("num_attr".to_string(), "9".to_string())]), &funs, sys_context).await?;
+    assert!(!find_account_ids_by_attr(
+        RbumItemAttrValueFilterReq {
+            name: "num_attr".to_string(),
+            ge: Some("10".to_string()),
+            ..Default::default()
+        },
+        &funs,
+        sys_context,
+    )
+    .await?
+    .contains(&sys_context.owner));
+    assert!(find_account_ids_by_attr(
+        RbumItemAttrValueFilterReq {
+            name: "num_attr".to_string(),
+            ge: Some("8.5".to_string()),
+            le: Some("10".to_string()),
+            ..Default::default()
+        },
+        &funs,
+        sys_context,
+    )
+    .await?
+    .contains(&sys_context.owner));
+    assert_eq!(
+        find_account_ids_by_attr(
+            RbumItemAttrValueFilterReq {
+                name: "num_attr".to_string(),
+                ge: Some("ten".to_string()),
+                ..Default::default()
+            },
+            &funs,
+            sys_context,
+        )
+        .await
+        .unwrap_err()
+        .code,
+        "400-rbum-item-attr-bound-invalid"
+    );
+
     info!("【test_cc_attr】 : test_multi_level : Delete Account Attr value By sys_context");
     IamAttrServ::delete_account_attr_value(&sys_attr1_value, &funs, sys_context).await?;
     IamAttrServ::delete_account_attr_value(&sys_attr2_global_value, &funs, sys_context).await?;
@@ -519,5 +666,24 @@ fn package_test_attr_modify_req(label: &str) -> RbumKindAttrModifyReq {
         action: None,
         ext: None,
         scope_level: None,
+        version: None,
     }
 }
+
+async fn find_account_ids_by_attr(attr_filter: RbumItemAttrValueFilterReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Vec<String>> {
+    IamAccountServ::find_id_items(
+        &IamAccountFilterReq {
+            basic: RbumBasicFilterReq {
+                with_sub_own_paths: true,
+                ..Default::default()
+            },
+            attrs: Some(vec![attr_filter]),
+            ..Default::default()
+        },
+        None,
+        None,
+        funs,
+        ctx,
+    )
+    .await
+}
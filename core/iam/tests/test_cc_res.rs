@@ -98,6 +98,7 @@ async fn test_single_level(context: &TardisContext, another_context: &TardisCont
             action: None,
             scope_level: None,
             disabled: None
+            version: None,
         },
         &funs,
         another_context
@@ -115,6 +116,7 @@ async fn test_single_level(context: &TardisContext, another_context: &TardisCont
             action: None,
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         context,
@@ -353,6 +355,7 @@ pub async fn test_multi_level_by_sys_context(
             action: None,
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         sys_context,
@@ -368,6 +371,7 @@ pub async fn test_multi_level_by_sys_context(
             action: None,
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         sys_context,
@@ -383,6 +387,7 @@ pub async fn test_multi_level_by_sys_context(
             action: None,
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         sys_context,
@@ -501,6 +506,7 @@ pub async fn test_multi_level_by_tenant_context(
             action: None,
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t1_context,
@@ -517,6 +523,7 @@ pub async fn test_multi_level_by_tenant_context(
             action: None,
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t1_context,
@@ -533,6 +540,7 @@ pub async fn test_multi_level_by_tenant_context(
             action: None,
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t2_context,
@@ -549,6 +557,7 @@ pub async fn test_multi_level_by_tenant_context(
             action: None,
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t2_context,
@@ -564,6 +573,7 @@ pub async fn test_multi_level_by_tenant_context(
             action: None,
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t2_context,
@@ -672,6 +682,7 @@ pub async fn test_multi_level_by_app_context(
             action: None,
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -688,6 +699,7 @@ pub async fn test_multi_level_by_app_context(
             action: None,
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -704,6 +716,7 @@ pub async fn test_multi_level_by_app_context(
             action: None,
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -720,6 +733,7 @@ pub async fn test_multi_level_by_app_context(
             action: None,
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -736,6 +750,7 @@ pub async fn test_multi_level_by_app_context(
             action: None,
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -752,6 +767,7 @@ pub async fn test_multi_level_by_app_context(
             action: None,
             scope_level: None,
             disabled: None,
+            version: None,
         },
         &funs,
         t2_a1_context,
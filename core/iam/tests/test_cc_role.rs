@@ -1,19 +1,28 @@
 use tardis::basic::dto::TardisContext;
 use tardis::basic::field::TrimString;
 use tardis::basic::result::TardisResult;
+use tardis::chrono::{Duration, Utc};
 use tardis::log::info;
 use tardis::TardisFunsInst;
 
-use bios_basic::rbum::dto::rbum_filer_dto::RbumBasicFilterReq;
+use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumRelFilterReq};
+use bios_basic::rbum::dto::rbum_rel_env_dto::RbumRelEnvAddReq;
+use bios_basic::rbum::helper::rbum_rel_env_helper;
+use bios_basic::rbum::rbum_enumeration::RbumRelEnvKind;
+use bios_basic::rbum::serv::rbum_crud_serv::RbumCrudOperation;
 use bios_basic::rbum::serv::rbum_item_serv::RbumItemCrudOperation;
-use bios_iam::basic::dto::iam_filer_dto::IamRoleFilterReq;
+use bios_basic::rbum::serv::rbum_rel_serv::RbumRelEnvServ;
+use bios_iam::basic::dto::iam_filer_dto::{IamResFilterReq, IamRoleFilterReq};
 use bios_iam::basic::dto::iam_res_dto::IamResAddReq;
 use bios_iam::basic::dto::iam_role_dto::{IamRoleAddReq, IamRoleModifyReq};
+use bios_iam::basic::serv::iam_key_cache_serv::IamResCacheServ;
+use bios_iam::basic::serv::iam_rel_serv::IamRelServ;
 use bios_iam::basic::serv::iam_res_serv::IamResServ;
 use bios_iam::basic::serv::iam_role_serv::IamRoleServ;
+use bios_iam::iam_config::IamConfig;
 use bios_iam::iam_constants;
 use bios_iam::iam_constants::{RBUM_ITEM_NAME_SYS_ADMIN_ACCOUNT, RBUM_SCOPE_LEVEL_GLOBAL, RBUM_SCOPE_LEVEL_TENANT};
-use bios_iam::iam_enumeration::{IamResKind, IamRoleKind};
+use bios_iam::iam_enumeration::{IamRelKind, IamResKind, IamRoleKind};
 
 pub async fn test(
     sys_context: &TardisContext,
@@ -75,6 +84,7 @@ async fn test_single_level(context: &TardisContext, account_name: &str, another_
             disabled: None,
             sort: None,
             kind: None,
+            version: None,
         },
         &funs,
         another_context
@@ -90,6 +100,7 @@ async fn test_single_level(context: &TardisContext, account_name: &str, another_
             disabled: None,
             sort: None,
             kind: None,
+            version: None,
         },
         &funs,
         context,
@@ -104,6 +115,112 @@ async fn test_single_level(context: &TardisContext, account_name: &str, another_
     assert_eq!(role.icon, "/icon/icon.png");
     assert!(!role.disabled);
 
+    info!("【test_cc_role】 : test_single_level : Modify Role By Stale Version");
+    assert!(IamRoleServ::modify_item(
+        &role_id1,
+        &mut IamRoleModifyReq {
+            name: None,
+            icon: None,
+            scope_level: None,
+            disabled: None,
+            sort: Some(1),
+            kind: None,
+            version: Some(role.version - 1),
+        },
+        &funs,
+        context
+    )
+    .await
+    .is_err());
+    IamRoleServ::modify_item(
+        &role_id1,
+        &mut IamRoleModifyReq {
+            name: None,
+            icon: None,
+            scope_level: None,
+            disabled: None,
+            sort: Some(1),
+            kind: None,
+            version: Some(role.version),
+        },
+        &funs,
+        context,
+    )
+    .await?;
+    assert_eq!(
+        IamRoleServ::get_item(&role_id1, &IamRoleFilterReq::default(), &funs, context).await?.version,
+        role.version + 1
+    );
+
+    info!("【test_cc_role】 : test_single_level : Find Role Histories");
+    assert_eq!(IamRoleServ::paginate_item_histories(&role_id1, 1, 10, &funs, another_context).await?.total_size, 0);
+    let histories = IamRoleServ::paginate_item_histories(&role_id1, 1, 10, &funs, context).await?;
+    assert_eq!(histories.total_size, 3);
+    // The latest first, the changes in the same second are ordered by their sequence
+    assert_eq!(histories.records.iter().map(|h| h.operate.as_str()).collect::<Vec<&str>>(), vec!["u", "u", "c"]);
+    assert_eq!(histories.records[0].changed_fields, vec!["sort".to_string()]);
+    assert!(histories.records[1].changed_fields.contains(&"name".to_string()) && histories.records[1].before.as_ref().unwrap()["name"] == "角色1");
+    assert!(histories.records[2].before.is_none() && histories.records[2].after.as_ref().unwrap()["name"] == "角色1");
+    assert!(IamRoleServ::get_item_snapshot_as_of(&role_id1, Utc::now() - Duration::days(1), &funs, context).await?.is_none());
+    let snapshot = IamRoleServ::get_item_snapshot_as_of(&role_id1, Utc::now(), &funs, context).await?.unwrap();
+    assert_eq!(snapshot["code"], "role1");
+    assert_eq!(snapshot["sort"], 1);
+
+    info!("【test_cc_role】 : test_single_level : Batch Add Roles");
+    let new_add_req = |code: &str| IamRoleAddReq {
+        code: TrimString(code.to_string()),
+        name: TrimString(code.to_string()),
+        icon: None,
+        scope_level: None,
+        disabled: None,
+        sort: None,
+        kind: Some(IamRoleKind::Tenant),
+    };
+    let resp = IamRoleServ::add_items(&mut [new_add_req("role_batch1"), new_add_req("role1"), new_add_req("role_batch2")], &funs, context).await?;
+    assert_eq!(resp.succeed_ids.len(), 2);
+    assert_eq!(resp.errors.len(), 1);
+    assert_eq!(resp.errors[0].index, 1);
+    let batch_role_ids = resp.succeed_ids;
+    assert_eq!(
+        IamRoleServ::get_item(&batch_role_ids[1], &IamRoleFilterReq::default(), &funs, context).await?.name,
+        "role_batch2"
+    );
+
+    info!("【test_cc_role】 : test_single_level : Batch Modify Roles");
+    let new_modify_req = |name: &str| IamRoleModifyReq {
+        name: Some(TrimString(name.to_string())),
+        icon: None,
+        scope_level: None,
+        disabled: None,
+        sort: None,
+        kind: None,
+        version: None,
+    };
+    let resp = IamRoleServ::modify_items(
+        &mut [
+            (batch_role_ids[0].to_string(), new_modify_req("批量角色1")),
+            (role_id2.to_string(), new_modify_req("批量角色2")),
+        ],
+        &funs,
+        context,
+    )
+    .await?;
+    assert_eq!(resp.succeed_ids, vec![batch_role_ids[0].to_string()]);
+    assert_eq!(resp.errors.len(), 1);
+    assert_eq!(resp.errors[0].id, role_id2);
+    assert_eq!(
+        IamRoleServ::get_item(&batch_role_ids[0], &IamRoleFilterReq::default(), &funs, context).await?.name,
+        "批量角色1"
+    );
+
+    info!("【test_cc_role】 : test_single_level : Batch Delete Roles");
+    let resp = IamRoleServ::delete_items(&[batch_role_ids[0].to_string(), role_id2.to_string(), batch_role_ids[1].to_string()], &funs, context).await?;
+    assert_eq!(resp.succeed_ids, batch_role_ids);
+    assert_eq!(resp.errors.len(), 1);
+    assert_eq!(resp.errors[0].index, 1);
+    assert!(IamRoleServ::get_item(&batch_role_ids[0], &IamRoleFilterReq::default(), &funs, context).await.is_err());
+    assert!(IamRoleServ::get_item(&role_id2, &IamRoleFilterReq::default(), &funs, another_context).await.is_ok());
+
     info!("【test_cc_role】 : test_single_level : Find Roles");
     let roles = IamRoleServ::paginate_items(&IamRoleFilterReq::default(), 1, 10, None, None, &funs, context).await?;
     assert_eq!(roles.page_number, 1);
@@ -128,6 +245,48 @@ async fn test_single_level(context: &TardisContext, account_name: &str, another_
     assert_eq!(role_accounts.records.len(), 1);
     assert_eq!(role_accounts.records.get(0).unwrap().rel_name, account_name);
 
+    info!("【test_cc_role】 : test_single_level : Need Role With Ips Env");
+    IamRoleServ::need_role(&role_id1, &funs, context).await?;
+    let rel = IamRelServ::find_rels(
+        &RbumRelFilterReq {
+            tag: Some(IamRelKind::IamAccountRole.to_string()),
+            from_rbum_id: Some(context.owner.clone()),
+            to_rbum_item_id: Some(role_id1.clone()),
+            ..Default::default()
+        },
+        None,
+        None,
+        &funs,
+        context,
+    )
+    .await?
+    .pop()
+    .unwrap();
+    RbumRelEnvServ::add_rbum(
+        &mut RbumRelEnvAddReq {
+            kind: RbumRelEnvKind::Ips,
+            value1: "192.168.0.0/24".to_string(),
+            value2: None,
+            rel_rbum_rel_id: rel.rel.id.clone(),
+        },
+        &funs,
+        context,
+    )
+    .await?;
+    let ip_context = TardisContext {
+        own_paths: context.own_paths.clone(),
+        ak: context.ak.clone(),
+        owner: context.owner.clone(),
+        roles: context.roles.clone(),
+        groups: context.groups.clone(),
+        ..Default::default()
+    };
+    assert!(IamRoleServ::need_role(&role_id1, &funs, &ip_context).await.is_err());
+    ip_context.add_ext(rbum_rel_env_helper::CTX_EXT_CLIENT_IP, "10.0.0.1")?;
+    assert!(IamRoleServ::need_role(&role_id1, &funs, &ip_context).await.is_err());
+    ip_context.add_ext(rbum_rel_env_helper::CTX_EXT_CLIENT_IP, "192.168.0.10")?;
+    IamRoleServ::need_role(&role_id1, &funs, &ip_context).await?;
+
     info!("【test_cc_role】 : test_single_level : Delete Rel Account By Id");
     IamRoleServ::delete_rel_account(&role_id1, &role_accounts.records.get(0).unwrap().rel_id, None, &funs, context).await?;
     let role_accounts = IamRoleServ::paginate_simple_rel_accounts(&role_id1, 1, 10, None, None, &funs, context).await?;
@@ -168,6 +327,19 @@ async fn test_single_level(context: &TardisContext, account_name: &str, another_
     assert_eq!(role_res.records.len(), 1);
     assert_eq!(role_res.records.get(0).unwrap().rel_name, "测试资源");
 
+    info!("【test_cc_role】 : test_single_level : Soft Delete And Restore Role");
+    let res = IamResServ::peek_item(&res_id, &IamResFilterReq::default(), &funs, context).await?;
+    let res_uri = IamResCacheServ::package_uri_mixed(&res.code, &res.method);
+    let role_flag = format!("#{}#", role_id1);
+    assert!(funs.cache().hget(&funs.conf::<IamConfig>().cache_key_res_info, &res_uri).await?.unwrap().contains(&role_flag));
+    IamRoleServ::delete_item(&role_id1, &funs, context).await?;
+    // The role in the recycle bin keeps its rels but grants nothing
+    assert!(IamRoleServ::is_disabled(&role_id1, &funs).await?);
+    assert!(!funs.cache().hget(&funs.conf::<IamConfig>().cache_key_res_info, &res_uri).await?.unwrap().contains(&role_flag));
+    IamRoleServ::restore_item(&role_id1, &funs, context).await?;
+    assert!(!IamRoleServ::is_disabled(&role_id1, &funs).await?);
+    assert!(funs.cache().hget(&funs.conf::<IamConfig>().cache_key_res_info, &res_uri).await?.unwrap().contains(&role_flag));
+
     info!("【test_cc_role】 : test_single_level : Delete Rel Res By Id");
     IamRoleServ::delete_rel_res(&role_id1, &role_res.records.get(0).unwrap().rel_id, &funs, context).await?;
     let role_res = IamRoleServ::paginate_simple_rel_res(&role_id1, 1, 10, None, None, &funs, context).await?;
@@ -345,6 +517,7 @@ pub async fn test_multi_level_by_sys_context(
             disabled: None,
             sort: None,
             kind: None,
+            version: None,
         },
         &funs,
         sys_context,
@@ -359,6 +532,7 @@ pub async fn test_multi_level_by_sys_context(
             disabled: None,
             sort: None,
             kind: None,
+            version: None,
         },
         &funs,
         sys_context,
@@ -373,6 +547,7 @@ pub async fn test_multi_level_by_sys_context(
             disabled: None,
             sort: None,
             kind: None,
+            version: None,
         },
         &funs,
         sys_context,
@@ -496,6 +671,7 @@ pub async fn test_multi_level_by_tenant_context(
             disabled: None,
             sort: None,
             kind: None,
+            version: None,
         },
         &funs,
         t1_context,
@@ -511,6 +687,7 @@ pub async fn test_multi_level_by_tenant_context(
             disabled: None,
             sort: None,
             kind: None,
+            version: None,
         },
         &funs,
         t1_context,
@@ -526,6 +703,7 @@ pub async fn test_multi_level_by_tenant_context(
             disabled: None,
             sort: None,
             kind: None,
+            version: None,
         },
         &funs,
         t2_context,
@@ -541,6 +719,7 @@ pub async fn test_multi_level_by_tenant_context(
             disabled: None,
             sort: None,
             kind: None,
+            version: None,
         },
         &funs,
         t2_context,
@@ -555,6 +734,7 @@ pub async fn test_multi_level_by_tenant_context(
             disabled: None,
             sort: None,
             kind: None,
+            version: None,
         },
         &funs,
         t2_context,
@@ -643,6 +823,7 @@ pub async fn test_multi_level_by_app_context(
             disabled: None,
             sort: None,
             kind: None,
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -658,6 +839,7 @@ pub async fn test_multi_level_by_app_context(
             disabled: None,
             sort: None,
             kind: None,
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -673,6 +855,7 @@ pub async fn test_multi_level_by_app_context(
             disabled: None,
             sort: None,
             kind: None,
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -688,6 +871,7 @@ pub async fn test_multi_level_by_app_context(
             disabled: None,
             sort: None,
             kind: None,
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -703,6 +887,7 @@ pub async fn test_multi_level_by_app_context(
             disabled: None,
             sort: None,
             kind: None,
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -718,6 +903,7 @@ pub async fn test_multi_level_by_app_context(
             disabled: None,
             sort: None,
             kind: None,
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -733,6 +919,7 @@ pub async fn test_multi_level_by_app_context(
             disabled: None,
             sort: None,
             kind: None,
+            version: None,
         },
         &funs,
         t2_a1_context,
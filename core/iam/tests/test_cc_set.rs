@@ -91,6 +91,7 @@ async fn test_single_level(context: &TardisContext, another_context: &TardisCont
             sort: None,
             ext: None,
             scope_level: None
+            version: None,
         },
         &funs,
         another_context
@@ -107,6 +108,7 @@ async fn test_single_level(context: &TardisContext, another_context: &TardisCont
             sort: None,
             ext: None,
             scope_level: None,
+            version: None,
         },
         &funs,
         context,
@@ -348,6 +350,7 @@ pub async fn test_multi_level_by_sys_context(
             sort: None,
             ext: None,
             scope_level: None,
+            version: None,
         },
         &funs,
         sys_context,
@@ -362,6 +365,7 @@ pub async fn test_multi_level_by_sys_context(
             sort: None,
             ext: None,
             scope_level: None,
+            version: None,
         },
         &funs,
         sys_context,
@@ -376,6 +380,7 @@ pub async fn test_multi_level_by_sys_context(
             sort: None,
             ext: None,
             scope_level: None,
+            version: None,
         },
         &funs,
         sys_context,
@@ -390,6 +395,7 @@ pub async fn test_multi_level_by_sys_context(
             sort: None,
             ext: None,
             scope_level: None,
+            version: None,
         },
         &funs,
         sys_context,
@@ -445,6 +451,7 @@ pub async fn test_multi_level_by_tenant_context(
             sort: None,
             ext: None,
             scope_level: None
+            version: None,
         },
         &funs,
         t1_context,
@@ -460,6 +467,7 @@ pub async fn test_multi_level_by_tenant_context(
             sort: None,
             ext: None,
             scope_level: None
+            version: None,
         },
         &funs,
         t1_context,
@@ -475,6 +483,7 @@ pub async fn test_multi_level_by_tenant_context(
             sort: None,
             ext: None,
             scope_level: None
+            version: None,
         },
         &funs,
         t2_context,
@@ -490,6 +499,7 @@ pub async fn test_multi_level_by_tenant_context(
             sort: None,
             ext: None,
             scope_level: None,
+            version: None,
         },
         &funs,
         t2_context,
@@ -504,6 +514,7 @@ pub async fn test_multi_level_by_tenant_context(
             sort: None,
             ext: None,
             scope_level: None,
+            version: None,
         },
         &funs,
         t2_context,
@@ -518,6 +529,7 @@ pub async fn test_multi_level_by_tenant_context(
             sort: None,
             ext: None,
             scope_level: None,
+            version: None,
         },
         &funs,
         t2_context,
@@ -573,6 +585,7 @@ pub async fn test_multi_level_by_app_context(
             sort: None,
             ext: None,
             scope_level: None
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -588,6 +601,7 @@ pub async fn test_multi_level_by_app_context(
             sort: None,
             ext: None,
             scope_level: None
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -603,6 +617,7 @@ pub async fn test_multi_level_by_app_context(
             sort: None,
             ext: None,
             scope_level: None
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -618,6 +633,7 @@ pub async fn test_multi_level_by_app_context(
             sort: None,
             ext: None,
             scope_level: None
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -633,6 +649,7 @@ pub async fn test_multi_level_by_app_context(
             sort: None,
             ext: None,
             scope_level: None
+            version: None,
         },
         &funs,
         t2_a1_context,
@@ -648,6 +665,7 @@ pub async fn test_multi_level_by_app_context(
             sort: None,
             ext: None,
             scope_level: None,
+            version: None,
         },
         &funs,
         t2_a1_context,
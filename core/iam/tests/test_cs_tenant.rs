@@ -145,6 +145,7 @@ pub async fn test(context: &TardisContext) -> TardisResult<()> {
             scope_level: None,
             note: None,
             account_self_reg: None,
+            version: None,
         },
         &funs,
         context,
@@ -162,6 +163,7 @@ pub async fn test(context: &TardisContext) -> TardisResult<()> {
             scope_level: None,
             note: None,
             account_self_reg: None,
+            version: None,
         },
         &funs,
         context,
@@ -53,6 +53,7 @@ pub async fn test(context1: &TardisContext, context2: &TardisContext) -> TardisR
             contact_phone: Some("13333333333".to_string()),
             disabled: None,
             scope_level: None,
+            version: None,
         },
         &funs,
         context2
@@ -69,6 +70,7 @@ pub async fn test(context1: &TardisContext, context2: &TardisContext) -> TardisR
             contact_phone: Some("13333333333".to_string()),
             disabled: None,
             scope_level: None,
+            version: None,
         },
         &funs,
         context1,
@@ -25,6 +25,7 @@ pub async fn test(context1: &TardisContext, _: &TardisContext) -> TardisResult<(
             disabled: None,
             note: None,
             account_self_reg: None,
+            version: None,
         },
         &funs,
         context1,
@@ -123,6 +123,7 @@ pub async fn app_console_project_mgr_page(tenant_id: &str, client: &mut BIOSWebT
                 contact_phone: None,
                 disabled: None,
                 scope_level: None,
+                version: None,
             },
         )
         .await;
@@ -199,6 +200,7 @@ pub async fn app_console_auth_mgr_page(client: &mut BIOSWebTestClient) -> Tardis
                     icon: None,
                     sort: None,
                     kind: None,
+                    version: None,
                 },
                 res_ids: Some(vec![]),
             },
@@ -173,6 +173,7 @@ pub async fn sys_console_tenant_mgr_page(sysadmin_name: &str, sysadmin_password:
                 account_self_reg: None,
                 cert_conf_by_wechat_mp: None,
                 cert_conf_by_ldap: None,
+                version: None,
             },
         )
         .await;
@@ -235,6 +236,7 @@ pub async fn sys_console_tenant_mgr_page(sysadmin_name: &str, sysadmin_password:
                 role_ids: None,
                 org_cate_ids: None,
                 exts: None,
+                version: None,
             },
         )
         .await;
@@ -347,6 +349,7 @@ pub async fn sys_console_account_mgr_page(client: &mut BIOSWebTestClient) -> Tar
                 action: None,
                 ext: None,
                 scope_level: None,
+                version: None,
             },
         )
         .await;
@@ -435,7 +438,8 @@ pub async fn sys_console_account_mgr_page(client: &mut BIOSWebTestClient) -> Tar
                 icon: None,
                 role_ids: Some(vec![]),
                 org_cate_ids: None,
-                exts: Some(HashMap::from([("ext1_idx".to_string(), "".to_string())])),
+                exts: Some(HashMap::from([("ext1_idx".to_string(), "00002".to_string())])),
+                version: None,
             },
         )
         .await;
@@ -445,7 +449,7 @@ pub async fn sys_console_account_mgr_page(client: &mut BIOSWebTestClient) -> Tar
     assert_eq!(account.name, "用户2");
     assert_eq!(account.roles.len(), 0);
     assert_eq!(account.exts.len(), 1);
-    assert_eq!(account.exts.into_iter().find(|r| r.name == "ext1_idx").unwrap().value, "".to_string());
+    assert_eq!(account.exts.into_iter().find(|r| r.name == "ext1_idx").unwrap().value, "00002".to_string());
     assert_eq!(account.certs.len(), 2);
     assert!(account.certs.contains_key(&("UserPwd".to_string())));
 
@@ -527,6 +531,7 @@ pub async fn sys_console_res_mgr_page(client: &mut BIOSWebTestClient) -> TardisR
                 icon: None,
                 sort: None,
                 ext: None,
+                version: None,
             },
         )
         .await;
@@ -640,6 +645,7 @@ pub async fn sys_console_res_mgr_page(client: &mut BIOSWebTestClient) -> TardisR
                 action: None,
                 scope_level: None,
                 disabled: None,
+                version: None,
             },
         )
         .await;
@@ -702,6 +708,7 @@ pub async fn sys_console_auth_mgr_page(res_menu_id: &str, client: &mut BIOSWebTe
                     icon: None,
                     sort: None,
                     kind: None,
+                    version: None,
                 },
                 res_ids: Some(vec![res_menu_id.to_string()]),
             },
@@ -117,6 +117,7 @@ pub async fn tenant_console_tenant_mgr_page(client: &mut BIOSWebTestClient) -> T
                 account_self_reg: None,
                 cert_conf_by_wechat_mp: None,
                 cert_conf_by_ldap: None,
+                version: None,
             },
         )
         .await;
@@ -183,6 +184,7 @@ pub async fn tenant_console_org_mgr_page(tenant_admin_user_name: &str, tenant_ad
                 icon: None,
                 sort: None,
                 ext: None,
+                version: None,
             },
         )
         .await;
@@ -329,6 +331,7 @@ pub async fn tenant_console_account_mgr_page(client: &mut BIOSWebTestClient) ->
                 action: None,
                 ext: None,
                 scope_level: None,
+                version: None,
             },
         )
         .await;
@@ -424,6 +427,7 @@ pub async fn tenant_console_account_mgr_page(client: &mut BIOSWebTestClient) ->
                 role_ids: Some(vec![]),
                 org_cate_ids: Some(vec![]),
                 exts: Some(HashMap::from([("ext1_idx".to_string(), "".to_string())])),
+                version: None,
             },
         )
         .await;
@@ -514,6 +518,7 @@ pub async fn tenant_console_auth_mgr_page(client: &mut BIOSWebTestClient) -> Tar
                     icon: None,
                     sort: None,
                     kind: None,
+                    version: None,
                 },
                 res_ids: Some(vec![]),
             },
@@ -719,6 +724,7 @@ pub async fn tenant_console_app_set_mgr_page(client: &mut BIOSWebTestClient) ->
                 icon: None,
                 sort: None,
                 ext: None,
+                version: None,
             },
         )
         .await;
@@ -440,6 +440,7 @@ pub async fn test(system_admin_context: &TardisContext) -> TardisResult<()> {
                 icon: None,
                 sort: None,
                 kind: None,
+                version: None,
             },
             res_ids: None,
         },
@@ -508,6 +509,7 @@ pub async fn test(system_admin_context: &TardisContext) -> TardisResult<()> {
                 icon: None,
                 sort: None,
                 kind: None,
+                version: None,
             },
             res_ids: None,
         },
@@ -617,6 +619,7 @@ pub async fn test(system_admin_context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             contact_phone: None,
+            version: None,
         },
         &funs,
         system_admin_context,
@@ -691,6 +694,7 @@ pub async fn test(system_admin_context: &TardisContext) -> TardisResult<()> {
             icon: None,
             sort: None,
             contact_phone: None,
+            version: None,
         },
         &funs,
         system_admin_context,
@@ -747,6 +751,7 @@ pub async fn test(system_admin_context: &TardisContext) -> TardisResult<()> {
             contact_phone: None,
             note: None,
             account_self_reg: None,
+            version: None,
         },
         &funs,
         system_admin_context,
@@ -788,6 +793,7 @@ pub async fn test(system_admin_context: &TardisContext) -> TardisResult<()> {
             contact_phone: None,
             note: None,
             account_self_reg: None,
+            version: None,
         },
         &funs,
         system_admin_context,
@@ -883,6 +889,7 @@ pub async fn test(system_admin_context: &TardisContext) -> TardisResult<()> {
             action: None,
             scope_level: None,
             disabled: Some(true),
+            version: None,
         },
         &funs,
         system_admin_context,
@@ -902,6 +909,7 @@ pub async fn test(system_admin_context: &TardisContext) -> TardisResult<()> {
             action: None,
             scope_level: None,
             disabled: Some(false),
+            version: None,
         },
         &funs,
         system_admin_context,
@@ -32,3 +32,11 @@ impl ChatMessageKind {
         }
     }
 }
+
+/// Status of a message for a recipient, derived from the delivered and read marks of the recipient.
+#[derive(Display, Clone, Debug, PartialEq, Eq, Deserialize, Serialize, poem_openapi::Enum)]
+pub enum ChatMessageStatusKind {
+    Sent,
+    Delivered,
+    Read,
+}
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use tardis::chrono::{DateTime, Utc};
 use tardis::web::poem_openapi;
 
-use crate::chat_enumeration::ChatMessageKind;
+use crate::chat_enumeration::{ChatMessageKind, ChatMessageStatusKind};
 
 #[derive(poem_openapi::Object, Serialize, Deserialize, Debug)]
 pub struct ChatMessageAddReq {
@@ -13,7 +13,7 @@ pub struct ChatMessageAddReq {
     pub content: String,
 }
 
-#[derive(poem_openapi::Object, Serialize, Deserialize, Debug)]
+#[derive(poem_openapi::Object, Serialize, Deserialize, Debug, Clone)]
 pub struct ChatMessageInfoResp {
     pub id: String,
     pub kind: ChatMessageKind,
@@ -22,3 +22,17 @@ pub struct ChatMessageInfoResp {
     pub owner: String,
     pub create_time: DateTime<Utc>,
 }
+
+#[derive(poem_openapi::Object, Serialize, Deserialize, Debug)]
+pub struct ChatMessageStatusResp {
+    pub account_id: String,
+    pub status: ChatMessageStatusKind,
+}
+
+#[derive(poem_openapi::Object, Serialize, Deserialize, Debug)]
+pub struct ChatMessageUnreadResp {
+    pub kind: ChatMessageKind,
+    /// Peer account id for direct messages, otherwise the same as the target id
+    pub conv_id: String,
+    pub unread_count: u64,
+}
@@ -1 +1,3 @@
 pub mod chat_account;
+pub mod chat_message;
+pub mod chat_message_mark;
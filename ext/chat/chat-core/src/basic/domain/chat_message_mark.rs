@@ -0,0 +1,73 @@
+use tardis::basic::dto::TardisContext;
+use tardis::chrono::{self, Utc};
+use tardis::db::reldb_client::TardisActiveModel;
+use tardis::db::sea_orm;
+use tardis::db::sea_orm::sea_query::{ColumnDef, Index, IndexCreateStatement, Table, TableCreateStatement};
+use tardis::db::sea_orm::*;
+
+/// Delivered and read position of an account in a conversation.
+///
+/// Messages are ordered by create time then id, so a position is the create time and id of a message.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "chat_message_mark")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub account_id: String,
+    /// [Message Kind](bios_chat_basic::chat_enumeration::ChatMessageKind)
+    pub kind: i8,
+    /// Peer account id for direct messages, otherwise the same as the target id
+    pub conv_id: String,
+    pub delivered_message_id: String,
+    pub delivered_time: Option<chrono::DateTime<Utc>>,
+    pub read_message_id: String,
+    pub read_time: Option<chrono::DateTime<Utc>>,
+
+    pub own_paths: String,
+    pub owner: String,
+    pub create_time: chrono::DateTime<Utc>,
+    pub update_time: chrono::DateTime<Utc>,
+}
+
+impl TardisActiveModel for ActiveModel {
+    fn fill_ctx(&mut self, ctx: &TardisContext, is_insert: bool) {
+        if is_insert {
+            self.own_paths = Set(ctx.own_paths.to_string());
+            self.owner = Set(ctx.owner.to_string());
+        }
+    }
+
+    fn create_table_statement(_: DbBackend) -> TableCreateStatement {
+        Table::create()
+            .table(Entity.table_ref())
+            .if_not_exists()
+            .engine("InnoDB")
+            .character_set("utf8mb4")
+            .collate("utf8mb4_0900_as_cs")
+            .col(ColumnDef::new(Column::Id).not_null().string().primary_key())
+            .col(ColumnDef::new(Column::AccountId).not_null().string())
+            .col(ColumnDef::new(Column::Kind).not_null().tiny_integer())
+            .col(ColumnDef::new(Column::ConvId).not_null().string())
+            .col(ColumnDef::new(Column::DeliveredMessageId).not_null().string())
+            .col(ColumnDef::new(Column::DeliveredTime).null().timestamp())
+            .col(ColumnDef::new(Column::ReadMessageId).not_null().string())
+            .col(ColumnDef::new(Column::ReadTime).null().timestamp())
+            .col(ColumnDef::new(Column::OwnPaths).not_null().string())
+            .col(ColumnDef::new(Column::Owner).not_null().string())
+            .col(ColumnDef::new(Column::CreateTime).extra("DEFAULT CURRENT_TIMESTAMP".to_string()).timestamp())
+            .col(ColumnDef::new(Column::UpdateTime).extra("DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP".to_string()).timestamp())
+            .to_owned()
+    }
+
+    fn create_index_statement() -> Vec<IndexCreateStatement> {
+        vec![
+            Index::create().name(&format!("idx-{}-idx1", Entity.table_name())).table(Entity).col(Column::AccountId).col(Column::Kind).col(Column::ConvId).unique().to_owned(),
+            Index::create().name(&format!("idx-{}-idx2", Entity.table_name())).table(Entity).col(Column::Kind).col(Column::ConvId).to_owned(),
+        ]
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
@@ -1 +1,4 @@
-
+pub mod chat_channel_serv;
+pub mod chat_message_mark_serv;
+pub mod chat_message_serv;
+pub mod chat_target_serv;
@@ -0,0 +1,122 @@
+use std::collections::{HashMap, HashSet};
+
+use bios_chat_basic::chat_enumeration::ChatMessageKind;
+use bios_chat_basic::dto::chat_message_dto::ChatMessageInfoResp;
+use serde::{Deserialize, Serialize};
+use tardis::basic::dto::TardisContext;
+use tardis::basic::result::TardisResult;
+use tardis::log::{trace, warn};
+use tardis::tokio::sync::mpsc::UnboundedSender;
+use tardis::tokio::sync::RwLock;
+use tardis::{TardisFuns, TardisFunsInst};
+
+use crate::basic::serv::chat_message_mark_serv::ChatMessageMarkServ;
+use crate::basic::serv::chat_target_serv::ChatTargetServ;
+use crate::chat_config::ChatConfig;
+
+#[derive(Serialize, Deserialize, Debug)]
+struct ChatChannelEnvelope {
+    message: ChatMessageInfoResp,
+    // the tenant channel of tenant messages, otherwise the account channels of the recipients
+    channels: Vec<String>,
+}
+
+lazy_static! {
+    // channel -> [(connection id, sender)]
+    static ref SUBSCRIBERS: RwLock<HashMap<String, Vec<(String, UnboundedSender<ChatMessageInfoResp>)>>> = RwLock::new(HashMap::new());
+}
+
+/// Delivery of new messages to the WebSocket connections of the current node.
+///
+/// A channel is identified by `<message kind>:<target id>`, messages are broadcast to all nodes through the mq
+/// together with their channels, and every node pushes them to its own connections of the channels.
+pub struct ChatChannelServ;
+
+impl ChatChannelServ {
+    /// Channels a connection of the context listens to.
+    ///
+    /// Recipients of tenant messages are all accounts of the tenant, so a connection listens to its tenant besides its own account,
+    /// the recipients of the other messages are resolved when the message is sent.
+    pub fn get_channels(ctx: &TardisContext) -> Vec<String> {
+        let mut channels = vec![Self::package_channel(&ChatMessageKind::ToAccount, &ctx.owner)];
+        let tenant_id = ChatTargetServ::get_tenant_id(ctx);
+        if !tenant_id.is_empty() {
+            channels.push(Self::package_channel(&ChatMessageKind::ToTenant, &tenant_id));
+        }
+        channels
+    }
+
+    pub async fn subscribe(conn_id: &str, channels: &[String], sender: UnboundedSender<ChatMessageInfoResp>) {
+        let mut subscribers = SUBSCRIBERS.write().await;
+        for channel in channels {
+            subscribers.entry(channel.to_string()).or_insert_with(Vec::new).push((conn_id.to_string(), sender.clone()));
+        }
+    }
+
+    pub async fn unsubscribe(conn_id: &str, channels: &[String]) {
+        let mut subscribers = SUBSCRIBERS.write().await;
+        for channel in channels {
+            if let Some(conns) = subscribers.get_mut(channel) {
+                conns.retain(|(id, _)| id != conn_id);
+                if conns.is_empty() {
+                    subscribers.remove(channel);
+                }
+            }
+        }
+    }
+
+    /// Broadcast the message to the channel of the tenant for tenant messages, otherwise to the channels of the recipients resolved by the target.
+    pub async fn publish(message: ChatMessageInfoResp, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        let account_ids = ChatTargetServ::find_account_ids(&message.kind, &message.to_id, ctx).await?;
+        ChatMessageMarkServ::incr_unread_counts(&message, &account_ids, funs).await?;
+        let channels = if message.kind == ChatMessageKind::ToTenant {
+            vec![Self::package_channel(&message.kind, &message.to_id)]
+        } else {
+            account_ids.iter().map(|account_id| Self::package_channel(&ChatMessageKind::ToAccount, account_id)).collect()
+        };
+        let envelope = ChatChannelEnvelope { message, channels };
+        funs.mq().publish(&funs.conf::<ChatConfig>().mq_topic_message, TardisFuns::json.obj_to_string(&envelope)?, &HashMap::new()).await
+    }
+
+    pub async fn init_listener(funs: &TardisFunsInst) -> TardisResult<()> {
+        funs.mq()
+            .subscribe(&funs.conf::<ChatConfig>().mq_topic_message, |(_, message)| async move {
+                Self::dispatch(message).await;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn dispatch(message: String) {
+        let envelope = match TardisFuns::json.str_to_obj::<ChatChannelEnvelope>(&message) {
+            Ok(envelope) => envelope,
+            Err(e) => {
+                warn!("[BIOS.Chat] parse message error: {}", e);
+                return;
+            }
+        };
+        let message = envelope.message;
+        // Sync to the other connections of the sender as well
+        let mut channels = envelope.channels;
+        channels.push(Self::package_channel(&ChatMessageKind::ToAccount, &message.owner));
+        let subscribers = SUBSCRIBERS.read().await;
+        // A connection listening to several of the channels receives the message once
+        let mut sent_conn_ids = HashSet::new();
+        for channel in channels {
+            if let Some(conns) = subscribers.get(&channel) {
+                for (conn_id, sender) in conns {
+                    if !sent_conn_ids.insert(conn_id.as_str()) {
+                        continue;
+                    }
+                    if sender.send(message.clone()).is_err() {
+                        trace!("[BIOS.Chat] connection {} is closed", conn_id);
+                    }
+                }
+            }
+        }
+    }
+
+    fn package_channel(kind: &ChatMessageKind, to_id: &str) -> String {
+        format!("{}:{}", kind, to_id)
+    }
+}
@@ -0,0 +1,231 @@
+use bios_chat_basic::chat_enumeration::{ChatMessageKind, ChatMessageStatusKind};
+use bios_chat_basic::dto::chat_message_dto::{ChatMessageInfoResp, ChatMessageStatusResp, ChatMessageUnreadResp};
+use tardis::basic::dto::TardisContext;
+use tardis::basic::result::TardisResult;
+use tardis::cache::redis;
+use tardis::chrono::{DateTime, Utc};
+use tardis::db::sea_orm;
+use tardis::db::sea_orm::sea_query::{Expr, Query, SelectStatement};
+use tardis::db::sea_orm::*;
+use tardis::{TardisFuns, TardisFunsInst};
+
+use crate::basic::domain::chat_message_mark;
+use crate::basic::serv::chat_message_serv::ChatMessageServ;
+use crate::basic::serv::chat_target_serv::ChatTargetServ;
+use crate::chat_config::ChatConfig;
+
+const INCR_IF_EXISTS_SCRIPT: &str = "if redis.call('EXISTS', KEYS[1]) == 1 then return redis.call('INCR', KEYS[1]) end return nil";
+
+/// Delivered and read marks of the recipients, and the unread counters derived from them.
+///
+/// The unread counters are cached and only incremented while cached, they are recounted from the read marks when missing or read.
+pub struct ChatMessageMarkServ;
+
+#[derive(sea_orm::FromQueryResult)]
+struct ChatMessageMarkResp {
+    pub id: String,
+    pub account_id: String,
+    pub delivered_message_id: String,
+    pub delivered_time: Option<DateTime<Utc>>,
+    pub read_message_id: String,
+    pub read_time: Option<DateTime<Utc>>,
+}
+
+impl ChatMessageMarkServ {
+    pub fn get_obj_name() -> String {
+        chat_message_mark::Entity.table_name().to_string()
+    }
+
+    /// The conversation of the message seen by the account, i.e. the peer of direct messages or the target of the others.
+    pub fn get_conv_id(message: &ChatMessageInfoResp, account_id: &str) -> String {
+        if message.kind == ChatMessageKind::ToAccount && message.owner != account_id {
+            message.owner.clone()
+        } else {
+            message.to_id.clone()
+        }
+    }
+
+    pub async fn mark_delivered(message: &ChatMessageInfoResp, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        if message.owner == ctx.owner {
+            return Ok(());
+        }
+        Self::do_mark(message, false, funs, ctx).await
+    }
+
+    /// Mark the messages of the conversation read up to the message, the message is also delivered.
+    ///
+    /// Only the members of the target can mark its messages.
+    pub async fn mark_read(message_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        let message = ChatMessageServ::get_message(message_id, funs, ctx).await?;
+        ChatTargetServ::check_member(&message.kind, &message.to_id, funs, ctx).await?;
+        Self::do_mark(&message, true, funs, ctx).await?;
+        let conv_id = Self::get_conv_id(&message, &ctx.owner);
+        Self::recount_unread(&message.kind, &conv_id, funs, ctx).await?;
+        Ok(())
+    }
+
+    pub async fn get_unread_count(kind: &ChatMessageKind, conv_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<ChatMessageUnreadResp> {
+        ChatTargetServ::check_member(kind, conv_id, funs, ctx).await?;
+        let cache_key = Self::package_unread_cache_key(&ctx.owner, kind, conv_id, funs);
+        let unread_count = match funs.cache().get(&cache_key).await?.and_then(|count| count.parse::<u64>().ok()) {
+            Some(unread_count) => unread_count,
+            None => Self::recount_unread(kind, conv_id, funs, ctx).await?,
+        };
+        Ok(ChatMessageUnreadResp {
+            kind: kind.clone(),
+            conv_id: conv_id.to_string(),
+            unread_count,
+        })
+    }
+
+    /// Increase the cached unread counters of the recipients in one pipeline, the sender is excluded.
+    pub async fn incr_unread_counts(message: &ChatMessageInfoResp, account_ids: &[String], funs: &TardisFunsInst) -> TardisResult<()> {
+        let cache_keys = account_ids
+            .iter()
+            .filter(|account_id| account_id != &&message.owner)
+            .map(|account_id| Self::package_unread_cache_key(account_id, &message.kind, &Self::get_conv_id(message, account_id), funs))
+            .collect::<Vec<String>>();
+        if cache_keys.is_empty() {
+            return Ok(());
+        }
+        let mut pipe = redis::pipe();
+        for cache_key in cache_keys {
+            // Not cached counters are recounted on the next read, so the check and the increase must be atomic
+            pipe.cmd("EVAL").arg(INCR_IF_EXISTS_SCRIPT).arg(1).arg(cache_key).ignore();
+        }
+        pipe.query_async::<_, ()>(&mut *funs.cache().cmd().await?).await?;
+        Ok(())
+    }
+
+    /// Find the status of the message for each recipient, only the sender can see them.
+    pub async fn find_message_statuses(message_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<Vec<ChatMessageStatusResp>> {
+        let message = ChatMessageServ::get_message(message_id, funs, ctx).await?;
+        if message.owner != ctx.owner {
+            return Err(funs.err().bad_request(
+                &Self::get_obj_name(),
+                "find_message_statuses",
+                &format!("message {} is not sent by the current account", message_id),
+                "403-chat-message-not-owner",
+            ));
+        }
+        let account_ids = ChatTargetServ::find_account_ids(&message.kind, &message.to_id, ctx).await?;
+        let mut query = Self::package_query();
+        query.and_where(Expr::col(chat_message_mark::Column::Kind).eq(message.kind.to_int()));
+        if message.kind == ChatMessageKind::ToAccount {
+            query.and_where(Expr::col(chat_message_mark::Column::ConvId).eq(message.owner.as_str()));
+        } else {
+            query.and_where(Expr::col(chat_message_mark::Column::ConvId).eq(message.to_id.as_str()));
+        }
+        query.and_where(Expr::col(chat_message_mark::Column::AccountId).is_in(account_ids.clone()));
+        let marks = funs.db().find_dtos::<ChatMessageMarkResp>(&query).await?;
+        Ok(account_ids
+            .into_iter()
+            .filter(|account_id| account_id != &message.owner)
+            .map(|account_id| {
+                let status = match marks.iter().find(|mark| mark.account_id == account_id) {
+                    Some(mark) if !Self::is_after(&message, mark.read_time, &mark.read_message_id) => ChatMessageStatusKind::Read,
+                    Some(mark) if !Self::is_after(&message, mark.delivered_time, &mark.delivered_message_id) => ChatMessageStatusKind::Delivered,
+                    _ => ChatMessageStatusKind::Sent,
+                };
+                ChatMessageStatusResp { account_id, status }
+            })
+            .collect())
+    }
+
+    async fn do_mark(message: &ChatMessageInfoResp, read: bool, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        let conv_id = Self::get_conv_id(message, &ctx.owner);
+        let mut query = Self::package_query();
+        query
+            .and_where(Expr::col(chat_message_mark::Column::AccountId).eq(ctx.owner.as_str()))
+            .and_where(Expr::col(chat_message_mark::Column::Kind).eq(message.kind.to_int()))
+            .and_where(Expr::col(chat_message_mark::Column::ConvId).eq(conv_id.as_str()));
+        let mark = match funs.db().get_dto::<ChatMessageMarkResp>(&query).await? {
+            Some(mark) => mark,
+            None => {
+                funs.db()
+                    .insert_one(
+                        chat_message_mark::ActiveModel {
+                            id: Set(TardisFuns::field.nanoid()),
+                            account_id: Set(ctx.owner.to_string()),
+                            kind: Set(message.kind.to_int()),
+                            conv_id: Set(conv_id),
+                            delivered_message_id: Set(message.id.clone()),
+                            delivered_time: Set(Some(message.create_time)),
+                            read_message_id: Set(if read { message.id.clone() } else { "".to_string() }),
+                            read_time: Set(if read { Some(message.create_time) } else { None }),
+                            ..Default::default()
+                        },
+                        ctx,
+                    )
+                    .await?;
+                return Ok(());
+            }
+        };
+        // Marks never move backwards
+        let mut mark_model = chat_message_mark::ActiveModel {
+            id: Set(mark.id.clone()),
+            ..Default::default()
+        };
+        let mut changed = false;
+        if Self::is_after(message, mark.delivered_time, &mark.delivered_message_id) {
+            mark_model.delivered_message_id = Set(message.id.clone());
+            mark_model.delivered_time = Set(Some(message.create_time));
+            changed = true;
+        }
+        if read && Self::is_after(message, mark.read_time, &mark.read_message_id) {
+            mark_model.read_message_id = Set(message.id.clone());
+            mark_model.read_time = Set(Some(message.create_time));
+            changed = true;
+        }
+        if changed {
+            funs.db().update_one(mark_model, ctx).await?;
+        }
+        Ok(())
+    }
+
+    async fn recount_unread(kind: &ChatMessageKind, conv_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<u64> {
+        let mut query = Self::package_query();
+        query
+            .and_where(Expr::col(chat_message_mark::Column::AccountId).eq(ctx.owner.as_str()))
+            .and_where(Expr::col(chat_message_mark::Column::Kind).eq(kind.to_int()))
+            .and_where(Expr::col(chat_message_mark::Column::ConvId).eq(conv_id));
+        let after = funs.db().get_dto::<ChatMessageMarkResp>(&query).await?.and_then(|mark| mark.read_time.map(|read_time| (read_time, mark.read_message_id)));
+        let unread_count = ChatMessageServ::count_received_messages_after(kind, conv_id, after, funs, ctx).await?;
+        let conf = funs.conf::<ChatConfig>();
+        funs.cache()
+            .set_ex(
+                &Self::package_unread_cache_key(&ctx.owner, kind, conv_id, funs),
+                &unread_count.to_string(),
+                conf.cache_key_unread_expire_sec,
+            )
+            .await?;
+        Ok(unread_count)
+    }
+
+    /// Whether the message is after the mark, messages are ordered by create time then id.
+    fn is_after(message: &ChatMessageInfoResp, mark_time: Option<DateTime<Utc>>, mark_message_id: &str) -> bool {
+        match mark_time {
+            Some(mark_time) => message.create_time > mark_time || (message.create_time == mark_time && message.id.as_str() > mark_message_id),
+            None => true,
+        }
+    }
+
+    fn package_query() -> SelectStatement {
+        let mut query = Query::select();
+        query
+            .columns(vec![
+                chat_message_mark::Column::Id,
+                chat_message_mark::Column::AccountId,
+                chat_message_mark::Column::DeliveredMessageId,
+                chat_message_mark::Column::DeliveredTime,
+                chat_message_mark::Column::ReadMessageId,
+                chat_message_mark::Column::ReadTime,
+            ])
+            .from(chat_message_mark::Entity);
+        query
+    }
+
+    fn package_unread_cache_key(account_id: &str, kind: &ChatMessageKind, conv_id: &str, funs: &TardisFunsInst) -> String {
+        format!("{}{}:{}:{}", funs.conf::<ChatConfig>().cache_key_unread_, account_id, kind, conv_id)
+    }
+}
@@ -0,0 +1,200 @@
+use bios_basic::rbum::helper::rbum_scope_helper;
+use bios_basic::rbum::rbum_enumeration::RbumScopeLevelKind;
+use bios_chat_basic::chat_enumeration::ChatMessageKind;
+use bios_chat_basic::dto::chat_message_dto::{ChatMessageAddReq, ChatMessageInfoResp};
+use tardis::basic::dto::TardisContext;
+use tardis::basic::result::TardisResult;
+use tardis::chrono::{DateTime, Utc};
+use tardis::db::sea_orm;
+use tardis::db::sea_orm::sea_query::{Cond, Expr, Order, Query, SelectStatement};
+use tardis::db::sea_orm::*;
+use tardis::web::web_resp::TardisPage;
+use tardis::{TardisFuns, TardisFunsInst};
+
+use crate::basic::domain::chat_message;
+use crate::basic::serv::chat_target_serv::ChatTargetServ;
+
+pub struct ChatMessageServ;
+
+#[derive(sea_orm::FromQueryResult)]
+struct ChatMessageResp {
+    pub id: String,
+    pub kind: i8,
+    pub content: String,
+    pub to_id: String,
+    pub owner: String,
+    pub create_time: DateTime<Utc>,
+}
+
+impl ChatMessageServ {
+    pub fn get_obj_name() -> String {
+        chat_message::Entity.table_name().to_string()
+    }
+
+    pub async fn add_message(add_req: &ChatMessageAddReq, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<String> {
+        ChatTargetServ::check_sender(&add_req.kind, &add_req.to_id, funs, ctx).await?;
+        let id = TardisFuns::field.nanoid();
+        funs.db()
+            .insert_one(
+                chat_message::ActiveModel {
+                    id: Set(id.clone()),
+                    kind: Set(add_req.kind.to_int()),
+                    to_id: Set(add_req.to_id.to_string()),
+                    content: Set(add_req.content.to_string()),
+                    ..Default::default()
+                },
+                ctx,
+            )
+            .await?;
+        Ok(id)
+    }
+
+    /// Find the messages of the conversation, `conv_id` is the peer account id for direct messages, otherwise the target id.
+    ///
+    /// The account of the context must be a member of the target.
+    pub async fn paginate_messages(
+        kind: &ChatMessageKind,
+        conv_id: &str,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+        page_number: u64,
+        page_size: u64,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<TardisPage<ChatMessageInfoResp>> {
+        ChatTargetServ::check_member(kind, conv_id, funs, ctx).await?;
+        let mut query = Self::package_query(ctx);
+        query.and_where(Expr::col(chat_message::Column::Kind).eq(kind.to_int()));
+        if kind == &ChatMessageKind::ToAccount {
+            query.cond_where(
+                Cond::any()
+                    .add(Expr::col(chat_message::Column::Owner).eq(ctx.owner.as_str()).and(Expr::col(chat_message::Column::ToId).eq(conv_id)))
+                    .add(Expr::col(chat_message::Column::Owner).eq(conv_id).and(Expr::col(chat_message::Column::ToId).eq(ctx.owner.as_str()))),
+            );
+        } else {
+            query.and_where(Expr::col(chat_message::Column::ToId).eq(conv_id));
+        }
+        query
+            .and_where(Expr::col(chat_message::Column::CreateTime).gte(start_time))
+            .and_where(Expr::col(chat_message::Column::CreateTime).lte(end_time))
+            .order_by(chat_message::Column::CreateTime, Order::Desc)
+            .order_by(chat_message::Column::Id, Order::Desc);
+        let (records, total_size) = funs.db().paginate_dtos::<ChatMessageResp>(&query, page_number, page_size).await?;
+        Ok(TardisPage {
+            page_size,
+            page_number,
+            total_size,
+            records: records.into_iter().map(Self::convert).collect::<TardisResult<Vec<ChatMessageInfoResp>>>()?,
+        })
+    }
+
+    pub async fn get_message(id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<ChatMessageInfoResp> {
+        let mut query = Self::package_query(ctx);
+        query.and_where(Expr::col(chat_message::Column::Id).eq(id));
+        let resp = funs
+            .db()
+            .get_dto::<ChatMessageResp>(&query)
+            .await?
+            .ok_or_else(|| funs.err().not_found(&Self::get_obj_name(), "get", &format!("not found message {}", id), "404-chat-message-not-exist"))?;
+        Self::convert(resp)
+    }
+
+    /// Find the messages after the last received one, in ascending order, used by reconnected connections.
+    pub async fn find_messages_since(
+        last_message_id: &str,
+        set_ids: &[String],
+        max_size: u64,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<Vec<ChatMessageInfoResp>> {
+        let last_message = Self::get_message(last_message_id, funs, ctx).await?;
+        let mut targets = Cond::any().add(Expr::col(chat_message::Column::Kind).eq(ChatMessageKind::ToAccount.to_int()));
+        if let Some(tenant_id) = rbum_scope_helper::get_path_item(RbumScopeLevelKind::L1.to_int(), &ctx.own_paths) {
+            targets = targets.add(Expr::col(chat_message::Column::Kind).eq(ChatMessageKind::ToTenant.to_int()).and(Expr::col(chat_message::Column::ToId).eq(tenant_id)));
+        }
+        if let Some(app_id) = rbum_scope_helper::get_path_item(RbumScopeLevelKind::L2.to_int(), &ctx.own_paths) {
+            targets = targets.add(Expr::col(chat_message::Column::Kind).eq(ChatMessageKind::ToApp.to_int()).and(Expr::col(chat_message::Column::ToId).eq(app_id)));
+        }
+        if !set_ids.is_empty() {
+            targets = targets.add(Expr::col(chat_message::Column::Kind).eq(ChatMessageKind::ToSet.to_int()).and(Expr::col(chat_message::Column::ToId).is_in(set_ids.to_vec())));
+        }
+        let mut query = Self::package_query(ctx);
+        query
+            .cond_where(targets)
+            .cond_where(
+                Cond::any()
+                    .add(Expr::col(chat_message::Column::CreateTime).gt(last_message.create_time))
+                    .add(Expr::col(chat_message::Column::CreateTime).eq(last_message.create_time).and(Expr::col(chat_message::Column::Id).gt(last_message.id.as_str()))),
+            )
+            .order_by(chat_message::Column::CreateTime, Order::Asc)
+            .order_by(chat_message::Column::Id, Order::Asc)
+            .limit(max_size);
+        let records = funs.db().find_dtos::<ChatMessageResp>(&query).await?;
+        records.into_iter().map(Self::convert).collect()
+    }
+
+    /// Count the messages received by the account of the context in the conversation after the position.
+    pub async fn count_received_messages_after(
+        kind: &ChatMessageKind,
+        conv_id: &str,
+        after: Option<(DateTime<Utc>, String)>,
+        funs: &TardisFunsInst,
+        ctx: &TardisContext,
+    ) -> TardisResult<u64> {
+        let mut query = Self::package_query(ctx);
+        query.and_where(Expr::col(chat_message::Column::Kind).eq(kind.to_int()));
+        if kind == &ChatMessageKind::ToAccount {
+            query.and_where(Expr::col(chat_message::Column::Owner).eq(conv_id)).and_where(Expr::col(chat_message::Column::ToId).eq(ctx.owner.as_str()));
+        } else {
+            query.and_where(Expr::col(chat_message::Column::ToId).eq(conv_id)).and_where(Expr::col(chat_message::Column::Owner).ne(ctx.owner.as_str()));
+        }
+        if let Some((create_time, id)) = after {
+            query.cond_where(
+                Cond::any()
+                    .add(Expr::col(chat_message::Column::CreateTime).gt(create_time))
+                    .add(Expr::col(chat_message::Column::CreateTime).eq(create_time).and(Expr::col(chat_message::Column::Id).gt(id))),
+            );
+        }
+        funs.db().count(&query).await
+    }
+
+    /// Messages visible to the current context.
+    ///
+    /// All messages are limited to the tenant of the context, direct messages are only visible to the sender and the receiver.
+    fn package_query(ctx: &TardisContext) -> SelectStatement {
+        let mut query = Query::select();
+        query
+            .columns(vec![
+                chat_message::Column::Id,
+                chat_message::Column::Kind,
+                chat_message::Column::Content,
+                chat_message::Column::ToId,
+                chat_message::Column::Owner,
+                chat_message::Column::CreateTime,
+            ])
+            .from(chat_message::Entity)
+            .and_where(Expr::col(chat_message::Column::OwnPaths).like(format!("{}%", Self::get_tenant_id(ctx)).as_str()))
+            .cond_where(
+                Cond::any()
+                    .add(Expr::col(chat_message::Column::Kind).ne(ChatMessageKind::ToAccount.to_int()))
+                    .add(Expr::col(chat_message::Column::Owner).eq(ctx.owner.as_str()))
+                    .add(Expr::col(chat_message::Column::ToId).eq(ctx.owner.as_str())),
+            );
+        query
+    }
+
+    fn get_tenant_id(ctx: &TardisContext) -> String {
+        rbum_scope_helper::get_path_item(RbumScopeLevelKind::L1.to_int(), &ctx.own_paths).unwrap_or_default()
+    }
+
+    fn convert(resp: ChatMessageResp) -> TardisResult<ChatMessageInfoResp> {
+        Ok(ChatMessageInfoResp {
+            id: resp.id,
+            kind: ChatMessageKind::from_int(resp.kind)?,
+            content: resp.content,
+            to_id: resp.to_id,
+            owner: resp.owner,
+            create_time: resp.create_time,
+        })
+    }
+}
@@ -0,0 +1,150 @@
+use bios_basic::rbum::dto::rbum_filer_dto::{RbumBasicFilterReq, RbumRelFilterReq, RbumSetItemFilterReq};
+use bios_basic::rbum::helper::rbum_scope_helper;
+use bios_basic::rbum::rbum_enumeration::RbumScopeLevelKind;
+use bios_basic::rbum::serv::rbum_crud_serv::RbumCrudOperation;
+use bios_basic::rbum::serv::rbum_item_serv::RbumItemCrudOperation;
+use bios_basic::rbum::serv::rbum_rel_serv::RbumRelServ;
+use bios_basic::rbum::serv::rbum_set_serv::RbumSetItemServ;
+use bios_chat_basic::chat_enumeration::ChatMessageKind;
+use bios_iam::basic::dto::iam_filer_dto::IamAccountFilterReq;
+use bios_iam::basic::serv::iam_account_serv::IamAccountServ;
+use bios_iam::basic::serv::iam_app_serv::IamAppServ;
+use bios_iam::iam_config::IamBasicConfigApi;
+use bios_iam::iam_constants;
+use bios_iam::iam_enumeration::IamRelKind;
+use tardis::basic::dto::TardisContext;
+use tardis::basic::result::TardisResult;
+use tardis::TardisFunsInst;
+
+use crate::basic::serv::chat_message_serv::ChatMessageServ;
+
+/// Resolution of message targets to the recipient accounts through IAM.
+///
+/// The IAM services are called with the IAM instance, because they depend on the IAM config.
+pub struct ChatTargetServ;
+
+impl ChatTargetServ {
+    /// Find the ids of the accounts receiving messages sent to the target.
+    pub async fn find_account_ids(kind: &ChatMessageKind, to_id: &str, ctx: &TardisContext) -> TardisResult<Vec<String>> {
+        let iam_funs = iam_constants::get_tardis_inst();
+        match kind {
+            ChatMessageKind::ToAccount => Ok(vec![to_id.to_string()]),
+            ChatMessageKind::ToSet => {
+                let set_items =
+                    RbumSetItemServ::find_detail_rbums(&Self::package_set_item_filter(to_id, None, &iam_funs), None, None, &iam_funs, &Self::get_tenant_ctx(ctx)).await?;
+                let mut account_ids = set_items.into_iter().map(|set_item| set_item.rel_rbum_item_id).collect::<Vec<String>>();
+                account_ids.sort();
+                account_ids.dedup();
+                Ok(account_ids)
+            }
+            ChatMessageKind::ToApp => {
+                let mut account_ids = IamAppServ::find_rel_account(to_id, &iam_funs, ctx).await?.into_iter().map(|rel| rel.rel_id).collect::<Vec<String>>();
+                account_ids.sort();
+                account_ids.dedup();
+                Ok(account_ids)
+            }
+            ChatMessageKind::ToTenant => IamAccountServ::find_id_items(&Self::package_tenant_account_filter(to_id, None), None, None, &iam_funs, &Self::get_tenant_ctx(ctx)).await,
+        }
+    }
+
+    /// Check that the sender of the context can send messages to the target.
+    ///
+    /// Tenant and app messages can only be sent to the tenant and app of the context,
+    /// and the sender must be a member of the set, app or tenant.
+    pub async fn check_sender(kind: &ChatMessageKind, to_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        let ctx_target_id = match kind {
+            ChatMessageKind::ToTenant => Some(Self::get_tenant_id(ctx)),
+            ChatMessageKind::ToApp => Some(rbum_scope_helper::get_path_item(RbumScopeLevelKind::L2.to_int(), &ctx.own_paths).unwrap_or_default()),
+            _ => None,
+        };
+        if let Some(ctx_target_id) = ctx_target_id {
+            if ctx_target_id != to_id {
+                return Err(funs.err().bad_request(
+                    &ChatMessageServ::get_obj_name(),
+                    "add",
+                    &format!("target {} of kind {} does not match the context", to_id, kind),
+                    "400-chat-message-target-invalid",
+                ));
+            }
+        }
+        Self::check_member(kind, to_id, funs, ctx).await
+    }
+
+    /// Check that the account of the context is a member of the target, direct messages have no membership.
+    pub async fn check_member(kind: &ChatMessageKind, to_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<()> {
+        let iam_funs = iam_constants::get_tardis_inst();
+        let account_ids = Some(vec![ctx.owner.clone()]);
+        let count = match kind {
+            ChatMessageKind::ToSet => RbumSetItemServ::count_rbums(&Self::package_set_item_filter(to_id, account_ids, &iam_funs), &iam_funs, &Self::get_tenant_ctx(ctx)).await?,
+            ChatMessageKind::ToApp => {
+                RbumRelServ::count_rbums(
+                    &RbumRelFilterReq {
+                        basic: RbumBasicFilterReq {
+                            own_paths: Some(ctx.own_paths.to_string()),
+                            with_sub_own_paths: true,
+                            ignore_scope: true,
+                            ..Default::default()
+                        },
+                        tag: Some(IamRelKind::IamAccountApp.to_string()),
+                        from_rbum_id: Some(ctx.owner.clone()),
+                        to_rbum_item_id: Some(to_id.to_string()),
+                        ..Default::default()
+                    },
+                    &iam_funs,
+                    ctx,
+                )
+                .await?
+            }
+            ChatMessageKind::ToTenant => IamAccountServ::count_items(&Self::package_tenant_account_filter(to_id, account_ids), &iam_funs, &Self::get_tenant_ctx(ctx)).await?,
+            ChatMessageKind::ToAccount => return Ok(()),
+        };
+        if count == 0 {
+            return Err(funs.err().forbidden(
+                &ChatMessageServ::get_obj_name(),
+                "check_member",
+                &format!("account {} is not a member of target {} of kind {}", ctx.owner, to_id, kind),
+                "403-chat-message-not-member",
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn get_tenant_id(ctx: &TardisContext) -> String {
+        rbum_scope_helper::get_path_item(RbumScopeLevelKind::L1.to_int(), &ctx.own_paths).unwrap_or_default()
+    }
+
+    fn package_set_item_filter(set_cate_id: &str, account_ids: Option<Vec<String>>, iam_funs: &TardisFunsInst) -> RbumSetItemFilterReq {
+        RbumSetItemFilterReq {
+            basic: RbumBasicFilterReq {
+                with_sub_own_paths: true,
+                ..Default::default()
+            },
+            rel_rbum_set_cate_ids: Some(vec![set_cate_id.to_string()]),
+            rel_rbum_item_disabled: Some(false),
+            rel_rbum_item_ids: account_ids,
+            rel_rbum_item_kind_ids: Some(vec![iam_funs.iam_basic_kind_account_id()]),
+            ..Default::default()
+        }
+    }
+
+    fn package_tenant_account_filter(tenant_id: &str, account_ids: Option<Vec<String>>) -> IamAccountFilterReq {
+        IamAccountFilterReq {
+            basic: RbumBasicFilterReq {
+                ids: account_ids,
+                own_paths: Some(tenant_id.to_string()),
+                with_sub_own_paths: false,
+                enabled: Some(true),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    // Sets and accounts belong to the tenant, query them from the tenant even if the context is an app
+    fn get_tenant_ctx(ctx: &TardisContext) -> TardisContext {
+        TardisContext {
+            own_paths: Self::get_tenant_id(ctx),
+            ..ctx.clone()
+        }
+    }
+}
@@ -6,10 +6,27 @@ use std::fmt::Debug;
 #[serde(default)]
 pub struct ChatConfig {
     pub rbum: RbumConfig,
+    // broadcast topic of new messages, every node delivers them to its own connections
+    pub mq_topic_message: String,
+    pub ws_heartbeat_interval_sec: u64,
+    // connections without any frame in this period are closed
+    pub ws_idle_timeout_sec: u64,
+    // max number of messages sent when a connection catches up from the last message id
+    pub ws_catch_up_max_size: u64,
+    pub cache_key_unread_: String,
+    pub cache_key_unread_expire_sec: usize,
 }
 
 impl Default for ChatConfig {
     fn default() -> Self {
-        ChatConfig { rbum: Default::default() }
+        ChatConfig {
+            rbum: Default::default(),
+            mq_topic_message: "chat::message".to_string(),
+            ws_heartbeat_interval_sec: 30,
+            ws_idle_timeout_sec: 90,
+            ws_catch_up_max_size: 500,
+            cache_key_unread_: "chat:cache:unread:".to_string(),
+            cache_key_unread_expire_sec: 60 * 60 * 24 * 7,
+        }
     }
 }
@@ -1,11 +1,17 @@
+use bios_basic::rbum::helper::rbum_event_helper;
 use bios_chat_basic::chat_constants;
-use tardis::{basic::result::TardisResult, web::web_server::TardisWebServer, TardisFunsInst};
+use tardis::db::reldb_client::TardisActiveModel;
+use tardis::{basic::result::TardisResult, web::web_server::TardisWebServer, TardisFuns, TardisFunsInst};
 
+use crate::basic::domain::{chat_message, chat_message_mark};
+use crate::basic::serv::chat_channel_serv::ChatChannelServ;
 use crate::{chat_config::ChatConfig, console_common::api::chat_cc_message};
 
 pub async fn init(web_server: &TardisWebServer) -> TardisResult<()> {
     let funs = chat_constants::get_tardis_inst();
     init_db(funs).await?;
+    rbum_event_helper::start_relay_task(chat_constants::COMPONENT_CODE);
+    ChatChannelServ::init_listener(&chat_constants::get_tardis_inst()).await?;
     init_api(web_server).await
 }
 
@@ -17,7 +23,8 @@ async fn init_api(web_server: &TardisWebServer) -> TardisResult<()> {
 pub async fn init_db(mut funs: TardisFunsInst) -> TardisResult<()> {
     bios_basic::rbum::rbum_initializer::init(funs.module_code(), funs.conf::<ChatConfig>().rbum.clone()).await?;
     funs.begin().await?;
-    // TODO
+    funs.db().create_table_and_index(&chat_message::ActiveModel::create_table_and_index_statement(TardisFuns::reldb().backend())).await?;
+    funs.db().create_table_and_index(&chat_message_mark::ActiveModel::create_table_and_index_statement(TardisFuns::reldb().backend())).await?;
     funs.commit().await?;
     Ok(())
 }
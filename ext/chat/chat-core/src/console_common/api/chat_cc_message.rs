@@ -1,14 +1,28 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
 use bios_chat_basic::chat_constants;
-use bios_chat_basic::dto::chat_message_dto::{ChatMessageAddReq, ChatMessageInfoResp};
+use bios_chat_basic::chat_enumeration::ChatMessageKind;
+use bios_chat_basic::dto::chat_message_dto::{ChatMessageAddReq, ChatMessageInfoResp, ChatMessageStatusResp, ChatMessageUnreadResp};
+use tardis::basic::dto::TardisContext;
 use tardis::chrono::{DateTime, Utc};
+use tardis::futures::{Sink, SinkExt, StreamExt};
+use tardis::log::{trace, warn};
+use tardis::tokio;
+use tardis::tokio::sync::mpsc;
 use tardis::web::context_extractor::TardisContextExtractor;
-use tardis::web::poem::web::websocket::{Message, WebSocket};
-use tardis::web::poem::web::Data;
-use tardis::web::poem::{handler, IntoResponse};
+use tardis::web::poem::web::websocket::{BoxWebSocketUpgraded, Message, WebSocket};
 use tardis::web::poem_openapi;
-use tardis::web::poem_openapi::param::Query;
+use tardis::web::poem_openapi::param::{Path, Query};
 use tardis::web::poem_openapi::payload::Json;
-use tardis::web::web_resp::{TardisApiResult, TardisPage, TardisResp};
+use tardis::web::web_resp::{TardisApiResult, TardisPage, TardisResp, Void};
+use tardis::{TardisFuns, TardisFunsInst};
+
+use crate::basic::serv::chat_channel_serv::ChatChannelServ;
+use crate::basic::serv::chat_message_mark_serv::ChatMessageMarkServ;
+use crate::basic::serv::chat_message_serv::ChatMessageServ;
+use crate::basic::serv::chat_target_serv::ChatTargetServ;
+use crate::chat_config::ChatConfig;
 
 pub struct ChatCcMessageApi;
 
@@ -20,16 +34,22 @@ impl ChatCcMessageApi {
     async fn add_message(&self, add_req: Json<ChatMessageAddReq>, ctx: TardisContextExtractor) -> TardisApiResult<String> {
         let mut funs = chat_constants::get_tardis_inst();
         funs.begin().await?;
-        // TODO
+        let result = ChatMessageServ::add_message(&add_req.0, &funs, &ctx.0).await?;
         funs.commit().await?;
-        TardisResp::ok("".to_string())
+        // Deliver only after the message is persisted, so that receivers can always find it when catching up
+        let message = ChatMessageServ::get_message(&result, &funs, &ctx.0).await?;
+        ChatChannelServ::publish(message, &funs, &ctx.0).await?;
+        TardisResp::ok(result)
     }
 
-    /// Find Message
+    /// Find Message of Conversation
+    ///
+    /// `conv_id` is the peer account id for direct messages, otherwise the target id.
     #[oai(path = "/", method = "get")]
     async fn paginate_message(
         &self,
-        to_id: Query<String>,
+        kind: Query<ChatMessageKind>,
+        conv_id: Query<String>,
         start_time: Query<DateTime<Utc>>,
         end_time: Query<DateTime<Utc>>,
         page_number: Query<u64>,
@@ -37,12 +57,155 @@ impl ChatCcMessageApi {
         ctx: TardisContextExtractor,
     ) -> TardisApiResult<TardisPage<ChatMessageInfoResp>> {
         let funs = chat_constants::get_tardis_inst();
-        // TODO
-        TardisResp::ok(TardisPage {
-            page_size: todo!(),
-            page_number: todo!(),
-            total_size: todo!(),
-            records: todo!(),
-        })
+        let result = ChatMessageServ::paginate_messages(&kind.0, &conv_id.0, start_time.0, end_time.0, page_number.0, page_size.0, &funs, &ctx.0).await?;
+        TardisResp::ok(result)
+    }
+
+    /// Mark Messages Read up to the Message
+    #[oai(path = "/:id/read", method = "put")]
+    async fn mark_read(&self, id: Path<String>, ctx: TardisContextExtractor) -> TardisApiResult<Void> {
+        let mut funs = chat_constants::get_tardis_inst();
+        funs.begin().await?;
+        ChatMessageMarkServ::mark_read(&id.0, &funs, &ctx.0).await?;
+        funs.commit().await?;
+        TardisResp::ok(Void {})
+    }
+
+    /// Find Message Statuses of Recipients
+    #[oai(path = "/:id/status", method = "get")]
+    async fn find_message_statuses(&self, id: Path<String>, ctx: TardisContextExtractor) -> TardisApiResult<Vec<ChatMessageStatusResp>> {
+        let funs = chat_constants::get_tardis_inst();
+        let result = ChatMessageMarkServ::find_message_statuses(&id.0, &funs, &ctx.0).await?;
+        TardisResp::ok(result)
+    }
+
+    /// Get Unread Count of Conversation
+    ///
+    /// `conv_id` is the peer account id for direct messages, otherwise the target id.
+    #[oai(path = "/unread", method = "get")]
+    async fn get_unread_count(&self, kind: Query<ChatMessageKind>, conv_id: Query<String>, ctx: TardisContextExtractor) -> TardisApiResult<ChatMessageUnreadResp> {
+        let funs = chat_constants::get_tardis_inst();
+        let result = ChatMessageMarkServ::get_unread_count(&kind.0, &conv_id.0, &funs, &ctx.0).await?;
+        TardisResp::ok(result)
+    }
+
+    /// Receive Messages by WebSocket
+    ///
+    /// The connection receives the messages of which the account is a recipient,
+    /// after reconnecting, pass the id of the last received message by `last_message_id` and the sets of the account
+    /// by `set_ids` (comma separated) to get the missed messages.
+    #[oai(path = "/ws", method = "get")]
+    async fn ws(
+        &self,
+        last_message_id: Query<Option<String>>,
+        set_ids: Query<Option<String>>,
+        websocket: WebSocket,
+        ctx: TardisContextExtractor,
+    ) -> TardisApiResult<BoxWebSocketUpgraded> {
+        let ctx = ctx.0;
+        let set_ids = set_ids.0.map(|set_ids| set_ids.split(',').map(|set_id| set_id.trim().to_string()).filter(|set_id| !set_id.is_empty()).collect()).unwrap_or_default();
+        let funs = chat_constants::get_tardis_inst();
+        let conf = funs.conf::<ChatConfig>();
+        for set_id in &set_ids {
+            ChatTargetServ::check_member(&ChatMessageKind::ToSet, set_id, &funs, &ctx).await?;
+        }
+        let last_message_id = last_message_id.0;
+        if let Some(last_message_id) = &last_message_id {
+            ChatMessageServ::get_message(last_message_id, &funs, &ctx).await?;
+        }
+        let catch_up_max_size = conf.ws_catch_up_max_size;
+        let heartbeat_interval = Duration::from_secs(conf.ws_heartbeat_interval_sec);
+        let idle_timeout = Duration::from_secs(conf.ws_idle_timeout_sec);
+        let upgraded = websocket
+            .on_upgrade(move |socket| async move {
+                let conn_id = TardisFuns::field.nanoid();
+                let channels = ChatChannelServ::get_channels(&ctx);
+                let (mut sink, mut stream) = socket.split();
+                let (tx, mut rx) = mpsc::unbounded_channel::<ChatMessageInfoResp>();
+                let funs = chat_constants::get_tardis_inst();
+                // Subscribe before finding the missed messages, so that a message sent in between is either found or received from the channel
+                ChatChannelServ::subscribe(&conn_id, &channels, tx).await;
+                let mut pending = true;
+                // The messages both found and received from the channel are sent once
+                let mut caught_up_message_ids = HashSet::new();
+                if let Some(last_message_id) = &last_message_id {
+                    match ChatMessageServ::find_messages_since(last_message_id, &set_ids, catch_up_max_size, &funs, &ctx).await {
+                        Ok(messages) => {
+                            for message in messages {
+                                if !Self::send_message(&mut sink, &message, &funs, &ctx).await {
+                                    pending = false;
+                                    break;
+                                }
+                                caught_up_message_ids.insert(message.id);
+                            }
+                        }
+                        Err(e) => {
+                            warn!("[BIOS.Chat] find the missed messages of connection {} error: {}", conn_id, e);
+                            let _ = sink.send(Message::Close(None)).await;
+                            pending = false;
+                        }
+                    }
+                }
+                let mut heartbeat = tokio::time::interval(heartbeat_interval);
+                let mut last_active_time = tokio::time::Instant::now();
+                while pending {
+                    tokio::select! {
+                        message = rx.recv() => {
+                            match message {
+                                Some(message) => {
+                                    if !caught_up_message_ids.remove(&message.id) {
+                                        pending = Self::send_message(&mut sink, &message, &funs, &ctx).await;
+                                    }
+                                }
+                                None => pending = false,
+                            }
+                        }
+                        frame = stream.next() => {
+                            match frame {
+                                Some(Ok(Message::Text(text))) => {
+                                    last_active_time = tokio::time::Instant::now();
+                                    if text == "ping" {
+                                        pending = sink.send(Message::Text("pong".to_string())).await.is_ok();
+                                    }
+                                }
+                                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => pending = false,
+                                Some(Ok(_)) => last_active_time = tokio::time::Instant::now(),
+                            }
+                        }
+                        _ = heartbeat.tick() => {
+                            if last_active_time.elapsed() > idle_timeout {
+                                trace!("[BIOS.Chat] close idle connection {}", conn_id);
+                                let _ = sink.send(Message::Close(None)).await;
+                                pending = false;
+                            } else {
+                                pending = sink.send(Message::Ping(vec![])).await.is_ok();
+                            }
+                        }
+                    }
+                }
+                ChatChannelServ::unsubscribe(&conn_id, &channels).await;
+            })
+            .boxed();
+        TardisResp::ok(upgraded)
+    }
+}
+
+impl ChatCcMessageApi {
+    /// Send the message to the connection and mark it delivered, return false if the connection is closed.
+    async fn send_message<S: Sink<Message> + Unpin>(sink: &mut S, message: &ChatMessageInfoResp, funs: &TardisFunsInst, ctx: &TardisContext) -> bool {
+        let text = match TardisFuns::json.obj_to_string(message) {
+            Ok(text) => text,
+            Err(e) => {
+                warn!("[BIOS.Chat] serialize message error: {}", e);
+                return true;
+            }
+        };
+        if sink.send(Message::Text(text)).await.is_err() {
+            return false;
+        }
+        if let Err(e) = ChatMessageMarkServ::mark_delivered(message, funs, ctx).await {
+            warn!("[BIOS.Chat] mark message {} delivered error: {}", message.id, e);
+        }
+        true
     }
 }
@@ -1,4 +1,6 @@
-mod basic;
-mod chat_config;
-mod chat_initializer;
-mod console_common;
+#[macro_use]
+extern crate lazy_static;
+pub mod basic;
+pub mod chat_config;
+pub mod chat_initializer;
+pub mod console_common;
@@ -0,0 +1,28 @@
+use tardis::basic::result::TardisResult;
+use tardis::{testcontainers, tokio};
+
+use bios_chat_basic::chat_constants;
+use bios_chat_core::basic::serv::chat_channel_serv::ChatChannelServ;
+use bios_chat_core::chat_initializer;
+use bios_iam::iam_constants;
+
+mod test_basic;
+mod test_chat_channel;
+mod test_chat_message;
+mod test_chat_message_mark;
+mod test_chat_target;
+
+#[tokio::test]
+async fn test_chat() -> TardisResult<()> {
+    let docker = testcontainers::clients::Cli::default();
+    let _x = test_basic::init(&docker).await?;
+    bios_iam::iam_initializer::init_db(iam_constants::get_tardis_inst()).await?;
+    chat_initializer::init_db(chat_constants::get_tardis_inst()).await?;
+    ChatChannelServ::init_listener(&chat_constants::get_tardis_inst()).await?;
+    let targets = test_basic::prepare_targets().await?;
+    test_chat_message::test(&targets).await?;
+    test_chat_target::test(&targets).await?;
+    test_chat_message_mark::test(&targets).await?;
+    test_chat_channel::test(&targets).await?;
+    Ok(())
+}
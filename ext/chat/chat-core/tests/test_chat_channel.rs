@@ -0,0 +1,113 @@
+use std::time::Duration;
+
+use tardis::basic::result::TardisResult;
+use tardis::log::info;
+use tardis::tokio::sync::mpsc::{self, UnboundedReceiver};
+use tardis::tokio::time::{sleep, timeout};
+
+use bios_chat_basic::chat_constants;
+use bios_chat_basic::chat_enumeration::ChatMessageKind;
+use bios_chat_basic::dto::chat_message_dto::ChatMessageInfoResp;
+use bios_chat_core::basic::serv::chat_channel_serv::ChatChannelServ;
+use bios_chat_core::basic::serv::chat_message_serv::ChatMessageServ;
+
+use crate::test_basic::ChatTestTargets;
+use crate::test_chat_message::add_message;
+
+pub async fn test(targets: &ChatTestTargets) -> TardisResult<()> {
+    let mut funs = chat_constants::get_tardis_inst();
+    funs.begin().await?;
+
+    info!("【test_chat_channel】 : Test Channels : ChatChannelServ::get_channels");
+    let channels1 = ChatChannelServ::get_channels(&targets.context1);
+    let channels2 = ChatChannelServ::get_channels(&targets.context2);
+    assert_eq!(channels2, vec![format!("ToAccount:{}", targets.context2.owner), format!("ToTenant:{}", targets.tenant_id)]);
+
+    info!("【test_chat_channel】 : Test Publish : ChatChannelServ::publish");
+    let (tx1, mut rx1) = mpsc::unbounded_channel::<ChatMessageInfoResp>();
+    let (tx2, mut rx2) = mpsc::unbounded_channel::<ChatMessageInfoResp>();
+    ChatChannelServ::subscribe("conn1", &channels1, tx1).await;
+    ChatChannelServ::subscribe("conn2", &channels2, tx2).await;
+
+    let direct_message_id = add_message(&ChatMessageKind::ToAccount, &targets.context2.owner, "hello account 2", &funs, &targets.context1).await?;
+    ChatChannelServ::publish(ChatMessageServ::get_message(&direct_message_id, &funs, &targets.context1).await?, &funs, &targets.context1).await?;
+    assert_eq!(recv(&mut rx2).await.unwrap().id, direct_message_id);
+    // The other connections of the sender receive it as well
+    assert_eq!(recv(&mut rx1).await.unwrap().id, direct_message_id);
+
+    let set_message_id = add_message(&ChatMessageKind::ToSet, &targets.set_cate_id, "hello set", &funs, &targets.context1).await?;
+    ChatChannelServ::publish(ChatMessageServ::get_message(&set_message_id, &funs, &targets.context1).await?, &funs, &targets.context1).await?;
+    // The sender is also a recipient, the message is received once
+    assert_eq!(recv(&mut rx1).await.unwrap().id, set_message_id);
+    assert!(recv_none(&mut rx1).await);
+    // Not a member of the set
+    assert!(recv_none(&mut rx2).await);
+
+    // Tenant messages are published to the channel of the tenant
+    let tenant_message_id = add_message(&ChatMessageKind::ToTenant, &targets.tenant_id, "hello tenant", &funs, &targets.context1).await?;
+    ChatChannelServ::publish(ChatMessageServ::get_message(&tenant_message_id, &funs, &targets.context1).await?, &funs, &targets.context1).await?;
+    assert_eq!(recv(&mut rx2).await.unwrap().id, tenant_message_id);
+    assert_eq!(recv(&mut rx1).await.unwrap().id, tenant_message_id);
+    assert!(recv_none(&mut rx1).await);
+
+    ChatChannelServ::unsubscribe("conn1", &channels1).await;
+    ChatChannelServ::unsubscribe("conn2", &channels2).await;
+    let direct_message_id = add_message(&ChatMessageKind::ToAccount, &targets.context2.owner, "hello again", &funs, &targets.context1).await?;
+    ChatChannelServ::publish(ChatMessageServ::get_message(&direct_message_id, &funs, &targets.context1).await?, &funs, &targets.context1).await?;
+    assert!(recv_none(&mut rx2).await);
+
+    info!("【test_chat_channel】 : Test Catch Up : ChatMessageServ::find_messages_since");
+    // Messages are ordered by the create time in seconds
+    sleep(Duration::from_secs(1)).await;
+    let last_message_id = add_message(&ChatMessageKind::ToAccount, &targets.context2.owner, "last received", &funs, &targets.context1).await?;
+    sleep(Duration::from_secs(1)).await;
+    let set_message_id = add_message(&ChatMessageKind::ToSet, &targets.set_cate_id, "missed set message", &funs, &targets.context1).await?;
+    sleep(Duration::from_secs(1)).await;
+    let tenant_message_id = add_message(&ChatMessageKind::ToTenant, &targets.tenant_id, "missed tenant message", &funs, &targets.context2).await?;
+    sleep(Duration::from_secs(1)).await;
+    let direct_message_id = add_message(
+        &ChatMessageKind::ToAccount,
+        &targets.outsider_context.owner,
+        "missed direct message",
+        &funs,
+        &targets.context2,
+    )
+    .await?;
+    sleep(Duration::from_secs(1)).await;
+    let app_message_id = add_message(&ChatMessageKind::ToApp, &targets.app_id, "missed app message", &funs, &targets.app_context1).await?;
+
+    let messages = ChatMessageServ::find_messages_since(&last_message_id, &[targets.set_cate_id.clone()], 3, &funs, &targets.app_context1).await?;
+    assert_eq!(
+        messages.into_iter().map(|message| message.id).collect::<Vec<String>>(),
+        vec![set_message_id.clone(), tenant_message_id.clone(), app_message_id]
+    );
+    // Up to the max size
+    let messages = ChatMessageServ::find_messages_since(&last_message_id, &[targets.set_cate_id.clone()], 2, &funs, &targets.app_context1).await?;
+    assert_eq!(
+        messages.into_iter().map(|message| message.id).collect::<Vec<String>>(),
+        vec![set_message_id, tenant_message_id.clone()]
+    );
+    // Messages of the sets not passed, of other apps and of other conversations are excluded
+    let messages = ChatMessageServ::find_messages_since(&last_message_id, &[], 3, &funs, &targets.context2).await?;
+    assert_eq!(
+        messages.into_iter().map(|message| message.id).collect::<Vec<String>>(),
+        vec![tenant_message_id, direct_message_id]
+    );
+    // The last message must be visible to the account
+    assert_eq!(
+        ChatMessageServ::find_messages_since(&last_message_id, &[], 3, &funs, &targets.outsider_context).await.unwrap_err().code,
+        "404-chat-message-not-exist"
+    );
+
+    funs.rollback().await?;
+    Ok(())
+}
+
+async fn recv(rx: &mut UnboundedReceiver<ChatMessageInfoResp>) -> Option<ChatMessageInfoResp> {
+    // Messages are delivered through the mq
+    timeout(Duration::from_secs(5), rx.recv()).await.ok().flatten()
+}
+
+async fn recv_none(rx: &mut UnboundedReceiver<ChatMessageInfoResp>) -> bool {
+    !matches!(timeout(Duration::from_secs(1), rx.recv()).await, Ok(Some(_)))
+}
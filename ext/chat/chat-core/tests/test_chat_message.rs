@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+use tardis::basic::dto::TardisContext;
+use tardis::basic::result::TardisResult;
+use tardis::chrono::{self, Utc};
+use tardis::log::info;
+use tardis::tokio::time::sleep;
+use tardis::TardisFunsInst;
+
+use bios_chat_basic::chat_constants;
+use bios_chat_basic::chat_enumeration::ChatMessageKind;
+use bios_chat_basic::dto::chat_message_dto::ChatMessageAddReq;
+use bios_chat_core::basic::serv::chat_message_serv::ChatMessageServ;
+
+use crate::test_basic::ChatTestTargets;
+
+pub async fn test(targets: &ChatTestTargets) -> TardisResult<()> {
+    let mut funs = chat_constants::get_tardis_inst();
+    funs.begin().await?;
+    let start_time = Utc::now() - chrono::Duration::hours(1);
+
+    info!("【test_chat_message】 : Test Add : ChatMessageServ::add_message");
+    let tenant_message_id1 = add_message(&ChatMessageKind::ToTenant, &targets.tenant_id, "hello tenant 1", &funs, &targets.context1).await?;
+    // Messages are ordered by the create time in seconds
+    sleep(Duration::from_secs(1)).await;
+    let tenant_message_id2 = add_message(&ChatMessageKind::ToTenant, &targets.tenant_id, "hello tenant 2", &funs, &targets.context1).await?;
+    sleep(Duration::from_secs(1)).await;
+    let tenant_message_id3 = add_message(&ChatMessageKind::ToTenant, &targets.tenant_id, "hello tenant 3", &funs, &targets.context2).await?;
+    // Tenant messages can only be sent to the tenant of the context
+    assert_eq!(
+        add_message(&ChatMessageKind::ToTenant, "t-other", "hello other tenant", &funs, &targets.context1).await.unwrap_err().code,
+        "400-chat-message-target-invalid"
+    );
+    let direct_message_id1 = add_message(&ChatMessageKind::ToAccount, &targets.context2.owner, "hello account 2", &funs, &targets.context1).await?;
+    sleep(Duration::from_secs(1)).await;
+    let direct_message_id2 = add_message(&ChatMessageKind::ToAccount, &targets.context1.owner, "hello account 1", &funs, &targets.context2).await?;
+
+    info!("【test_chat_message】 : Test Get : ChatMessageServ::get_message");
+    let message = ChatMessageServ::get_message(&tenant_message_id1, &funs, &targets.context2).await?;
+    assert_eq!(message.id, tenant_message_id1);
+    assert_eq!(message.kind, ChatMessageKind::ToTenant);
+    assert_eq!(message.to_id, targets.tenant_id);
+    assert_eq!(message.content, "hello tenant 1");
+    assert_eq!(message.owner, targets.context1.owner);
+    let message = ChatMessageServ::get_message(&direct_message_id1, &funs, &targets.context2).await?;
+    assert_eq!(message.kind, ChatMessageKind::ToAccount);
+    assert_eq!(message.to_id, targets.context2.owner);
+    // Direct messages are only visible to the sender and the receiver
+    assert_eq!(
+        ChatMessageServ::get_message(&direct_message_id1, &funs, &targets.outsider_context).await.unwrap_err().code,
+        "404-chat-message-not-exist"
+    );
+    // Messages of other tenants are invisible
+    let other_tenant_context = TardisContext {
+        own_paths: "t-other".to_string(),
+        owner: targets.context2.owner.clone(),
+        ..Default::default()
+    };
+    assert_eq!(
+        ChatMessageServ::get_message(&tenant_message_id1, &funs, &other_tenant_context).await.unwrap_err().code,
+        "404-chat-message-not-exist"
+    );
+
+    info!("【test_chat_message】 : Test Paginate : ChatMessageServ::paginate_messages");
+    let end_time = Utc::now() + chrono::Duration::hours(1);
+    let page = ChatMessageServ::paginate_messages(&ChatMessageKind::ToTenant, &targets.tenant_id, start_time, end_time, 1, 2, &funs, &targets.context2).await?;
+    assert_eq!(page.page_number, 1);
+    assert_eq!(page.page_size, 2);
+    assert_eq!(page.total_size, 3);
+    // The latest messages first
+    assert_eq!(
+        page.records.iter().map(|message| message.id.clone()).collect::<Vec<String>>(),
+        vec![tenant_message_id3, tenant_message_id2]
+    );
+    let page = ChatMessageServ::paginate_messages(&ChatMessageKind::ToTenant, &targets.tenant_id, start_time, end_time, 2, 2, &funs, &targets.context2).await?;
+    assert_eq!(page.total_size, 3);
+    assert_eq!(page.records.iter().map(|message| message.id.clone()).collect::<Vec<String>>(), vec![tenant_message_id1]);
+    // Messages out of the time range
+    let page = ChatMessageServ::paginate_messages(&ChatMessageKind::ToTenant, &targets.tenant_id, start_time, start_time, 1, 2, &funs, &targets.context2).await?;
+    assert_eq!(page.total_size, 0);
+    assert!(page.records.is_empty());
+
+    // Both directions of the direct conversation
+    let page = ChatMessageServ::paginate_messages(&ChatMessageKind::ToAccount, &targets.context1.owner, start_time, end_time, 1, 10, &funs, &targets.context2).await?;
+    assert_eq!(page.total_size, 2);
+    assert_eq!(
+        page.records.iter().map(|message| message.id.clone()).collect::<Vec<String>>(),
+        vec![direct_message_id2.clone(), direct_message_id1.clone()]
+    );
+    let page = ChatMessageServ::paginate_messages(&ChatMessageKind::ToAccount, &targets.context2.owner, start_time, end_time, 1, 10, &funs, &targets.context1).await?;
+    assert_eq!(
+        page.records.iter().map(|message| message.id.clone()).collect::<Vec<String>>(),
+        vec![direct_message_id2, direct_message_id1]
+    );
+    // The conversation of other accounts is invisible
+    let page = ChatMessageServ::paginate_messages(
+        &ChatMessageKind::ToAccount,
+        &targets.context1.owner,
+        start_time,
+        end_time,
+        1,
+        10,
+        &funs,
+        &targets.outsider_context,
+    )
+    .await?;
+    assert_eq!(page.total_size, 0);
+
+    funs.rollback().await?;
+    Ok(())
+}
+
+pub async fn add_message(kind: &ChatMessageKind, to_id: &str, content: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<String> {
+    ChatMessageServ::add_message(
+        &ChatMessageAddReq {
+            kind: kind.clone(),
+            to_id: to_id.to_string(),
+            content: content.to_string(),
+        },
+        funs,
+        ctx,
+    )
+    .await
+}
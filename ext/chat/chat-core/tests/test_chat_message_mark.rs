@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use tardis::basic::dto::TardisContext;
+use tardis::basic::result::TardisResult;
+use tardis::log::info;
+use tardis::tokio::time::sleep;
+use tardis::TardisFunsInst;
+
+use bios_chat_basic::chat_constants;
+use bios_chat_basic::chat_enumeration::{ChatMessageKind, ChatMessageStatusKind};
+use bios_chat_core::basic::serv::chat_channel_serv::ChatChannelServ;
+use bios_chat_core::basic::serv::chat_message_mark_serv::ChatMessageMarkServ;
+use bios_chat_core::basic::serv::chat_message_serv::ChatMessageServ;
+
+use crate::test_basic::ChatTestTargets;
+use crate::test_chat_message::add_message;
+
+pub async fn test(targets: &ChatTestTargets) -> TardisResult<()> {
+    let mut funs = chat_constants::get_tardis_inst();
+    funs.begin().await?;
+
+    let tenant_message_id1 = add_message(&ChatMessageKind::ToTenant, &targets.tenant_id, "hello tenant 1", &funs, &targets.context1).await?;
+    // Messages are ordered by the create time in seconds
+    sleep(Duration::from_secs(1)).await;
+    let tenant_message_id2 = add_message(&ChatMessageKind::ToTenant, &targets.tenant_id, "hello tenant 2", &funs, &targets.context1).await?;
+    sleep(Duration::from_secs(1)).await;
+    let tenant_message_id3 = add_message(&ChatMessageKind::ToTenant, &targets.tenant_id, "hello tenant 3", &funs, &targets.context1).await?;
+
+    info!("【test_chat_message_mark】 : Test Unread : ChatMessageMarkServ::get_unread_count");
+    assert_eq!(get_unread_count(&ChatMessageKind::ToTenant, &targets.tenant_id, &funs, &targets.context2).await?, 3);
+    // Messages sent by the account are not counted
+    assert_eq!(get_unread_count(&ChatMessageKind::ToTenant, &targets.tenant_id, &funs, &targets.context1).await?, 0);
+    // The cached counters are increased when the message is published
+    sleep(Duration::from_secs(1)).await;
+    let tenant_message_id4 = add_message(&ChatMessageKind::ToTenant, &targets.tenant_id, "hello tenant 4", &funs, &targets.context1).await?;
+    ChatChannelServ::publish(ChatMessageServ::get_message(&tenant_message_id4, &funs, &targets.context1).await?, &funs, &targets.context1).await?;
+    assert_eq!(get_unread_count(&ChatMessageKind::ToTenant, &targets.tenant_id, &funs, &targets.context2).await?, 4);
+    assert_eq!(get_unread_count(&ChatMessageKind::ToTenant, &targets.tenant_id, &funs, &targets.context1).await?, 0);
+    // Only the members of the target have unread counters
+    assert_eq!(
+        ChatMessageMarkServ::get_unread_count(&ChatMessageKind::ToTenant, &targets.tenant_id, &funs, &targets.outsider_context).await.unwrap_err().code,
+        "403-chat-message-not-member"
+    );
+
+    info!("【test_chat_message_mark】 : Test Mark Read : ChatMessageMarkServ::mark_read");
+    ChatMessageMarkServ::mark_read(&tenant_message_id2, &funs, &targets.context2).await?;
+    assert_eq!(get_unread_count(&ChatMessageKind::ToTenant, &targets.tenant_id, &funs, &targets.context2).await?, 2);
+    // Marks never move backwards
+    ChatMessageMarkServ::mark_read(&tenant_message_id1, &funs, &targets.context2).await?;
+    assert_eq!(get_unread_count(&ChatMessageKind::ToTenant, &targets.tenant_id, &funs, &targets.context2).await?, 2);
+    assert_eq!(
+        ChatMessageMarkServ::mark_read(&tenant_message_id3, &funs, &targets.outsider_context).await.unwrap_err().code,
+        "403-chat-message-not-member"
+    );
+
+    info!("【test_chat_message_mark】 : Test Status : ChatMessageMarkServ::find_message_statuses");
+    // Marking read also marks delivered, up to the read message only
+    assert_eq!(get_status(&tenant_message_id2, &funs, targets).await?, ChatMessageStatusKind::Read);
+    assert_eq!(get_status(&tenant_message_id3, &funs, targets).await?, ChatMessageStatusKind::Sent);
+    ChatMessageMarkServ::mark_delivered(
+        &ChatMessageServ::get_message(&tenant_message_id3, &funs, &targets.context2).await?,
+        &funs,
+        &targets.context2,
+    )
+    .await?;
+    assert_eq!(get_status(&tenant_message_id3, &funs, targets).await?, ChatMessageStatusKind::Delivered);
+    assert_eq!(get_status(&tenant_message_id4, &funs, targets).await?, ChatMessageStatusKind::Sent);
+    ChatMessageMarkServ::mark_read(&tenant_message_id4, &funs, &targets.context2).await?;
+    assert_eq!(get_status(&tenant_message_id3, &funs, targets).await?, ChatMessageStatusKind::Read);
+    assert_eq!(get_unread_count(&ChatMessageKind::ToTenant, &targets.tenant_id, &funs, &targets.context2).await?, 0);
+    // Only the sender can see the statuses
+    assert_eq!(
+        ChatMessageMarkServ::find_message_statuses(&tenant_message_id3, &funs, &targets.context2).await.unwrap_err().code,
+        "403-chat-message-not-owner"
+    );
+
+    info!("【test_chat_message_mark】 : Test Direct Conversation : ChatMessageMarkServ::mark_read");
+    let direct_message_id = add_message(&ChatMessageKind::ToAccount, &targets.context2.owner, "hello account 2", &funs, &targets.context1).await?;
+    // The conversation of direct messages is the peer account
+    assert_eq!(get_unread_count(&ChatMessageKind::ToAccount, &targets.context1.owner, &funs, &targets.context2).await?, 1);
+    assert_eq!(get_unread_count(&ChatMessageKind::ToAccount, &targets.context2.owner, &funs, &targets.context1).await?, 0);
+    ChatMessageMarkServ::mark_read(&direct_message_id, &funs, &targets.context2).await?;
+    assert_eq!(get_unread_count(&ChatMessageKind::ToAccount, &targets.context1.owner, &funs, &targets.context2).await?, 0);
+    assert_eq!(get_status(&direct_message_id, &funs, targets).await?, ChatMessageStatusKind::Read);
+
+    funs.rollback().await?;
+    Ok(())
+}
+
+async fn get_unread_count(kind: &ChatMessageKind, conv_id: &str, funs: &TardisFunsInst, ctx: &TardisContext) -> TardisResult<u64> {
+    let unread = ChatMessageMarkServ::get_unread_count(kind, conv_id, funs, ctx).await?;
+    assert_eq!(&unread.kind, kind);
+    assert_eq!(unread.conv_id, conv_id);
+    Ok(unread.unread_count)
+}
+
+// Status of the message sent by the first account for the second account
+async fn get_status(message_id: &str, funs: &TardisFunsInst, targets: &ChatTestTargets) -> TardisResult<ChatMessageStatusKind> {
+    let statuses = ChatMessageMarkServ::find_message_statuses(message_id, funs, &targets.context1).await?;
+    assert_eq!(statuses.len(), 1);
+    assert_eq!(statuses[0].account_id, targets.context2.owner);
+    Ok(statuses[0].status.clone())
+}
@@ -0,0 +1,76 @@
+use tardis::basic::result::TardisResult;
+use tardis::log::info;
+
+use bios_chat_basic::chat_constants;
+use bios_chat_basic::chat_enumeration::ChatMessageKind;
+use bios_chat_core::basic::serv::chat_target_serv::ChatTargetServ;
+
+use crate::test_basic::ChatTestTargets;
+use crate::test_chat_message::add_message;
+
+pub async fn test(targets: &ChatTestTargets) -> TardisResult<()> {
+    let mut funs = chat_constants::get_tardis_inst();
+    funs.begin().await?;
+
+    info!("【test_chat_target】 : Test Find Recipients : ChatTargetServ::find_account_ids");
+    assert_eq!(
+        ChatTargetServ::find_account_ids(&ChatMessageKind::ToAccount, &targets.context2.owner, &targets.context1).await?,
+        vec![targets.context2.owner.clone()]
+    );
+    assert_eq!(
+        ChatTargetServ::find_account_ids(&ChatMessageKind::ToSet, &targets.set_cate_id, &targets.context1).await?,
+        vec![targets.context1.owner.clone()]
+    );
+    // Sets are resolved from the tenant even if the context is an app
+    assert_eq!(
+        ChatTargetServ::find_account_ids(&ChatMessageKind::ToSet, &targets.set_cate_id, &targets.app_context1).await?,
+        vec![targets.context1.owner.clone()]
+    );
+    assert_eq!(
+        ChatTargetServ::find_account_ids(&ChatMessageKind::ToApp, &targets.app_id, &targets.app_context1).await?,
+        vec![targets.context1.owner.clone()]
+    );
+    let mut tenant_account_ids = ChatTargetServ::find_account_ids(&ChatMessageKind::ToTenant, &targets.tenant_id, &targets.context1).await?;
+    tenant_account_ids.sort();
+    let mut expected_account_ids = vec![targets.context1.owner.clone(), targets.context2.owner.clone()];
+    expected_account_ids.sort();
+    assert_eq!(tenant_account_ids, expected_account_ids);
+
+    info!("【test_chat_target】 : Test Check Member : ChatTargetServ::check_member");
+    ChatTargetServ::check_member(&ChatMessageKind::ToSet, &targets.set_cate_id, &funs, &targets.context1).await?;
+    assert_eq!(
+        ChatTargetServ::check_member(&ChatMessageKind::ToSet, &targets.set_cate_id, &funs, &targets.context2).await.unwrap_err().code,
+        "403-chat-message-not-member"
+    );
+    ChatTargetServ::check_member(&ChatMessageKind::ToApp, &targets.app_id, &funs, &targets.app_context1).await?;
+    assert_eq!(
+        ChatTargetServ::check_member(&ChatMessageKind::ToApp, &targets.app_id, &funs, &targets.app_context2).await.unwrap_err().code,
+        "403-chat-message-not-member"
+    );
+    ChatTargetServ::check_member(&ChatMessageKind::ToTenant, &targets.tenant_id, &funs, &targets.context2).await?;
+    assert_eq!(
+        ChatTargetServ::check_member(&ChatMessageKind::ToTenant, &targets.tenant_id, &funs, &targets.outsider_context).await.unwrap_err().code,
+        "403-chat-message-not-member"
+    );
+    // Direct messages have no membership
+    ChatTargetServ::check_member(&ChatMessageKind::ToAccount, &targets.context1.owner, &funs, &targets.outsider_context).await?;
+
+    info!("【test_chat_target】 : Test Check Sender : ChatTargetServ::check_sender");
+    ChatTargetServ::check_sender(&ChatMessageKind::ToApp, &targets.app_id, &funs, &targets.app_context1).await?;
+    // App messages can only be sent from the app
+    assert_eq!(
+        ChatTargetServ::check_sender(&ChatMessageKind::ToApp, &targets.app_id, &funs, &targets.context1).await.unwrap_err().code,
+        "400-chat-message-target-invalid"
+    );
+    assert_eq!(
+        add_message(&ChatMessageKind::ToSet, &targets.set_cate_id, "hello set", &funs, &targets.context2).await.unwrap_err().code,
+        "403-chat-message-not-member"
+    );
+    assert_eq!(
+        add_message(&ChatMessageKind::ToApp, &targets.app_id, "hello app", &funs, &targets.app_context2).await.unwrap_err().code,
+        "403-chat-message-not-member"
+    );
+
+    funs.rollback().await?;
+    Ok(())
+}